@@ -3,8 +3,8 @@ use crate::types::SpannedValue;
 use serde::Serialize;
 
 /// One RFC 0018 facet: `min = 1` or (RFC 0017) `min = 5s`. The value is a
-/// number or duration literal (`SpannedValue` holding `Value::Number` or
-/// `Value::Duration`); an undecodable literal is DROPPED with a diagnostic
+/// number, duration, string, or boolean literal (a `Value::Array` for a
+/// `["USD", "GBP"]` list); an undecodable literal is DROPPED with a diagnostic
 /// by both builders — never zero-recovered into a phantom bound.
 #[derive(Debug, Clone, Serialize)]
 pub struct FacetExpr {
@@ -26,7 +26,11 @@ pub enum FieldTypeExpr {
         name: Identifier,
         facets: Vec<FacetExpr>,
     },
-    Array(Box<FieldTypeExpr>),
+    /// `[]T`, with any collection facets (`minItems`, `maxItems`,
+    /// `nonEmpty`). They are authored inside the element name's parens
+    /// (`[]endpoint(minItems = 1)`) and hoisted here at lowering, so the
+    /// element keeps only its own facets.
+    Array(Box<FieldTypeExpr>, Vec<FacetExpr>),
     Union(Vec<FieldTypeExpr>),
     /// `(K -> V)` — a typed arm set (RFC 0007): the field's body is ordered,
     /// first-match [`Arm`]s whose keys conform to `K` and whose targets are
@@ -39,8 +43,67 @@ pub enum FieldTypeExpr {
     },
     /// `set<T>` — an unordered, unique-element collection (RFC 0032). A bare
     /// union argument (`set<a | b>`) is the canonical spelling; the element
-    /// here is then `Union`.
-    Set(Box<FieldTypeExpr>),
+    /// here is then `Union`. Collection facets follow the angles
+    /// (`set<string>(maxItems = 8)`).
+    Set(Box<FieldTypeExpr>, Vec<FacetExpr>),
+}
+
+/// A string facet value as a single-line NML literal, escaped with the
+/// lexer's own escapes so the rendering re-parses to the same text.
+pub(crate) fn quote_facet_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Writes a `(key = value, ...)` facet list; nothing when empty.
+fn write_facets<'a>(
+    f: &mut std::fmt::Formatter<'_>,
+    facets: impl IntoIterator<Item = &'a FacetExpr>,
+) -> std::fmt::Result {
+    let mut wrote = false;
+    for facet in facets {
+        f.write_str(if wrote { ", " } else { "(" })?;
+        wrote = true;
+        write!(f, "{} = ", facet.key.name)?;
+        match &facet.value.value {
+            crate::types::Value::Number(n) => write!(f, "{n}")?,
+            // A duration renders as authored (`250ms`, RFC 0017 Display)
+            // — the fmt fixed point depends on canonical value rendering.
+            crate::types::Value::Duration(d) => write!(f, "{d}")?,
+            crate::types::Value::Bool(b) => write!(f, "{b}")?,
+            crate::types::Value::String(s) => f.write_str(&quote_facet_string(s))?,
+            crate::types::Value::Array(items) => {
+                f.write_str("[")?;
+                for (j, item) in items.iter().enumerate() {
+                    if j > 0 {
+                        f.write_str(", ")?;
+                    }
+                    match &item.value {
+                        crate::types::Value::String(s) => f.write_str(&quote_facet_string(s))?,
+                        other => write!(f, "{other:?}")?,
+                    }
+                }
+                f.write_str("]")?;
+            }
+            other => write!(f, "{other:?}")?,
+        }
+    }
+    if wrote {
+        f.write_str(")")?;
+    }
+    Ok(())
 }
 
 /// Renders the type expression in NML source syntax: `string`, `[]route`,
@@ -50,27 +113,24 @@ impl std::fmt::Display for FieldTypeExpr {
         match self {
             FieldTypeExpr::Named { name, facets } => {
                 f.write_str(&name.name)?;
-                if !facets.is_empty() {
-                    f.write_str("(")?;
-                    for (i, facet) in facets.iter().enumerate() {
-                        if i > 0 {
-                            f.write_str(", ")?;
-                        }
-                        write!(f, "{} = ", facet.key.name)?;
-                        match &facet.value.value {
-                            crate::types::Value::Number(n) => write!(f, "{n}")?,
-                            // A duration renders as authored (`250ms`,
-                            // RFC 0017 Display) — the fmt fixed point
-                            // depends on canonical value rendering.
-                            crate::types::Value::Duration(d) => write!(f, "{d}")?,
-                            other => write!(f, "{other:?}")?,
-                        }
-                    }
-                    f.write_str(")")?;
-                }
-                Ok(())
+                write_facets(f, facets)
             }
-            FieldTypeExpr::Array(inner) => write!(f, "[]{inner}"),
+            // Collection facets render back inside the element name's
+            // parens, where they were authored: `[]string(minLength = 1,
+            // minItems = 2)`.
+            FieldTypeExpr::Array(inner, facets) => match inner.as_ref() {
+                FieldTypeExpr::Named {
+                    name,
+                    facets: element,
+                } if !facets.is_empty() => {
+                    write!(f, "[]{}", name.name)?;
+                    write_facets(f, element.iter().chain(facets))
+                }
+                _ => {
+                    write!(f, "[]{inner}")?;
+                    write_facets(f, facets)
+                }
+            },
             FieldTypeExpr::Union(variants) => {
                 f.write_str("(")?;
                 for (i, v) in variants.iter().enumerate() {
@@ -82,7 +142,7 @@ impl std::fmt::Display for FieldTypeExpr {
                 f.write_str(")")
             }
             FieldTypeExpr::Arms { key, target } => write!(f, "({key} -> {target})"),
-            FieldTypeExpr::Set(inner) => {
+            FieldTypeExpr::Set(inner, facets) => {
                 // Canonical: bare union inside the angles (RFC 0032 Decision 4).
                 f.write_str("set<")?;
                 match inner.as_ref() {
@@ -96,7 +156,8 @@ impl std::fmt::Display for FieldTypeExpr {
                     }
                     other => write!(f, "{other}")?,
                 }
-                f.write_str(">")
+                f.write_str(">")?;
+                write_facets(f, facets)
             }
         }
     }
@@ -140,6 +201,8 @@ pub enum DeclarationKind {
     Template(TemplateDecl),
     /// A discriminated union of models: `oneof Name by <field>: "v" -> Model ...`
     OneOf(OneOfDecl),
+    /// An import of another file's declarations: `import "shared.nml"`
+    Import(ImportDecl),
 }
 
 /// A discriminated-union declaration:
@@ -165,6 +228,19 @@ pub struct OneOfDecl {
     pub arms: Vec<OneOfArm>,
 }
 
+/// An import: `import "shared.nml"`, or `import "shared.nml" (dbUrl, Primary)`
+/// to bring in only the named declarations. Resolved and loaded by
+/// [`crate::import`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportDecl {
+    /// The path as written, relative to the importing file.
+    pub path: String,
+    /// Span of the path literal (for diagnostics).
+    pub path_span: Span,
+    /// The selected names; `None` imports every declaration.
+    pub names: Option<Vec<Identifier>>,
+}
+
 /// One `"value" -> ModelName` arm of a [`OneOfDecl`].
 #[derive(Debug, Clone, Serialize)]
 pub struct OneOfArm {
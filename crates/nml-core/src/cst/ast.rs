@@ -86,6 +86,7 @@ pub enum Decl {
     Const(ConstDecl),
     Template(TemplateDecl),
     OneOf(OneOfDecl),
+    Import(ImportDecl),
 }
 
 impl AstNode for Decl {
@@ -96,6 +97,7 @@ impl AstNode for Decl {
             SyntaxKind::ConstDecl => Decl::Const(ConstDecl(node)),
             SyntaxKind::TemplateDecl => Decl::Template(TemplateDecl(node)),
             SyntaxKind::OneOfDecl => Decl::OneOf(OneOfDecl(node)),
+            SyntaxKind::ImportDecl => Decl::Import(ImportDecl(node)),
             _ => return None,
         })
     }
@@ -106,12 +108,13 @@ impl AstNode for Decl {
             Decl::Const(d) => d.syntax(),
             Decl::Template(d) => d.syntax(),
             Decl::OneOf(d) => d.syntax(),
+            Decl::Import(d) => d.syntax(),
         }
     }
 }
 
 impl Decl {
-    /// The declaration's [`Name`], regardless of kind.
+    /// The declaration's [`Name`], regardless of kind (an import has none).
     pub fn name(&self) -> Option<Name> {
         match self {
             Decl::Block(d) => d.name(),
@@ -119,6 +122,7 @@ impl Decl {
             Decl::Const(d) => d.name(),
             Decl::Template(d) => d.name(),
             Decl::OneOf(d) => d.name(),
+            Decl::Import(_) => None,
         }
     }
 
@@ -313,6 +317,24 @@ impl OneOfDecl {
     }
 }
 
+ast_node!(/// `import "path"` with an optional `(name, …)` selection
+    ImportDecl => ImportDecl);
+
+impl ImportDecl {
+    /// The quoted path, relative to the importing file.
+    pub fn path(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::String)
+    }
+    /// Whether a `(…)` selection follows the path (even an empty one).
+    pub fn is_selective(&self) -> bool {
+        token(&self.0, SyntaxKind::LParen).is_some()
+    }
+    /// The selected names, in source order.
+    pub fn names(&self) -> impl Iterator<Item = Name> + '_ {
+        children(&self.0)
+    }
+}
+
 ast_node!(/// `"value" -> Model`
     OneOfArm => OneOfArm);
 
@@ -614,10 +636,11 @@ impl Directive {
 ast_node!(/// `Name` | `[]TypeExpr` | `(TypeExpr (| TypeExpr)*)` | `(TypeExpr -> TypeExpr)`
     TypeExpr => TypeExpr);
 
-ast_node!(/// RFC 0018: `(min = 1, max = 65535)` after a type name.
+ast_node!(/// RFC 0018: `(min = 1, max = 65535)` after a type name or a `set<T>`.
     FacetList => FacetList);
 
-ast_node!(/// One `key = value` facet — a number or duration literal.
+ast_node!(/// One `key = value` facet — a number, duration, string, or boolean
+    /// literal, or a one-line `[…]` list of strings.
     Facet => Facet);
 
 ast_node!(/// RFC 0017: a duration literal (`5s`, `1h30m`) — the wrapped
@@ -678,6 +701,15 @@ impl Facet {
     pub fn name(&self) -> Option<SyntaxToken> {
         token(&self.0, SyntaxKind::Ident)
     }
+    /// The value's `true`/`false` token (`nonEmpty = true`) — the second
+    /// ident, the first being the key.
+    pub fn boolean(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Ident)
+            .nth(1)
+    }
     /// The value's sign token, when the literal is negative.
     pub fn dash(&self) -> Option<SyntaxToken> {
         token(&self.0, SyntaxKind::Dash)
@@ -690,6 +722,20 @@ impl Facet {
     pub fn duration_literal(&self) -> Option<DurationLiteral> {
         child(&self.0)
     }
+    /// The value's string tokens, in order: one for `key = "x"`, every
+    /// element for `key = ["x", "y"]`.
+    pub fn strings(&self) -> Vec<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::String)
+            .collect()
+    }
+    /// The `[` opening a string-list value — `["USD"]` is a list of one,
+    /// distinct from the bare `"USD"`.
+    pub fn l_bracket(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::LBracket)
+    }
 }
 
 /// The shape of a [`TypeExpr`].
@@ -741,8 +787,8 @@ impl TypeExpr {
         children(&self.0)
     }
     /// The RFC 0018 facet list (`number(min = 1)`), when present.
-    /// Only the `Named` form can carry one — the parser attaches it
-    /// after a bare type name exclusively.
+    /// Only the `Named` and `Set` forms carry one — the parser attaches
+    /// it after a bare type name or a constructor's closing `>`.
     pub fn facet_list(&self) -> Option<FacetList> {
         child(&self.0)
     }
@@ -883,6 +929,7 @@ mod tests {
             "model M:\n    f\n",
             "oneof x\n",
             "[]\n",
+            "import \"a.nml\" (\n",
         ] {
             let r = root(src);
             for decl in r.decls() {
@@ -916,6 +963,9 @@ mod tests {
                     Decl::Template(t) => {
                         let _ = (t.name(), t.value());
                     }
+                    Decl::Import(i) => {
+                        let _ = (i.path(), i.is_selective(), i.names().count());
+                    }
                 }
             }
         }
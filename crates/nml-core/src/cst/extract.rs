@@ -30,7 +30,7 @@ pub fn extract(root: &Root) -> ExtractedSchema {
                 _ => {}
             },
             Decl::OneOf(oneof) => schema.oneofs.push(extract_oneof(&oneof)),
-            Decl::Array(_) | Decl::Const(_) | Decl::Template(_) => {}
+            Decl::Array(_) | Decl::Const(_) | Decl::Template(_) | Decl::Import(_) => {}
         }
     }
     schema
@@ -247,13 +247,116 @@ fn decode_duration_facet(f: &Facet) -> Option<crate::duration::Duration> {
     crate::duration::parse_components_cst(&components, span).ok()
 }
 
+/// Build a `money` field's facet record. `currency` is the only money
+/// facet; its codes are kept only if they decode and name a known ISO
+/// 4217 currency — the definition pass (NML2058) reports the rest, and a
+/// facet left with no codes is dropped rather than loaded as an
+/// allow-list that rejects everything. Last writer wins, as for bounds.
+fn extract_money_facets(te: &TypeExpr) -> crate::model::MoneyFacets {
+    let mut out = crate::model::MoneyFacets::default();
+    let Some(list) = te.facet_list() else {
+        return out;
+    };
+    for f in list.facets() {
+        if token_text(f.name()) != "currency" {
+            continue;
+        }
+        let mut codes: Vec<String> = Vec::new();
+        for tok in f.strings() {
+            let Ok(code) = super::value::decode_string_token(&tok) else {
+                continue;
+            };
+            if crate::money::currency_exponent(&code).is_some() && !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        out.currency = (!codes.is_empty()).then_some(crate::model::CurrencyFacet {
+            codes,
+            span: node_span(f.syntax()),
+        });
+    }
+    out
+}
+
+/// Build a `string` field's facet record. Lengths must decode as
+/// non-negative integers and the pattern must compile; anything else is
+/// dropped here and reported by the definition pass (NML2058), like every
+/// other facet domain.
+fn extract_string_facets(te: &TypeExpr) -> crate::model::StringFacets {
+    let mut out = crate::model::StringFacets::default();
+    let Some(list) = te.facet_list() else {
+        return out;
+    };
+    for f in list.facets() {
+        let span = node_span(f.syntax());
+        let length = || {
+            let n = decode_number_facet(&f)?;
+            let value = usize::try_from(n.to_u64()?).ok()?;
+            Some(crate::model::LengthFacet { value, span })
+        };
+        match token_text(f.name()).as_str() {
+            "minLength" => out.min_length = length(),
+            "maxLength" => out.max_length = length(),
+            "pattern" => {
+                out.pattern = match f.strings().as_slice() {
+                    [tok] if f.l_bracket().is_none() => super::value::decode_string_token(tok)
+                        .ok()
+                        .and_then(|text| crate::pattern::Pattern::new(&text).ok())
+                        .map(|pattern| crate::model::PatternFacet { pattern, span }),
+                    _ => None,
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Build a collection's facet record from `list` — the element name's
+/// facet list for `[]T`, the constructor's own for `set<T>`. Only the
+/// collection keys are read (the element's own facets stay with the
+/// element); counts must decode as non-negative integers and `nonEmpty`
+/// as a boolean, the rest is dropped and reported by the definition pass
+/// (NML2058).
+fn extract_collection_facets(te: &TypeExpr) -> crate::model::CollectionFacets {
+    let mut out = crate::model::ItemFacets::default();
+    let Some(list) = te.facet_list() else {
+        return crate::model::CollectionFacets::None;
+    };
+    for f in list.facets() {
+        let span = node_span(f.syntax());
+        let count = || {
+            let n = decode_number_facet(&f)?;
+            let value = usize::try_from(n.to_u64()?).ok()?;
+            Some(crate::model::LengthFacet { value, span })
+        };
+        match token_text(f.name()).as_str() {
+            "minItems" => out.min_items = count(),
+            "maxItems" => out.max_items = count(),
+            "nonEmpty" => {
+                out.non_empty = f.boolean().map(|b| crate::model::NonEmptyFacet {
+                    value: b.text() == "true",
+                    span,
+                })
+            }
+            _ => {}
+        }
+    }
+    let facets = crate::model::CollectionFacets::Items(Box::new(out));
+    if facets.is_none() {
+        crate::model::CollectionFacets::None
+    } else {
+        facets
+    }
+}
+
 fn resolve_field_type(te: &TypeExpr) -> FieldType {
     match te.kind() {
         TypeExprKind::Named => {
             let name = token_text(te.name());
             match name.parse::<PrimitiveType>() {
                 Ok(prim) => FieldType::Primitive {
-                    // Facets attach to Number and Duration ONLY, each in
+                    // Facets attach to Number, Duration, Money and String ONLY, each in
                     // its own domain — misplacement is reported by
                     // `schema::facet_definition_diagnostics` (emitted
                     // from `extract_schema` itself); dropping them here
@@ -281,6 +384,22 @@ fn resolve_field_type(te: &TypeExpr) -> FieldType {
                                 crate::model::PrimitiveFacets::Duration(Box::new(facets))
                             }
                         }
+                        PrimitiveType::String => {
+                            let facets = extract_string_facets(te);
+                            if facets.is_none() {
+                                crate::model::PrimitiveFacets::None
+                            } else {
+                                crate::model::PrimitiveFacets::String(Box::new(facets))
+                            }
+                        }
+                        PrimitiveType::Money => {
+                            let facets = extract_money_facets(te);
+                            if facets.is_none() {
+                                crate::model::PrimitiveFacets::None
+                            } else {
+                                crate::model::PrimitiveFacets::Money(Box::new(facets))
+                            }
+                        }
                         _ => crate::model::PrimitiveFacets::None,
                     },
                     ty: prim,
@@ -291,12 +410,18 @@ fn resolve_field_type(te: &TypeExpr) -> FieldType {
                 Err(_) => FieldType::ModelRef(name),
             }
         }
-        TypeExprKind::Array => FieldType::List(Box::new(
-            te.children()
-                .next()
-                .map(|inner| resolve_field_type(&inner))
-                .unwrap_or_else(unknown_type),
-        )),
+        TypeExprKind::Array => match te.children().next() {
+            // `[]endpoint(minItems = 1)`: the element name's parens carry
+            // the list's facets too (see `lower::type_expr`).
+            Some(inner) => FieldType::List(
+                Box::new(resolve_field_type(&inner)),
+                extract_collection_facets(&inner),
+            ),
+            None => FieldType::List(
+                Box::new(unknown_type()),
+                crate::model::CollectionFacets::None,
+            ),
+        },
         TypeExprKind::Union => {
             FieldType::Union(te.children().map(|v| resolve_field_type(&v)).collect())
         }
@@ -326,7 +451,7 @@ fn resolve_field_type(te: &TypeExpr) -> FieldType {
                 1 => children.pop().expect("len checked"),
                 _ => FieldType::Union(children),
             };
-            FieldType::Set(Box::new(element))
+            FieldType::Set(Box::new(element), extract_collection_facets(te))
         }
     }
 }
@@ -421,7 +546,7 @@ oneof email by provider as providerKind = \"log\":
             field("region").default_value.as_ref().map(|sv| &sv.value),
             Some(Value::String(s)) if s == "us"
         ));
-        assert!(matches!(field("tags").field_type, FieldType::List(_)));
+        assert!(matches!(field("tags").field_type, FieldType::List(_, _)));
         assert!(matches!(field("mode").field_type, FieldType::Union(_)));
         assert!(matches!(
             field("price").default_value.as_ref().map(|sv| &sv.value),
@@ -515,8 +640,8 @@ model M:
         assert!(
             matches!(
                 &field.field_type,
-                FieldType::List(inner)
-                    if matches!(&**inner, FieldType::List(_))
+                FieldType::List(inner, _)
+                    if matches!(&**inner, FieldType::List(_, _))
             ),
             "expected nested list, got {:?}",
             field.field_type
@@ -59,6 +59,13 @@ impl Lower {
                 value: self.value_of(t.value()),
             }),
             ast::Decl::OneOf(o) => DeclarationKind::OneOf(self.oneof(&o)),
+            ast::Decl::Import(i) => DeclarationKind::Import(ImportDecl {
+                path: i.path().map(|t| self.string_token(&t)).unwrap_or_default(),
+                path_span: i.path().map(|t| token_span(&t)).unwrap_or(EMPTY_SPAN),
+                names: i
+                    .is_selective()
+                    .then(|| i.names().map(|n| name_of(Some(n))).collect()),
+            }),
         };
         Declaration { kind, span }
     }
@@ -411,7 +418,7 @@ fn bare_named(id: Identifier) -> FieldTypeExpr {
     }
 }
 
-/// RFC 0018 facet lowering. Facet values are number literals and route
+/// RFC 0018 facet lowering. Numeric facet values route
 /// through [`super::value::parse_number`] — the one place a
 /// `NumberError` gains a span and an NML code — so a domain-rejected
 /// facet (`min = 1e9999`) reports the SAME NML0013/NML0014 surface as
@@ -428,6 +435,44 @@ fn facets_of(te: &ast::TypeExpr, errors: &mut Vec<NmlError>) -> Vec<FacetExpr> {
                 name: String::new(),
                 span,
             });
+            // String-valued facets (`currency = "USD"`, or a `[…]` list of
+            // strings) decode through the config-literal string decoder,
+            // same decode-or-drop contract as the numeric path below.
+            if let Some(b) = f.boolean() {
+                let value = crate::types::Value::Bool(b.text() == "true");
+                return Some(FacetExpr {
+                    key,
+                    value: SpannedValue::new(value, token_span(&b)),
+                    span,
+                });
+            }
+            let strings = f.strings();
+            if f.l_bracket().is_some() || !strings.is_empty() {
+                let mut items = Vec::with_capacity(strings.len());
+                for tok in &strings {
+                    match super::value::decode_string_token(tok) {
+                        Ok(text) => items.push(SpannedValue::new(
+                            crate::types::Value::String(text),
+                            Span::new(
+                                usize::from(tok.text_range().start()),
+                                usize::from(tok.text_range().end()),
+                            ),
+                        )),
+                        Err(e) => {
+                            errors.push(e);
+                            return None;
+                        }
+                    }
+                }
+                let value = match f.l_bracket() {
+                    Some(lb) => SpannedValue::new(
+                        crate::types::Value::Array(items),
+                        Span::new(usize::from(lb.text_range().start()), span.end),
+                    ),
+                    None => items.pop()?,
+                };
+                return Some(FacetExpr { key, value, span });
+            }
             let (text, vspan) = if let Some(dl) = f.duration_literal() {
                 let components = dl.components();
                 let first = components.first()?.0.text().to_string();
@@ -519,12 +564,30 @@ fn type_expr(te: &ast::TypeExpr, errors: &mut Vec<NmlError>) -> FieldTypeExpr {
             name: ident_of(te.name()),
             facets: facets_of(te, errors),
         },
-        ast::TypeExprKind::Array => FieldTypeExpr::Array(Box::new(
-            te.children()
+        ast::TypeExprKind::Array => {
+            let element = te
+                .children()
                 .next()
                 .map(|t| type_expr(&t, errors))
-                .unwrap_or_else(|| bare_named(empty_ident())),
-        )),
+                .unwrap_or_else(|| bare_named(empty_ident()));
+            // `[]endpoint(minItems = 1)`: the collection keys sit in the
+            // element name's parens but constrain the list — hoist them.
+            match element {
+                FieldTypeExpr::Named { name, facets } => {
+                    let (list, element): (Vec<_>, Vec<_>) = facets
+                        .into_iter()
+                        .partition(|f| crate::model::is_collection_facet(&f.key.name));
+                    FieldTypeExpr::Array(
+                        Box::new(FieldTypeExpr::Named {
+                            name,
+                            facets: element,
+                        }),
+                        list,
+                    )
+                }
+                other => FieldTypeExpr::Array(Box::new(other), Vec::new()),
+            }
+        }
         ast::TypeExprKind::Union => {
             FieldTypeExpr::Union(te.children().map(|t| type_expr(&t, errors)).collect())
         }
@@ -555,7 +618,7 @@ fn type_expr(te: &ast::TypeExpr, errors: &mut Vec<NmlError>) -> FieldTypeExpr {
                 1 => children.pop().expect("len checked"),
                 _ => FieldTypeExpr::Union(children),
             };
-            FieldTypeExpr::Set(Box::new(element))
+            FieldTypeExpr::Set(Box::new(element), facets_of(te, errors))
         }
     }
 }
@@ -790,6 +853,30 @@ mod tests {
         to_ast(&ast::Root::cast(parse(src).syntax()).unwrap())
     }
 
+    /// `import "path"` lowers with or without a selection; `import` followed
+    /// by a name is still a block keyword.
+    #[test]
+    fn import_lowers_path_and_selection() {
+        use crate::ast::DeclarationKind;
+        let file = cst_ast("import \"lib/shared.nml\" (dbUrl, Primary)\nimport \"all.nml\"\n");
+        let DeclarationKind::Import(selective) = &file.declarations[0].kind else {
+            panic!("expected import");
+        };
+        assert_eq!(selective.path, "lib/shared.nml");
+        let names: Vec<_> = selective.names.iter().flatten().map(|n| &n.name).collect();
+        assert_eq!(names, ["dbUrl", "Primary"]);
+        let DeclarationKind::Import(whole) = &file.declarations[1].kind else {
+            panic!("expected import");
+        };
+        assert!(whole.names.is_none());
+
+        let file = cst_ast("import Legacy:\n    path = \"x\"\n");
+        assert!(matches!(
+            &file.declarations[0].kind,
+            DeclarationKind::Block(b) if b.keyword.name == "import"
+        ));
+    }
+
     /// RFC 0030: `- Name:` (trailing colon, no entries) lowers as a Named item
     /// with an empty body — an inline instance visible to validation — while
     /// `- Name` (no colon) stays a Reference link. Collapsing the colon form
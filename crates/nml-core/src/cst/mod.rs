@@ -14,7 +14,8 @@
 //! 4. **Termination / bounded output** — recovery always makes forward progress
 //!    and the error list is capped, so adversarial input is safe (RFC 0004 §9).
 //!
-//! Public surface: `parse` (→ lossless `Parse`), `parse_to_ast` /
+//! Public surface: `parse` (→ lossless `Parse`; `Parse::reparse` for an
+//! edit, reparsing only the declaration it falls in), `parse_to_ast` /
 //! `parse_to_ast_all` (→ semantic `ast`), `parse_with_comments`,
 //! `extract_schema`, the `ast` / `extract` / `lower` layers, and `edit`
 //! (structural green-tree splicing, RFC 0030 P2).
@@ -26,6 +27,7 @@ pub mod extract;
 mod lexer;
 pub mod lower;
 mod parser;
+mod reparse;
 mod syntax;
 mod value;
 
@@ -39,6 +41,7 @@ use rowan::GreenNode;
 
 /// The result of parsing: always a (best-effort, lossless) tree, plus every
 /// error (RFC 0004 §4.3).
+#[derive(Clone)]
 pub struct Parse {
     green: GreenNode,
     errors: Vec<NmlError>,
@@ -148,8 +151,14 @@ fn finalize_diagnostics(
 /// [`Parse`] too (callers needing the tree, e.g. for comments). The single home
 /// for the parse → AST + diagnostics pipeline.
 fn parse_lowered(source: &str) -> (Parse, crate::ast::File, Vec<NmlError>, usize) {
-    use ast::AstNode as _;
     let parsed = parse(source);
+    let (file, errors, suppressed) = lower_parsed(source, &parsed);
+    (parsed, file, errors, suppressed)
+}
+
+/// [`parse_lowered`] from an existing parse of `source`.
+fn lower_parsed(source: &str, parsed: &Parse) -> (crate::ast::File, Vec<NmlError>, usize) {
+    use ast::AstNode as _;
     let root = ast::Root::cast(parsed.syntax()).expect("parse always yields a Root node");
     let (file, mut errors, lower_suppressed) = lower::to_ast_with_errors(&root);
     errors.extend(parsed.errors().iter().cloned());
@@ -175,7 +184,7 @@ fn parse_lowered(source: &str) -> (Parse, crate::ast::File, Vec<NmlError>, usize
     errors.sort_by_key(|e| e.span().start);
     coalesce_expected(&mut errors);
     let suppressed = parsed.suppressed + lower_suppressed + policy_suppressed;
-    (parsed, file, errors, suppressed)
+    (file, errors, suppressed)
 }
 
 /// Merge consecutive same-offset `Expected` diagnostics by unioning their
@@ -298,9 +307,23 @@ pub fn parse_and_extract(
     crate::ast::File,
     crate::schema::ExtractedSchema,
     Vec<crate::diagnostic::Diagnostic>,
+) {
+    extract_parsed(source, &parse(source))
+}
+
+/// [`parse_and_extract`] over an existing [`Parse`] of `source` — for
+/// callers that keep one per document version (the LSP's incrementally
+/// reparsed buffers), so their findings never cost a second parse.
+pub fn extract_parsed(
+    source: &str,
+    parsed: &Parse,
+) -> (
+    crate::ast::File,
+    crate::schema::ExtractedSchema,
+    Vec<crate::diagnostic::Diagnostic>,
 ) {
     use ast::AstNode as _;
-    let (parsed, lowered_ast, errors, suppressed) = parse_lowered(source);
+    let (lowered_ast, errors, suppressed) = lower_parsed(source, parsed);
     let root = ast::Root::cast(parsed.syntax()).expect("parse always yields a Root node");
     let facet_diags = crate::schema::facet_definition_diagnostics(&lowered_ast);
     let mut diags = finalize_diagnostics(errors, suppressed);
@@ -2452,7 +2475,7 @@ service App is Base:
         assert!(errs.is_empty(), "extract errors: {errs:?}");
         let ft = &schema.models[0].fields[0].field_type;
         assert!(
-            matches!(ft, FieldType::Set(inner)
+            matches!(ft, FieldType::Set(inner, _)
                 if matches!(**inner, FieldType::Primitive { ty: PrimitiveType::String, .. })),
             "expected set<string>, got {ft}"
         );
@@ -296,6 +296,9 @@ impl<'a> Parser<'a> {
                 "const" => self.const_decl(),
                 "template" => self.template_decl(),
                 "oneof" => self.oneof_decl(),
+                // `import` stays usable as a block keyword: only a path
+                // string makes it an import.
+                "import" if self.nth(1) == SyntaxKind::String => self.import_decl(),
                 _ => self.block_decl(),
             }
         } else {
@@ -389,6 +392,25 @@ impl<'a> Parser<'a> {
         m.complete(self, SyntaxKind::TemplateDecl);
     }
 
+    /// `import "path" ( "(" name ("," name)* ")" )?`
+    fn import_decl(&mut self) {
+        let m = self.start();
+        self.bump(); // import
+        self.bump(); // "path"
+        if self.at(SyntaxKind::LParen) && !self.newline_before() {
+            self.bump(); // (
+            self.name();
+            while self.eat(SyntaxKind::Comma) {
+                if self.at(SyntaxKind::RParen) {
+                    break; // trailing comma
+                }
+                self.name();
+            }
+            self.expect(SyntaxKind::RParen);
+        }
+        m.complete(self, SyntaxKind::ImportDecl);
+    }
+
     /// `oneof name by disc (as enum)? (= "default")? : arm+`
     fn oneof_decl(&mut self) {
         let m = self.start();
@@ -845,6 +867,12 @@ impl<'a> Parser<'a> {
                 self.error_kind(crate::error::ParseErrorKind::SetSeparator);
             }
             self.expect(SyntaxKind::Gt);
+            // Collection facets (`set<string>(minItems = 1)`) follow the
+            // closing angle; which keys a set takes is the same schema-load
+            // rule as for a named type.
+            if self.at(SyntaxKind::LParen) && !self.newline_before() {
+                self.facet_list();
+            }
         } else {
             self.expect_desc(SyntaxKind::Ident, "a type name");
             // RFC 0018: a facet list may follow the type NAME
@@ -855,104 +883,150 @@ impl<'a> Parser<'a> {
             // finding singular. No ambiguity: `(` after a bare type
             // name was previously always a parse error.
             if self.at(SyntaxKind::LParen) && !self.newline_before() {
-                let fl = self.start();
-                self.bump(); // (
+                self.facet_list();
+            }
+        }
+        m.complete(self, SyntaxKind::TypeExpr);
+        self.depth -= 1;
+    }
+
+    /// An RFC 0018 facet list — `(key = value, ...)` — on the current
+    /// line, after a type name or a `set<T>` constructor. Called only at
+    /// an `(` with no newline before it.
+    fn facet_list(&mut self) {
+        let fl = self.start();
+        self.bump(); // (
+        loop {
+            // Facet lists never span lines — the field-type
+            // rule (a swallowed newline would absorb the NEXT
+            // field into this type).
+            if self.newline_before() {
+                self.expected(
+                    vec![crate::error::ExpectedItem::Desc(
+                        "a facet on the same line — facet lists do not span lines",
+                    )],
+                    None,
+                );
+                break;
+            }
+            // First failed expectation ends the list (singular
+            // findings; recovery stays structured).
+            if !self.at(SyntaxKind::Ident) {
+                self.expected(
+                    vec![crate::error::ExpectedItem::Desc(
+                        "a facet name (min, max, multipleOf, currency, minLength, pattern, minItems, ...)",
+                    )],
+                    None,
+                );
+                break;
+            }
+            let f = self.start();
+            self.bump(); // facet name
+            if self.newline_before() {
+                self.expected(
+                    vec![crate::error::ExpectedItem::Desc(
+                        "`= value` on the same line — facet lists do not span lines",
+                    )],
+                    None,
+                );
+                f.complete(self, SyntaxKind::Facet);
+                break;
+            }
+            if !self.eat(SyntaxKind::Eq) {
+                self.expected(
+                    vec![crate::error::ExpectedItem::Desc("`=` and a facet value")],
+                    None,
+                );
+                f.complete(self, SyntaxKind::Facet);
+                break;
+            }
+            if self.newline_before() {
+                self.expected(
+                    vec![crate::error::ExpectedItem::Desc(
+                        "the facet value on the same line — facet lists do not span lines",
+                    )],
+                    None,
+                );
+                f.complete(self, SyntaxKind::Facet);
+                break;
+            }
+            // A number or duration literal, optionally signed, a
+            // string / one-line list of strings (`currency = ["USD",
+            // "GBP"]`), or `true`/`false` (`nonEmpty = true`) — the
+            // only facet value types (schemas are contracts, not
+            // programs; no references, no expressions). Which key
+            // takes which kind is a schema-load rule (NML2058).
+            if self.at(SyntaxKind::String) {
+                self.bump(); // string
+            } else if self.at(SyntaxKind::Ident) && matches!(self.current_text(), "true" | "false")
+            {
+                self.bump(); // boolean
+            } else if self.at(SyntaxKind::LBracket) {
+                self.bump(); // [
+                let mut closed = false;
                 loop {
-                    // Facet lists never span lines — the field-type
-                    // rule (a swallowed newline would absorb the NEXT
-                    // field into this type).
-                    if self.newline_before() {
+                    if self.newline_before() || !self.at(SyntaxKind::String) {
                         self.expected(
                             vec![crate::error::ExpectedItem::Desc(
-                                "a facet on the same line — facet lists do not span lines",
+                                "a string on the same line — facet lists do not span lines",
                             )],
                             None,
                         );
                         break;
                     }
-                    // First failed expectation ends the list (singular
-                    // findings; recovery stays structured).
-                    if !self.at(SyntaxKind::Ident) {
-                        self.expected(
-                            vec![crate::error::ExpectedItem::Desc(
-                                "a facet name (min, max, exclusiveMin, exclusiveMax, multipleOf)",
-                            )],
-                            None,
-                        );
-                        break;
-                    }
-                    let f = self.start();
-                    self.bump(); // facet name
-                    if self.newline_before() {
-                        self.expected(
-                            vec![crate::error::ExpectedItem::Desc(
-                                "`= value` on the same line — facet lists do not span lines",
-                            )],
-                            None,
-                        );
-                        f.complete(self, SyntaxKind::Facet);
-                        break;
-                    }
-                    if !self.eat(SyntaxKind::Eq) {
-                        self.expected(
-                            vec![crate::error::ExpectedItem::Desc("`=` and a number literal")],
-                            None,
-                        );
-                        f.complete(self, SyntaxKind::Facet);
-                        break;
-                    }
-                    if self.newline_before() {
-                        self.expected(
-                            vec![crate::error::ExpectedItem::Desc(
-                                "the facet value on the same line — facet lists do not span lines",
-                            )],
-                            None,
-                        );
-                        f.complete(self, SyntaxKind::Facet);
-                        break;
-                    }
-                    // A number or duration literal, optionally signed —
-                    // the only facet value types (schemas are contracts,
-                    // not programs; no references, no expressions).
-                    self.eat(SyntaxKind::Dash);
-                    if !self.at(SyntaxKind::Number) {
-                        self.expected(
-                            vec![crate::error::ExpectedItem::Desc(
-                                "a number or duration literal",
-                            )],
-                            None,
-                        );
-                        f.complete(self, SyntaxKind::Facet);
-                        break;
-                    }
-                    if self.peek_number_suffix() {
-                        let suffix_text = self.token_text_at(self.pos + 1).to_string();
-                        let dl = self.start();
-                        self.bump(); // magnitude
-                        self.bump(); // unit (facet values have no money path)
-                        if crate::duration::is_duration_suffix_shape(&suffix_text) {
-                            self.consume_duration_component_tail();
-                        }
-                        dl.complete(self, SyntaxKind::DurationLiteral);
-                    } else {
-                        self.bump(); // bare number
-                    }
-                    f.complete(self, SyntaxKind::Facet);
+                    self.bump(); // string
                     if !self.eat(SyntaxKind::Comma) {
+                        closed = !self.newline_before() && self.eat(SyntaxKind::RBracket);
+                        if !closed {
+                            self.expected(
+                                vec![crate::error::ExpectedItem::Kind(SyntaxKind::RBracket)],
+                                None,
+                            );
+                        }
                         break;
                     }
                 }
-                if self.newline_before() {
-                    // Already reported above (or the list simply ends the
-                    // line): never consume a cross-line `)`.
+                if !closed {
+                    f.complete(self, SyntaxKind::Facet);
+                    break;
+                }
+            } else {
+                self.eat(SyntaxKind::Dash);
+                if !self.at(SyntaxKind::Number) {
+                    self.expected(
+                        vec![crate::error::ExpectedItem::Desc(
+                            "a number, duration, string, or boolean literal",
+                        )],
+                        None,
+                    );
+                    f.complete(self, SyntaxKind::Facet);
+                    break;
+                }
+                if self.peek_number_suffix() {
+                    let suffix_text = self.token_text_at(self.pos + 1).to_string();
+                    let dl = self.start();
+                    self.bump(); // magnitude
+                    self.bump(); // unit (facet values have no money path)
+                    if crate::duration::is_duration_suffix_shape(&suffix_text) {
+                        self.consume_duration_component_tail();
+                    }
+                    dl.complete(self, SyntaxKind::DurationLiteral);
                 } else {
-                    self.expect(SyntaxKind::RParen);
+                    self.bump(); // bare number
                 }
-                fl.complete(self, SyntaxKind::FacetList);
+            }
+            f.complete(self, SyntaxKind::Facet);
+            if !self.eat(SyntaxKind::Comma) {
+                break;
             }
         }
-        m.complete(self, SyntaxKind::TypeExpr);
-        self.depth -= 1;
+        if self.newline_before() {
+            // Already reported above (or the list simply ends the
+            // line): never consume a cross-line `)`.
+        } else {
+            self.expect(SyntaxKind::RParen);
+        }
+        fl.complete(self, SyntaxKind::FacetList);
     }
 
     /// Enter the value/type recursion guard; on overflow, emit a diagnostic and
@@ -0,0 +1,276 @@
+//! Incremental reparsing at declaration granularity — the editor's
+//! per-keystroke path.
+//!
+//! A top-level declaration starts at column 0 with the lexer in its ground
+//! state (no open indentation, no open `"""`), so its tree depends only on
+//! its own text. An edit that falls inside one declaration therefore
+//! reparses just that declaration's new text and swaps the result into the
+//! green tree with [`SyntaxNode::replace_with`]; every other declaration's
+//! green node is reused as is.
+//!
+//! The fast path is taken only when it provably matches a full parse: the
+//! new text must parse cleanly as exactly one declaration, keep its line
+//! structure at the declaration's end, and no existing error may touch the
+//! edited declaration (errors before it are kept, errors after it shift by
+//! the edit's length). Anything else falls back to [`parse`] — a slower
+//! keystroke, never a different tree.
+
+use super::edit::SpliceEdit;
+use super::{Parse, SyntaxKind, SyntaxNode, parse};
+
+impl Parse {
+    /// The parse of `new_source`, which is this parse's source with `edit`
+    /// applied — reusing every top-level declaration the edit leaves
+    /// untouched. Always equal to `parse(new_source)`.
+    pub fn reparse(&self, edit: &SpliceEdit, new_source: &str) -> Parse {
+        self.reparse_declaration(edit, new_source)
+            .unwrap_or_else(|| parse(new_source))
+    }
+
+    /// The fast path: reparse only the declaration `edit` falls in, or
+    /// `None` when the edit can reach past it.
+    fn reparse_declaration(&self, edit: &SpliceEdit, new_source: &str) -> Option<Parse> {
+        // A clipped error list cannot be carried over exactly.
+        if self.suppressed > 0 {
+            return None;
+        }
+        let root = self.syntax();
+        let old_len = usize::from(root.text_range().end());
+        let (start, end) = (edit.span.start, edit.span.end);
+        if start > end
+            || end > old_len
+            || old_len - (end - start) + edit.replacement.len() != new_source.len()
+        {
+            return None;
+        }
+
+        // The declaration whose text after its first token holds the edit.
+        // Its leading trivia (blank lines, comments) is excluded: where those
+        // attach depends on the declaration before it.
+        let node: SyntaxNode = root.children().find(|n| {
+            first_token_start(n).is_some_and(|first| first < start)
+                && end <= usize::from(n.text_range().end())
+        })?;
+        let old_start = usize::from(node.text_range().start());
+        let old_end = usize::from(node.text_range().end());
+        let new_end = old_end - (end - start) + edit.replacement.len();
+        let fragment = new_source.get(old_start..new_end)?;
+        // A declaration that lost (or gained) its final newline runs into
+        // (or apart from) the next line: not a local change.
+        if node.text().to_string().ends_with('\n') != fragment.ends_with('\n') {
+            return None;
+        }
+
+        let mut errors = Vec::with_capacity(self.errors.len());
+        let delta = new_end as isize - old_end as isize;
+        for error in &self.errors {
+            let span = error.span();
+            if span.start < old_start && span.end <= old_start {
+                errors.push(error.clone());
+            } else if span.start > old_end {
+                errors.push(error.shifted(delta)?);
+            } else {
+                return None;
+            }
+        }
+
+        let reparsed = parse(fragment);
+        if !reparsed.errors.is_empty() {
+            return None;
+        }
+        let fragment_root = reparsed.syntax();
+        let mut children = fragment_root.children_with_tokens();
+        let (Some(rowan::NodeOrToken::Node(declaration)), None) =
+            (children.next(), children.next())
+        else {
+            return None;
+        };
+        // A declaration ending mid-line with an indented continuation open
+        // would, in context, absorb the blank lines that follow it.
+        if !fragment.ends_with('\n')
+            && new_end < new_source.len()
+            && declaration
+                .descendants_with_tokens()
+                .any(|e| e.kind() == SyntaxKind::Indent)
+        {
+            return None;
+        }
+
+        Some(Parse {
+            green: node.replace_with(declaration.green().into_owned()),
+            errors,
+            suppressed: 0,
+        })
+    }
+}
+
+/// Where `node`'s first non-trivia token starts.
+fn first_token_start(node: &SyntaxNode) -> Option<usize> {
+    node.descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .find(|t| {
+            !matches!(
+                t.kind(),
+                SyntaxKind::Whitespace
+                    | SyntaxKind::Newline
+                    | SyntaxKind::Comment
+                    | SyntaxKind::Indent
+                    | SyntaxKind::Dedent
+            )
+        })
+        .map(|t| usize::from(t.text_range().start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::Span;
+
+    const SOURCE: &str = "\
+// Shared settings.
+const region = \"eu-west-1\"
+
+service Api:
+    host = \"localhost\"
+    port = 8080
+    limits:
+        rate = 100
+
+    // Inner comment.
+    timeout = 30s
+
+[]route routes:
+    - Home:
+        path = \"/\"
+    - Docs:
+        path = \"/docs\"
+
+model service:
+    host string
+    port number = 80
+";
+
+    fn apply(source: &str, edit: &SpliceEdit) -> String {
+        let mut out = source.to_string();
+        out.replace_range(edit.span.start..edit.span.end, &edit.replacement);
+        out
+    }
+
+    fn edit(start: usize, end: usize, replacement: &str) -> SpliceEdit {
+        SpliceEdit {
+            span: Span::new(start, end),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    /// The incremental result for `edit`, asserted equal to a full parse —
+    /// tree and errors.
+    fn check(source: &str, edit: &SpliceEdit) -> Parse {
+        let old = parse(source);
+        let new_source = apply(source, edit);
+        let reparsed = old.reparse(edit, &new_source);
+        let full = parse(&new_source);
+        assert_eq!(
+            reparsed.green, full.green,
+            "tree mismatch for {edit:?} on {source:?}"
+        );
+        assert_eq!(
+            format!("{:?}", reparsed.errors),
+            format!("{:?}", full.errors),
+            "error mismatch for {edit:?} on {source:?}"
+        );
+        reparsed
+    }
+
+    fn fast_path_taken(source: &str, edit: &SpliceEdit) -> bool {
+        parse(source)
+            .reparse_declaration(edit, &apply(source, edit))
+            .is_some()
+    }
+
+    #[test]
+    fn edit_inside_a_block_reparses_only_that_block() {
+        let at = SOURCE.find("8080").unwrap();
+        let edit = edit(at, at + 4, "9090");
+        assert!(fast_path_taken(SOURCE, &edit));
+        check(SOURCE, &edit);
+
+        // Every other declaration's green node is the old one, shared.
+        let old = parse(SOURCE);
+        let reparsed = old.reparse(&edit, &apply(SOURCE, &edit));
+        let old_decls: Vec<_> = old.syntax().children().collect();
+        let new_decls: Vec<_> = reparsed.syntax().children().collect();
+        assert_eq!(old_decls.len(), new_decls.len());
+        let shared = old_decls
+            .iter()
+            .zip(&new_decls)
+            .filter(|(a, b)| std::ptr::eq(a.green().as_ref(), b.green().as_ref()))
+            .count();
+        assert_eq!(shared, old_decls.len() - 1);
+    }
+
+    #[test]
+    fn inserted_lines_and_const_edits_take_the_fast_path() {
+        let at = SOURCE.find("    timeout").unwrap();
+        let insert = edit(at, at, "    retries = 3\n");
+        assert!(fast_path_taken(SOURCE, &insert));
+        check(SOURCE, &insert);
+
+        // The end of a `const` line (its newline belongs to the root).
+        let at = SOURCE.find("\"eu-west-1\"").unwrap() + "\"eu-west-1".len();
+        let append = edit(at, at, "b");
+        assert!(fast_path_taken(SOURCE, &append));
+        check(SOURCE, &append);
+    }
+
+    #[test]
+    fn errors_elsewhere_are_kept_and_shifted() {
+        let source =
+            "service A:\n    x = = 1\n\nservice B:\n    y = 2\n\nservice C:\n    z = = 3\n";
+        let at = source.find("y = 2").unwrap();
+        let edit = edit(at, at + 1, "yy");
+        assert!(fast_path_taken(source, &edit));
+        let reparsed = check(source, &edit);
+        assert_eq!(reparsed.errors().len(), parse(source).errors().len());
+    }
+
+    #[test]
+    fn edits_that_reach_past_a_declaration_fall_back() {
+        // Dropping a block's last newline joins it to the next declaration.
+        let at = SOURCE.find("rate = 100\n").unwrap() + "rate = 100\n".len();
+        let joined = edit(at - 1, at, "");
+        check(SOURCE, &joined);
+        // An opened `"""` swallows what follows.
+        let at = SOURCE.find("\"localhost\"").unwrap();
+        let opened = edit(at, at + 1, "\"\"\"");
+        assert!(!fast_path_taken(SOURCE, &opened));
+        check(SOURCE, &opened);
+        // A new top-level declaration typed inside a block's trailing lines.
+        let at = SOURCE.find("[]route").unwrap();
+        let split = edit(at - 1, at - 1, "\nconst x = 1\n");
+        assert!(!fast_path_taken(SOURCE, &split));
+        check(SOURCE, &split);
+        // An edit spanning two declarations.
+        let from = SOURCE.find("30s").unwrap();
+        let to = SOURCE.find("Home").unwrap();
+        let spanning = edit(from, to, "1s\n\n[]route routes:\n    - ");
+        assert!(!fast_path_taken(SOURCE, &spanning));
+        check(SOURCE, &spanning);
+    }
+
+    /// Every single-character insertion and deletion at every offset —
+    /// including the layout-sensitive ones (newlines, indentation, colons,
+    /// quotes) — reparses to exactly the full parse.
+    #[test]
+    fn every_small_edit_matches_a_full_parse() {
+        let insertions = ["x", " ", "\n", ":", "=", "-", "\"", "\"\"\"", "//", "    "];
+        for at in 0..=SOURCE.len() {
+            for text in insertions {
+                check(SOURCE, &edit(at, at, text));
+            }
+            if at < SOURCE.len() {
+                check(SOURCE, &edit(at, at + 1, ""));
+            }
+        }
+    }
+}
@@ -80,6 +80,8 @@ pub enum SyntaxKind {
     TemplateDecl,
     OneOfDecl,
     OneOfArm,
+    /// `import "shared.nml"` / `import "shared.nml" (a, b)`.
+    ImportDecl,
     Name,
     Extends,
     // bodies & entries
@@ -163,6 +165,7 @@ impl SyntaxKind {
             TemplateDecl => "a template declaration",
             OneOfDecl => "a oneof declaration",
             OneOfArm => "a oneof arm",
+            ImportDecl => "an import declaration",
             Name => "a name",
             Extends => "an `is` clause",
             Body => "a block body",
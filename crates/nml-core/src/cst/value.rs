@@ -647,6 +647,13 @@ pub(super) fn parse_number(raw: &str, span: Span) -> Result<Number, NmlError> {
             crate::decimal::NumberError::Range(issue) => {
                 crate::error::ParseErrorKind::NumberOutOfRange { issue }
             }
+            // Arithmetic-only kinds never flow through a parse; degrade to
+            // the generic invalid-number code rather than panic.
+            crate::decimal::NumberError::Inexact | crate::decimal::NumberError::DivisionByZero => {
+                crate::error::ParseErrorKind::InvalidNumber {
+                    raw: crate::error::echo_capture(raw),
+                }
+            }
         };
         NmlError::syntax(kind, span)
     })
@@ -53,7 +53,9 @@ use serde::de::{
 };
 
 use crate::ast::*;
+use crate::diagnostic::{Diagnostic, codes};
 use crate::resolve::{self, ValueResolver};
+use crate::span::Span;
 use crate::template;
 use crate::types::{Number, Value};
 
@@ -71,10 +73,29 @@ macro_rules! deserialize_int {
 }
 
 /// Errors that can occur during NML deserialization.
+///
+/// A shape error keeps its *site*: the structured key path from the
+/// deserialized root and the innermost source span the deserializers
+/// passed through — so an embedding application can point at the line
+/// that failed (`app.nml:12:5: error[NML3009]: …`) via
+/// [`Error::to_diagnostic`], the same finding shape `nml check` prints.
 #[derive(Debug)]
 pub enum Error {
     /// A deserialization / shape error (serde-level message).
-    De(String),
+    De {
+        /// The redacted reason, without the path prefix (see
+        /// [`de::Error`]'s impl below: messages never echo values).
+        message: String,
+        /// Outermost-first key path to the failing site; empty at the
+        /// root.
+        path: Vec<PathSegment>,
+        /// The innermost located site the error unwound through — a
+        /// property's value, a nested block, a list item, an array
+        /// element. `None` only for root-level failures (a missing
+        /// top-level field) and for [`from_value`], whose input carries
+        /// no span.
+        span: Option<Span>,
+    },
     /// A value-resolution failure (`$ENV`, fallback, reference cycle). The typed
     /// [`ResolveError`](crate::resolve::ResolveError) is **preserved** (not flattened to a
     /// string) so a caller can react to a specific kind — e.g. remap
@@ -83,26 +104,106 @@ pub enum Error {
     Resolve(crate::resolve::ResolveError),
 }
 
-impl Error {
-    /// Prefix a `De` message with the failing field, building a key path
-    /// as nested deserializers unwind (`field \`database\`: field
-    /// \`port\`: u8 value out of range`). This is the locator half of
-    /// the redaction posture: messages never echo values, so the path —
-    /// which errors historically lacked entirely — pinpoints the site
-    /// instead. Typed `Resolve` errors pass through untouched: they
-    /// self-describe (the env var name) and callers match the variant.
-    fn with_field(self, key: &str) -> Self {
+/// One step of an [`Error::De`] key path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A property, nested block, or named list item (by its label).
+    Field(String),
+    /// An anonymous list item or array element, by position.
+    Element(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::De(msg) => Error::De(format!("field `{key}`: {msg}")),
-            other => other,
+            PathSegment::Field(key) => write!(f, "field `{key}`"),
+            PathSegment::Element(index) => write!(f, "element {index}"),
         }
     }
+}
+
+impl Error {
+    /// A shape error with no site yet; the deserializers locate it as
+    /// they unwind.
+    fn de(message: impl Into<String>) -> Self {
+        Error::De {
+            message: message.into(),
+            path: Vec::new(),
+            span: None,
+        }
+    }
+
+    /// Prefix the failing field onto the key path as nested deserializers
+    /// unwind (`field \`database\`: field \`port\`: u8 value out of
+    /// range`). This is the locator half of the redaction posture:
+    /// messages never echo values, so the path — which errors
+    /// historically lacked entirely — pinpoints the site instead. Typed
+    /// `Resolve` errors pass through untouched: they self-describe (the
+    /// env var name) and callers match the variant.
+    fn with_field(self, key: &str) -> Self {
+        self.with_segment(PathSegment::Field(key.to_string()))
+    }
 
     /// Element twin of [`Error::with_field`] for array positions.
     fn with_element(self, index: usize) -> Self {
+        self.with_segment(PathSegment::Element(index))
+    }
+
+    fn with_segment(mut self, segment: PathSegment) -> Self {
+        if let Error::De { path, .. } = &mut self {
+            path.insert(0, segment);
+        }
+        self
+    }
+
+    /// Record the site's span — innermost wins, so an outer block never
+    /// overwrites the property inside it that actually failed.
+    fn at(mut self, site: Span) -> Self {
+        if let Error::De {
+            span: span @ None, ..
+        } = &mut self
+        {
+            *span = Some(site);
+        }
+        self
+    }
+
+    /// The source span of the failing site, when one is known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::De { span, .. } => *span,
+            Error::Resolve(_) => None,
+        }
+    }
+
+    /// The key path to the failing site (empty for resolution errors,
+    /// which fail before deserialization walks the tree).
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            Error::De { path, .. } => path,
+            Error::Resolve(_) => &[],
+        }
+    }
+
+    /// Lower into the unified findings model (RFC 0008): shape errors as
+    /// `NML3009` at the failing site, resolution failures as `NML1003`
+    /// (span-less — resolution runs before the walk that locates). The
+    /// message is this error's `Display`, path included; attach a
+    /// [`source`](crate::diagnostic::Diagnostic::with_source) for
+    /// multi-file hosts.
+    pub fn to_diagnostic(&self) -> Diagnostic {
         match self {
-            Error::De(msg) => Error::De(format!("element {index}: {msg}")),
-            other => other,
+            Error::De { span, .. } => {
+                let diag =
+                    Diagnostic::error(self.to_string()).with_code(codes::DESERIALIZE_MISMATCH);
+                match span {
+                    Some(span) => diag.with_span(*span),
+                    None => diag,
+                }
+            }
+            Error::Resolve(e) => {
+                Diagnostic::error(e.to_string()).with_code(codes::UNRESOLVED_VALUE)
+            }
         }
     }
 
@@ -113,7 +214,7 @@ impl Error {
     pub fn env_disabled_var(&self) -> Option<&str> {
         match self {
             Error::Resolve(e) => e.env_disabled_var(),
-            Error::De(_) => None,
+            Error::De { .. } => None,
         }
     }
 }
@@ -121,7 +222,12 @@ impl Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::De(msg) => write!(f, "{msg}"),
+            Error::De { message, path, .. } => {
+                for segment in path {
+                    write!(f, "{segment}: ")?;
+                }
+                write!(f, "{message}")
+            }
             Error::Resolve(e) => write!(f, "{e}"),
         }
     }
@@ -141,25 +247,25 @@ impl std::error::Error for Error {}
 /// half of the UX, and never carry values.
 impl de::Error for Error {
     fn custom<T: fmt::Display>(msg: T) -> Self {
-        Error::De(msg.to_string())
+        Error::de(msg.to_string())
     }
 
     fn invalid_type(unexp: de::Unexpected, exp: &dyn de::Expected) -> Self {
-        Error::De(format!(
+        Error::de(format!(
             "invalid type: {}, expected {exp}",
             unexpected_kind(&unexp)
         ))
     }
 
     fn invalid_value(unexp: de::Unexpected, exp: &dyn de::Expected) -> Self {
-        Error::De(format!(
+        Error::de(format!(
             "invalid value: {}, expected {exp}",
             unexpected_kind(&unexp)
         ))
     }
 
     fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
-        Error::De(if expected.is_empty() {
+        Error::de(if expected.is_empty() {
             "unknown variant: there are no variants".to_string()
         } else {
             format!("unknown variant, expected one of: {}", expected.join(", "))
@@ -223,12 +329,12 @@ fn number_to_int<T: TryFrom<i128> + TryFrom<u128>>(
     type_name: &'static str,
 ) -> Result<T, Error> {
     if let Some(i) = n.to_i128() {
-        return T::try_from(i).map_err(|_| Error::De(format!("{type_name} value out of range")));
+        return T::try_from(i).map_err(|_| Error::de(format!("{type_name} value out of range")));
     }
     if let Some(u) = n.to_u128() {
-        return T::try_from(u).map_err(|_| Error::De(format!("{type_name} value out of range")));
+        return T::try_from(u).map_err(|_| Error::de(format!("{type_name} value out of range")));
     }
-    Err(Error::De(if n.is_integral() {
+    Err(Error::de(if n.is_integral() {
         format!("{type_name} value out of range")
     } else {
         format!("{type_name} value has a fractional part")
@@ -327,7 +433,9 @@ impl<'de> de::Deserializer<'de> for BodyDeserializer<'de> {
 
 enum BodyMapEntry<'a> {
     Property(&'a Property),
-    Block(&'a Body, &'a str),
+    /// A nested block and its key, with the entry's span (the site a
+    /// failure inside the block's own shape — a missing field — reports).
+    Block(&'a Body, &'a str, Span),
     /// `.name = value` shared property (deserializes like a normal property value).
     SharedScalar {
         key: &'a str,
@@ -335,6 +443,18 @@ enum BodyMapEntry<'a> {
     },
 }
 
+impl BodyMapEntry<'_> {
+    /// Where a failure deserializing this entry's value locates: the
+    /// value itself for scalars, the entry for blocks.
+    fn span(&self) -> Span {
+        match self {
+            BodyMapEntry::Property(p) => p.value.span,
+            BodyMapEntry::Block(_, _, span) => *span,
+            BodyMapEntry::SharedScalar { value, .. } => value.span,
+        }
+    }
+}
+
 fn collect_body_map_entries<'a>(body: &'a Body) -> Vec<BodyMapEntry<'a>> {
     use crate::ast::SharedPropertyKind;
     body.entries
@@ -342,10 +462,12 @@ fn collect_body_map_entries<'a>(body: &'a Body) -> Vec<BodyMapEntry<'a>> {
         .filter_map(|e| match &e.kind {
             BodyEntryKind::Property(p) => Some(BodyMapEntry::Property(p)),
             BodyEntryKind::NestedBlock(nb) => {
-                Some(BodyMapEntry::Block(&nb.body, nb.name.name.as_str()))
+                Some(BodyMapEntry::Block(&nb.body, nb.name.name.as_str(), e.span))
             }
             BodyEntryKind::SharedProperty(sp) => match &sp.kind {
-                SharedPropertyKind::Block(b) => Some(BodyMapEntry::Block(b, sp.name.name.as_str())),
+                SharedPropertyKind::Block(b) => {
+                    Some(BodyMapEntry::Block(b, sp.name.name.as_str(), e.span))
+                }
                 SharedPropertyKind::Scalar(sv) => Some(BodyMapEntry::SharedScalar {
                     key: sp.name.name.as_str(),
                     value: sv,
@@ -387,7 +509,7 @@ impl<'de> MapAccess<'de> for BodyMapAccess<'de> {
         }
         let key = match &self.entries[self.index] {
             BodyMapEntry::Property(p) => p.name.name.as_str(),
-            BodyMapEntry::Block(_, name) => name,
+            BodyMapEntry::Block(_, name, _) => name,
             BodyMapEntry::SharedScalar { key, .. } => key,
         };
         seed.deserialize(de::value::StrDeserializer::new(key))
@@ -402,19 +524,19 @@ impl<'de> MapAccess<'de> for BodyMapAccess<'de> {
         self.index += 1;
         let key = match entry {
             BodyMapEntry::Property(p) => p.name.name.as_str(),
-            BodyMapEntry::Block(_, name) => name,
+            BodyMapEntry::Block(_, name, _) => name,
             BodyMapEntry::SharedScalar { key, .. } => key,
         };
         match entry {
             BodyMapEntry::Property(prop) => seed.deserialize(ValueDeserializer {
                 value: &prop.value.value,
             }),
-            BodyMapEntry::Block(body, _) => seed.deserialize(NestedBlockDeserializer { body }),
+            BodyMapEntry::Block(body, ..) => seed.deserialize(NestedBlockDeserializer { body }),
             BodyMapEntry::SharedScalar { value, .. } => seed.deserialize(ValueDeserializer {
                 value: &value.value,
             }),
         }
-        .map_err(|e| e.with_field(key))
+        .map_err(|e| e.with_field(key).at(entry.span()))
     }
 }
 
@@ -655,7 +777,7 @@ impl<'de> SeqAccess<'de> for ListItemSeqAccess<'de> {
                 .deserialize(de::value::StrDeserializer::<Error>::new(s))
                 .map(Some),
         }
-        .map_err(locate)
+        .map_err(|e| locate(e).at(item.span))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -771,7 +893,7 @@ impl<'de> MapAccess<'de> for NamedItemMapAccess<'de> {
         }
         let key = match &self.body_entries[self.body_index] {
             BodyMapEntry::Property(p) => p.name.name.as_str(),
-            BodyMapEntry::Block(_, name) => name,
+            BodyMapEntry::Block(_, name, _) => name,
             BodyMapEntry::SharedScalar { key, .. } => key,
         };
         seed.deserialize(de::value::StrDeserializer::new(key))
@@ -796,19 +918,19 @@ impl<'de> MapAccess<'de> for NamedItemMapAccess<'de> {
         self.body_index += 1;
         let key = match entry {
             BodyMapEntry::Property(p) => p.name.name.as_str(),
-            BodyMapEntry::Block(_, name) => name,
+            BodyMapEntry::Block(_, name, _) => name,
             BodyMapEntry::SharedScalar { key, .. } => key,
         };
         match entry {
             BodyMapEntry::Property(prop) => seed.deserialize(ValueDeserializer {
                 value: &prop.value.value,
             }),
-            BodyMapEntry::Block(body, _) => seed.deserialize(NestedBlockDeserializer { body }),
+            BodyMapEntry::Block(body, ..) => seed.deserialize(NestedBlockDeserializer { body }),
             BodyMapEntry::SharedScalar { value, .. } => seed.deserialize(ValueDeserializer {
                 value: &value.value,
             }),
         }
-        .map_err(|e| e.with_field(key))
+        .map_err(|e| e.with_field(key).at(entry.span()))
     }
 }
 
@@ -834,17 +956,17 @@ impl<'de> MapAccess<'de> for NamedItemMapAccess<'de> {
 ///   target means the caller used a non-resolving entry point; say so.
 fn coercion_provenance_error(target: &str, value: &Value) -> Error {
     match value {
-        Value::String(_) => Error::De(format!(
+        Value::String(_) => Error::de(format!(
             "expected {target}, got a quoted string literal — the quoted spelling was \
              replaced by the typed literal (drop the quotes); only `$ENV.KEY`-resolved \
              text coerces into typed fields"
         )),
-        Value::Secret(s) => Error::De(format!(
+        Value::Secret(s) => Error::de(format!(
             "expected {target}, got the unresolved reference `{s}` — this entry point \
              does not resolve `$ENV`; deserialize through a resolving entry point \
              (`from_body_resolved`/`from_body_defaulted`)"
         )),
-        other => Error::De(format!("expected {target}, got {}", other.type_name())),
+        other => Error::de(format!("expected {target}, got {}", other.type_name())),
     }
 }
 
@@ -861,7 +983,7 @@ fn coerce_to_number(value: &Value, target: &'static str) -> Result<Number, Error
         other => return Err(coercion_provenance_error(target, other)),
     };
     Number::parse_coercion(r.as_str()).map_err(|e| {
-        Error::De(format!(
+        Error::de(format!(
             "expected {target}: {} resolved to text that is not {target} syntax ({e})",
             r.var()
         ))
@@ -881,7 +1003,7 @@ fn coerce_to_duration(value: &Value) -> Result<crate::duration::Duration, Error>
         other => return Err(coercion_provenance_error("duration", other)),
     };
     crate::duration::Duration::parse_text(r.as_str()).map_err(|e| {
-        Error::De(format!(
+        Error::de(format!(
             "expected duration: {} resolved to text that is not duration syntax ({e})",
             r.var()
         ))
@@ -940,7 +1062,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                 } else {
                     // No value interpolation: integer-form Display here
                     // can be ~6 KB (bounded-echo posture, §1.2).
-                    Err(Error::De(
+                    Err(Error::de(
                         "integer exceeds 128 bits; capture it exactly with an \
                          `nml_core::types::Number` field or a String"
                             .to_string(),
@@ -973,7 +1095,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
             Value::Bool(b) => visitor.visit_bool(*b),
             Value::Resolved(r) => match coerce_to_bool(self.value) {
                 Some(b) => visitor.visit_bool(b),
-                None => Err(Error::De(format!(
+                None => Err(Error::de(format!(
                     "expected bool: {} resolved to text that is not a bool \
                      (accepted: true/1/yes, false/0/no)",
                     r.var()
@@ -1015,7 +1137,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
             Value::Reference(s) | Value::Role(s) => visitor.visit_str(s),
             Value::Money(m) => visitor.visit_string(m.format_display()),
             Value::Duration(d) => visitor.visit_string(d.to_string()),
-            _ => Err(Error::De(format!(
+            _ => Err(Error::de(format!(
                 "expected string, got {}",
                 self.value.type_name()
             ))),
@@ -1035,7 +1157,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                 // leftovers are config the author wrote and the program
                 // would never see. Never silent.
                 if seq.remaining() > 0 {
-                    return Err(Error::De(format!(
+                    return Err(Error::de(format!(
                         "array has {} element(s), but the target accepts only {}",
                         items.len(),
                         seq.index
@@ -1043,7 +1165,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                 }
                 Ok(out)
             }
-            _ => Err(Error::De(format!(
+            _ => Err(Error::de(format!(
                 "expected array, got {}",
                 self.value.type_name()
             ))),
@@ -1087,7 +1209,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                 value: &primary.value,
             }
             .deserialize_enum(name, variants, visitor),
-            _ => Err(Error::De(format!(
+            _ => Err(Error::de(format!(
                 "expected string for enum {name}, got {}",
                 self.value.type_name()
             ))),
@@ -1250,7 +1372,7 @@ impl<'de> SeqAccess<'de> for ArraySeqAccess<'de> {
         self.index += 1;
         seed.deserialize(ValueDeserializer { value: &item.value })
             .map(Some)
-            .map_err(|e| e.with_element(position))
+            .map_err(|e| e.with_element(position).at(item.span))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -2585,6 +2707,73 @@ workflow W:
         assert!(!err.contains("70000"), "value must not leak: {err}");
     }
 
+    /// The path is structure, not just prefix text, and the error keeps
+    /// the innermost span it unwound through — so an embedder can print
+    /// `app.nml:3:16` from the same `Diagnostic` shape `nml check` uses.
+    #[test]
+    fn test_errors_carry_spans_and_lower_to_diagnostics() {
+        #[derive(Deserialize, Debug)]
+        struct Ep {
+            #[serde(rename = "port")]
+            _port: u8,
+        }
+        #[derive(Deserialize, Debug)]
+        struct Cfg {
+            #[serde(rename = "endpoints")]
+            _e: Vec<Ep>,
+            #[serde(rename = "ports")]
+            _ports: Vec<u8>,
+        }
+        let source = "service App:\n    ports = [1]\n    endpoints:\n        - Api:\n            port = 70000\n";
+        let file = parse_to_ast(source).unwrap();
+        let doc = Document::new(&file);
+        let body = doc.block("service", "App").body().unwrap();
+        let err = from_body::<Cfg>(body).unwrap_err();
+        assert_eq!(
+            err.path(),
+            [
+                PathSegment::Field("endpoints".into()),
+                PathSegment::Field("Api".into()),
+                PathSegment::Field("port".into()),
+            ]
+        );
+        let span = err.span().expect("a located failure");
+        assert_eq!(
+            &source[span.start..span.end],
+            "70000",
+            "innermost site wins"
+        );
+
+        let diag = err.to_diagnostic();
+        assert_eq!(
+            diag.code,
+            Some(crate::diagnostic::codes::DESERIALIZE_MISMATCH)
+        );
+        assert_eq!(diag.span, Some(span));
+        let loc = crate::span::SourceMap::new(source).location(span.start);
+        assert_eq!((loc.line, loc.column), (5, 20));
+
+        // A missing field fails in the block's own shape: it locates at
+        // the enclosing block entry, the nearest site that exists.
+        let source = "service App:\n    ports = [1]\n    endpoints:\n        - Api:\n            other = 1\n";
+        let file = parse_to_ast(source).unwrap();
+        let doc = Document::new(&file);
+        let body = doc.block("service", "App").body().unwrap();
+        let err = from_body::<Cfg>(body).unwrap_err();
+        let span = err.span().expect("the list item locates");
+        assert!(source[span.start..].starts_with("- Api:"), "{err}");
+
+        // Root-level failures have no site; resolution failures get their
+        // own code and no span.
+        let err = from_body::<Cfg>(&Body::fresh(Vec::new())).unwrap_err();
+        assert!(err.span().is_none() && err.path().is_empty());
+        let err = Error::from(crate::resolve::ResolveError::EnvNotSet("KEY".into()));
+        assert_eq!(
+            err.to_diagnostic().code,
+            Some(crate::diagnostic::codes::UNRESOLVED_VALUE)
+        );
+    }
+
     /// Fixed-size targets must never silently swallow config: an array
     /// longer than the tuple/array target it deserializes into is a
     /// hard error, not a truncation (implementation-review finding).
@@ -8,12 +8,14 @@
 //! model; f64/f32 exist only as the explicit, correctly-rounded conversions
 //! [`Number::to_f64`](crate::decimal::Number::to_f64) / [`Number::to_f32`](crate::decimal::Number::to_f32).
 //!
-//! This module is deliberately dependency-free and arithmetic-free: NML
-//! parses, stores, compares, formats, and converts numbers — it never does
-//! math on them — so the entire core is representation + one shared `const`
-//! parse path + total ordering + formatting. See RFC 0016 §1.1–§1.7 for the
-//! normative rules; the fixture names in the tests mirror the RFC's worked
-//! consequences.
+//! This module is deliberately dependency-free: the core is a
+//! representation, one shared `const` parse path, total ordering, and
+//! formatting. The
+//! arithmetic on top of it (`checked_add`/`checked_sub`/`checked_mul`, and
+//! `checked_div_rounded` with an explicit [`RoundingMode`]) is checked and
+//! exact — an unrepresentable result is a [`NumberError`], never a rounded
+//! one. See RFC 0016 §1.1–§1.7 for the normative rules; the fixture names
+//! in the tests mirror the RFC's worked consequences.
 
 use std::cmp::Ordering;
 use std::fmt;
@@ -30,11 +32,12 @@ const SCALE_LO: i64 = -6111;
 const SCALE_HI: i64 = 6176;
 
 /// The private serde handshake name (RFC 0016 §1.7 tier 3): [`Number`]'s
-/// `Deserialize` asks for a newtype struct with this name; NML's own
-/// `Deserializer` recognizes it and answers with the compact exact member
-/// encoding `{coeff}e{-scale}` (≤ ~42 bytes for any member, member-exact
-/// through the §1.4 data grammar), so `struct P { temperature: Number }`
-/// never detours through f64.
+/// `Deserialize` asks for a newtype struct with this name (and its
+/// `Serialize` wraps itself in one, which [`crate::ser`] recognizes to
+/// emit an exact literal); NML's own `Deserializer` recognizes it and
+/// answers with the compact exact member encoding `{coeff}e{-scale}` (≤ ~42
+/// bytes for any member, member-exact through the §1.4 data grammar), so
+/// `struct P { temperature: Number }` never detours through f64.
 /// Foreign formats treat the newtype as transparent and fall through to the
 /// visitor's i64/u64/i128/u128/f64/str arms.
 ///
@@ -91,6 +94,12 @@ pub enum NumberError {
     BadSeparator,
     /// The value is outside the decimal128 domain.
     Range(NumberRangeIssue),
+    /// Arithmetic only: the exact result needs more than 34 significant
+    /// digits (or, for [`Number::checked_div_rounded`], more than 34
+    /// digits at the requested scale). Never produced by a parse.
+    Inexact,
+    /// Arithmetic only: [`Number::checked_div_rounded`] by zero.
+    DivisionByZero,
 }
 
 /// Why a value falls outside the decimal128 domain (RFC 0016 §1.5).
@@ -169,6 +178,11 @@ impl fmt::Display for NumberError {
                 f.write_str("misplaced digit separator: `_` is allowed only between two digits")
             }
             NumberError::Range(issue) => issue.fmt(f),
+            NumberError::Inexact => f.write_str(
+                "result needs more than 34 significant digits; NML arithmetic \
+                 never rounds implicitly (divide with an explicit scale and rounding mode)",
+            ),
+            NumberError::DivisionByZero => f.write_str("division by zero"),
         }
     }
 }
@@ -958,6 +972,270 @@ impl Number {
     }
 }
 
+/// How [`Number::checked_div_rounded`] resolves a quotient that does not
+/// land exactly on the requested scale. The one place NML rounds, and
+/// only when the caller names the mode: every other arithmetic result is
+/// exact or an error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Toward zero (truncation).
+    Down,
+    /// Away from zero.
+    Up,
+    /// Toward +∞.
+    Ceiling,
+    /// Toward −∞.
+    Floor,
+    /// To nearest; ties away from zero (commercial rounding).
+    HalfUp,
+    /// To nearest; ties toward zero.
+    HalfDown,
+    /// To nearest; ties to the even neighbour (banker's rounding, IEEE
+    /// 754's default).
+    HalfEven,
+}
+
+/// `10^17`, the limb base of [`wide_mul`].
+const LIMB: u128 = 100_000_000_000_000_000;
+
+/// The exact product of two magnitudes below `10^34` as four base-`10^17`
+/// limbs, least significant first: every partial product is below
+/// `2 × 10^34`, so the schoolbook multiply stays inside u128 without a
+/// bignum (the same no-allocation posture as [`mulmod`]).
+fn wide_mul(a: u128, b: u128) -> [u128; 4] {
+    let (a0, a1) = (a % LIMB, a / LIMB);
+    let (b0, b1) = (b % LIMB, b / LIMB);
+    let mut limbs = [a0 * b0, a0 * b1 + a1 * b0, a1 * b1, 0];
+    for i in 0..3 {
+        limbs[i + 1] += limbs[i] / LIMB;
+        limbs[i] %= LIMB;
+    }
+    limbs
+}
+
+/// Place an exact arithmetic result `coeff × 10^(−scale)` into the domain
+/// under the storage rule (RFC 0016 §1.1), with `preferred` standing in
+/// for the written scale: normalize, reject more than 34 significant
+/// digits as [`NumberError::Inexact`], then clamp `preferred` into the
+/// constructible window — empty windows are `TooLarge`/`TooSmall`,
+/// classified exactly as the parse path classifies them.
+fn fit(coeff: i128, scale: i64, preferred: i64) -> Result<Number, NumberError> {
+    if coeff == 0 {
+        return Ok(Number {
+            coeff: 0,
+            scale: clamp_i64(preferred, 0, SCALE_HI) as i16,
+        });
+    }
+    let (mut c, mut s_min) = (coeff, scale);
+    while c % 10 == 0 {
+        c /= 10;
+        s_min -= 1;
+    }
+    let d = digit_count_u128(c.unsigned_abs()) as i64;
+    if d > 34 {
+        return Err(NumberError::Inexact);
+    }
+    let w_lo = s_min.max(SCALE_LO);
+    let w_hi = (s_min + 34 - d).min(SCALE_HI);
+    if w_lo > w_hi {
+        return Err(NumberError::Range(if s_min > SCALE_HI {
+            NumberRangeIssue::TooSmall
+        } else {
+            NumberRangeIssue::TooLarge
+        }));
+    }
+    let scale = clamp_i64(preferred, w_lo, w_hi);
+    Ok(Number {
+        coeff: c * 10i128.pow((scale - s_min) as u32),
+        scale: scale as i16,
+    })
+}
+
+/// Checked arithmetic in the decimal128 domain. Sums, differences and
+/// products are computed **exactly** and then stored under the storage
+/// rule, the exact operands' natural scale standing in for the written
+/// one (`2.50 + 1` → `3.50`, `1.5 × 2.00` → `3.000`). A result that needs
+/// more than 34 significant digits is [`NumberError::Inexact`] and one
+/// beyond the exponent range is [`NumberError::Range`] — never a silently
+/// rounded value. Division is the one operation whose results are
+/// routinely inexact, so it exists only with an explicit scale and
+/// [`RoundingMode`].
+impl Number {
+    /// `self + rhs`, exactly; the result keeps the finer operand scale
+    /// (`2.50 + 1` → `3.50`).
+    ///
+    /// ```
+    /// use nml_core::decimal::{Number, NumberError};
+    ///
+    /// let sum = Number::parse_literal("2.50").unwrap().checked_add(&Number::from(1)).unwrap();
+    /// assert_eq!(sum.to_string(), "3.50");
+    ///
+    /// // 10^40 + 1 needs 41 significant digits: an error, not 10^40.
+    /// let big = Number::parse_literal(&format!("1{}", "0".repeat(40))).unwrap();
+    /// assert_eq!(big.checked_add(&Number::from(1)), Err(NumberError::Inexact));
+    /// ```
+    pub fn checked_add(&self, rhs: &Number) -> Result<Number, NumberError> {
+        let preferred = i64::from(self.scale.max(rhs.scale));
+        let (ca, sa) = self.normalized();
+        let (cb, sb) = rhs.normalized();
+        if ca == 0 || cb == 0 {
+            let (c, s) = if ca == 0 { (cb, sb) } else { (ca, sa) };
+            return fit(c, i64::from(s), preferred);
+        }
+        // Align on the finer normalized scale. The finer operand's last
+        // digit is nonzero and the shifted one contributes a multiple of
+        // 10, so for a shift k ≥ 1 the exact sum has ≥ k + 1 significant
+        // digits — beyond 34 (or beyond i128) it is inexact without
+        // computing it.
+        let s = sa.max(sb);
+        let shift = |c: i128, from: i32| -> Option<i128> {
+            let k = (s - from) as u32;
+            if k > 34 {
+                return None;
+            }
+            c.checked_mul(10i128.pow(k))
+        };
+        let sum = shift(ca, sa)
+            .zip(shift(cb, sb))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(NumberError::Inexact)?;
+        fit(sum, i64::from(s), preferred)
+    }
+
+    /// `self − rhs`, exactly; the same scale rule as
+    /// [`Number::checked_add`].
+    pub fn checked_sub(&self, rhs: &Number) -> Result<Number, NumberError> {
+        self.checked_add(&Number {
+            coeff: -rhs.coeff,
+            scale: rhs.scale,
+        })
+    }
+
+    /// `self × rhs`, exactly; the result's preferred scale is the sum of
+    /// the operand scales (`1.5 × 2.00` → `3.000`), as in IEEE 754
+    /// decimal multiplication.
+    pub fn checked_mul(&self, rhs: &Number) -> Result<Number, NumberError> {
+        let preferred = i64::from(self.scale) + i64::from(rhs.scale);
+        let (ca, sa) = self.normalized();
+        let (cb, sb) = rhs.normalized();
+        let mut s = i64::from(sa) + i64::from(sb);
+        // Up to 68 digits: multiply in base-10^17 limbs, strip the
+        // trailing zeros a 2 × 5 pairing can create, and the result fits
+        // 34 digits exactly when the top two limbs are empty.
+        let mut limbs = wide_mul(ca.unsigned_abs(), cb.unsigned_abs());
+        if limbs != [0; 4] {
+            while limbs[0] % 10 == 0 {
+                let mut carry = 0;
+                for limb in limbs.iter_mut().rev() {
+                    let cur = carry * LIMB + *limb;
+                    *limb = cur / 10;
+                    carry = cur % 10;
+                }
+                s -= 1;
+            }
+        }
+        if limbs[2] != 0 || limbs[3] != 0 {
+            return Err(NumberError::Inexact);
+        }
+        let mag = (limbs[1] * LIMB + limbs[0]) as i128;
+        let coeff = if (ca < 0) != (cb < 0) { -mag } else { mag };
+        fit(coeff, s, preferred)
+    }
+
+    /// `self ÷ rhs` rounded to exactly `scale` fraction digits under
+    /// `mode` — the explicit-rounding escape hatch for quotients such as
+    /// `1 / 3`. The result is stored at `scale` (zero at `max(scale, 0)`,
+    /// per the zero invariant).
+    ///
+    /// Errors: [`NumberError::DivisionByZero`]; a `scale` outside
+    /// `[−6111, 6176]` is [`NumberRangeIssue::ScaleOutOfRange`]; a rounded
+    /// quotient needing more than 34 digits at `scale` is
+    /// [`NumberError::Inexact`] (ask for a coarser scale).
+    ///
+    /// ```
+    /// use nml_core::decimal::{Number, RoundingMode};
+    ///
+    /// let third = Number::from(1)
+    ///     .checked_div_rounded(&Number::from(3), 4, RoundingMode::HalfEven)
+    ///     .unwrap();
+    /// assert_eq!(third.to_string(), "0.3333");
+    /// ```
+    pub fn checked_div_rounded(
+        &self,
+        rhs: &Number,
+        scale: i16,
+        mode: RoundingMode,
+    ) -> Result<Number, NumberError> {
+        if rhs.coeff == 0 {
+            return Err(NumberError::DivisionByZero);
+        }
+        if i64::from(scale) < SCALE_LO || i64::from(scale) > SCALE_HI {
+            return Err(NumberError::Range(NumberRangeIssue::ScaleOutOfRange {
+                got: scale,
+            }));
+        }
+        let neg = (self.coeff < 0) != (rhs.coeff < 0);
+        let a = self.coeff.unsigned_abs();
+        let b = rhs.coeff.unsigned_abs();
+        // quotient coefficient = a × 10^k / b at the requested scale.
+        let k = i64::from(scale) - i64::from(self.scale) + i64::from(rhs.scale);
+        // (q, remainder vs. half the divisor, remainder nonzero)
+        let (mut q, half, inexact) = if k >= 0 {
+            // Long division one digit at a time: r < b < 10^34, so r × 10
+            // fits u128, and the loop stops as soon as q outgrows 34
+            // digits — at most ~70 steps whatever k is.
+            let (mut q, mut r) = (a / b, a % b);
+            for _ in 0..k {
+                if q > COEFF_ABS_MAX {
+                    return Err(NumberError::Inexact);
+                }
+                q = q * 10 + r * 10 / b;
+                r = r * 10 % b;
+            }
+            (q, r.cmp(&(b - r)), r != 0)
+        } else {
+            match 10u128
+                .checked_pow((-k) as u32)
+                .and_then(|p| b.checked_mul(p))
+            {
+                Some(d) => (a / d, (a % d).cmp(&(d - a % d)), a % d != 0),
+                // The divisor outgrows u128: the quotient is below one
+                // unit at `scale`, and below half of it.
+                None => (0, Ordering::Less, a != 0),
+            }
+        };
+        let away = inexact
+            && match mode {
+                RoundingMode::Down => false,
+                RoundingMode::Up => true,
+                RoundingMode::Ceiling => !neg,
+                RoundingMode::Floor => neg,
+                RoundingMode::HalfUp => half != Ordering::Less,
+                RoundingMode::HalfDown => half == Ordering::Greater,
+                RoundingMode::HalfEven => {
+                    half == Ordering::Greater || (half == Ordering::Equal && q % 2 == 1)
+                }
+            };
+        if away {
+            q += 1;
+        }
+        if q > COEFF_ABS_MAX {
+            return Err(NumberError::Inexact);
+        }
+        if q == 0 {
+            return Ok(Number {
+                coeff: 0,
+                scale: scale.max(0),
+            });
+        }
+        let q = q as i128;
+        Ok(Number {
+            coeff: if neg { -q } else { q },
+            scale,
+        })
+    }
+}
+
 /// The pinned Display algorithm (RFC 0016 §1.10): sign (from `coeff`);
 /// `scale ≤ 0` → the coefficient digits followed by `−scale` zeros;
 /// `scale > 0` → the digits split at `scale` from the right, zero-padded
@@ -1188,17 +1466,33 @@ impl TryFrom<u128> for Number {
 /// be deserialized from RON at all: the handshake token is not a valid RON
 /// identifier. Both are properties of those formats meeting the private
 /// token, not silent-loss paths: nothing rounds, everything errors.
+///
+/// The ladder rides inside the same private newtype the `Deserialize`
+/// side requests, so NML's own `Serializer` ([`crate::ser`]) can tell a
+/// `Number` from a string that merely looks like one and emit the exact
+/// literal. Every foreign format treats the newtype as transparent, so
+/// the wire shape above is unchanged.
 impl Serialize for Number {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        if self.scale <= 0 {
-            if let Some(v) = self.to_i64() {
+        serializer.serialize_newtype_struct(NUMBER_NEWTYPE_TOKEN, &NumberWire(*self))
+    }
+}
+
+/// The serialize ladder itself — see `impl Serialize for Number`.
+struct NumberWire(Number);
+
+impl Serialize for NumberWire {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let n = &self.0;
+        if n.scale <= 0 {
+            if let Some(v) = n.to_i64() {
                 return serializer.serialize_i64(v);
             }
-            if let Some(v) = self.to_u64() {
+            if let Some(v) = n.to_u64() {
                 return serializer.serialize_u64(v);
             }
         }
-        serializer.collect_str(self)
+        serializer.collect_str(n)
     }
 }
 
@@ -2411,6 +2705,158 @@ mod tests {
         }
     }
 
+    // ---- checked arithmetic ----------------------------------------------
+
+    #[test]
+    fn add_is_exact_and_keeps_the_finer_scale() {
+        assert_eq!(parts(lit("2.50").checked_add(&lit("1")).unwrap()), (350, 2));
+        assert_eq!(parts(lit("0.1").checked_add(&lit("0.2")).unwrap()), (3, 1));
+        assert_eq!(parts(lit("1").checked_sub(&lit("0.01")).unwrap()), (99, 2));
+        assert_eq!(parts(lit("1.50").checked_sub(&lit("1.5")).unwrap()), (0, 2));
+        // A carry into a 35th digit is still exact: 10^34 stores as
+        // (10^33, −1), exactly as the parser stores it.
+        let nines = lit(&"9".repeat(34));
+        assert_eq!(parts(nines.checked_add(&lit("1")).unwrap()), (p10(33), -1));
+    }
+
+    #[test]
+    fn add_rejects_inexact_and_out_of_range_sums() {
+        let big = lit(&pow10_str(40));
+        assert_eq!(big.checked_add(&lit("1")), Err(NumberError::Inexact));
+        assert_eq!(
+            lit(&format!("0.{}1", "0".repeat(6175))).checked_add(&lit("1")),
+            Err(NumberError::Inexact)
+        );
+        let half_max = lit(&format!("5{}", "0".repeat(6144)));
+        assert_eq!(
+            half_max.checked_add(&half_max),
+            Err(NumberError::Range(NumberRangeIssue::TooLarge))
+        );
+    }
+
+    #[test]
+    fn mul_is_exact_across_the_wide_product() {
+        assert_eq!(
+            parts(lit("1.5").checked_mul(&lit("2.00")).unwrap()),
+            (3000, 3)
+        );
+        assert_eq!(parts(lit("-2").checked_mul(&lit("3")).unwrap()), (-6, 0));
+        assert_eq!(parts(lit("0").checked_mul(&lit("1.50")).unwrap()), (0, 2));
+        // 5^48 × 2^48 = 10^48: a 49-digit product whose value has one
+        // significant digit.
+        let five = Number::try_from(5i128.pow(48)).unwrap();
+        let two = Number::try_from(2i128.pow(48)).unwrap();
+        assert_eq!(parts(five.checked_mul(&two).unwrap()), (p10(33), -15));
+        let nines = lit(&"9".repeat(34));
+        assert_eq!(nines.checked_mul(&nines), Err(NumberError::Inexact));
+        let tiny = lit(&format!("0.{}1", "0".repeat(5999)));
+        assert_eq!(
+            tiny.checked_mul(&tiny),
+            Err(NumberError::Range(NumberRangeIssue::TooSmall))
+        );
+    }
+
+    #[test]
+    fn div_rounds_only_as_asked() {
+        use RoundingMode::*;
+        let div = |a: &str, b: &str, scale: i16, mode| {
+            lit(a)
+                .checked_div_rounded(&lit(b), scale, mode)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(div("1", "3", 4, HalfEven), "0.3333");
+        assert_eq!(div("2", "3", 4, Down), "0.6666");
+        assert_eq!(div("2", "3", 4, HalfUp), "0.6667");
+        assert_eq!(div("-1", "3", 2, Floor), "-0.34");
+        assert_eq!(div("-1", "3", 2, Ceiling), "-0.33");
+        assert_eq!(div("1", "3", 2, Up), "0.34");
+        // Ties.
+        assert_eq!(div("2.5", "1", 0, HalfEven), "2");
+        assert_eq!(div("3.5", "1", 0, HalfEven), "4");
+        assert_eq!(div("2.5", "1", 0, HalfUp), "3");
+        assert_eq!(div("2.5", "1", 0, HalfDown), "2");
+        assert_eq!(div("-2.5", "1", 0, HalfUp), "-3");
+        // Exact quotients are untouched by every mode.
+        for mode in [Down, Up, Ceiling, Floor, HalfUp, HalfDown, HalfEven] {
+            assert_eq!(div("7.5", "2.5", 1, mode), "3.0");
+        }
+        // Negative scales quantize above the units digit.
+        assert_eq!(div("12345", "1", -2, Down), "12300");
+        assert_eq!(div("12350", "1", -2, HalfUp), "12400");
+        // A divisor scaled past u128 leaves a sub-half remainder.
+        assert_eq!(
+            parts(
+                lit("1")
+                    .checked_div_rounded(&lit("1"), -6111, HalfUp)
+                    .unwrap()
+            ),
+            (0, 0)
+        );
+        assert_eq!(
+            parts(lit("1").checked_div_rounded(&lit("1"), -6111, Up).unwrap()),
+            (1, -6111)
+        );
+    }
+
+    #[test]
+    fn div_errors() {
+        let one = lit("1");
+        assert_eq!(
+            one.checked_div_rounded(&lit("0.00"), 2, RoundingMode::HalfEven),
+            Err(NumberError::DivisionByZero)
+        );
+        assert_eq!(
+            one.checked_div_rounded(&one, 6177, RoundingMode::HalfEven),
+            Err(NumberError::Range(NumberRangeIssue::ScaleOutOfRange {
+                got: 6177
+            }))
+        );
+        assert_eq!(
+            lit(&pow10_str(33)).checked_div_rounded(&lit("0.1"), 0, RoundingMode::Down),
+            Err(NumberError::Inexact)
+        );
+    }
+
+    #[test]
+    fn seeded_arithmetic_matches_integer_oracle() {
+        // Small operands at scales 0..=6: every result is exact and the
+        // i128 cross-scaled oracle cannot overflow.
+        let mut st = 0x5eed_a417_u64;
+        for _ in 0..5_000 {
+            let ca = (xorshift(&mut st) % 2_000_001) as i128 - 1_000_000;
+            let cb = (xorshift(&mut st) % 2_000_001) as i128 - 1_000_000;
+            let sa = (xorshift(&mut st) % 7) as i16;
+            let sb = (xorshift(&mut st) % 7) as i16;
+            let a = Number::try_new(ca, sa).unwrap();
+            let b = Number::try_new(cb, sb).unwrap();
+            let s = sa.max(sb);
+            let at = |c: i128, from: i16| c * p10((s - from) as u32);
+            let sum = a.checked_add(&b).unwrap();
+            assert_eq!(sum, Number::try_new(at(ca, sa) + at(cb, sb), s).unwrap());
+            assert_eq!(sum.total_cmp(&b.checked_add(&a).unwrap()), Ordering::Equal);
+            assert_eq!(sum.checked_sub(&b).unwrap(), a, "({a}) + ({b}) - ({b})");
+            let prod = a.checked_mul(&b).unwrap();
+            assert_eq!(prod, Number::try_new(ca * cb, sa + sb).unwrap());
+            if cb != 0 {
+                let back = prod
+                    .checked_div_rounded(&b, sa, RoundingMode::Down)
+                    .unwrap();
+                assert_eq!(back, a, "({a}) * ({b}) / ({b})");
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic_messages() {
+        assert_eq!(
+            NumberError::Inexact.to_string(),
+            "result needs more than 34 significant digits; NML arithmetic \
+             never rounds implicitly (divide with an explicit scale and rounding mode)"
+        );
+        assert_eq!(NumberError::DivisionByZero.to_string(), "division by zero");
+    }
+
     // ---- §1.5 pinned messages --------------------------------------------
 
     #[test]
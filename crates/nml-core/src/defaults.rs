@@ -306,7 +306,7 @@ impl<'a> Defaulter<'a> {
             if present.contains(&field.name.as_str()) {
                 continue;
             }
-            if let Some(default) = &field.default_value {
+            if let Some(default) = admissible_default(field) {
                 entries.push(property_entry(&field.name, field.span, default.clone()));
             } else if !field.optional {
                 if let FieldTarget::Model(nested) = self.index.resolve_field(field) {
@@ -343,7 +343,7 @@ impl<'a> Defaulter<'a> {
         };
         // A set's items default exactly like a list's (RFC 0032): element
         // instances get their model defaults; uniqueness is validation's job.
-        if let FieldType::List(inner) | FieldType::Set(inner) = &field.field_type {
+        if let FieldType::List(inner, _) | FieldType::Set(inner, _) = &field.field_type {
             return self.list_body(inner, nb_body, depth + 1);
         }
         // A union field's variant is body-dependent — its structural shape or an
@@ -484,7 +484,7 @@ fn compute_defaultable(index: &SchemaIndex) -> std::collections::HashSet<&str> {
             }
             let satisfiable = model.fields.iter().all(|f| {
                 f.optional
-                    || f.default_value.is_some()
+                    || admissible_default(f).is_some()
                     || matches!(
                         index.resolve_field(f),
                         FieldTarget::Model(m) if set.contains(m.name.as_str())
@@ -502,6 +502,24 @@ fn compute_defaultable(index: &SchemaIndex) -> std::collections::HashSet<&str> {
     set
 }
 
+/// A field's declared default, unless it is a list the field's own
+/// collection facets reject (`[]endpoint(minItems = 1) = []`). The schema
+/// load already reports that default (NML2057); injecting it anyway would
+/// hand serde a value the schema forbids, so it is withheld and the field
+/// reads as absent — a missing-field error for a required `Vec`, never a
+/// silently empty one.
+fn admissible_default(field: &crate::model::FieldDef) -> Option<&SpannedValue> {
+    let default = field.default_value.as_ref()?;
+    match (&field.field_type, &default.value) {
+        (FieldType::List(_, facets) | FieldType::Set(_, facets), Value::Array(items))
+            if !facets.admits(items.len()) =>
+        {
+            None
+        }
+        _ => Some(default),
+    }
+}
+
 /// Rebuild a body, replacing each list item's body with `f(item_body)` and
 /// leaving every other entry untouched. Covers named items AND materialized
 /// positional items (`Shorthand` with a body — produced by
@@ -656,6 +674,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collection_default_rejected_by_its_facets_is_not_injected() {
+        // `= []` against `minItems = 1` is reported at schema load; the
+        // defaulted deserialize must not quietly hand serde the empty list.
+        #[derive(Debug, serde::Deserialize)]
+        struct Svc {
+            #[allow(dead_code)]
+            hosts: Vec<String>,
+        }
+        let index = index_from(
+            "model svc:\n    hosts []string(minItems = 1) = []\n    tags []string(maxItems = 2) = [\"a\"]\n",
+        );
+        let defaulted = apply_defaults(&index, "svc", &body_of("svc s:\n    x = 1\n"));
+        assert!(prop(&defaulted, "hosts").is_none());
+        assert!(matches!(prop(&defaulted, "tags"), Some(Value::Array(items)) if items.len() == 1));
+
+        let err = from_body_defaulted::<Svc>(
+            &index,
+            "svc",
+            &body_of("svc s:\n    tags = []\n"),
+            &ValueResolver::env(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("hosts"), "{err}");
+    }
+
     fn index_from(schema: &str) -> SchemaIndex {
         let mut ex = crate::cst::extract_schema(schema).0;
         crate::schema::resolve_model_inheritance(&mut ex);
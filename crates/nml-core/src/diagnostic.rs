@@ -226,6 +226,20 @@ pub mod codes {
         UNRESOLVED_REFERENCE = 1001;
         /// `const` definitions form a reference cycle.
         CONST_CYCLE = 1002;
+        /// A value failed to resolve at load time: an unset or disallowed
+        /// `$ENV` variable, an unknown variable source, or a reference chain
+        /// past the depth limit (the `de` resolving entry points).
+        UNRESOLVED_VALUE = 1003;
+        /// A template expression's path resolves to nothing in its
+        /// namespace's provider (`TemplateRenderer`).
+        UNRESOLVED_TEMPLATE_PATH = 1004;
+        /// An `import` names a file that cannot be read.
+        IMPORT_NOT_FOUND = 1005;
+        /// `import` declarations form a cycle.
+        IMPORT_CYCLE = 1006;
+        /// A selective `import` names a declaration the imported file does
+        /// not have.
+        UNKNOWN_IMPORT_NAME = 1007;
 
         /// A value is not one of the enum's declared variants.
         INVALID_ENUM_VALUE = 2000;
@@ -422,6 +436,10 @@ pub mod codes {
         /// A compound duration has a dangling magnitude without a unit
         /// suffix (`1h30`, `5m2`).
         MALFORMED_COMPOUND_DURATION = 3008;
+        /// A value does not fit the Rust type an embedding application
+        /// deserializes it into (`nml_core::de`): a wrong kind, an
+        /// out-of-range integer, a missing field, an unknown variant.
+        DESERIALIZE_MISMATCH = 3009;
 
         /// A package validator binding is fully shadowed by earlier
         /// bindings — its globs can never match first (RFC 0030).
@@ -377,7 +377,9 @@ pub fn synthesize_config_root(root_name: &str, fields: &[ConfigRootField]) -> Mo
             let inner = FieldType::ModelRef(f.model.clone());
             let field_type = match f.kind {
                 ConfigFieldKind::Block => inner,
-                ConfigFieldKind::Array => FieldType::List(Box::new(inner)),
+                ConfigFieldKind::Array => {
+                    FieldType::List(Box::new(inner), crate::model::CollectionFacets::None)
+                }
             };
             FieldDef {
                 name: f.name.clone(),
@@ -712,10 +714,13 @@ fn diff_unmodeled_remainder(
     if !o_items.is_empty() || !n_items.is_empty() {
         let synth = FieldDef {
             name: String::new(),
-            field_type: FieldType::List(Box::new(FieldType::Primitive {
-                ty: crate::types::PrimitiveType::String,
-                facets: crate::model::PrimitiveFacets::None,
-            })),
+            field_type: FieldType::List(
+                Box::new(FieldType::Primitive {
+                    ty: crate::types::PrimitiveType::String,
+                    facets: crate::model::PrimitiveFacets::None,
+                }),
+                crate::model::CollectionFacets::None,
+            ),
             optional: true,
             shorthand: false,
             default_value: None,
@@ -726,7 +731,9 @@ fn diff_unmodeled_remainder(
         let items_field = model
             .fields
             .iter()
-            .find(|f| f.shorthand && matches!(f.field_type, FieldType::List(_) | FieldType::Set(_)))
+            .find(|f| {
+                f.shorthand && matches!(f.field_type, FieldType::List(_, _) | FieldType::Set(_, _))
+            })
             .unwrap_or(&synth);
         diff_collections(
             index,
@@ -932,58 +939,65 @@ fn effective<'a>(
     name: &str,
     field: &'a FieldDef,
 ) -> Effective<'a> {
-    // Nested blocks merge across files (property-level overlay), so collect
-    // every file's sub-body; scalar/collection entries take the last file.
-    let mut bodies: Vec<(PathBuf, &'a Body)> = Vec::new();
-    let mut last_value: Option<(&'a SpannedValue, &'a Path)> = None;
-    let mut last_items: Option<(&'a [ListItem], &'a Path)> = None;
+    let layered = files.iter().map(|(file, body)| (file.as_path(), *body));
+    match keyed_entry(layered, name) {
+        // Both model-instance bodies AND list-bodied collections collect
+        // here; `collect_elems` applies last-file-wins to item bodies,
+        // `diff_bodies` overlays keyed bodies.
+        KeyedEntry::Bodies(nested) => Effective::Bodies(
+            nested
+                .into_iter()
+                .map(|(file, _, nb)| (file.to_path_buf(), &nb.body))
+                .collect(),
+        ),
+        KeyedEntry::Value(file, _, sv) => Effective::Value(sv, file),
+        KeyedEntry::Items(file, _, items) => Effective::Items(items, file),
+        KeyedEntry::Absent => match &field.default_value {
+            Some(d) => Effective::Default(d),
+            None => Effective::Absent,
+        },
+    }
+}
 
-    for (file, body) in files {
+/// What decides `key` across precedence-ordered bodies, shared by the diff
+/// engine and [`crate::layer`]. A property or modifier replaces everything
+/// written before it; nested blocks written since the last of those merge,
+/// so all of them are returned. Type-annotation modifiers carry no value
+/// and are skipped.
+pub(crate) enum KeyedEntry<'a, F> {
+    Absent,
+    /// A property, or a modifier with an inline value.
+    Value(F, &'a BodyEntry, &'a SpannedValue),
+    /// A modifier block's items.
+    Items(F, &'a BodyEntry, &'a [ListItem]),
+    Bodies(Vec<(F, &'a BodyEntry, &'a NestedBlock)>),
+}
+
+pub(crate) fn keyed_entry<'a, F: Copy>(
+    bodies: impl IntoIterator<Item = (F, &'a Body)>,
+    key: &str,
+) -> KeyedEntry<'a, F> {
+    let mut found = KeyedEntry::Absent;
+    for (file, body) in bodies {
         for entry in &body.entries {
             match &entry.kind {
-                BodyEntryKind::Property(p) if p.name.name == name => {
-                    last_value = Some((&p.value, file.as_path()));
-                    bodies.clear();
-                    last_items = None;
+                BodyEntryKind::Property(p) if p.name.name == key => {
+                    found = KeyedEntry::Value(file, entry, &p.value);
                 }
-                BodyEntryKind::NestedBlock(nb) if nb.name.name == name => {
-                    // Both model-instance bodies AND list-bodied collections
-                    // collect here; `collect_elems` applies last-file-wins to
-                    // item bodies, `diff_bodies` overlays keyed bodies.
-                    bodies.push((file.clone(), &nb.body));
-                    last_value = None;
-                    last_items = None;
-                }
-                BodyEntryKind::Modifier(m) if m.name.name == name => match &m.value {
-                    ModifierValue::Inline(sv) => {
-                        last_value = Some((sv, file.as_path()));
-                        bodies.clear();
-                        last_items = None;
-                    }
-                    ModifierValue::Block(items) => {
-                        last_items = Some((items.as_slice(), file.as_path()));
-                        last_value = None;
-                        bodies.clear();
-                    }
+                BodyEntryKind::NestedBlock(nb) if nb.name.name == key => match &mut found {
+                    KeyedEntry::Bodies(nested) => nested.push((file, entry, nb)),
+                    _ => found = KeyedEntry::Bodies(vec![(file, entry, nb)]),
+                },
+                BodyEntryKind::Modifier(m) if m.name.name == key => match &m.value {
+                    ModifierValue::Inline(sv) => found = KeyedEntry::Value(file, entry, sv),
+                    ModifierValue::Block(items) => found = KeyedEntry::Items(file, entry, items),
                     ModifierValue::TypeAnnotation { .. } => {}
                 },
                 _ => {}
             }
         }
     }
-    if let Some((items, file)) = last_items {
-        return Effective::Items(items, file);
-    }
-    if let Some((sv, file)) = last_value {
-        return Effective::Value(sv, file);
-    }
-    if !bodies.is_empty() {
-        return Effective::Bodies(bodies);
-    }
-    match &field.default_value {
-        Some(d) => Effective::Default(d),
-        None => Effective::Absent,
-    }
+    found
 }
 
 /// Compare two value-shaped effectives at a leaf. `path` already ends in the
@@ -2202,7 +2216,7 @@ fn elem_key(id: &ElemId) -> ElemKey {
 
 fn elem_type(ft: &FieldType) -> &FieldType {
     match ft {
-        FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
+        FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
             elem_type(inner)
         }
         other => other,
@@ -2211,7 +2225,7 @@ fn elem_type(ft: &FieldType) -> &FieldType {
 
 fn is_set(ft: &FieldType) -> bool {
     match ft {
-        FieldType::Set(_) => true,
+        FieldType::Set(_, _) => true,
         FieldType::Modifier(inner) => is_set(inner),
         _ => false,
     }
@@ -2223,7 +2237,7 @@ fn is_secret(ft: &FieldType) -> bool {
             ty: PrimitiveType::Secret,
             ..
         } => true,
-        FieldType::Modifier(i) | FieldType::List(i) | FieldType::Set(i) => is_secret(i),
+        FieldType::Modifier(i) | FieldType::List(i, _) | FieldType::Set(i, _) => is_secret(i),
         _ => false,
     }
 }
@@ -163,7 +163,11 @@ pub fn parse_components_cst(
                 },
                 Span::new(literal_span.start, c.magnitude_span.end),
             ),
-            crate::decimal::NumberError::Malformed => NmlError::syntax(
+            // Arithmetic-only kinds are unreachable from a parse; they
+            // share the malformed arm rather than panic.
+            crate::decimal::NumberError::Malformed
+            | crate::decimal::NumberError::Inexact
+            | crate::decimal::NumberError::DivisionByZero => NmlError::syntax(
                 crate::error::ParseErrorKind::InvalidNumber {
                     raw: crate::error::echo_capture(digits),
                 },
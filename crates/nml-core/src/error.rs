@@ -486,6 +486,35 @@ impl NmlError {
         }
     }
 
+    /// This syntax error with every source position moved by `delta`
+    /// bytes — an incremental reparse keeps the errors after the edited
+    /// declaration, which the edit shifted. `None` for the value-layer
+    /// variants, which lowering (not the parse) produces.
+    pub(crate) fn shifted(&self, delta: isize) -> Option<NmlError> {
+        let shift = |span: Span| {
+            Some(Span::new(
+                span.start.checked_add_signed(delta)?,
+                span.end.checked_add_signed(delta)?,
+            ))
+        };
+        let NmlError::Syntax { kind, span } = self else {
+            return None;
+        };
+        let kind = match kind {
+            ParseErrorKind::UnterminatedString { open, multiline } => {
+                ParseErrorKind::UnterminatedString {
+                    open: shift(*open)?,
+                    multiline: *multiline,
+                }
+            }
+            kind => kind.clone(),
+        };
+        Some(NmlError::Syntax {
+            kind,
+            span: shift(*span)?,
+        })
+    }
+
     /// Returns the human-readable error message. Owned: syntax-error
     /// messages derive from their [`ParseErrorKind`] payload (RFC 0009).
     pub fn message(&self) -> String {
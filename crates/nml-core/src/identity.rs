@@ -366,7 +366,7 @@ impl Positionalizer<'_> {
         let Some(field) = model.fields.iter().find(|f| f.name == field_name) else {
             return body.clone();
         };
-        if let FieldType::List(inner) = &field.field_type {
+        if let FieldType::List(inner, _) = &field.field_type {
             return self.list_body(inner, body, depth + 1);
         }
         // A union field's variant is body-dependent (structural shape or an RFC
@@ -382,8 +382,8 @@ impl Positionalizer<'_> {
                 FieldTarget::OneOf(o) => self.oneof_body(o, body, depth + 1),
                 FieldTarget::Arms { target, .. } => self.arm_set_body(target, body, depth + 1),
                 FieldTarget::ListOf(_, _) => {
-                    match variants.iter().find(|v| matches!(v, FieldType::List(_))) {
-                        Some(FieldType::List(inner)) => self.list_body(inner, body, depth + 1),
+                    match variants.iter().find(|v| matches!(v, FieldType::List(_, _))) {
+                        Some(FieldType::List(inner, _)) => self.list_body(inner, body, depth + 1),
                         _ => body.clone(),
                     }
                 }
@@ -0,0 +1,374 @@
+//! Import resolution: `import "shared.nml"` and `import "shared.nml" (a, b)`.
+//!
+//! [`ImportSet::load`] follows a file's imports transitively — paths resolve
+//! relative to the importing file's directory — reading each file once
+//! through a caller-supplied reader (the disk for the CLI, open buffers
+//! first for the editor), and reports unreadable files (`NML1005`), cycles
+//! (`NML1006`) and selected names the imported file does not declare
+//! (`NML1007`). [`ImportSet::symbol_table`] then registers the root file
+//! and what its imports bring in.
+//!
+//! Imports are **not transitive**: a file sees its own declarations and
+//! those of the files it imports directly. Whatever an imported file in
+//! turn imports is loaded and checked, but stays that file's business.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::ast::{Declaration, DeclarationKind, File, ImportDecl};
+use crate::diagnostic::{Diagnostic, codes};
+use crate::symbols::{ImportedFrom, SymbolTable};
+
+/// One imported file, parsed.
+#[derive(Debug, Clone)]
+pub struct ImportedFile {
+    /// The resolved path (see [`resolve_path`]).
+    pub path: PathBuf,
+    pub source: String,
+    pub file: File,
+}
+
+/// A root file's import closure: every file reachable through `import`,
+/// plus the findings loading it produced.
+#[derive(Debug, Clone)]
+pub struct ImportSet {
+    root: PathBuf,
+    files: Vec<ImportedFile>,
+    /// Each importing file's resolved imports, by importer path.
+    edges: HashMap<PathBuf, Vec<(PathBuf, ImportDecl)>>,
+    diagnostics: Vec<(PathBuf, Diagnostic)>,
+}
+
+/// The path an `import` in `importer` names: `relative` joined onto the
+/// importer's directory and lexically normalized (`.` and `..` folded, no
+/// filesystem access), so one file is one key however it is reached.
+pub fn resolve_path(importer: &Path, relative: &str) -> PathBuf {
+    let joined = importer
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(relative);
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// The `import` declarations of a file, in source order.
+pub fn imports_of(file: &File) -> impl Iterator<Item = &ImportDecl> {
+    file.declarations.iter().filter_map(|d| match &d.kind {
+        DeclarationKind::Import(import) => Some(import),
+        _ => None,
+    })
+}
+
+impl ImportSet {
+    /// Load everything `file` (parsed from `root`) imports, transitively,
+    /// reading each file through `read`. Never fails: problems are
+    /// [`Self::diagnostics`], and whatever loaded is still usable.
+    pub fn load(
+        root: &Path,
+        file: &File,
+        mut read: impl FnMut(&Path) -> io::Result<String>,
+    ) -> Self {
+        let mut set = ImportSet {
+            root: root.to_path_buf(),
+            files: Vec::new(),
+            edges: HashMap::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut stack = vec![root.to_path_buf()];
+        set.visit(root, file, &mut stack, &mut read);
+        set
+    }
+
+    /// Depth-first over `file`'s imports; `stack` is the import chain
+    /// leading to `path` (for cycle reporting).
+    fn visit(
+        &mut self,
+        path: &Path,
+        file: &File,
+        stack: &mut Vec<PathBuf>,
+        read: &mut impl FnMut(&Path) -> io::Result<String>,
+    ) {
+        let mut edges = Vec::new();
+        for import in imports_of(file) {
+            let target = resolve_path(path, &import.path);
+            if let Some(pos) = stack.iter().position(|p| *p == target) {
+                let cycle: Vec<String> = stack[pos..]
+                    .iter()
+                    .chain(std::iter::once(&target))
+                    .map(|p| p.display().to_string())
+                    .collect();
+                self.diagnostics.push((
+                    path.to_path_buf(),
+                    Diagnostic::error(format!("import cycle: {}", cycle.join(" -> ")))
+                        .with_code(codes::IMPORT_CYCLE)
+                        .with_span(import.path_span),
+                ));
+                continue;
+            }
+            if self.file(&target).is_none() {
+                let source = match read(&target) {
+                    Ok(source) => source,
+                    Err(e) => {
+                        self.diagnostics.push((
+                            path.to_path_buf(),
+                            Diagnostic::error(format!(
+                                "cannot read imported file '{}': {e}",
+                                target.display()
+                            ))
+                            .with_code(codes::IMPORT_NOT_FOUND)
+                            .with_span(import.path_span),
+                        ));
+                        continue;
+                    }
+                };
+                let (imported, parse_errors) = crate::cst::parse_to_ast_all(&source);
+                self.diagnostics
+                    .extend(parse_errors.into_iter().map(|d| (target.clone(), d)));
+                self.files.push(ImportedFile {
+                    path: target.clone(),
+                    source,
+                    file: imported.clone(),
+                });
+                stack.push(target.clone());
+                self.visit(&target, &imported, stack, read);
+                stack.pop();
+            }
+            if let (Some(names), Some(imported)) = (&import.names, self.file(&target)) {
+                let declared: Vec<&str> = imported
+                    .file
+                    .declarations
+                    .iter()
+                    .filter_map(declared_name)
+                    .collect();
+                let unknown: Vec<Diagnostic> = names
+                    .iter()
+                    .filter(|n| !declared.contains(&n.name.as_str()))
+                    .map(|n| {
+                        let mut diag = Diagnostic::error(format!(
+                            "'{}' is not declared in '{}'",
+                            n.name, import.path
+                        ))
+                        .with_code(codes::UNKNOWN_IMPORT_NAME)
+                        .with_span(n.span);
+                        if let Some(s) = crate::suggest::suggest(&n.name, declared.iter().copied())
+                        {
+                            diag = diag.with_suggestion(s, n.span);
+                        }
+                        diag
+                    })
+                    .collect();
+                self.diagnostics
+                    .extend(unknown.into_iter().map(|d| (path.to_path_buf(), d)));
+            }
+            edges.push((target, import.clone()));
+        }
+        self.edges.insert(path.to_path_buf(), edges);
+    }
+
+    /// The root file's path.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Every file loaded, in first-import order (the root excluded).
+    pub fn files(&self) -> &[ImportedFile] {
+        &self.files
+    }
+
+    /// A loaded file by resolved path.
+    pub fn file(&self, path: &Path) -> Option<&ImportedFile> {
+        self.files.iter().find(|f| f.path == path)
+    }
+
+    /// Loading findings, each with the file its span indexes: a missing
+    /// file, a cycle or an unknown name at the importing file's `import`,
+    /// an imported file's parse errors at that file.
+    pub fn diagnostics(&self) -> &[(PathBuf, Diagnostic)] {
+        &self.diagnostics
+    }
+
+    /// What `importer`'s imports bring in: for each loaded import, the
+    /// imported file and the declarations it selects.
+    pub fn imported_by(&self, importer: &Path) -> Vec<(&ImportDecl, &ImportedFile, File)> {
+        self.edges
+            .get(importer)
+            .into_iter()
+            .flatten()
+            .filter_map(|(target, import)| {
+                let imported = self.file(target)?;
+                let declarations: Vec<Declaration> = imported
+                    .file
+                    .declarations
+                    .iter()
+                    .filter(|d| match (&import.names, declared_name(d)) {
+                        (_, None) => false,
+                        (None, Some(_)) => true,
+                        (Some(names), Some(name)) => names.iter().any(|n| n.name == name),
+                    })
+                    .cloned()
+                    .collect();
+                Some((import, imported, File { declarations }))
+            })
+            .collect()
+    }
+
+    /// The root's symbol table: `root_file`'s own declarations, then each
+    /// import's selection, tagged with where it came from.
+    pub fn symbol_table(&self, root_file: &File) -> SymbolTable {
+        let mut symbols = SymbolTable::new();
+        symbols.register_file(root_file);
+        for (import, imported, selected) in self.imported_by(&self.root) {
+            symbols.register_imported(
+                &selected,
+                ImportedFrom {
+                    path: imported.path.clone(),
+                    import_span: import.path_span,
+                },
+            );
+        }
+        symbols
+    }
+}
+
+/// The name a declaration contributes to an importer's scope.
+fn declared_name(decl: &Declaration) -> Option<&str> {
+    match &decl.kind {
+        DeclarationKind::Block(b) => Some(&b.name.name),
+        DeclarationKind::Array(a) => Some(&a.name.name),
+        DeclarationKind::Const(c) => Some(&c.name.name),
+        DeclarationKind::Template(t) => Some(&t.name.name),
+        DeclarationKind::OneOf(o) => Some(&o.name.name),
+        DeclarationKind::Import(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reader(files: &[(&str, &str)]) -> impl FnMut(&Path) -> io::Result<String> {
+        let files: HashMap<PathBuf, String> = files
+            .iter()
+            .map(|(p, s)| (PathBuf::from(p), s.to_string()))
+            .collect();
+        move |path| {
+            files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+        }
+    }
+
+    fn codes_of(set: &ImportSet) -> Vec<String> {
+        set.diagnostics()
+            .iter()
+            .map(|(_, d)| d.code.map(|c| c.to_string()).unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn resolve_path_is_relative_to_the_importer() {
+        assert_eq!(
+            resolve_path(Path::new("cfg/service.nml"), "shared.nml"),
+            PathBuf::from("cfg/shared.nml")
+        );
+        assert_eq!(
+            resolve_path(Path::new("cfg/env/prod.nml"), "../shared.nml"),
+            PathBuf::from("cfg/shared.nml")
+        );
+        assert_eq!(
+            resolve_path(Path::new("service.nml"), "./lib/db.nml"),
+            PathBuf::from("lib/db.nml")
+        );
+    }
+
+    #[test]
+    fn imported_names_resolve_and_selection_narrows() {
+        let root = "\
+import \"shared.nml\" (dbUrl)
+import \"providers.nml\"
+
+service Api:
+    database = dbUrl
+    provider = Groq
+    region = region
+";
+        let file = crate::parse(root).unwrap();
+        let set = ImportSet::load(
+            Path::new("cfg/service.nml"),
+            &file,
+            reader(&[
+                (
+                    "cfg/shared.nml",
+                    "const dbUrl = \"postgres://db\"\n\nconst region = \"eu\"\n",
+                ),
+                ("cfg/providers.nml", "provider Groq:\n    type = \"groq\"\n"),
+            ]),
+        );
+        assert!(set.diagnostics().is_empty(), "{:?}", set.diagnostics());
+        assert_eq!(set.files().len(), 2);
+
+        let symbols = set.symbol_table(&file);
+        let unresolved = symbols.find_unresolved_references(&file);
+        // `region` was not selected, so only it stays unresolved.
+        assert_eq!(unresolved.len(), 1, "{unresolved:?}");
+        assert!(unresolved[0].message.contains("'region'"));
+        let db = &symbols.lookup("dbUrl").unwrap()[0];
+        assert_eq!(
+            db.imported.as_ref().unwrap().path,
+            PathBuf::from("cfg/shared.nml")
+        );
+        assert_eq!(
+            symbols.resolve_const_value("dbUrl"),
+            Some(&crate::types::Value::String("postgres://db".into()))
+        );
+    }
+
+    #[test]
+    fn missing_files_cycles_and_unknown_names_are_reported() {
+        let root = "import \"a.nml\"\nimport \"gone.nml\"\nimport \"b.nml\" (dbUrl)\n";
+        let file = crate::parse(root).unwrap();
+        let set = ImportSet::load(
+            Path::new("root.nml"),
+            &file,
+            reader(&[
+                ("a.nml", "import \"root.nml\"\n"),
+                ("b.nml", "const dbURL = \"x\"\n"),
+            ]),
+        );
+        assert_eq!(codes_of(&set), ["NML1006", "NML1005", "NML1007"]);
+        let (at, cycle) = &set.diagnostics()[0];
+        assert_eq!(at, Path::new("a.nml"));
+        assert!(cycle.message.contains("root.nml -> a.nml -> root.nml"));
+        let (_, unknown) = &set.diagnostics()[2];
+        assert_eq!(unknown.suggestions[0].replacement, "dbURL");
+    }
+
+    #[test]
+    fn an_import_colliding_with_a_local_name_is_a_duplicate() {
+        let root = "import \"shared.nml\"\n\nconst region = \"us\"\n";
+        let file = crate::parse(root).unwrap();
+        let set = ImportSet::load(
+            Path::new("root.nml"),
+            &file,
+            reader(&[("shared.nml", "const region = \"eu\"\n")]),
+        );
+        let dups = set.symbol_table(&file).find_duplicates();
+        assert_eq!(dups.len(), 1);
+        assert!(dups[0].message.contains("imported from 'shared.nml'"));
+        // Reported at the import, in the importing file.
+        assert_eq!(dups[0].span.unwrap().start, root.find('"').unwrap());
+    }
+}
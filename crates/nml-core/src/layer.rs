@@ -0,0 +1,471 @@
+//! Layered configuration: a base file plus environment overlays.
+//!
+//! [`LayeredConfig`] takes config files in **precedence order** (`app.nml`,
+//! then `app.prod.nml`, then `app.local.nml` — later overrides earlier) and
+//! merges them with the semantics [`crate::diff`] already applies when it
+//! compares multi-file configs:
+//!
+//! - blocks with the same keyword and name merge **property-level, at every
+//!   depth** — a property, modifier, or scalar from a later layer replaces
+//!   the earlier one; nested blocks written in several layers merge
+//!   recursively;
+//! - a property written after a nested block of the same name (or the other
+//!   way round) replaces it outright;
+//! - collections overlay by **replacement**, never element merge: the last
+//!   layer whose body carries list items or arms supplies all of them, with
+//!   its shared properties;
+//! - top-level arrays, `const`s and templates are replaced whole by the last
+//!   layer declaring them.
+//!
+//! The merged [`Body`] is ordinary AST, so the canonical deserialize
+//! pipeline runs on it unchanged:
+//!
+//! ```rust
+//! use std::path::PathBuf;
+//! use nml_core::layer::LayeredConfig;
+//!
+//! let base = nml_core::parse("service Api:\n    port = 8080\n    host = \"localhost\"\n").unwrap();
+//! let prod = nml_core::parse("service Api:\n    host = \"api.example.com\"\n").unwrap();
+//! let config = LayeredConfig::new(vec![
+//!     (PathBuf::from("app.nml"), base),
+//!     (PathBuf::from("app.prod.nml"), prod),
+//! ]);
+//!
+//! let body = config.block("service", "Api").unwrap();
+//! assert_eq!(body.entries.len(), 2);
+//! let origin = config.provenance(&["service", "Api", "host"]).unwrap();
+//! assert!(matches!(origin, nml_core::diff::Origin::File { file, .. } if file.ends_with("app.prod.nml")));
+//! ```
+//!
+//! Every span in a merged body indexes the source of the layer that
+//! supplied that entry, not one combined text; [`LayeredConfig::provenance`]
+//! names the file.
+
+use std::path::{Path, PathBuf};
+
+use crate::ast::{Body, BodyEntry, BodyEntryKind, Declaration, DeclarationKind, File, NestedBlock};
+use crate::diff::{KeyedEntry, Origin, keyed_entry};
+use crate::span::Span;
+
+/// Recursion bound on nested-block merging, matching the diff engine's.
+const MAX_DEPTH: u32 = 64;
+
+/// An ordered stack of config files, merged on demand (see the module docs
+/// for the merge rules).
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    layers: Vec<(PathBuf, File)>,
+}
+
+impl LayeredConfig {
+    /// Layers in precedence order: each overrides the ones before it.
+    pub fn new(layers: Vec<(PathBuf, File)>) -> Self {
+        Self { layers }
+    }
+
+    /// The layers, in precedence order.
+    pub fn layers(&self) -> &[(PathBuf, File)] {
+        &self.layers
+    }
+
+    /// The merged body of block `keyword name` — what
+    /// [`from_body_defaulted`](crate::from_body_defaulted) takes with
+    /// `keyword` as its root. `None` when no layer declares the block.
+    pub fn block(&self, keyword: &str, name: &str) -> Option<Body> {
+        let bodies = self.block_bodies(keyword, name);
+        (!bodies.is_empty()).then(|| merge_bodies(&bodies, 0))
+    }
+
+    /// Every layer merged into one file: blocks merged per keyword and name,
+    /// other declarations taken from the last layer declaring them, in
+    /// first-declared order. Ready for [`crate::Document`] and
+    /// [`from_document_defaulted`](crate::from_document_defaulted).
+    pub fn merged(&self) -> File {
+        let mut order: Vec<DeclKey<'_>> = Vec::new();
+        for (_, file) in &self.layers {
+            for decl in &file.declarations {
+                let key = decl_key(decl);
+                if !order.contains(&key) {
+                    order.push(key);
+                }
+            }
+        }
+        let declarations = order
+            .into_iter()
+            .filter_map(|key| match key {
+                DeclKey::Block(keyword, name) => {
+                    let (_, last) = self.last_decl(&key)?;
+                    let DeclarationKind::Block(block) = &last.kind else {
+                        return None;
+                    };
+                    let mut block = block.clone();
+                    block.body = self.block(keyword, name)?;
+                    Some(Declaration {
+                        kind: DeclarationKind::Block(block),
+                        span: last.span,
+                    })
+                }
+                DeclKey::Named(_) | DeclKey::Import(_) => {
+                    self.last_decl(&key).map(|(_, d)| d.clone())
+                }
+            })
+            .collect();
+        File { declarations }
+    }
+
+    /// Which file, and where in it, supplied the effective value at `path`.
+    ///
+    /// A path is `[keyword, name, field, field, ...]` for a block's contents
+    /// (`["service", "Api", "database", "port"]`); `[keyword, name]` is the
+    /// block itself, and a single segment names a top-level array, `const` or
+    /// template. A value's origin is its value span; a nested block's is its
+    /// name in the last layer that wrote it. `None` when nothing at `path`
+    /// is written in any layer, or the path runs through a plain value.
+    pub fn provenance(&self, path: &[&str]) -> Option<Origin> {
+        match path {
+            [] => None,
+            [name] => self
+                .last_decl(&DeclKey::Named(name))
+                .map(|(file, decl)| origin(file, decl_name_span(decl))),
+            [keyword, name, fields @ ..] => {
+                let mut bodies = self.block_bodies(keyword, name);
+                let (file, decl) = self.last_decl(&DeclKey::Block(keyword, name))?;
+                let mut found = origin(file, decl_name_span(decl));
+                for field in fields {
+                    match keyed_entry(bodies.iter().copied(), field) {
+                        KeyedEntry::Absent => return None,
+                        KeyedEntry::Value(file, _, sv) => {
+                            found = origin(file, sv.span);
+                            bodies = Vec::new();
+                        }
+                        KeyedEntry::Items(file, entry, _) => {
+                            found = origin(file, entry.span);
+                            bodies = Vec::new();
+                        }
+                        KeyedEntry::Bodies(nested) => {
+                            let (file, _, nb) = nested.last()?;
+                            found = origin(file, nb.name.span);
+                            bodies = nested.iter().map(|(f, _, nb)| (*f, &nb.body)).collect();
+                        }
+                    }
+                }
+                Some(found)
+            }
+        }
+    }
+
+    /// Each layer's body for block `keyword name`, in precedence order.
+    fn block_bodies(&self, keyword: &str, name: &str) -> Vec<(&Path, &Body)> {
+        self.layers
+            .iter()
+            .flat_map(|(file, f)| {
+                f.declarations.iter().filter_map(move |d| match &d.kind {
+                    DeclarationKind::Block(b)
+                        if b.keyword.name == keyword && b.name.name == name =>
+                    {
+                        Some((file.as_path(), &b.body))
+                    }
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+
+    /// The last layer's declaration matching `key`.
+    fn last_decl(&self, key: &DeclKey<'_>) -> Option<(&Path, &Declaration)> {
+        self.layers.iter().rev().find_map(|(file, f)| {
+            f.declarations
+                .iter()
+                .rev()
+                .find(|d| decl_key(d) == *key)
+                .map(|d| (file.as_path(), d))
+        })
+    }
+}
+
+/// Declaration identity across layers: blocks by keyword and name,
+/// imports by path, everything else by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclKey<'a> {
+    Block(&'a str, &'a str),
+    Named(&'a str),
+    Import(&'a str),
+}
+
+fn decl_key(decl: &Declaration) -> DeclKey<'_> {
+    match &decl.kind {
+        DeclarationKind::Block(b) => DeclKey::Block(&b.keyword.name, &b.name.name),
+        DeclarationKind::Array(a) => DeclKey::Named(&a.name.name),
+        DeclarationKind::Const(c) => DeclKey::Named(&c.name.name),
+        DeclarationKind::Template(t) => DeclKey::Named(&t.name.name),
+        DeclarationKind::OneOf(o) => DeclKey::Named(&o.name.name),
+        DeclarationKind::Import(i) => DeclKey::Import(&i.path),
+    }
+}
+
+fn decl_name_span(decl: &Declaration) -> Span {
+    match &decl.kind {
+        DeclarationKind::Block(b) => b.name.span,
+        DeclarationKind::Array(a) => a.name.span,
+        DeclarationKind::Const(c) => c.name.span,
+        DeclarationKind::Template(t) => t.name.span,
+        DeclarationKind::OneOf(o) => o.name.span,
+        DeclarationKind::Import(i) => i.path_span,
+    }
+}
+
+fn origin(file: &Path, span: Span) -> Origin {
+    Origin::File {
+        file: file.to_path_buf(),
+        span,
+    }
+}
+
+/// Entries that make up a body's collection content — replaced wholesale
+/// by the last layer carrying any.
+fn is_collection_entry(kind: &BodyEntryKind) -> bool {
+    matches!(
+        kind,
+        BodyEntryKind::ListItem(_) | BodyEntryKind::Arm(_) | BodyEntryKind::SharedProperty(_)
+    )
+}
+
+fn entry_key(kind: &BodyEntryKind) -> Option<&str> {
+    match kind {
+        BodyEntryKind::Property(p) => Some(&p.name.name),
+        BodyEntryKind::NestedBlock(nb) => Some(&nb.name.name),
+        BodyEntryKind::Modifier(m) => Some(&m.name.name),
+        BodyEntryKind::FieldDefinition(f) => Some(&f.name.name),
+        _ => None,
+    }
+}
+
+/// Where a merged entry goes: a keyed entry, or the collection content.
+#[derive(PartialEq)]
+enum Slot<'a> {
+    Key(&'a str),
+    Collection,
+}
+
+/// Merge one block's bodies (precedence order) into a fresh body: keys in
+/// first-written order, collection content where it first appeared.
+fn merge_bodies(bodies: &[(&Path, &Body)], depth: u32) -> Body {
+    let Some((_, last)) = bodies.last() else {
+        return Body::fresh(Vec::new());
+    };
+    if depth >= MAX_DEPTH {
+        return (*last).clone();
+    }
+    let mut order: Vec<Slot<'_>> = Vec::new();
+    for (_, body) in bodies {
+        for entry in &body.entries {
+            let slot = match entry_key(&entry.kind) {
+                Some(key) => Slot::Key(key),
+                None if is_collection_entry(&entry.kind) => Slot::Collection,
+                None => continue,
+            };
+            if !order.contains(&slot) {
+                order.push(slot);
+            }
+        }
+    }
+    let mut entries = Vec::new();
+    for slot in order {
+        match slot {
+            Slot::Key(key) => match keyed_entry(bodies.iter().copied(), key) {
+                KeyedEntry::Absent => {
+                    // A field definition or type annotation — never a value,
+                    // kept last-wins.
+                    if let Some(entry) = bodies.iter().rev().find_map(|(_, b)| {
+                        b.entries
+                            .iter()
+                            .rev()
+                            .find(|e| entry_key(&e.kind) == Some(key))
+                    }) {
+                        entries.push(entry.clone());
+                    }
+                }
+                KeyedEntry::Value(_, entry, _) | KeyedEntry::Items(_, entry, _) => {
+                    entries.push(entry.clone())
+                }
+                KeyedEntry::Bodies(nested) => {
+                    let Some((_, last, nb)) = nested.last() else {
+                        continue;
+                    };
+                    let merged: Vec<(&Path, &Body)> = nested
+                        .iter()
+                        .map(|(file, _, nb)| (*file, &nb.body))
+                        .collect();
+                    entries.push(BodyEntry {
+                        span: last.span,
+                        kind: BodyEntryKind::NestedBlock(NestedBlock {
+                            name: nb.name.clone(),
+                            body: merge_bodies(&merged, depth + 1),
+                        }),
+                    });
+                }
+            },
+            Slot::Collection => {
+                if let Some((_, body)) = bodies
+                    .iter()
+                    .rev()
+                    .find(|(_, b)| b.entries.iter().any(|e| is_collection_entry(&e.kind)))
+                {
+                    entries.extend(
+                        body.entries
+                            .iter()
+                            .filter(|e| is_collection_entry(&e.kind))
+                            .cloned(),
+                    );
+                }
+            }
+        }
+    }
+    Body {
+        entries,
+        type_annotation: bodies
+            .iter()
+            .rev()
+            .find_map(|(_, b)| b.type_annotation.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::ValueResolver;
+    use crate::schema_index::SchemaIndex;
+
+    const BASE: &str = "\
+service Api:
+    host = \"localhost\"
+    port = 8080
+    database:
+        url = \"postgres://localhost/app\"
+        pool = 5
+    routes:
+        - \"/health\"
+        - \"/metrics\"
+";
+
+    const PROD: &str = "\
+service Api:
+    host = \"api.example.com\"
+    database:
+        pool = 50
+    routes:
+        - \"/v1\"
+";
+
+    const LOCAL: &str = "\
+service Api:
+    port = 9000
+";
+
+    fn config() -> LayeredConfig {
+        LayeredConfig::new(
+            [
+                ("app.nml", BASE),
+                ("app.prod.nml", PROD),
+                ("app.local.nml", LOCAL),
+            ]
+            .into_iter()
+            .map(|(path, src)| (PathBuf::from(path), crate::parse(src).unwrap()))
+            .collect(),
+        )
+    }
+
+    fn file_of(origin: Origin) -> (PathBuf, Span) {
+        match origin {
+            Origin::File { file, span } => (file, span),
+            Origin::Default => panic!("layers only report file origins"),
+        }
+    }
+
+    #[test]
+    fn overlays_merge_property_level_and_report_provenance() {
+        #[derive(serde::Deserialize)]
+        struct Database {
+            url: String,
+            pool: u32,
+        }
+        #[derive(serde::Deserialize)]
+        struct Service {
+            host: String,
+            port: u16,
+            database: Database,
+            routes: Vec<String>,
+            timeout: String,
+        }
+
+        let mut ex = crate::cst::extract_schema(
+            "model database:\n    url string\n    pool number\n\n\
+             model service:\n    host string\n    port number\n    database database\n    \
+             routes []string\n    timeout string = \"30s\"\n",
+        )
+        .0;
+        crate::schema::resolve_model_inheritance(&mut ex);
+        let index = SchemaIndex::build(ex.models, ex.enums, ex.oneofs);
+
+        let config = config();
+        let body = config.block("service", "Api").unwrap();
+        let svc: Service =
+            crate::from_body_defaulted(&index, "service", &body, &ValueResolver::env()).unwrap();
+        assert_eq!(svc.host, "api.example.com");
+        assert_eq!(svc.port, 9000);
+        assert_eq!(svc.database.url, "postgres://localhost/app");
+        assert_eq!(svc.database.pool, 50);
+        // Collections replace, never element-merge.
+        assert_eq!(svc.routes, ["/v1"]);
+        assert_eq!(svc.timeout, "30s");
+
+        let (file, span) = file_of(config.provenance(&["service", "Api", "host"]).unwrap());
+        assert_eq!(file, PathBuf::from("app.prod.nml"));
+        assert_eq!(&PROD[span.start..span.end], "\"api.example.com\"");
+        let (file, span) = file_of(
+            config
+                .provenance(&["service", "Api", "database", "url"])
+                .unwrap(),
+        );
+        assert_eq!(file, PathBuf::from("app.nml"));
+        assert_eq!(&BASE[span.start..span.end], "\"postgres://localhost/app\"");
+        let (file, _) = file_of(config.provenance(&["service", "Api", "port"]).unwrap());
+        assert_eq!(file, PathBuf::from("app.local.nml"));
+        let (file, _) = file_of(config.provenance(&["service", "Api", "routes"]).unwrap());
+        assert_eq!(file, PathBuf::from("app.prod.nml"));
+        // Unwritten (schema-defaulted) and through-a-value paths have no file.
+        assert!(config.provenance(&["service", "Api", "timeout"]).is_none());
+        assert!(
+            config
+                .provenance(&["service", "Api", "host", "x"])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn a_later_value_replaces_an_earlier_nested_block() {
+        let config = LayeredConfig::new(vec![
+            (
+                PathBuf::from("a.nml"),
+                crate::parse("service Api:\n    tls:\n        cert = \"a.pem\"\n").unwrap(),
+            ),
+            (
+                PathBuf::from("b.nml"),
+                crate::parse("service Api:\n    tls = \"off\"\n\nconst region = \"eu\"\n").unwrap(),
+            ),
+        ]);
+        let merged = config.merged();
+        assert_eq!(merged.declarations.len(), 2);
+        let crate::query::BlockQuery::Found(body) =
+            crate::Document::new(&merged).block("service", "Api")
+        else {
+            panic!("merged file keeps the block");
+        };
+        assert_eq!(body.entries.len(), 1);
+        assert!(
+            matches!(&body.entries[0].kind, BodyEntryKind::Property(p) if p.name.name == "tls")
+        );
+        let (file, _) = file_of(config.provenance(&["region"]).unwrap());
+        assert_eq!(file, PathBuf::from("b.nml"));
+    }
+}
@@ -72,8 +72,17 @@ pub mod diff;
 pub mod duration;
 pub mod error;
 pub mod identity;
+/// `import` declarations: path resolution, the transitive loader with
+/// cycle detection, and the importer's symbol table.
+pub mod import;
+/// Layered configuration: a base file plus overlays (`app.prod.nml`,
+/// `app.local.nml`) merged last-file-wins, with per-value provenance.
+pub mod layer;
 pub mod model;
 pub mod money;
+/// The dependency-free regular-expression subset the `pattern` string
+/// facet compiles — whole-value, linear-time matching.
+pub mod pattern;
 pub mod project;
 pub mod query;
 pub mod resolve;
@@ -83,6 +92,10 @@ pub mod resolve;
 /// the leaf definitions; this holds the aggregate and the checks.
 pub mod schema;
 pub mod schema_index;
+/// The serde `Serialize` bridge — the mirror of [`de`]: any `Serialize`
+/// value back out as a semantic [`ast::Body`] / [`ast::File`], ready for
+/// `nml_fmt` to render as canonical source.
+pub mod ser;
 pub mod source_policy;
 pub mod span;
 /// The near-miss suggestion engine (RFC 0008; formerly in nml-validate) —
@@ -198,6 +198,308 @@ pub type NumberFacets = Facets<crate::types::Number>;
 /// Facets on a `duration` field.
 pub type DurationFacets = Facets<crate::duration::Duration>;
 
+/// Facets on a `money` field: the currencies it admits. Its own record,
+/// not a [`Facets<T>`] — currency is membership, not an ordered range, so
+/// min/max bounds have nothing to say about it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MoneyFacets {
+    pub currency: Option<CurrencyFacet>,
+}
+
+/// `currency = "USD"` or `currency = ["USD", "GBP", "EUR"]`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrencyFacet {
+    /// Known ISO 4217 codes, authored order. Never empty in a loaded
+    /// schema: extraction drops unknown codes (the definition pass reports
+    /// them, NML2058) and drops the facet if none survive, rather than
+    /// loading an allow-list that rejects every value.
+    pub codes: Vec<String>,
+    /// The facet's `key = value` span in the schema source.
+    pub span: Span,
+}
+
+impl MoneyFacets {
+    pub fn is_none(&self) -> bool {
+        self.currency.is_none()
+    }
+
+    /// The canonical `key = value` rendering: a single code as a string,
+    /// several as a list.
+    pub fn canonical_parts(&self) -> Vec<String> {
+        let Some(c) = &self.currency else {
+            return Vec::new();
+        };
+        let quoted: Vec<String> = c.codes.iter().map(|code| format!("\"{code}\"")).collect();
+        vec![match quoted.as_slice() {
+            [one] => format!("currency = {one}"),
+            _ => format!("currency = [{}]", quoted.join(", ")),
+        }]
+    }
+
+    /// Whether `m`'s currency is one the facet admits.
+    pub fn admits(&self, m: &crate::money::Money) -> bool {
+        self.currency
+            .as_ref()
+            .is_none_or(|c| c.codes.contains(&m.currency))
+    }
+
+    /// The violation, described without the amount — the currency code
+    /// alone decides it, and the [`Facets::violation_descriptions`]
+    /// contract (never render the value) carries over.
+    pub fn violation_descriptions(&self, m: &crate::money::Money) -> Vec<String> {
+        if self.admits(m) {
+            return Vec::new();
+        }
+        vec![format!(
+            "in {}, which the schema's {} does not admit",
+            m.currency,
+            self.canonical_parts().join(", ")
+        )]
+    }
+
+    /// Every violation `m` commits, as message tails — `is 6.55 GBP, in
+    /// GBP, …`, the [`Facets::violations`] shape.
+    pub fn violations(&self, m: &crate::money::Money) -> Vec<String> {
+        self.violation_descriptions(m)
+            .into_iter()
+            .map(|desc| format!("is {}, {desc}", m.format_display()))
+            .collect()
+    }
+}
+
+/// Facets on a `string` field: length bounds counted in characters
+/// (Unicode scalar values, not bytes) and a whole-value
+/// [`crate::pattern`] match.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StringFacets {
+    pub min_length: Option<LengthFacet>,
+    pub max_length: Option<LengthFacet>,
+    pub pattern: Option<PatternFacet>,
+}
+
+/// `minLength = n` / `maxLength = n`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LengthFacet {
+    pub value: usize,
+    pub span: Span,
+}
+
+/// `pattern = "..."`, compiled at extraction — a pattern that does not
+/// compile never loads (the definition pass reports it, NML2058).
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternFacet {
+    pub pattern: crate::pattern::Pattern,
+    pub span: Span,
+}
+
+impl StringFacets {
+    pub fn is_none(&self) -> bool {
+        self.min_length.is_none() && self.max_length.is_none() && self.pattern.is_none()
+    }
+
+    /// The canonical `key = value` renderings, in canonical order.
+    pub fn canonical_parts(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(l) = &self.min_length {
+            parts.push(format!("minLength = {}", l.value));
+        }
+        if let Some(l) = &self.max_length {
+            parts.push(format!("maxLength = {}", l.value));
+        }
+        if let Some(p) = &self.pattern {
+            parts.push(format!(
+                "pattern = {}",
+                crate::ast::quote_facet_string(p.pattern.as_str())
+            ));
+        }
+        parts
+    }
+
+    pub fn admits(&self, s: &str) -> bool {
+        self.failures(s).is_empty()
+    }
+
+    /// Every violation `s` commits, as message tails (`has 0 characters,
+    /// fewer than the schema's minLength = 1`). The string itself is never
+    /// echoed — it may be long, and the count or the pattern is what the
+    /// author needs.
+    pub fn violations(&self, s: &str) -> Vec<String> {
+        self.failures(s)
+            .into_iter()
+            .map(|(count, rule)| match count {
+                Some(n) => format!("has {}, {rule}", characters(n)),
+                None => rule,
+            })
+            .collect()
+    }
+
+    /// The same violations phrased to follow "a value" — the
+    /// [`Facets::violation_descriptions`] lane for redacted messages.
+    pub fn violation_descriptions(&self, s: &str) -> Vec<String> {
+        self.failures(s)
+            .into_iter()
+            .map(|(count, rule)| match count {
+                Some(n) => format!("of {}, {rule}", characters(n)),
+                None => format!("that {rule}"),
+            })
+            .collect()
+    }
+
+    /// `(character count for length rules, rule text)` per violation.
+    fn failures(&self, s: &str) -> Vec<(Option<usize>, String)> {
+        let mut out = Vec::new();
+        let n = s.chars().count();
+        if let Some(l) = &self.min_length {
+            if n < l.value {
+                out.push((
+                    Some(n),
+                    format!("fewer than the schema's minLength = {}", l.value),
+                ));
+            }
+        }
+        if let Some(l) = &self.max_length {
+            if n > l.value {
+                out.push((
+                    Some(n),
+                    format!("more than the schema's maxLength = {}", l.value),
+                ));
+            }
+        }
+        if let Some(p) = &self.pattern {
+            if !p.pattern.is_match(s) {
+                out.push((
+                    None,
+                    format!(
+                        "does not match the schema's pattern = {}",
+                        crate::ast::quote_facet_string(p.pattern.as_str())
+                    ),
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn characters(n: usize) -> String {
+    if n == 1 {
+        "1 character".to_string()
+    } else {
+        format!("{n} characters")
+    }
+}
+
+/// The facet keys that constrain a collection rather than its elements.
+/// Authored in the element name's parens for `[]T`
+/// (`[]endpoint(minItems = 1)`), after the angles for `set<T>`.
+pub const COLLECTION_FACET_KEYS: [&str; 3] = ["minItems", "maxItems", "nonEmpty"];
+
+/// Whether `key` is a [`COLLECTION_FACET_KEYS`] member.
+pub fn is_collection_facet(key: &str) -> bool {
+    COLLECTION_FACET_KEYS.contains(&key)
+}
+
+/// The facets a `[]T` or `set<T>` field carries: bounds on its element
+/// count. `None` means no collection facet was authored — the element
+/// type keeps its own facets either way. Boxed for the same reason as
+/// [`PrimitiveFacets`]: most lists carry none.
+#[derive(Debug, Clone, Default, Serialize)]
+pub enum CollectionFacets {
+    #[default]
+    None,
+    Items(Box<ItemFacets>),
+}
+
+/// `minItems = n`, `maxItems = n`, `nonEmpty = true|false`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ItemFacets {
+    pub min_items: Option<LengthFacet>,
+    pub max_items: Option<LengthFacet>,
+    pub non_empty: Option<NonEmptyFacet>,
+}
+
+/// `nonEmpty = true` — the readable spelling of `minItems = 1`. The
+/// definition pass rejects it alongside `minItems`; `false` is kept so
+/// the type renders as authored, and constrains nothing.
+#[derive(Debug, Clone, Serialize)]
+pub struct NonEmptyFacet {
+    pub value: bool,
+    pub span: Span,
+}
+
+impl CollectionFacets {
+    pub fn is_none(&self) -> bool {
+        match self {
+            CollectionFacets::None => true,
+            CollectionFacets::Items(f) => {
+                f.min_items.is_none() && f.max_items.is_none() && f.non_empty.is_none()
+            }
+        }
+    }
+
+    /// The canonical `key = value` renderings, in canonical order.
+    pub fn canonical_parts(&self) -> Vec<String> {
+        let CollectionFacets::Items(f) = self else {
+            return Vec::new();
+        };
+        let mut parts = Vec::new();
+        if let Some(l) = &f.min_items {
+            parts.push(format!("minItems = {}", l.value));
+        }
+        if let Some(l) = &f.max_items {
+            parts.push(format!("maxItems = {}", l.value));
+        }
+        if let Some(n) = &f.non_empty {
+            parts.push(format!("nonEmpty = {}", n.value));
+        }
+        parts
+    }
+
+    pub fn admits(&self, count: usize) -> bool {
+        self.violations(count).is_empty()
+    }
+
+    /// Every violation a collection of `count` elements commits, as
+    /// message tails (`has 0 items, fewer than the schema's minItems =
+    /// 1`). Only the count is reported — the elements themselves are
+    /// diagnosed on their own spans.
+    pub fn violations(&self, count: usize) -> Vec<String> {
+        let CollectionFacets::Items(f) = self else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        if let Some(l) = &f.min_items {
+            if count < l.value {
+                out.push(format!(
+                    "has {}, fewer than the schema's minItems = {}",
+                    items(count),
+                    l.value
+                ));
+            }
+        }
+        if let Some(l) = &f.max_items {
+            if count > l.value {
+                out.push(format!(
+                    "has {}, more than the schema's maxItems = {}",
+                    items(count),
+                    l.value
+                ));
+            }
+        }
+        if f.non_empty.as_ref().is_some_and(|n| n.value) && count == 0 {
+            out.push("is empty, but the schema requires nonEmpty = true".to_string());
+        }
+        out
+    }
+}
+
+fn items(n: usize) -> String {
+    if n == 1 {
+        "1 item".to_string()
+    } else {
+        format!("{n} items")
+    }
+}
+
 /// The facets a primitive field carries, tagged by domain — a `number`
 /// field's bounds are `Number`s, a `duration` field's are `Duration`s,
 /// and the type system keeps the two from ever being compared across
@@ -217,6 +519,8 @@ pub enum PrimitiveFacets {
     None,
     Number(Box<NumberFacets>),
     Duration(Box<DurationFacets>),
+    Money(Box<MoneyFacets>),
+    String(Box<StringFacets>),
 }
 
 impl PrimitiveFacets {
@@ -225,6 +529,8 @@ impl PrimitiveFacets {
             PrimitiveFacets::None => true,
             PrimitiveFacets::Number(f) => f.is_none(),
             PrimitiveFacets::Duration(f) => f.is_none(),
+            PrimitiveFacets::Money(f) => f.is_none(),
+            PrimitiveFacets::String(f) => f.is_none(),
         }
     }
 }
@@ -384,7 +690,9 @@ pub enum FieldType {
         ty: PrimitiveType,
         facets: PrimitiveFacets,
     },
-    List(Box<FieldType>),
+    /// `[]T`, with its collection facets (`minItems`, `maxItems`,
+    /// `nonEmpty`) — `None` unless authored.
+    List(Box<FieldType>, CollectionFacets),
     ModelRef(String),
     /// A typed modifier field (`|allow []string?`); the inner type is the
     /// declared type of the modifier's value.
@@ -405,8 +713,8 @@ pub enum FieldType {
     /// identity: for a union element type, the admitting arm is irrelevant).
     /// Unlike `List`, element order never carries meaning — diffs are
     /// order-insensitive (`SetDelta`), and authored order is preserved in
-    /// source but semantically inert.
-    Set(Box<FieldType>),
+    /// source but semantically inert. Carries collection facets like `List`.
+    Set(Box<FieldType>, CollectionFacets),
 }
 
 impl FieldType {
@@ -440,17 +748,33 @@ impl std::fmt::Display for FieldType {
                 // every model-side rendering show it in canonical form.
                 // A `None` here is empty by definition (extraction never
                 // wraps an empty list), so only the domain arms render.
-                let parts = match facets {
-                    PrimitiveFacets::None => Vec::new(),
-                    PrimitiveFacets::Number(fs) => fs.canonical_parts(),
-                    PrimitiveFacets::Duration(fs) => fs.canonical_parts(),
-                };
+                let parts = primitive_facet_parts(facets);
                 if !parts.is_empty() {
                     write!(f, "({})", parts.join(", "))?;
                 }
                 Ok(())
             }
-            FieldType::List(inner) => write!(f, "[]{inner}"),
+            // Collection facets render inside the element name's parens,
+            // after its own (`[]string(minLength = 1, minItems = 2)`).
+            FieldType::List(inner, facets) => {
+                f.write_str("[]")?;
+                let parts = facets.canonical_parts();
+                match inner.as_ref() {
+                    FieldType::Primitive { ty, facets: own } if !parts.is_empty() => {
+                        f.write_str(ty.as_str())?;
+                        let mut all = primitive_facet_parts(own);
+                        all.extend(parts);
+                        write!(f, "({})", all.join(", "))
+                    }
+                    other => {
+                        write!(f, "{other}")?;
+                        if !parts.is_empty() {
+                            write!(f, "({})", parts.join(", "))?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
             FieldType::ModelRef(name) => f.write_str(name),
             FieldType::Modifier(inner) => write!(f, "{inner}"),
             FieldType::Union(variants) => {
@@ -464,7 +788,7 @@ impl std::fmt::Display for FieldType {
                 f.write_str(")")
             }
             FieldType::Arms { key, target } => write!(f, "({key} -> {target})"),
-            FieldType::Set(inner) => {
+            FieldType::Set(inner, facets) => {
                 // Canonical form: bare union inside the angles (`set<a | b>`)
                 // — the angles already bound it, so the union's grouping
                 // parens would be redundant (RFC 0032 Decision 4).
@@ -480,12 +804,27 @@ impl std::fmt::Display for FieldType {
                     }
                     other => write!(f, "{other}")?,
                 }
-                f.write_str(">")
+                f.write_str(">")?;
+                let parts = facets.canonical_parts();
+                if !parts.is_empty() {
+                    write!(f, "({})", parts.join(", "))?;
+                }
+                Ok(())
             }
         }
     }
 }
 
+fn primitive_facet_parts(facets: &PrimitiveFacets) -> Vec<String> {
+    match facets {
+        PrimitiveFacets::None => Vec::new(),
+        PrimitiveFacets::Number(fs) => fs.canonical_parts(),
+        PrimitiveFacets::Duration(fs) => fs.canonical_parts(),
+        PrimitiveFacets::Money(fs) => fs.canonical_parts(),
+        PrimitiveFacets::String(fs) => fs.canonical_parts(),
+    }
+}
+
 #[cfg(test)]
 mod facet_tests {
     use super::*;
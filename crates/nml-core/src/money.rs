@@ -1,6 +1,8 @@
+use crate::decimal::{Number, NumberError, RoundingMode};
 use crate::error::NmlError;
 use crate::span::Span;
 use serde::Serialize;
+use std::fmt;
 
 /// A money value stored as integer minor units with ISO 4217 currency code.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -26,8 +28,181 @@ impl Money {
         crate::decimal::Number::try_new(self.amount as i128, self.exponent as i16)
             .expect("i64 minor units at an ISO 4217 exponent are always a valid Number")
     }
+
+    /// `self + rhs` in minor units. Both sides must carry the same
+    /// currency; a sum outside `i64` minor units is
+    /// [`MoneyArithmeticError::Overflow`], never a wrapped value.
+    pub fn checked_add(&self, rhs: &Money) -> Result<Money, MoneyArithmeticError> {
+        self.same_currency(rhs)?;
+        self.amount
+            .checked_add(rhs.amount)
+            .map(|amount| self.with_amount(amount))
+            .ok_or(MoneyArithmeticError::Overflow)
+    }
+
+    /// `self − rhs` in minor units, under the same rules as
+    /// [`Money::checked_add`].
+    pub fn checked_sub(&self, rhs: &Money) -> Result<Money, MoneyArithmeticError> {
+        self.same_currency(rhs)?;
+        self.amount
+            .checked_sub(rhs.amount)
+            .map(|amount| self.with_amount(amount))
+            .ok_or(MoneyArithmeticError::Overflow)
+    }
+
+    /// `self × factor`, rounded to the currency's minor unit under `mode`
+    /// — percentage discounts, tax rates, FX. The product is computed
+    /// exactly in the decimal core first, so `mode` is the only rounding
+    /// that ever happens.
+    ///
+    /// ```
+    /// use nml_core::decimal::{Number, RoundingMode};
+    /// use nml_core::money::parse_money;
+    /// use nml_core::span::Span;
+    ///
+    /// let price = parse_money("19.99", "USD", Span::empty(0)).unwrap();
+    /// let discount = Number::parse_literal("0.15").unwrap();
+    /// let off = price.checked_mul_rounded(&discount, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(off.format_display(), "3.00 USD");
+    /// ```
+    pub fn checked_mul_rounded(
+        &self,
+        factor: &Number,
+        mode: RoundingMode,
+    ) -> Result<Money, MoneyArithmeticError> {
+        // Minor units × factor, quantized to whole minor units.
+        let units = Number::from(self.amount).checked_mul(factor)?;
+        let amount = units
+            .checked_div_rounded(&Number::from(1), 0, mode)?
+            .to_i64()
+            .ok_or(MoneyArithmeticError::Overflow)?;
+        Ok(self.with_amount(amount))
+    }
+
+    /// Split the amount into `ratios.len()` parts proportional to
+    /// `ratios` that sum **exactly** to `self`, largest-remainder style:
+    /// every part gets its floored share in minor units, and the minor
+    /// units left over go one each to the parts with the largest
+    /// remainders (earlier parts win ties). A negative amount splits its
+    /// magnitude and negates the parts, so `-10.00` in thirds mirrors
+    /// `10.00` (`-3.34, -3.33, -3.33`).
+    ///
+    /// Ratios must be non-negative with a nonzero sum
+    /// ([`MoneyArithmeticError::InvalidRatios`]); a zero ratio gets a
+    /// zero part.
+    ///
+    /// ```
+    /// use nml_core::decimal::Number;
+    /// use nml_core::money::parse_money;
+    /// use nml_core::span::Span;
+    ///
+    /// let total = parse_money("10.00", "USD", Span::empty(0)).unwrap();
+    /// let thirds = total.allocate(&[Number::from(1); 3]).unwrap();
+    /// let shown: Vec<_> = thirds.iter().map(|m| m.format_display()).collect();
+    /// assert_eq!(shown, ["3.34 USD", "3.33 USD", "3.33 USD"]);
+    /// ```
+    pub fn allocate(&self, ratios: &[Number]) -> Result<Vec<Money>, MoneyArithmeticError> {
+        if ratios.iter().any(|r| *r < Number::ZERO) {
+            return Err(MoneyArithmeticError::InvalidRatios);
+        }
+        let total = ratios
+            .iter()
+            .try_fold(Number::ZERO, |acc, r| acc.checked_add(r))?;
+        if total == Number::ZERO {
+            return Err(MoneyArithmeticError::InvalidRatios);
+        }
+        let magnitude = i128::from(self.amount).abs();
+        let units = Number::try_from(magnitude)?;
+        // Exact share = units × r / total = floor + remainder / total; the
+        // remainders share a denominator, so they rank directly.
+        let mut parts = Vec::with_capacity(ratios.len());
+        let mut remainders = Vec::with_capacity(ratios.len());
+        for r in ratios {
+            let share = units.checked_mul(r)?;
+            let floor = share.checked_div_rounded(&total, 0, RoundingMode::Floor)?;
+            remainders.push(share.checked_sub(&floor.checked_mul(&total)?)?);
+            parts.push(floor.to_i128().ok_or(MoneyArithmeticError::Overflow)?);
+        }
+        // Fewer leftover units than nonzero remainders, by construction.
+        let leftover = magnitude - parts.iter().sum::<i128>();
+        let mut order: Vec<usize> = (0..ratios.len()).collect();
+        order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]));
+        for &i in order.iter().take(leftover as usize) {
+            parts[i] += 1;
+        }
+        parts
+            .into_iter()
+            .map(|part| {
+                let signed = if self.amount < 0 { -part } else { part };
+                i64::try_from(signed)
+                    .map(|amount| self.with_amount(amount))
+                    .map_err(|_| MoneyArithmeticError::Overflow)
+            })
+            .collect()
+    }
+
+    fn same_currency(&self, rhs: &Money) -> Result<(), MoneyArithmeticError> {
+        if self.currency == rhs.currency {
+            Ok(())
+        } else {
+            Err(MoneyArithmeticError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: rhs.currency.clone(),
+            })
+        }
+    }
+
+    fn with_amount(&self, amount: i64) -> Money {
+        Money {
+            amount,
+            currency: self.currency.clone(),
+            exponent: self.exponent,
+        }
+    }
 }
 
+/// Why a [`Money`] operation failed — span-free, like
+/// [`NumberError`]: arithmetic runs on values, not on source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MoneyArithmeticError {
+    /// The operands carry different currencies. Money never converts,
+    /// and nothing here changes a value's currency: callers convert one
+    /// side outside this API (with their own rates) before combining.
+    CurrencyMismatch { left: String, right: String },
+    /// The result exceeds `i64` minor units.
+    Overflow,
+    /// [`Money::allocate`] ratios are empty, negative, or sum to zero.
+    InvalidRatios,
+    /// The exact decimal step failed (see [`NumberError`]).
+    Number(NumberError),
+}
+
+impl From<NumberError> for MoneyArithmeticError {
+    fn from(e: NumberError) -> Self {
+        MoneyArithmeticError::Number(e)
+    }
+}
+
+impl fmt::Display for MoneyArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyArithmeticError::CurrencyMismatch { left, right } => {
+                write!(f, "currency mismatch: cannot combine {left} with {right}")
+            }
+            MoneyArithmeticError::Overflow => {
+                f.write_str("money result exceeds the i64 minor-unit range")
+            }
+            MoneyArithmeticError::InvalidRatios => {
+                f.write_str("allocation ratios must be non-negative and must not sum to zero")
+            }
+            MoneyArithmeticError::Number(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MoneyArithmeticError {}
+
 /// Why a money literal failed to parse (RFC 0009 D13) — fully structured:
 /// message, stable code, and the ISO-4217 did-you-mean all derive from the
 /// payload, never from message text.
@@ -192,7 +367,11 @@ fn parse_minor_units(
             crate::decimal::NumberRangeIssue::CoefficientTooWide { .. }
             | crate::decimal::NumberRangeIssue::ScaleOutOfRange { .. }
             | crate::decimal::NumberRangeIssue::NegativeScaleZero { .. },
-        ) => NmlError::Money {
+        )
+        // Arithmetic-only kinds, unreachable from a parse for the same
+        // reason and mapped the same way.
+        | crate::decimal::NumberError::Inexact
+        | crate::decimal::NumberError::DivisionByZero => NmlError::Money {
             kind: MoneyErrorKind::OutOfRange {
                 raw: crate::error::echo_capture(amount_str),
             },
@@ -567,4 +746,119 @@ mod tests {
         let neg = parse_money("-0.50", "USD", Span::empty(0)).unwrap();
         assert_eq!(neg.format_display(), "-0.50 USD");
     }
+
+    // --- money arithmetic ---
+
+    fn usd(s: &str) -> Money {
+        parse_money(s, "USD", Span::empty(0)).unwrap()
+    }
+
+    #[test]
+    fn add_and_sub_check_currency_and_range() {
+        assert_eq!(
+            usd("19.99").checked_add(&usd("0.01")).unwrap(),
+            usd("20.00")
+        );
+        assert_eq!(usd("1.00").checked_sub(&usd("2.50")).unwrap(), usd("-1.50"));
+        let yen = parse_money("100", "JPY", Span::empty(0)).unwrap();
+        assert_eq!(
+            usd("1.00").checked_add(&yen),
+            Err(MoneyArithmeticError::CurrencyMismatch {
+                left: "USD".to_string(),
+                right: "JPY".to_string(),
+            })
+        );
+        let max = usd("92233720368547758.07");
+        assert_eq!(
+            max.checked_add(&usd("0.01")),
+            Err(MoneyArithmeticError::Overflow)
+        );
+    }
+
+    #[test]
+    fn mul_rounds_to_the_minor_unit_as_asked() {
+        let rate = crate::num!(0.125);
+        let m = usd("0.20");
+        // 2.5 cents.
+        assert_eq!(
+            m.checked_mul_rounded(&rate, RoundingMode::HalfEven)
+                .unwrap(),
+            usd("0.02")
+        );
+        assert_eq!(
+            m.checked_mul_rounded(&rate, RoundingMode::HalfUp).unwrap(),
+            usd("0.03")
+        );
+        assert_eq!(
+            usd("-0.20")
+                .checked_mul_rounded(&rate, RoundingMode::Floor)
+                .unwrap(),
+            usd("-0.03")
+        );
+        let bhd = parse_money("1.000", "BHD", Span::empty(0)).unwrap();
+        assert_eq!(
+            bhd.checked_mul_rounded(&crate::num!(1.0825), RoundingMode::Down)
+                .unwrap()
+                .format_display(),
+            "1.082 BHD"
+        );
+        assert_eq!(
+            usd("92233720368547758.07").checked_mul_rounded(&crate::num!(2), RoundingMode::Down),
+            Err(MoneyArithmeticError::Overflow)
+        );
+    }
+
+    #[test]
+    fn allocate_sums_exactly_with_largest_remainders() {
+        let split = |m: Money, ratios: &[Number]| -> Vec<String> {
+            m.allocate(ratios)
+                .unwrap()
+                .iter()
+                .map(Money::format_display)
+                .collect()
+        };
+        let one = Number::from(1);
+        assert_eq!(
+            split(usd("0.05"), &[crate::num!(0.3), crate::num!(0.7)]),
+            ["0.02 USD", "0.03 USD"]
+        );
+        // 100 cents over 1:1:1:3 floors to 16 × 3 + 50: two cents left,
+        // and the three tied .67 remainders hand them out in order.
+        assert_eq!(
+            split(usd("1.00"), &[one, one, one, Number::from(3)]),
+            ["0.17 USD", "0.17 USD", "0.16 USD", "0.50 USD"]
+        );
+        assert_eq!(
+            split(usd("-10.00"), &[one; 3]),
+            ["-3.34 USD", "-3.33 USD", "-3.33 USD"]
+        );
+        assert_eq!(
+            split(usd("1.00"), &[Number::ZERO, one]),
+            ["0.00 USD", "1.00 USD"]
+        );
+        let min = usd("-92233720368547758.08");
+        let parts = min.allocate(&[one; 7]).unwrap();
+        assert_eq!(
+            parts.iter().map(|m| i128::from(m.amount)).sum::<i128>(),
+            i128::from(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn allocate_rejects_degenerate_ratios() {
+        for ratios in [
+            &[][..],
+            &[Number::ZERO, Number::ZERO][..],
+            &[Number::from(2), Number::from(-1)][..],
+        ] {
+            assert_eq!(
+                usd("1.00").allocate(ratios),
+                Err(MoneyArithmeticError::InvalidRatios)
+            );
+        }
+        assert_eq!(
+            MoneyArithmeticError::InvalidRatios.to_string(),
+            "allocation ratios must be non-negative and must not sum to zero"
+        );
+    }
 }
@@ -0,0 +1,642 @@
+//! The regular-expression subset behind the `pattern` string facet.
+//!
+//! Deliberately small and dependency-free: a pattern compiles to a tiny NFA
+//! and matches by simulating every thread at once (a Pike VM without
+//! captures), so matching is linear in the input for any pattern — no
+//! backtracking, and therefore no pathological schema can stall a
+//! validator.
+//!
+//! A pattern always matches the **whole** value; a leading `^` and a
+//! trailing `$` are accepted and redundant. The supported syntax:
+//!
+//! | Syntax | Meaning |
+//! |--------|---------|
+//! | `a` | the character itself (anything but `\ . [ ] ( ) { } * + ? \| ^ $`) |
+//! | `.` | any character |
+//! | `[a-z0-9_]`, `[^"]` | a character class, ranges allowed; `-` first or last is literal |
+//! | `\d` `\w` `\s` | ASCII digit, word character (`[A-Za-z0-9_]`), whitespace |
+//! | `\D` `\W` `\S` | their complements (outside classes only) |
+//! | `\n` `\t` `\r` | newline, tab, carriage return |
+//! | `\.`, `\\`, … | any other escaped punctuation is that character |
+//! | `(…)` | grouping |
+//! | `a\|b` | alternation |
+//! | `*` `+` `?` | zero-or-more, one-or-more, optional |
+//! | `{n}` `{n,}` `{n,m}` | counted repetition, `n`, `m` ≤ 1000 |
+//!
+//! Not supported, and rejected with a message rather than misread:
+//! backreferences, lookaround, lazy quantifiers, POSIX and Unicode classes.
+//! Case matters; there is no case-insensitive flag.
+
+use serde::Serialize;
+
+/// The largest count a `{n,m}` repetition may name.
+const MAX_REPEAT: u32 = 1000;
+
+/// The largest compiled program a pattern may produce — bounds the work per
+/// input character, which is what keeps matching linear in practice.
+const MAX_PROGRAM: usize = 10_000;
+
+/// A compiled pattern. Compares and renders as its source text.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    program: Vec<Inst>,
+}
+
+/// Why a pattern failed to compile: the message and the character offset
+/// (not byte offset) in the pattern text where the problem starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at character {})", self.message, self.offset + 1)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl Pattern {
+    /// Compile `source`.
+    pub fn new(source: &str) -> Result<Pattern, PatternError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut start = 0;
+        let mut end = chars.len();
+        if chars.first() == Some(&'^') {
+            start = 1;
+        }
+        if end > start && chars[end - 1] == '$' && !ends_escaped(&chars[start..end - 1]) {
+            end -= 1;
+        }
+        let mut parser = Parser {
+            chars: &chars[..end],
+            pos: start,
+        };
+        let node = parser.alternation()?;
+        if let Some(&c) = parser.peek() {
+            return Err(parser.error(match c {
+                ')' => "unmatched `)`".to_string(),
+                _ => format!("unexpected `{c}`"),
+            }));
+        }
+        let mut program = Vec::new();
+        compile(&node, &mut program);
+        program.push(Inst::Match);
+        if program.len() > MAX_PROGRAM {
+            return Err(PatternError {
+                message: "pattern is too large (its repetitions expand past the size limit)"
+                    .to_string(),
+                offset: 0,
+            });
+        }
+        Ok(Pattern {
+            source: source.to_string(),
+            program,
+        })
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the whole of `text` matches.
+    pub fn is_match(&self, text: &str) -> bool {
+        let n = self.program.len();
+        let mut current = Threads::new(n);
+        let mut next = Threads::new(n);
+        current.add(&self.program, 0);
+        for c in text.chars() {
+            if current.list.is_empty() {
+                return false;
+            }
+            next.clear();
+            for &pc in &current.list {
+                if let Inst::Class(class) = &self.program[pc] {
+                    if class.matches(c) {
+                        next.add(&self.program, pc + 1);
+                    }
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+        }
+        current
+            .list
+            .iter()
+            .any(|&pc| matches!(self.program[pc], Inst::Match))
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Pattern {}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+/// Whether the last character of `chars` is escaped by an odd run of `\`.
+fn ends_escaped(chars: &[char]) -> bool {
+    chars.iter().rev().take_while(|c| **c == '\\').count() % 2 == 1
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Class(Class),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Class {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
+
+impl Class {
+    fn single(c: char) -> Class {
+        Class {
+            negated: false,
+            ranges: vec![(c, c)],
+        }
+    }
+
+    fn any() -> Class {
+        Class {
+            negated: true,
+            ranges: Vec::new(),
+        }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != self.negated
+    }
+}
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+const SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+struct Parser<'a> {
+    chars: &'a [char],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&char> {
+        self.chars.get(self.pos)
+    }
+
+    fn error(&self, message: impl Into<String>) -> PatternError {
+        PatternError {
+            message: message.into(),
+            offset: self.pos,
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Node, PatternError> {
+        let mut arms = vec![self.concat()?];
+        while self.peek() == Some(&'|') {
+            self.pos += 1;
+            arms.push(self.concat()?);
+        }
+        Ok(if arms.len() == 1 {
+            arms.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alt(arms)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, PatternError> {
+        let mut items = Vec::new();
+        while let Some(&c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            items.push(self.quantified(atom)?);
+        }
+        Ok(match items.len() {
+            0 => Node::Empty,
+            1 => items.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(items),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let start = self.pos;
+        let c = self.chars[self.pos];
+        self.pos += 1;
+        match c {
+            '.' => Ok(Node::Class(Class::any())),
+            '[' => self.class(start).map(Node::Class),
+            '(' => {
+                if self.peek() == Some(&'?') {
+                    self.pos = start;
+                    return Err(self.error("lookaround and group flags are not supported"));
+                }
+                let inner = self.alternation()?;
+                if self.peek() != Some(&')') {
+                    self.pos = start;
+                    return Err(self.error("unclosed `(`"));
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            '\\' => self.escape(false).map(Node::Class),
+            '*' | '+' | '?' | '{' => {
+                self.pos = start;
+                Err(self.error(format!("`{c}` has nothing to repeat")))
+            }
+            '^' | '$' => {
+                self.pos = start;
+                Err(self.error(format!(
+                    "`{c}` is only allowed at the pattern's edge — patterns always \
+                     match the whole value"
+                )))
+            }
+            ']' | '}' => {
+                self.pos = start;
+                Err(self.error(format!("unmatched `{c}` (escape it as `\\{c}`)")))
+            }
+            c => Ok(Node::Class(Class::single(c))),
+        }
+    }
+
+    /// The character after a `\`. Complement classes (`\D`) are refused
+    /// inside `[...]`, where a negation cannot be unioned with ranges.
+    fn escape(&mut self, in_class: bool) -> Result<Class, PatternError> {
+        let start = self.pos - 1;
+        let Some(&c) = self.peek() else {
+            self.pos = start;
+            return Err(self.error("pattern ends in a lone `\\`"));
+        };
+        self.pos += 1;
+        let set = |ranges: &[(char, char)], negated: bool| Class {
+            negated,
+            ranges: ranges.to_vec(),
+        };
+        Ok(match c {
+            'd' => set(DIGIT, false),
+            'w' => set(WORD, false),
+            's' => set(SPACE, false),
+            'D' | 'W' | 'S' if in_class => {
+                self.pos = start;
+                return Err(self.error(format!("`\\{c}` is not supported inside `[...]`")));
+            }
+            'D' => set(DIGIT, true),
+            'W' => set(WORD, true),
+            'S' => set(SPACE, true),
+            'n' => Class::single('\n'),
+            't' => Class::single('\t'),
+            'r' => Class::single('\r'),
+            c if c.is_ascii_punctuation() || c == ' ' => Class::single(c),
+            c if c.is_ascii_digit() => {
+                self.pos = start;
+                return Err(self.error("backreferences are not supported"));
+            }
+            c => {
+                self.pos = start;
+                return Err(self.error(format!("unknown escape `\\{c}`")));
+            }
+        })
+    }
+
+    fn class(&mut self, start: usize) -> Result<Class, PatternError> {
+        let negated = self.peek() == Some(&'^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(&c) = self.peek() else {
+                self.pos = start;
+                return Err(self.error("unclosed `[`"));
+            };
+            if c == ']' && !first {
+                self.pos += 1;
+                break;
+            }
+            first = false;
+            self.pos += 1;
+            if c == '[' && self.peek() == Some(&':') {
+                self.pos -= 1;
+                return Err(self.error("POSIX classes (`[:alpha:]`) are not supported"));
+            }
+            let lo = if c == '\\' {
+                let escaped = self.escape(true)?;
+                if escaped.ranges.len() != 1 || escaped.ranges[0].0 != escaped.ranges[0].1 {
+                    ranges.extend(escaped.ranges);
+                    continue;
+                }
+                escaped.ranges[0].0
+            } else {
+                c
+            };
+            // `a-z`; a `-` before the closing `]` is literal.
+            if self.peek() == Some(&'-') && self.chars.get(self.pos + 1).is_some_and(|n| *n != ']')
+            {
+                let dash = self.pos;
+                self.pos += 1;
+                let hi_char = self.chars[self.pos];
+                self.pos += 1;
+                let hi = if hi_char == '\\' {
+                    let escaped = self.escape(true)?;
+                    match escaped.ranges.as_slice() {
+                        [(a, b)] if a == b => *a,
+                        _ => {
+                            self.pos = dash;
+                            return Err(self.error("a range cannot end in a class escape"));
+                        }
+                    }
+                } else {
+                    hi_char
+                };
+                if hi < lo {
+                    self.pos = dash;
+                    return Err(self.error(format!("range `{lo}-{hi}` is out of order")));
+                }
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Ok(Class { negated, ranges })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node, PatternError> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.count(start)?;
+                let max = if self.peek() == Some(&',') {
+                    self.pos += 1;
+                    if self.peek() == Some(&'}') {
+                        None
+                    } else {
+                        Some(self.count(start)?)
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some(&'}') {
+                    self.pos = start;
+                    return Err(self.error("unclosed `{` (expected `{n}`, `{n,}` or `{n,m}`)"));
+                }
+                if max.is_some_and(|m| m < min) {
+                    self.pos = start;
+                    return Err(self.error(format!(
+                        "repetition `{{{min},{}}}` is out of order",
+                        max.unwrap_or_default()
+                    )));
+                }
+                (min, max)
+            }
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err(self.error(
+                "a quantifier cannot follow a quantifier (lazy and possessive forms are \
+                 not supported)",
+            ));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+
+    fn count(&mut self, start: usize) -> Result<u32, PatternError> {
+        let digits_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[digits_start..self.pos].iter().collect();
+        match digits.parse::<u32>() {
+            Ok(n) if n <= MAX_REPEAT => Ok(n),
+            Ok(_) | Err(_) if !digits.is_empty() => {
+                self.pos = start;
+                Err(self.error(format!("repetition counts are at most {MAX_REPEAT}")))
+            }
+            _ => {
+                self.pos = start;
+                Err(self.error("expected a count in `{...}`"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Class(Class),
+    Split(usize, usize),
+    Jmp(usize),
+    Match,
+}
+
+fn compile(node: &Node, out: &mut Vec<Inst>) {
+    // A runaway expansion stops growing once it is past the limit; the
+    // caller rejects the pattern on size, so the partial program is never
+    // run.
+    if out.len() > MAX_PROGRAM {
+        return;
+    }
+    match node {
+        Node::Empty => {}
+        Node::Class(c) => out.push(Inst::Class(c.clone())),
+        Node::Concat(items) => {
+            for item in items {
+                compile(item, out);
+            }
+        }
+        Node::Alt(arms) => {
+            let mut jumps = Vec::new();
+            for (i, arm) in arms.iter().enumerate() {
+                if i + 1 < arms.len() {
+                    let split = out.len();
+                    out.push(Inst::Split(split + 1, 0));
+                    compile(arm, out);
+                    jumps.push(out.len());
+                    out.push(Inst::Jmp(0));
+                    let next = out.len();
+                    out[split] = Inst::Split(split + 1, next);
+                } else {
+                    compile(arm, out);
+                }
+            }
+            let end = out.len();
+            for j in jumps {
+                out[j] = Inst::Jmp(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                compile(node, out);
+            }
+            match max {
+                None => {
+                    let split = out.len();
+                    out.push(Inst::Split(split + 1, 0));
+                    compile(node, out);
+                    out.push(Inst::Jmp(split));
+                    let end = out.len();
+                    out[split] = Inst::Split(split + 1, end);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(out.len());
+                        out.push(Inst::Split(out.len() + 1, 0));
+                        compile(node, out);
+                        if out.len() > MAX_PROGRAM {
+                            break;
+                        }
+                    }
+                    let end = out.len();
+                    for s in splits {
+                        out[s] = Inst::Split(s + 1, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A thread set for one input position, deduplicated by program counter.
+/// `list` holds the consuming and matching threads; `marked` every
+/// instruction visited while adding them (jumps and splits included), so
+/// clearing costs what adding did.
+struct Threads {
+    list: Vec<usize>,
+    marked: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(n: usize) -> Threads {
+        Threads {
+            list: Vec::with_capacity(n),
+            marked: Vec::with_capacity(n),
+            seen: vec![false; n],
+        }
+    }
+
+    fn clear(&mut self) {
+        for &pc in &self.marked {
+            self.seen[pc] = false;
+        }
+        self.marked.clear();
+        self.list.clear();
+    }
+
+    /// Add `pc`, following jumps and splits so only consuming and matching
+    /// instructions are kept.
+    fn add(&mut self, program: &[Inst], pc: usize) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if self.seen[pc] {
+                continue;
+            }
+            self.seen[pc] = true;
+            self.marked.push(pc);
+            match program[pc] {
+                Inst::Jmp(to) => stack.push(to),
+                Inst::Split(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Inst::Class(_) | Inst::Match => self.list.push(pc),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Pattern::new(pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn matches_the_whole_value() {
+        assert!(matches("[a-z0-9-]+", "my-slug-2"));
+        assert!(!matches("[a-z0-9-]+", "My-slug"));
+        assert!(!matches("[a-z0-9-]+", ""));
+        assert!(!matches("abc", "xabcx"));
+        // Edge anchors are accepted and change nothing.
+        assert!(matches("^abc$", "abc"));
+        assert!(!matches("^abc$", "abcd"));
+        assert!(matches("a\\$", "a$"));
+    }
+
+    #[test]
+    fn supports_the_documented_subset() {
+        assert!(matches("\\d{3}-\\d{4}", "555-1234"));
+        assert!(!matches("\\d{3}-\\d{4}", "555-123"));
+        assert!(matches("(ab|cd)*e?", "abcdab"));
+        assert!(matches("(ab|cd)*e?", "e"));
+        assert!(matches("a{2,}", "aaaa"));
+        assert!(!matches("a{2,3}", "aaaa"));
+        assert!(matches("[^\"]+", "no quotes"));
+        assert!(matches("\\w+\\s\\w+", "hello world"));
+        assert!(matches("[-_a]+", "-_a"));
+        assert!(matches("[a\\-z]+", "a-z"));
+        assert!(matches(".+\\.example\\.com", "api.example.com"));
+        assert!(!matches(".+\\.example\\.com", "apiXexampleXcom"));
+        // Characters, not bytes.
+        assert!(matches("é.{2}", "éüñ"));
+    }
+
+    #[test]
+    fn nested_stars_stay_linear() {
+        let p = Pattern::new("(a*)*b").unwrap();
+        let text = "a".repeat(10_000);
+        assert!(!p.is_match(&text));
+    }
+
+    #[test]
+    fn rejects_what_it_does_not_support() {
+        let err = |p: &str| Pattern::new(p).unwrap_err();
+        assert!(err("(?=a)").message.contains("lookaround"));
+        assert!(err("(a)\\1").message.contains("backreferences"));
+        assert!(err("a*?").message.contains("quantifier"));
+        assert!(err("[[:alpha:]]").message.contains("POSIX"));
+        assert!(err("\\p{L}").message.contains("unknown escape"));
+        assert!(err("[z-a]").message.contains("out of order"));
+        assert!(err("a{1001}").message.contains("at most 1000"));
+        assert_eq!(err("ab(c").offset, 2);
+        assert_eq!(err("ab)").offset, 2);
+        assert!(err("a^b").message.contains("edge"));
+        assert!(err("(a{1000}){1000}").message.contains("too large"));
+    }
+}
@@ -203,6 +203,40 @@ impl ProjectConfig {
     }
 }
 
+/// Pin schema package `name` in an existing `nml-project.nml` source,
+/// returning the complete new text via the structural CST insert
+/// ([`crate::cst::edit::insert_entry_at_path`], RFC 0030 P2). `None` when
+/// the package is already pinned or the source has no single `project`
+/// block to target.
+///
+/// Idempotency is decided by the same parser that reads pins at resolution
+/// time ([`ProjectConfig::from_file`]), so a `- name` inside a comment or
+/// string never counts as pinned. The pin is appended to the project's
+/// `schemaPackages:` block, which is created directly under the
+/// `project <Name>:` header when missing. The caller validates `name`
+/// against the package-name charset first.
+pub fn insert_pin(source: &str, name: &str) -> Option<String> {
+    use crate::cst::edit::{EntryPosition, insert_entry_at_path};
+    let config = ProjectConfig::from_file(&crate::cst::parse_best_effort(source));
+    if config.schema_packages.iter().any(|p| p == name) {
+        return None;
+    }
+    insert_entry_at_path(
+        source,
+        &["project", "schemaPackages"],
+        &format!("- {name}"),
+        EntryPosition::Last,
+    )
+    .or_else(|| {
+        insert_entry_at_path(
+            source,
+            &["project"],
+            &format!("schemaPackages:\n    - {name}"),
+            EntryPosition::AfterHeader,
+        )
+    })
+}
+
 fn extract_string_array(value: &Value) -> Vec<String> {
     match value {
         Value::Array(items) => items
@@ -1,7 +1,8 @@
 //! Convenience API for querying parsed NML documents.
 //!
 //! Provides a fluent interface for extracting typed values from the AST
-//! without manual pattern matching.
+//! without manual pattern matching, and [`QueryPath`] — the same lookups
+//! spelled as one string (`service Api.endpoints[Docs].url`).
 //!
 //! # Example
 //!
@@ -31,6 +32,10 @@
 use crate::ast::*;
 use crate::types::Value;
 
+mod path;
+
+pub use path::{ItemSelector, Name, PathMatch, PathStep, QueryPath, QueryPathError, Selected};
+
 /// A queryable wrapper around a parsed NML [`File`].
 pub struct Document<'a> {
     file: &'a File,
@@ -127,6 +132,8 @@ impl<'a> Document<'a> {
                 DeclarationKind::OneOf(o) => {
                     result.push(("oneof", o.name.name.as_str()));
                 }
+                // An import names a file, not a declaration.
+                DeclarationKind::Import(_) => {}
             }
         }
         result
@@ -0,0 +1,698 @@
+//! Path expressions: the [`Document`] query API as one string, for callers
+//! that cannot write Rust — `nml query`, CI steps, shell scripts.
+//!
+//! ```text
+//! service Api.endpoints[Docs].url
+//! ```
+//!
+//! | Syntax | Selects |
+//! |--------|---------|
+//! | `keyword Name` | a top-level declaration, as [`Document::declarations`] names it: a block (`service Api`), an array by its item keyword (`notifier alertNotifiers`), `const X`, `template X` |
+//! | `.field` | a property's value or a nested block |
+//! | `.\|name` | an access-control modifier (`.\|allow`) |
+//! | `[Name]` | a list item by name: a named item, a reference, a role, or a scalar key spelled `Name` |
+//! | `["key"]` | a list item by quoted key, for keys a name cannot spell (`["/api"]`) |
+//! | `[2]` | a list item or an array value's element by zero-based index |
+//! | `*`, `.*`, `[*]` | wildcards: any keyword or name, any field, any item |
+//!
+//! A path selects zero or more nodes; a path without a wildcard selects at
+//! most one (the first, should an invalid document repeat a name). A step
+//! through a reference to a top-level array declaration (`notifiers =
+//! alertNotifiers`) continues into that array, so references stay
+//! transparent to a query as they are to deserialization (RFC 0013).
+
+use crate::ast::*;
+use crate::types::Value;
+
+use super::Document;
+
+/// A parsed path expression. Renders back as its canonical spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPath {
+    keyword: Name,
+    name: Name,
+    steps: Vec<PathStep>,
+}
+
+/// A keyword, declaration name, or field name: exact or `*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Name {
+    Any,
+    Exact(String),
+}
+
+/// One step after the root declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathStep {
+    /// `.field` / `.*`
+    Field(Name),
+    /// `.|name`
+    Modifier(String),
+    /// `[…]`
+    Item(ItemSelector),
+}
+
+/// How a `[…]` step picks list items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemSelector {
+    /// `[*]`
+    Any,
+    /// `[2]`
+    Index(usize),
+    /// `[Docs]` / `["/api"]`
+    Key(String),
+}
+
+/// Why a path failed to parse: the message and the character offset (not
+/// byte offset) in the path text where the problem starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPathError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl std::fmt::Display for QueryPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at character {})", self.message, self.offset + 1)
+    }
+}
+
+impl std::error::Error for QueryPathError {}
+
+/// A node a path selected.
+#[derive(Debug, Clone, Copy)]
+pub enum Selected<'a> {
+    /// A block, a nested block, or the body of a named item.
+    Body(&'a Body),
+    /// A top-level array declaration.
+    Array(&'a ArrayBody),
+    /// A list item.
+    Item(&'a ListItem),
+    /// A modifier written as a list (`|allow:` over indented items).
+    Items(&'a [ListItem]),
+    /// A property, `const` or `template` value, an inline modifier value,
+    /// or an element of an array value.
+    Value(&'a Value),
+}
+
+/// One selection: the node and the concrete path that reached it — the
+/// wildcards filled in, so every match names itself.
+#[derive(Debug, Clone)]
+pub struct PathMatch<'a> {
+    pub path: String,
+    pub node: Selected<'a>,
+}
+
+impl QueryPath {
+    /// Parse `text`.
+    pub fn parse(text: &str) -> Result<QueryPath, QueryPathError> {
+        let mut p = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+        };
+        p.skip_spaces();
+        let keyword = p.name("a declaration keyword")?;
+        if !p.eat_spaces() {
+            return Err(p.error(
+                "expected a space and a declaration name after the keyword (`service Api`)",
+            ));
+        }
+        let name = p.name("a declaration name")?;
+        let mut steps = Vec::new();
+        loop {
+            match p.peek() {
+                None => break,
+                Some('.') => {
+                    p.pos += 1;
+                    if p.peek() == Some('|') {
+                        p.pos += 1;
+                        match p.name("a modifier name")? {
+                            Name::Exact(n) => steps.push(PathStep::Modifier(n)),
+                            Name::Any => {
+                                p.pos -= 1;
+                                return Err(p.error("a modifier step takes a name, not `*`"));
+                            }
+                        }
+                    } else {
+                        steps.push(PathStep::Field(p.name("a field name after `.`")?));
+                    }
+                }
+                Some('[') => {
+                    p.pos += 1;
+                    steps.push(PathStep::Item(p.item_selector()?));
+                    if p.peek() != Some(']') {
+                        return Err(p.error("expected `]`"));
+                    }
+                    p.pos += 1;
+                }
+                Some(' ') | Some('\t') => {
+                    p.skip_spaces();
+                    if p.peek().is_some() {
+                        return Err(p.error("unexpected text after the path"));
+                    }
+                }
+                Some(c) => return Err(p.error(&format!("expected `.` or `[`, found `{c}`"))),
+            }
+        }
+        Ok(QueryPath {
+            keyword,
+            name,
+            steps,
+        })
+    }
+
+    /// Whether any part of the path is a wildcard (so it may select more
+    /// than one node).
+    pub fn has_wildcard(&self) -> bool {
+        self.keyword == Name::Any
+            || self.name == Name::Any
+            || self.steps.iter().any(|s| {
+                matches!(
+                    s,
+                    PathStep::Field(Name::Any) | PathStep::Item(ItemSelector::Any)
+                )
+            })
+    }
+
+    /// The steps after the root declaration.
+    pub fn steps(&self) -> &[PathStep] {
+        &self.steps
+    }
+}
+
+impl std::fmt::Display for QueryPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.keyword, self.name)?;
+        for step in &self.steps {
+            match step {
+                PathStep::Field(name) => write!(f, ".{name}")?,
+                PathStep::Modifier(name) => write!(f, ".|{name}")?,
+                PathStep::Item(ItemSelector::Any) => f.write_str("[*]")?,
+                PathStep::Item(ItemSelector::Index(i)) => write!(f, "[{i}]")?,
+                PathStep::Item(ItemSelector::Key(k)) => f.write_str(&item_segment(k))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Name::Any => f.write_str("*"),
+            Name::Exact(n) => f.write_str(n),
+        }
+    }
+}
+
+impl Name {
+    fn matches(&self, candidate: &str) -> bool {
+        match self {
+            Name::Any => true,
+            Name::Exact(n) => n == candidate,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> QueryPathError {
+        QueryPathError {
+            message: message.to_string(),
+            offset: self.pos,
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ') | Some('\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip spaces, reporting whether there were any.
+    fn eat_spaces(&mut self) -> bool {
+        let start = self.pos;
+        self.skip_spaces();
+        self.pos > start
+    }
+
+    /// An identifier (the characters NML names are made of) or `*`.
+    fn name(&mut self, what: &str) -> Result<Name, QueryPathError> {
+        if self.peek() == Some('*') {
+            self.pos += 1;
+            return Ok(Name::Any);
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(is_name_char) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error(&format!("expected {what}")));
+        }
+        Ok(Name::Exact(self.chars[start..self.pos].iter().collect()))
+    }
+
+    fn item_selector(&mut self) -> Result<ItemSelector, QueryPathError> {
+        match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                Ok(ItemSelector::Any)
+            }
+            Some('"') => self.quoted().map(ItemSelector::Key),
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits: String = self.chars[start..self.pos].iter().collect();
+                digits
+                    .parse()
+                    .map(ItemSelector::Index)
+                    .map_err(|_| QueryPathError {
+                        message: "index out of range".to_string(),
+                        offset: start,
+                    })
+            }
+            _ => match self.name("an item name, quoted key, index, or `*` inside `[…]`")? {
+                Name::Exact(n) => Ok(ItemSelector::Key(n)),
+                Name::Any => Ok(ItemSelector::Any),
+            },
+        }
+    }
+
+    /// A double-quoted key; `\"` and `\\` are the only escapes.
+    fn quoted(&mut self) -> Result<String, QueryPathError> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(QueryPathError {
+                        message: "unterminated quoted key".to_string(),
+                        offset: open,
+                    });
+                }
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') if matches!(self.chars.get(self.pos + 1), Some('"') | Some('\\')) => {
+                    out.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The characters an NML identifier continues with.
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// `[Docs]` when the key spells a name, `["/api"]` otherwise.
+fn item_segment(key: &str) -> String {
+    if !key.is_empty()
+        && key.chars().all(is_name_char)
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+    {
+        format!("[{key}]")
+    } else {
+        format!("[\"{}\"]", key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// The text a list item is selected by, if it has one.
+fn item_key(item: &ListItem) -> Option<String> {
+    match &item.kind {
+        ListItemKind::Named { name, .. } => Some(name.name.clone()),
+        ListItemKind::Reference(ident) => Some(ident.name.clone()),
+        ListItemKind::Role(role) => Some(role.clone()),
+        ListItemKind::Shorthand { value, .. } => value_key(&value.value),
+    }
+}
+
+/// The text a scalar is selected by as an item key.
+fn value_key(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) | Value::Role(s) | Value::Reference(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Duration(d) => Some(d.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Evaluate a [`QueryPath`], in document order.
+    pub fn select(&self, path: &QueryPath) -> Vec<PathMatch<'a>> {
+        let mut current = Vec::new();
+        for decl in &self.file.declarations {
+            let (keyword, name, node) = match &decl.kind {
+                DeclarationKind::Block(b) => (
+                    b.keyword.name.as_str(),
+                    b.name.name.as_str(),
+                    Selected::Body(&b.body),
+                ),
+                DeclarationKind::Array(a) => (
+                    a.item_keyword.name.as_str(),
+                    a.name.name.as_str(),
+                    Selected::Array(&a.body),
+                ),
+                DeclarationKind::Const(c) => (
+                    "const",
+                    c.name.name.as_str(),
+                    Selected::Value(&c.value.value),
+                ),
+                DeclarationKind::Template(t) => (
+                    "template",
+                    t.name.name.as_str(),
+                    Selected::Value(&t.value.value),
+                ),
+                DeclarationKind::OneOf(_) | DeclarationKind::Import(_) => continue,
+            };
+            if path.keyword.matches(keyword) && path.name.matches(name) {
+                current.push(PathMatch {
+                    path: format!("{keyword} {name}"),
+                    node,
+                });
+            }
+        }
+        if !path.has_wildcard() {
+            current.truncate(1);
+        }
+        for step in &path.steps {
+            let mut next = Vec::new();
+            for m in &current {
+                self.step(m, step, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+
+    fn step(&self, from: &PathMatch<'a>, step: &PathStep, out: &mut Vec<PathMatch<'a>>) {
+        let exact = !matches!(
+            step,
+            PathStep::Field(Name::Any) | PathStep::Item(ItemSelector::Any)
+        );
+        let before = out.len();
+        let push = |out: &mut Vec<PathMatch<'a>>, segment: String, node| {
+            out.push(PathMatch {
+                path: format!("{}{segment}", from.path),
+                node,
+            });
+        };
+        match (self.enter(from.node), step) {
+            (Container::Body(body), PathStep::Field(name)) => {
+                for entry in &body.entries {
+                    match &entry.kind {
+                        BodyEntryKind::Property(p) if name.matches(&p.name.name) => push(
+                            out,
+                            format!(".{}", p.name.name),
+                            Selected::Value(&p.value.value),
+                        ),
+                        BodyEntryKind::NestedBlock(n) if name.matches(&n.name.name) => {
+                            push(out, format!(".{}", n.name.name), Selected::Body(&n.body))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            (Container::Body(body), PathStep::Modifier(name)) => {
+                for entry in &body.entries {
+                    if let BodyEntryKind::Modifier(m) = &entry.kind {
+                        if &m.name.name == name {
+                            if let Some(node) = modifier_node(m) {
+                                push(out, format!(".|{name}"), node);
+                            }
+                        }
+                    }
+                }
+            }
+            (Container::Body(body), PathStep::Item(selector)) => {
+                let items = body.entries.iter().filter_map(|e| match &e.kind {
+                    BodyEntryKind::ListItem(item) => Some(item),
+                    _ => None,
+                });
+                select_items(items, selector, |segment, node| push(out, segment, node));
+            }
+            (Container::Array(array), PathStep::Field(name)) => {
+                for p in &array.properties {
+                    if name.matches(&p.name.name) {
+                        push(
+                            out,
+                            format!(".{}", p.name.name),
+                            Selected::Value(&p.value.value),
+                        );
+                    }
+                }
+            }
+            (Container::Array(array), PathStep::Modifier(name)) => {
+                for m in array.modifiers.iter().filter(|m| &m.name.name == name) {
+                    if let Some(node) = modifier_node(m) {
+                        push(out, format!(".|{name}"), node);
+                    }
+                }
+            }
+            (Container::Array(array), PathStep::Item(selector)) => {
+                select_items(array.items.iter(), selector, |segment, node| {
+                    push(out, segment, node)
+                });
+            }
+            (Container::Items(items), PathStep::Item(selector)) => {
+                select_items(items.iter(), selector, |segment, node| {
+                    push(out, segment, node)
+                });
+            }
+            (Container::Values(values), PathStep::Item(selector)) => {
+                for (i, v) in values.iter().enumerate() {
+                    let key = value_key(&v.value);
+                    let hit = match selector {
+                        ItemSelector::Any => true,
+                        ItemSelector::Index(n) => *n == i,
+                        ItemSelector::Key(k) => key.as_deref() == Some(k.as_str()),
+                    };
+                    if hit {
+                        let segment = match (selector, &key) {
+                            (ItemSelector::Index(_), _) | (_, None) => format!("[{i}]"),
+                            (_, Some(k)) => item_segment(k),
+                        };
+                        push(out, segment, Selected::Value(&v.value));
+                    }
+                }
+            }
+            _ => {}
+        }
+        if exact {
+            out.truncate((before + 1).min(out.len()));
+        }
+    }
+
+    /// What a node can be stepped into. A reference to a top-level array
+    /// declaration is entered as that array.
+    fn enter(&self, node: Selected<'a>) -> Container<'a> {
+        match node {
+            Selected::Body(body) => Container::Body(body),
+            Selected::Array(array) => Container::Array(array),
+            Selected::Items(items) => Container::Items(items),
+            Selected::Item(item) => match &item.kind {
+                ListItemKind::Named { body, .. }
+                | ListItemKind::Shorthand {
+                    body: Some(body), ..
+                } => Container::Body(body),
+                ListItemKind::Reference(ident) => self
+                    .array_body(&ident.name)
+                    .map_or(Container::None, Container::Array),
+                _ => Container::None,
+            },
+            Selected::Value(Value::Array(values)) => Container::Values(values),
+            Selected::Value(Value::Reference(name)) => self
+                .array_body(name)
+                .map_or(Container::None, Container::Array),
+            Selected::Value(_) => Container::None,
+        }
+    }
+}
+
+enum Container<'a> {
+    Body(&'a Body),
+    Array(&'a ArrayBody),
+    Items(&'a [ListItem]),
+    Values(&'a [crate::types::SpannedValue]),
+    None,
+}
+
+fn modifier_node(modifier: &Modifier) -> Option<Selected<'_>> {
+    match &modifier.value {
+        ModifierValue::Inline(v) => Some(Selected::Value(&v.value)),
+        ModifierValue::Block(items) => Some(Selected::Items(items)),
+        ModifierValue::TypeAnnotation { .. } => None,
+    }
+}
+
+fn select_items<'a>(
+    items: impl Iterator<Item = &'a ListItem>,
+    selector: &ItemSelector,
+    mut push: impl FnMut(String, Selected<'a>),
+) {
+    for (i, item) in items.enumerate() {
+        let key = item_key(item);
+        let hit = match selector {
+            ItemSelector::Any => true,
+            ItemSelector::Index(n) => *n == i,
+            ItemSelector::Key(k) => key.as_deref() == Some(k.as_str()),
+        };
+        if hit {
+            let segment = match (selector, &key) {
+                (ItemSelector::Index(_), _) | (_, None) => format!("[{i}]"),
+                (_, Some(k)) => item_segment(k),
+            };
+            push(segment, Selected::Item(item));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cst::parse_to_ast;
+
+    const SOURCE: &str = "\
+service Api:
+    |allow = [@public]
+    port = 8080
+    tags = [\"web\", \"api\"]
+    database:
+        url = \"postgres://db\"
+    endpoints:
+        - Api:
+            url = \"https://api\"
+        - Docs:
+            url = \"https://docs\"
+        - \"/health\"
+    notifiers = alerts
+
+service Worker:
+    port = 9090
+
+[]notifier alerts:
+    - Console:
+        level = \"error\"
+
+const Retries = 3
+";
+
+    fn select(path: &str) -> Vec<String> {
+        let file = parse_to_ast(SOURCE).unwrap();
+        let doc = Document::new(&file);
+        let path = QueryPath::parse(path).unwrap();
+        doc.select(&path).into_iter().map(|m| m.path).collect()
+    }
+
+    fn value(path: &str) -> Option<Value> {
+        let file = parse_to_ast(SOURCE).unwrap();
+        let doc = Document::new(&file);
+        let path = QueryPath::parse(path).unwrap();
+        match doc.select(&path).first().map(|m| m.node) {
+            Some(Selected::Value(v)) => Some(v.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn selects_nested_fields_and_named_items() {
+        assert_eq!(
+            value("service Api.endpoints[Docs].url"),
+            Some(Value::String("https://docs".into()))
+        );
+        assert_eq!(
+            value("service Api.database.url"),
+            Some(Value::String("postgres://db".into()))
+        );
+        assert_eq!(value("const Retries"), Some(Value::number(3)));
+        assert_eq!(value("service Api.endpoints[Missing].url"), None);
+    }
+
+    #[test]
+    fn selects_by_index_and_quoted_key() {
+        assert_eq!(
+            value("service Api.endpoints[0].url"),
+            Some(Value::String("https://api".into()))
+        );
+        assert_eq!(
+            select("service Api.endpoints[\"/health\"]"),
+            vec!["service Api.endpoints[\"/health\"]"]
+        );
+        assert_eq!(
+            value("service Api.tags[1]"),
+            Some(Value::String("api".into()))
+        );
+    }
+
+    #[test]
+    fn wildcards_fill_in_the_concrete_path() {
+        assert_eq!(
+            select("service *.port"),
+            vec!["service Api.port", "service Worker.port"]
+        );
+        assert_eq!(
+            select("service Api.endpoints[*]"),
+            vec![
+                "service Api.endpoints[Api]",
+                "service Api.endpoints[Docs]",
+                "service Api.endpoints[\"/health\"]",
+            ]
+        );
+        assert_eq!(
+            select("service Api.database.*"),
+            vec!["service Api.database.url"]
+        );
+    }
+
+    #[test]
+    fn steps_through_array_references_and_modifiers() {
+        assert_eq!(
+            value("service Api.notifiers[Console].level"),
+            Some(Value::String("error".into()))
+        );
+        assert_eq!(
+            value("notifier alerts[Console].level"),
+            Some(Value::String("error".into()))
+        );
+        assert_eq!(select("service Api.|allow"), vec!["service Api.|allow"]);
+    }
+
+    #[test]
+    fn parse_round_trips_and_rejects_with_offsets() {
+        for text in [
+            "service Api.endpoints[Docs].url",
+            "* *.|allow",
+            "service Api.endpoints[\"/a b\"][*].x",
+            "const X[3]",
+        ] {
+            assert_eq!(QueryPath::parse(text).unwrap().to_string(), text);
+        }
+        let err = QueryPath::parse("service").unwrap_err();
+        assert_eq!(err.offset, 7);
+        let err = QueryPath::parse("service Api..url").unwrap_err();
+        assert_eq!(err.offset, 12);
+        let err = QueryPath::parse("service Api[\"open").unwrap_err();
+        assert!(err.message.contains("unterminated"), "{err}");
+        assert!(!QueryPath::parse("service Api.port").unwrap().has_wildcard());
+        assert!(QueryPath::parse("service Api[*]").unwrap().has_wildcard());
+    }
+}
@@ -338,7 +338,7 @@ fn collect_trait_refs<'a>(
                 out.push(name.as_str());
             }
         }
-        FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
+        FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
             collect_trait_refs(inner, defs, out)
         }
         FieldType::Union(parts) => {
@@ -372,7 +372,7 @@ pub fn find_shorthand_errors(schema: &ExtractedSchema) -> Vec<Diagnostic> {
         let (mut key_fill, mut body_fill): (Vec<&str>, Vec<&str>) = (Vec::new(), Vec::new());
         for f in model.fields.iter().filter(|f| f.shorthand) {
             match f.field_type {
-                FieldType::List(_) | FieldType::Set(_) => body_fill.push(f.name.as_str()),
+                FieldType::List(_, _) | FieldType::Set(_, _) => body_fill.push(f.name.as_str()),
                 _ => key_fill.push(f.name.as_str()),
             }
         }
@@ -487,7 +487,9 @@ fn collect_refs_from_type<'a>(
                 refs.extend(variants.iter().copied());
             }
         }
-        FieldType::List(inner) => collect_refs_from_type(inner, known_models, oneof_variants, refs),
+        FieldType::List(inner, _) => {
+            collect_refs_from_type(inner, known_models, oneof_variants, refs)
+        }
         FieldType::Union(variants) => {
             for v in variants {
                 collect_refs_from_type(v, known_models, oneof_variants, refs);
@@ -764,15 +766,15 @@ mod tests {
         }
     }
 
-    /// Facets attach to `number` and `duration` ONLY; every other
-    /// primitive refuses with a message naming the actual type — the
-    /// author needs to know WHICH type refused. A `duration` field
+    /// Facets attach to `number`, `duration`, `money` and `string` ONLY;
+    /// every other primitive refuses with a message naming the actual
+    /// type — the author needs to know WHICH type refused. A `duration` field
     /// with a UNITLESS bound is the domain-agreement error instead
     /// (durations became legal carriers when RFC 0018 §3's deferral
     /// closed): the message teaches the literal shape.
     #[test]
     fn facets_on_other_primitives_name_the_type() {
-        for ty in ["string", "bool", "path", "secret", "money"] {
+        for ty in ["bool", "path", "secret"] {
             let src = format!("model m:\n    x {ty}(min = 1)\n");
             let (_s, diags) = crate::cst::extract_schema(&src);
             let msg = diags
@@ -795,6 +797,230 @@ mod tests {
             msg.contains("has no unit") && msg.contains("min = 1s"),
             "message must teach the duration-literal shape: {msg}"
         );
+        // `money` carries facets, but only `currency`.
+        let (_s, diags) = crate::cst::extract_schema("model m:\n    x money(min = 1)\n");
+        assert!(
+            diags.iter().any(|d| d
+                .rendered_message()
+                .contains("unknown facet 'min' (known: currency)")),
+            "{diags:?}"
+        );
+    }
+
+    /// The `currency` facet loads as a money facet (single code or list),
+    /// renders canonically through `FieldType`'s Display, and its
+    /// definition rules reject unknown and duplicate codes — unknown ones
+    /// with the ISO 4217 did-you-mean over the string itself.
+    #[test]
+    fn currency_facet_extracts_and_checks_codes() {
+        let (schema, diags) = crate::cst::extract_schema(
+            "model m:\n    a money(currency = \"USD\")\n    b money(currency = [\"USD\", \"GBP\"])\n",
+        );
+        assert!(diags.is_empty(), "{diags:?}");
+        let ty = |n: &str| {
+            schema.models[0]
+                .fields
+                .iter()
+                .find(|f| f.name == n)
+                .unwrap()
+                .field_type
+                .to_string()
+        };
+        assert_eq!(ty("a"), "money(currency = \"USD\")");
+        assert_eq!(ty("b"), "money(currency = [\"USD\", \"GBP\"])");
+
+        let src = "model m:\n    c money(currency = [\"usd\", \"GBP\", \"GBP\"])\n";
+        let (schema, diags) = crate::cst::extract_schema(src);
+        let unknown = diags
+            .iter()
+            .find(|d| d.rendered_message().contains("unknown currency 'usd'"))
+            .unwrap_or_else(|| panic!("{diags:?}"));
+        assert_eq!(
+            unknown.code,
+            Some(crate::diagnostic::codes::FACET_DEFINITION)
+        );
+        let fix = unknown.suggestions.first().expect("did-you-mean");
+        assert_eq!(fix.replacement, "\"USD\"");
+        assert_eq!(&src[fix.span.start..fix.span.end], "\"usd\"");
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.rendered_message().contains("duplicate currency 'GBP'")),
+            "{diags:?}"
+        );
+        // Only the known codes load.
+        assert_eq!(
+            schema.models[0].fields[0].field_type.to_string(),
+            "money(currency = \"GBP\")"
+        );
+
+        let (_s, diags) = crate::cst::extract_schema("model m:\n    n number(min = \"1\")\n");
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.rendered_message().contains("`min` takes no strings")),
+            "{diags:?}"
+        );
+    }
+
+    /// String facets load as a `String` domain record with canonical
+    /// Display; bad lengths, bad patterns (pointing at the offending
+    /// character) and empty length ranges are definition errors.
+    #[test]
+    fn string_facets_extract_and_check_definitions() {
+        let (schema, diags) = crate::cst::extract_schema(
+            "model m:\n    slug string( pattern = \"[a-z0-9-]+\", maxLength=63, minLength = 1)\n",
+        );
+        assert!(diags.is_empty(), "{diags:?}");
+        assert_eq!(
+            schema.models[0].fields[0].field_type.to_string(),
+            "string(minLength = 1, maxLength = 63, pattern = \"[a-z0-9-]+\")"
+        );
+
+        let bad = |src: &str, needle: &str| {
+            let (_s, diags) = crate::cst::extract_schema(src);
+            diags
+                .into_iter()
+                .find(|d| {
+                    d.code == Some(codes::FACET_DEFINITION) && d.rendered_message().contains(needle)
+                })
+                .unwrap_or_else(|| panic!("expected {needle:?} for {src:?}"))
+        };
+        bad(
+            "model m:\n    s string(minLength = -1)\n",
+            "whole, non-negative number",
+        );
+        bad(
+            "model m:\n    s string(maxLength = 1.5)\n",
+            "whole, non-negative number",
+        );
+        bad(
+            "model m:\n    s string(pattern = 5)\n",
+            "`pattern` takes a string literal",
+        );
+        bad(
+            "model m:\n    s string(minLength = 3, maxLength = 2)\n",
+            "length range is unsatisfiable",
+        );
+        bad(
+            "model m:\n    s string(min = 1)\n",
+            "unknown facet 'min' (known: minLength, maxLength, pattern)",
+        );
+        let src = "model m:\n    s string(pattern = \"ab(?=c)\")\n";
+        let d = bad(src, "invalid `pattern`: lookaround");
+        assert_eq!(&src[d.span.unwrap().start..d.span.unwrap().end], "(");
+        // With escapes in the literal the offsets no longer align, so the
+        // error covers the whole literal.
+        let src = "model m:\n    s string(pattern = \"\\\\d(\")\n";
+        let d = bad(src, "unclosed `(`");
+        assert_eq!(
+            &src[d.span.unwrap().start..d.span.unwrap().end],
+            "\"\\\\d(\""
+        );
+        // A default is held to its own string facets.
+        let (_s, diags) =
+            crate::cst::extract_schema("model m:\n    s string(minLength = 2) = \"x\"\n");
+        assert!(
+            diags.iter().any(|d| d.code == Some(codes::FACET_VIOLATION)
+                && d.rendered_message().contains("has 1 character, fewer than")),
+            "{diags:?}"
+        );
+    }
+
+    #[test]
+    fn collection_facets_extract_and_check_definitions() {
+        let (schema, diags) = crate::cst::extract_schema(
+            "model endpoint:\n    url string\n\nmodel m:\n    endpoints []endpoint(minItems = 1)\n    \
+             tags []string(maxLength = 8, maxItems = 4)\n    regions set<string>(nonEmpty = true)\n",
+        );
+        assert!(diags.is_empty(), "{diags:?}");
+        let m = schema.models.iter().find(|m| m.name == "m").unwrap();
+        let rendered: Vec<String> = m.fields.iter().map(|f| f.field_type.to_string()).collect();
+        assert_eq!(
+            rendered,
+            [
+                "[]endpoint(minItems = 1)",
+                "[]string(maxLength = 8, maxItems = 4)",
+                "set<string>(nonEmpty = true)",
+            ]
+        );
+        // The element keeps only its own facets.
+        let crate::model::FieldType::List(inner, facets) = &m.fields[1].field_type else {
+            panic!("expected a list");
+        };
+        assert_eq!(inner.to_string(), "string(maxLength = 8)");
+        assert!(!facets.admits(5));
+
+        let bad = |src: &str, needle: &str| {
+            let (_s, diags) = crate::cst::extract_schema(src);
+            assert!(
+                diags.iter().any(|d| {
+                    d.code == Some(codes::FACET_DEFINITION) && d.rendered_message().contains(needle)
+                }),
+                "expected {needle:?} for {src:?}, got {diags:?}"
+            );
+        };
+        bad(
+            "model m:\n    n number(minItems = 1)\n",
+            "`minItems` constrains a collection — it applies to `[]T` or `set<T>`, not `number`",
+        );
+        bad(
+            "model m:\n    s set<string(maxItems = 2)>\n",
+            "applies to `[]T` or `set<T>`, not `string`",
+        );
+        bad(
+            "model m:\n    s set<string>(minLength = 2)\n",
+            "unknown facet 'minLength' on a collection",
+        );
+        bad(
+            "model m:\n    s []string(minItems = 1.5)\n",
+            "whole, non-negative number of items",
+        );
+        bad(
+            "model m:\n    s []string(nonEmpty = 1)\n",
+            "`nonEmpty` takes `true` or `false`",
+        );
+        bad(
+            "model m:\n    s []string(minItems = 1, nonEmpty = true)\n",
+            "'minItems' and 'nonEmpty' are mutually exclusive",
+        );
+        bad(
+            "model m:\n    s []string(minItems = 3, maxItems = 2)\n",
+            "(minItems = 3 against maxItems = 2)",
+        );
+        bad(
+            "model m:\n    s []string(nonEmpty = true, maxItems = 0)\n",
+            "(nonEmpty = true against maxItems = 0)",
+        );
+        bad(
+            "model m:\n    n number(min = true)\n",
+            "`true` is a boolean",
+        );
+        // A default is held to its own collection facets.
+        let (_s, diags) =
+            crate::cst::extract_schema("model m:\n    s []string(minItems = 1) = []\n");
+        assert!(
+            diags.iter().any(|d| d.code == Some(codes::FACET_VIOLATION)
+                && d.rendered_message()
+                    == "default for 's' has 0 items, fewer than the schema's minItems = 1"),
+            "{diags:?}"
+        );
+    }
+
+    /// A money default outside its own `currency` facet is a facet
+    /// violation at load, like an out-of-range number default.
+    #[test]
+    fn money_default_must_satisfy_currency_facet() {
+        let (_s, diags) =
+            crate::cst::extract_schema("model m:\n    p money(currency = \"USD\") = 5.00 EUR\n");
+        assert!(
+            diags.iter().any(|d| d.code == Some(codes::FACET_VIOLATION)
+                && d.rendered_message().contains("in EUR")),
+            "{diags:?}"
+        );
+        let (_s, diags) =
+            crate::cst::extract_schema("model m:\n    p money(currency = \"USD\") = 5.00 USD\n");
+        assert!(diags.is_empty(), "{diags:?}");
     }
 
     /// Duration facets (RFC 0017 literals under the RFC 0018 grammar):
@@ -977,7 +1203,7 @@ mod tests {
     /// promises cannot exist.
     #[test]
     fn facet_rules_reach_typed_modifiers() {
-        let src = "model m:\n    |allow string(min = 1)\n    |cap number(min = 2, max = 1)\n";
+        let src = "model m:\n    |allow bool(min = 1)\n    |cap number(min = 2, max = 1)\n";
         let (_schema, diags) = crate::cst::extract_schema(src);
         let msgs: Vec<String> = diags
             .iter()
@@ -1441,10 +1667,10 @@ mod tests {
         let model = &schema.models[0];
         assert_eq!(model.fields.len(), 2);
 
-        assert!(matches!(model.fields[0].field_type, FieldType::List(_)));
+        assert!(matches!(model.fields[0].field_type, FieldType::List(_, _)));
         assert!(!model.fields[0].optional);
 
-        assert!(matches!(model.fields[1].field_type, FieldType::List(_)));
+        assert!(matches!(model.fields[1].field_type, FieldType::List(_, _)));
         assert!(model.fields[1].optional);
     }
 
@@ -2014,6 +2240,87 @@ fn pick_duration(v: &crate::types::Value) -> Option<crate::duration::Duration> {
     }
 }
 
+/// The AST facet list as a [`crate::model::MoneyFacets`], keeping only
+/// known codes — the AST-layer twin of extraction's money builder.
+fn money_facets_of_ast(facets: &[crate::ast::FacetExpr]) -> crate::model::MoneyFacets {
+    let mut out = crate::model::MoneyFacets::default();
+    for f in facets.iter().filter(|f| f.key.name == "currency") {
+        let entries: Vec<&crate::types::Value> = match &f.value.value {
+            crate::types::Value::Array(items) => items.iter().map(|i| &i.value).collect(),
+            v => vec![v],
+        };
+        let mut codes: Vec<String> = Vec::new();
+        for entry in entries {
+            if let crate::types::Value::String(code) = entry {
+                if crate::money::currency_exponent(code).is_some() && !codes.contains(code) {
+                    codes.push(code.clone());
+                }
+            }
+        }
+        out.currency = (!codes.is_empty()).then_some(crate::model::CurrencyFacet {
+            codes,
+            span: f.span,
+        });
+    }
+    out
+}
+
+/// The AST facet list as a [`crate::model::StringFacets`] — the AST-layer
+/// twin of extraction's string builder; invalid entries never load.
+fn string_facets_of_ast(facets: &[crate::ast::FacetExpr]) -> crate::model::StringFacets {
+    let mut out = crate::model::StringFacets::default();
+    for f in facets {
+        let length = || match &f.value.value {
+            crate::types::Value::Number(n) => Some(crate::model::LengthFacet {
+                value: usize::try_from(n.to_u64()?).ok()?,
+                span: f.span,
+            }),
+            _ => None,
+        };
+        match (f.key.name.as_str(), &f.value.value) {
+            ("minLength", _) => out.min_length = length(),
+            ("maxLength", _) => out.max_length = length(),
+            ("pattern", crate::types::Value::String(text)) => {
+                out.pattern = crate::pattern::Pattern::new(text).ok().map(|pattern| {
+                    crate::model::PatternFacet {
+                        pattern,
+                        span: f.span,
+                    }
+                })
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// The AST facet list as a [`crate::model::CollectionFacets`] — the
+/// AST-layer twin of extraction's collection builder.
+fn collection_facets_of_ast(facets: &[crate::ast::FacetExpr]) -> crate::model::CollectionFacets {
+    let mut out = crate::model::ItemFacets::default();
+    for f in facets {
+        let count = || match &f.value.value {
+            crate::types::Value::Number(n) => Some(crate::model::LengthFacet {
+                value: usize::try_from(n.to_u64()?).ok()?,
+                span: f.span,
+            }),
+            _ => None,
+        };
+        match (f.key.name.as_str(), &f.value.value) {
+            ("minItems", _) => out.min_items = count(),
+            ("maxItems", _) => out.max_items = count(),
+            ("nonEmpty", crate::types::Value::Bool(value)) => {
+                out.non_empty = Some(crate::model::NonEmptyFacet {
+                    value: *value,
+                    span: f.span,
+                })
+            }
+            _ => {}
+        }
+    }
+    crate::model::CollectionFacets::Items(Box::new(out))
+}
+
 /// Could this type hold `value` at all? The facet domain only needs
 /// the coarse question — a `number` literal cannot land in a `string`,
 /// `bool`, enum or model-ref variant — which is exactly what keeps a
@@ -2023,12 +2330,18 @@ fn facet_type_applies(te: &crate::ast::FieldTypeExpr, value: &crate::types::Valu
     match (te, value) {
         (T::Named { name, .. }, crate::types::Value::Number(_)) => name.name == "number",
         (T::Named { name, .. }, crate::types::Value::Duration(_)) => name.name == "duration",
+        (T::Named { name, .. }, crate::types::Value::Money(_)) => name.name == "money",
+        // A named ref may be an enum, whose variants are strings: it gets
+        // a (facet-free) vote rather than letting a string facet overrule it.
+        (T::Named { name, .. }, crate::types::Value::String(_)) => {
+            name.name == "string" || name.name.parse::<crate::types::PrimitiveType>().is_err()
+        }
         // Every element must face the element type — `[]string` must
         // NOT claim `[5]`. Mirrors enforcement's `value_matches_type`,
         // which requires all items to match `inner`; without it a
         // non-numeric collection variant vacuously admits a numeric
         // array, the union bug one level down.
-        (T::Array(inner) | T::Set(inner), crate::types::Value::Array(items)) => {
+        (T::Array(inner, _) | T::Set(inner, _), crate::types::Value::Array(items)) => {
             items.iter().all(|i| facet_type_applies(inner, &i.value))
         }
         (T::Union(vs), v) => vs.iter().any(|x| facet_type_applies(x, v)),
@@ -2082,8 +2395,39 @@ fn facet_default_violations(
                 );
             }
         }
-        T::Array(inner) | T::Set(inner) => {
+        T::Named { name, facets } if name.name == "string" && !facets.is_empty() => {
+            let crate::types::Value::String(s) = value else {
+                return;
+            };
+            for tail in string_facets_of_ast(facets).violations(s) {
+                diags.push(
+                    Diagnostic::error(format!("default for '{field_name}' {tail}"))
+                        .with_code(codes::FACET_VIOLATION)
+                        .with_span(span),
+                );
+            }
+        }
+        T::Named { name, facets } if name.name == "money" && !facets.is_empty() => {
+            let crate::types::Value::Money(m) = value else {
+                return;
+            };
+            for tail in money_facets_of_ast(facets).violations(m) {
+                diags.push(
+                    Diagnostic::error(format!("default for '{field_name}' {tail}"))
+                        .with_code(codes::FACET_VIOLATION)
+                        .with_span(span),
+                );
+            }
+        }
+        T::Array(inner, facets) | T::Set(inner, facets) => {
             if let crate::types::Value::Array(items) = value {
+                for tail in collection_facets_of_ast(facets).violations(items.len()) {
+                    diags.push(
+                        Diagnostic::error(format!("default for '{field_name}' {tail}"))
+                            .with_code(codes::FACET_VIOLATION)
+                            .with_span(span),
+                    );
+                }
                 for item in items {
                     facet_default_violations(inner, &item.value, field_name, item.span, diags);
                 }
@@ -2116,6 +2460,253 @@ fn facet_default_violations(
     }
 }
 
+/// The `money` facet rules: `currency` is the only key, and its value is
+/// one ISO 4217 code or a list of them. Unknown codes carry the same
+/// did-you-mean a money literal's unknown currency does, over the string
+/// token itself.
+fn money_facet_rules(
+    facets: &[crate::ast::FacetExpr],
+    field_name: &str,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let err = |msg: String, span: crate::span::Span| {
+        Diagnostic::error(msg)
+            .with_code(crate::diagnostic::codes::FACET_DEFINITION)
+            .with_span(span)
+    };
+    let mut seen_key = false;
+    for f in facets {
+        if f.key.name != "currency" {
+            diags.push(err(
+                format!(
+                    "'{field_name}': unknown facet '{}' (known: currency)",
+                    f.key.name
+                ),
+                f.span,
+            ));
+            continue;
+        }
+        if seen_key {
+            diags.push(err(
+                format!("'{field_name}': duplicate facet 'currency'"),
+                f.span,
+            ));
+        }
+        seen_key = true;
+        let entries: Vec<&crate::types::SpannedValue> = match &f.value.value {
+            crate::types::Value::String(_) => vec![&f.value],
+            crate::types::Value::Array(items) => items.iter().collect(),
+            _ => Vec::new(),
+        };
+        if entries.is_empty() {
+            diags.push(err(
+                format!(
+                    "'{field_name}': `currency` takes an ISO 4217 code (`currency = \"USD\"`) \
+                     or a list of them (`currency = [\"USD\", \"EUR\"]`)"
+                ),
+                f.span,
+            ));
+            continue;
+        }
+        let mut codes: Vec<&str> = Vec::new();
+        for entry in entries {
+            let crate::types::Value::String(code) = &entry.value else {
+                continue;
+            };
+            if crate::money::currency_exponent(code).is_none() {
+                let diag = err(
+                    format!("'{field_name}': unknown currency '{code}' in `currency`"),
+                    entry.span,
+                );
+                diags.push(
+                    match crate::suggest::suggest(code, crate::money::currency_codes()) {
+                        Some(s) => diag.with_suggestion(format!("\"{s}\""), entry.span),
+                        None => diag,
+                    },
+                );
+            } else if codes.contains(&code.as_str()) {
+                diags.push(err(
+                    format!("'{field_name}': duplicate currency '{code}' in `currency`"),
+                    entry.span,
+                ));
+            } else {
+                codes.push(code);
+            }
+        }
+    }
+}
+
+/// The `string` facet rules: `minLength`/`maxLength` are whole numbers
+/// of characters, `pattern` is a string literal in the [`crate::pattern`]
+/// subset, and the length range must be satisfiable. A pattern error
+/// points at the offending character when the literal has no escapes
+/// (source and pattern text then align byte for byte); otherwise at the
+/// whole literal.
+fn string_facet_rules(
+    facets: &[crate::ast::FacetExpr],
+    field_name: &str,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let err = |msg: String, span: crate::span::Span| {
+        Diagnostic::error(msg)
+            .with_code(crate::diagnostic::codes::FACET_DEFINITION)
+            .with_span(span)
+    };
+    let mut seen: Vec<&str> = Vec::new();
+    let mut lengths: Vec<(&crate::ast::FacetExpr, u64)> = Vec::new();
+    for f in facets {
+        let k = f.key.name.as_str();
+        if !["minLength", "maxLength", "pattern"].contains(&k) {
+            diags.push(err(
+                format!(
+                    "'{field_name}': unknown facet '{k}' (known: minLength, maxLength, pattern)"
+                ),
+                f.span,
+            ));
+            continue;
+        }
+        if seen.contains(&k) {
+            diags.push(err(
+                format!("'{field_name}': duplicate facet '{k}'"),
+                f.span,
+            ));
+        }
+        seen.push(k);
+        match (k, &f.value.value) {
+            ("pattern", crate::types::Value::String(text)) => {
+                if let Err(e) = crate::pattern::Pattern::new(text) {
+                    let literal = f.value.span;
+                    let span = if literal.end - literal.start == text.len() + 2 {
+                        let at = literal.start
+                            + 1
+                            + text
+                                .char_indices()
+                                .nth(e.offset)
+                                .map_or(text.len(), |(i, _)| i);
+                        let len = text[at - literal.start - 1..]
+                            .chars()
+                            .next()
+                            .map_or(0, char::len_utf8);
+                        crate::span::Span::new(at, at + len)
+                    } else {
+                        literal
+                    };
+                    diags.push(err(
+                        format!("'{field_name}': invalid `pattern`: {}", e.message),
+                        span,
+                    ));
+                }
+            }
+            ("pattern", _) => diags.push(err(
+                format!(
+                    "'{field_name}': `pattern` takes a string literal (`pattern = \"[a-z0-9-]+\"`)"
+                ),
+                f.span,
+            )),
+            (_, crate::types::Value::Number(n)) if n.to_u64().is_some() => {
+                lengths.push((f, n.to_u64().unwrap_or_default()));
+            }
+            (_, _) => diags.push(err(
+                format!("'{field_name}': `{k}` takes a whole, non-negative number of characters"),
+                f.span,
+            )),
+        }
+    }
+    let get = |k: &str| lengths.iter().find(|(f, _)| f.key.name == k);
+    if let (Some((_, lo)), Some((hi_f, hi))) = (get("minLength"), get("maxLength")) {
+        if lo > hi {
+            diags.push(err(
+                format!(
+                    "'{field_name}': the declared length range is unsatisfiable \
+                     (minLength = {lo} against maxLength = {hi})"
+                ),
+                hi_f.span,
+            ));
+        }
+    }
+}
+
+/// The collection facet rules: `minItems`/`maxItems` are whole numbers
+/// of items, `nonEmpty` is `true` or `false` and says what `minItems = 1`
+/// says (so the two never appear together), and the item range must be
+/// satisfiable.
+fn collection_facet_rules(
+    facets: &[crate::ast::FacetExpr],
+    field_name: &str,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let err = |msg: String, span: crate::span::Span| {
+        Diagnostic::error(msg)
+            .with_code(crate::diagnostic::codes::FACET_DEFINITION)
+            .with_span(span)
+    };
+    let mut seen: Vec<&str> = Vec::new();
+    for f in facets {
+        let k = f.key.name.as_str();
+        if !crate::model::is_collection_facet(k) {
+            diags.push(err(
+                format!(
+                    "'{field_name}': unknown facet '{k}' on a collection (known: minItems, \
+                     maxItems, nonEmpty)"
+                ),
+                f.span,
+            ));
+            continue;
+        }
+        if seen.contains(&k) {
+            diags.push(err(
+                format!("'{field_name}': duplicate facet '{k}'"),
+                f.span,
+            ));
+        }
+        seen.push(k);
+        match (k, &f.value.value) {
+            ("nonEmpty", crate::types::Value::Bool(_)) => {}
+            ("nonEmpty", _) => diags.push(err(
+                format!("'{field_name}': `nonEmpty` takes `true` or `false`"),
+                f.span,
+            )),
+            (_, crate::types::Value::Number(n)) if n.to_u64().is_some() => {}
+            (_, _) => diags.push(err(
+                format!("'{field_name}': `{k}` takes a whole, non-negative number of items"),
+                f.span,
+            )),
+        }
+    }
+    let get = |k: &str| facets.iter().find(|f| f.key.name == k);
+    if let (Some(_), Some(ne)) = (get("minItems"), get("nonEmpty")) {
+        diags.push(err(
+            format!("'{field_name}': 'minItems' and 'nonEmpty' are mutually exclusive"),
+            ne.span,
+        ));
+    }
+    let count = |f: &crate::ast::FacetExpr| match &f.value.value {
+        crate::types::Value::Number(n) => n.to_u64(),
+        _ => None,
+    };
+    let Some((hi_f, hi)) = get("maxItems").and_then(|f| Some((f, count(f)?))) else {
+        return;
+    };
+    let lo = match (get("minItems").and_then(count), get("nonEmpty")) {
+        (Some(lo), _) if lo > hi => Some(format!("minItems = {lo}")),
+        (None, Some(ne))
+            if hi == 0 && matches!(ne.value.value, crate::types::Value::Bool(true)) =>
+        {
+            Some("nonEmpty = true".to_string())
+        }
+        _ => None,
+    };
+    if let Some(lo) = lo {
+        diags.push(err(
+            format!(
+                "'{field_name}': the declared item range is unsatisfiable \
+                 ({lo} against maxItems = {hi})"
+            ),
+            hi_f.span,
+        ));
+    }
+}
+
 fn facet_rules_in_type(
     te: &crate::ast::FieldTypeExpr,
     field_name: &str,
@@ -2131,16 +2722,48 @@ fn facet_rules_in_type(
     };
     match te {
         T::Named { name, facets } => {
+            // Collection keys reach a Named type only when it is not a
+            // list's element (lowering hoists those): `set<string(minItems
+            // = 1)>`, or a scalar field. Taught before the domain rules so
+            // `endpoint(minItems = 1)` does not read as a model-ref error.
+            for f in facets
+                .iter()
+                .filter(|f| crate::model::is_collection_facet(&f.key.name))
+            {
+                err(
+                    diags,
+                    format!(
+                        "'{field_name}': `{}` constrains a collection — it applies to \
+                         `[]T` or `set<T>`, not `{}`",
+                        f.key.name, name.name
+                    ),
+                    f.span,
+                );
+            }
+            let facets: Vec<crate::ast::FacetExpr> = facets
+                .iter()
+                .filter(|f| !crate::model::is_collection_facet(&f.key.name))
+                .cloned()
+                .collect();
+            let facets = facets.as_slice();
             if facets.is_empty() {
                 return;
             }
             let domain = name.name.as_str();
+            if domain == "money" {
+                money_facet_rules(facets, field_name, diags);
+                return;
+            }
+            if domain == "string" {
+                string_facet_rules(facets, field_name, diags);
+                return;
+            }
             if domain != "number" && domain != "duration" {
                 err(
                     diags,
                     format!(
-                        "'{field_name}': facets attach only to `number` and `duration` — \
-                         `{}` cannot carry them",
+                        "'{field_name}': facets attach only to `number`, `duration`, \
+                         `money` and `string` — `{}` cannot carry them",
                         name.name
                     ),
                     facets[0].span,
@@ -2183,6 +2806,32 @@ fn facet_rules_in_type(
                             f.span,
                         );
                     }
+                    (crate::types::Value::Bool(b), _) => err(
+                        diags,
+                        format!(
+                            "'{field_name}': `{domain}` facets take {} — `{b}` is a boolean",
+                            if domain == "number" {
+                                "number values"
+                            } else {
+                                "duration literals"
+                            },
+                        ),
+                        f.span,
+                    ),
+                    (crate::types::Value::String(_) | crate::types::Value::Array(_), _) => err(
+                        diags,
+                        format!(
+                            "'{field_name}': `{domain}` facets take {} — `{}` takes no \
+                             strings",
+                            if domain == "number" {
+                                "number values"
+                            } else {
+                                "duration literals"
+                            },
+                            f.key.name
+                        ),
+                        f.span,
+                    ),
                     _ => {}
                 }
             }
@@ -2310,7 +2959,10 @@ fn facet_rules_in_type(
                 }
             }
         }
-        T::Array(inner) | T::Set(inner) => facet_rules_in_type(inner, field_name, diags),
+        T::Array(inner, facets) | T::Set(inner, facets) => {
+            collection_facet_rules(facets, field_name, diags);
+            facet_rules_in_type(inner, field_name, diags);
+        }
         T::Union(vs) => {
             for v in vs {
                 facet_rules_in_type(v, field_name, diags);
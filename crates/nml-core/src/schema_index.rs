@@ -178,7 +178,7 @@ impl SchemaIndex {
             FieldType::ModelRef(name) => self.model(name).is_some() || self.oneof(name).is_some(),
             FieldType::Modifier(inner) => self.field_type_admits_inline(inner),
             FieldType::Union(variants) => variants.iter().any(|v| self.field_type_admits_inline(v)),
-            FieldType::List(_) | FieldType::Set(_) | FieldType::Arms { .. } => false,
+            FieldType::List(_, _) | FieldType::Set(_, _) | FieldType::Arms { .. } => false,
             FieldType::Primitive { .. } => false,
         }
     }
@@ -195,7 +195,7 @@ impl SchemaIndex {
                 variants.iter().any(|v| self.field_type_admits_a_literal(v))
             }
             FieldType::ModelRef(name) => self.model(name).is_none() && self.oneof(name).is_none(),
-            FieldType::List(_) | FieldType::Set(_) | FieldType::Arms { .. } => false,
+            FieldType::List(_, _) | FieldType::Set(_, _) | FieldType::Arms { .. } => false,
         }
     }
 
@@ -289,7 +289,7 @@ impl SchemaIndex {
             .iter()
             .find(|variant| match variant {
                 FieldType::Arms { .. } => shape.has_arms,
-                FieldType::List(_) => !shape.has_arms && shape.has_list_items,
+                FieldType::List(_, _) => !shape.has_arms && shape.has_list_items,
                 FieldType::ModelRef(name) if shape.has_keyed => {
                     !shape.has_arms
                         && !shape.has_list_items
@@ -407,8 +407,10 @@ impl SchemaIndex {
                 ..
             } => FieldTarget::Object,
             FieldType::Primitive { .. } => FieldTarget::Leaf(ty),
-            FieldType::List(inner) => FieldTarget::ListOf(ty, Box::new(self.resolve_type(inner))),
-            FieldType::Set(inner) => FieldTarget::SetOf(ty, Box::new(self.resolve_type(inner))),
+            FieldType::List(inner, _) => {
+                FieldTarget::ListOf(ty, Box::new(self.resolve_type(inner)))
+            }
+            FieldType::Set(inner, _) => FieldTarget::SetOf(ty, Box::new(self.resolve_type(inner))),
             // A modifier field carries its declared inner type; classify by it.
             FieldType::Modifier(inner) => self.resolve_type(inner),
             FieldType::Union(_) => FieldTarget::Union(ty),
@@ -434,7 +436,7 @@ fn first_wins<T>(items: &[T], name: impl Fn(&T) -> &str) -> HashMap<String, usiz
 
 #[cfg(test)]
 mod tests {
-    use crate::model::{EnumDef, ModelKind, PrimitiveFacets};
+    use crate::model::{CollectionFacets, EnumDef, ModelKind, PrimitiveFacets};
     use crate::types::PrimitiveType;
 
     use super::*;
@@ -542,7 +544,10 @@ mod tests {
         assert!(matches!(
             idx.resolve_field(&field(
                 "x",
-                FieldType::List(Box::new(FieldType::ModelRef("inner".into())))
+                FieldType::List(
+                    Box::new(FieldType::ModelRef("inner".into())),
+                    CollectionFacets::None
+                )
             )),
             FieldTarget::ListOf(_, inner) if matches!(*inner, FieldTarget::Model(_))
         ));
@@ -585,7 +590,10 @@ mod tests {
         // `(step | []step)` — the workflow `parallel` shape.
         let union = FieldType::Union(vec![
             FieldType::ModelRef("step".into()),
-            FieldType::List(Box::new(FieldType::ModelRef("step".into()))),
+            FieldType::List(
+                Box::new(FieldType::ModelRef("step".into())),
+                CollectionFacets::None,
+            ),
         ]);
 
         // A scalar body selects the model-ref variant → Model.
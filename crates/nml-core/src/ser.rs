@@ -0,0 +1,1013 @@
+//! Serde `Serialize` bridge: Rust values back out as NML.
+//!
+//! The mirror of [`crate::de`]: where `de` reads a [`Body`] into a struct,
+//! this module builds a [`Body`] (or a one-declaration [`File`]) from any
+//! `Serialize` value, so a generated or migrated config can be written as
+//! NML. The tree is the semantic AST, not text — render it with
+//! `nml_fmt::ser::to_string` (or `nml_fmt::formatter::format`) for the
+//! canonical source form. Every synthesized span is `Span::empty(0)`: the
+//! tree has no source until it is formatted.
+//!
+//! The mapping is the inverse of what `de` accepts, so a round trip
+//! through both lands on an equal value:
+//!
+//! * structs and string-keyed maps → bodies; a nested one → a nested block;
+//! * [`Number`] → the exact literal (via the private newtype handshake its
+//!   `Serialize` negotiates — never through `f64`); integers → exact
+//!   numbers; floats → the shortest decimal that round-trips (NaN/∞ are
+//!   errors);
+//! * [`Money`] → a typed money literal (`19.99 USD`); [`Duration`] and
+//!   `std::time::Duration` → a duration literal (`30s`);
+//! * `Vec`s of scalars → arrays; `Vec`s of structs → named list items,
+//!   keyed by each element's `name` field (the label `de` injects back);
+//! * unit enum variants → strings; struct and newtype variants → blocks
+//!   with an RFC 0015 `as <Variant>` annotation (the synthesized external
+//!   tag `de` reads);
+//! * `None` and `()` fields are omitted.
+//!
+//! What has no NML spelling is an [`Error`], never a lossy guess: bytes,
+//! non-string map keys, keys that are not identifiers, `None` inside a
+//! list, and struct list elements without an identifier `name`.
+//!
+//! ```rust
+//! use serde::Serialize;
+//! use nml_core::ser::to_body;
+//!
+//! #[derive(Serialize)]
+//! struct Server {
+//!     port: u16,
+//!     host: String,
+//! }
+//!
+//! let body = to_body(&Server { port: 8080, host: "localhost".into() }).unwrap();
+//! assert_eq!(body.entries.len(), 2);
+//! ```
+//!
+//! [`Money`]: crate::money::Money
+//! [`Duration`]: crate::duration::Duration
+
+use std::fmt;
+
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+
+use crate::ast::*;
+use crate::decimal::NUMBER_NEWTYPE_TOKEN;
+use crate::duration::{Duration, DurationSegment, DurationUnit};
+use crate::money::{Money, currency_exponent};
+use crate::span::Span;
+use crate::types::{Number, SpannedValue, Value};
+
+/// Why a value has no NML spelling. The message carries the key path as
+/// nested serializers unwind (``field `db`: field `port`: …``), the same
+/// locator shape `de` errors use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Error {
+    fn new(msg: impl Into<String>) -> Self {
+        Error(msg.into())
+    }
+
+    fn with_field(self, key: &str) -> Self {
+        Error(format!("field `{key}`: {}", self.0))
+    }
+
+    fn with_element(self, index: usize) -> Self {
+        Error(format!("element {index}: {}", self.0))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Serialize a struct (or string-keyed map) into a block body.
+///
+/// A struct or newtype enum variant at the root becomes a body carrying
+/// the variant as its `as` annotation — the same root-level symmetry
+/// `de::from_body` honors. A scalar or sequence root is an error: a body
+/// holds entries, not a bare value.
+pub fn to_body<T: Serialize + ?Sized>(value: &T) -> Result<Body, Error> {
+    match value.serialize(NodeSerializer)? {
+        Node::Block { entries, variant } => block_body(entries, variant),
+        other => Err(Error::new(format!(
+            "the root must serialize as a struct or map, got {}",
+            other.kind()
+        ))),
+    }
+}
+
+/// Serialize a value as a single-declaration file: `keyword name:` over
+/// [`to_body`]'s body. `keyword` and `name` must be NML identifiers.
+pub fn to_file<T: Serialize + ?Sized>(keyword: &str, name: &str, value: &T) -> Result<File, Error> {
+    let keyword = identifier(keyword)?;
+    let name = identifier(name)?;
+    let body = to_body(value)?;
+    Ok(File {
+        declarations: vec![Declaration {
+            kind: DeclarationKind::Block(BlockDecl {
+                keyword,
+                name,
+                extends: Vec::new(),
+                body,
+            }),
+            span: SYNTHESIZED,
+        }],
+    })
+}
+
+/// The span every synthesized node carries.
+const SYNTHESIZED: Span = Span { start: 0, end: 0 };
+
+// ---------------------------------------------------------------------------
+// Node: the serializer's intermediate tree
+// ---------------------------------------------------------------------------
+
+/// What one `Serialize` call produced, before it is placed: whether a
+/// sequence becomes an array or a list of named items depends on its
+/// elements, and whether a struct is money or a block on its fields, so
+/// placement waits for the whole subtree.
+enum Node {
+    Value(Value),
+    Block {
+        entries: Vec<(String, Node)>,
+        /// The enum variant this block is the content of (RFC 0015 `as`).
+        variant: Option<String>,
+    },
+    Seq(Vec<Node>),
+    /// `None` / `()` — omitted where a field would hold it.
+    Absent,
+}
+
+impl Node {
+    fn kind(&self) -> &'static str {
+        match self {
+            Node::Value(v) => v.type_name(),
+            Node::Block { .. } => "a struct",
+            Node::Seq(_) => "a sequence",
+            Node::Absent => "nothing",
+        }
+    }
+}
+
+fn spanned(value: Value) -> SpannedValue {
+    SpannedValue::new(value, SYNTHESIZED)
+}
+
+/// An identifier per the lexer's rule (`[A-Za-z_][A-Za-z0-9_-]*`).
+fn identifier(text: &str) -> Result<Identifier, Error> {
+    let mut bytes = text.bytes();
+    let valid = bytes
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if valid {
+        Ok(Identifier::new(text, SYNTHESIZED))
+    } else {
+        Err(Error::new(format!("`{text}` is not an NML identifier")))
+    }
+}
+
+fn block_body(entries: Vec<(String, Node)>, variant: Option<String>) -> Result<Body, Error> {
+    let mut out = Vec::with_capacity(entries.len());
+    for (key, node) in entries {
+        if let Some(kind) = entry_kind(&key, node).map_err(|e| e.with_field(&key))? {
+            out.push(BodyEntry {
+                kind,
+                span: SYNTHESIZED,
+            });
+        }
+    }
+    let mut body = Body::fresh(out);
+    body.type_annotation = variant.map(|v| Identifier::new(v, SYNTHESIZED));
+    Ok(body)
+}
+
+/// Place one field: scalars and scalar sequences are properties, structs
+/// are nested blocks, struct sequences are blocks of named list items.
+fn entry_kind(key: &str, node: Node) -> Result<Option<BodyEntryKind>, Error> {
+    let name = identifier(key)?;
+    Ok(Some(match node {
+        Node::Absent => return Ok(None),
+        Node::Value(value) => BodyEntryKind::Property(Property {
+            name,
+            value: spanned(value),
+        }),
+        Node::Block { entries, variant } => BodyEntryKind::NestedBlock(NestedBlock {
+            name,
+            body: block_body(entries, variant)?,
+        }),
+        Node::Seq(items) if items.iter().any(|n| matches!(n, Node::Block { .. })) => {
+            let mut list = Vec::with_capacity(items.len());
+            for (i, item) in items.into_iter().enumerate() {
+                list.push(BodyEntry {
+                    kind: BodyEntryKind::ListItem(named_item(item).map_err(|e| e.with_element(i))?),
+                    span: SYNTHESIZED,
+                });
+            }
+            BodyEntryKind::NestedBlock(NestedBlock {
+                name,
+                body: Body::fresh(list),
+            })
+        }
+        Node::Seq(items) => BodyEntryKind::Property(Property {
+            name,
+            value: spanned(array(items)?),
+        }),
+    }))
+}
+
+/// A struct element of a list → `- Label:` with the element's `name`
+/// field as the label (removed from the body; `de` injects it back).
+fn named_item(node: Node) -> Result<ListItem, Error> {
+    let Node::Block {
+        mut entries,
+        variant,
+    } = node
+    else {
+        return Err(Error::new(format!(
+            "a list that holds structs cannot also hold {}",
+            node.kind()
+        )));
+    };
+    let label = match entries.iter().position(|(k, _)| k == "name") {
+        Some(i) => match entries.remove(i).1 {
+            Node::Value(Value::String(s)) => s,
+            _ => return Err(Error::new("a list element's `name` must be a string")),
+        },
+        None => {
+            return Err(Error::new(
+                "a struct list element needs a `name` field to label its item",
+            ));
+        }
+    };
+    Ok(ListItem {
+        kind: ListItemKind::Named {
+            name: identifier(&label)?,
+            body: block_body(entries, variant)?,
+        },
+        span: SYNTHESIZED,
+    })
+}
+
+fn array(items: Vec<Node>) -> Result<Value, Error> {
+    let mut out = Vec::with_capacity(items.len());
+    for (i, item) in items.into_iter().enumerate() {
+        let value = match item {
+            Node::Value(v) => v,
+            Node::Seq(inner) => array(inner).map_err(|e| e.with_element(i))?,
+            other => {
+                return Err(
+                    Error::new(format!("an array cannot hold {}", other.kind())).with_element(i)
+                );
+            }
+        };
+        out.push(spanned(value));
+    }
+    Ok(Value::Array(out))
+}
+
+// ---------------------------------------------------------------------------
+// Typed literals recognized by shape
+// ---------------------------------------------------------------------------
+
+/// `Money`'s derived `Serialize` is the struct `Money { amount, currency,
+/// exponent }`; a struct with exactly that name and those fields is the
+/// literal. Name-based like `de`'s `Duration` handshake — a user struct
+/// deliberately shaped the same way opts in.
+fn as_money(entries: &[(String, Node)]) -> Option<Result<Value, Error>> {
+    let [
+        (a, Node::Value(Value::Number(amount))),
+        (c, Node::Value(Value::String(currency))),
+        (e, Node::Value(Value::Number(exponent))),
+    ] = entries
+    else {
+        return None;
+    };
+    if (a.as_str(), c.as_str(), e.as_str()) != ("amount", "currency", "exponent") {
+        return None;
+    }
+    let (Some(amount), Some(exponent)) = (amount.to_i64(), exponent.to_u64()) else {
+        return Some(Err(Error::new("money amount or exponent out of range")));
+    };
+    if currency_exponent(currency).map(u64::from) != Some(exponent) {
+        return Some(Err(Error::new(format!(
+            "`{currency}` with exponent {exponent} is not an ISO 4217 currency"
+        ))));
+    }
+    Some(Ok(Value::Money(Money {
+        amount,
+        currency: currency.clone(),
+        exponent: exponent as u8,
+    })))
+}
+
+/// Both `Duration` shapes: `std::time::Duration`'s `{secs, nanos}` (the
+/// struct `de`'s handshake synthesizes) and NML's own `{segments}` wire
+/// form. A struct named `Duration` with neither shape stays a block.
+fn as_duration(entries: &[(String, Node)]) -> Option<Result<Value, Error>> {
+    let out_of_range = || Error::new("duration outside the NML duration domain");
+    match entries {
+        [
+            (s, Node::Value(Value::Number(secs))),
+            (n, Node::Value(Value::Number(nanos))),
+        ] if s == "secs" && n == "nanos" => {
+            let total = secs
+                .to_u128()
+                .zip(nanos.to_u128())
+                .and_then(|(s, n)| s.checked_mul(1_000_000_000)?.checked_add(n));
+            Some(total.and_then(duration_from_nanos).ok_or_else(out_of_range))
+        }
+        [(k, Node::Seq(segments))] if k == "segments" => {
+            let parsed: Option<Vec<DurationSegment>> = segments
+                .iter()
+                .map(|seg| match seg {
+                    Node::Block { entries, .. } => match entries.as_slice() {
+                        [
+                            (m, Node::Value(Value::Number(mag))),
+                            (u, Node::Value(Value::String(unit))),
+                        ] if m == "magnitude" && u == "unit" => Some(DurationSegment {
+                            magnitude: mag.to_u64()?,
+                            unit: DurationUnit::from_suffix(unit)?,
+                        }),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect();
+            let segments = parsed?;
+            Some(
+                Duration::from_segments(&segments)
+                    .map(Value::Duration)
+                    .ok_or_else(out_of_range),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// The coarsest single unit that spells `nanos` exactly (`90s` → `90s`,
+/// `120s` → `2m`) — a `std::time::Duration` carries no authored unit, so
+/// the shortest faithful literal is the canonical choice.
+fn duration_from_nanos(nanos: u128) -> Option<Value> {
+    if nanos == 0 {
+        return Duration::new(0, DurationUnit::Seconds).map(Value::Duration);
+    }
+    DurationUnit::ALL.iter().find_map(|&unit| {
+        let unit_nanos = unit.nanos() as u128;
+        if nanos % unit_nanos != 0 {
+            return None;
+        }
+        let magnitude = u64::try_from(nanos / unit_nanos).ok()?;
+        Duration::new(magnitude, unit).map(Value::Duration)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// The serializer
+// ---------------------------------------------------------------------------
+
+struct NodeSerializer;
+
+fn number(n: Result<Number, crate::decimal::NumberError>) -> Result<Node, Error> {
+    n.map(|n| Node::Value(Value::Number(n)))
+        .map_err(|e| Error::new(format!("number outside the NML domain ({e})")))
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = Error;
+    type SerializeSeq = SeqCollector;
+    type SerializeTuple = SeqCollector;
+    type SerializeTupleStruct = SeqCollector;
+    type SerializeTupleVariant = Impossible<Node, Error>;
+    type SerializeMap = MapCollector;
+    type SerializeStruct = StructCollector;
+    type SerializeStructVariant = StructCollector;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, Error> {
+        Ok(Node::Value(Value::Bool(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Node, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Node, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Node, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Node, Error> {
+        Ok(Node::Value(Value::Number(Number::from(v))))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Node, Error> {
+        number(Number::try_from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Node, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Node, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Node, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Node, Error> {
+        Ok(Node::Value(Value::Number(Number::from_u64(v))))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Node, Error> {
+        number(Number::try_from(v))
+    }
+
+    /// Shortest-round-trip digits of the f32 itself — widening to f64
+    /// first would print the binary expansion (`0.1f32` as
+    /// `0.10000000149011612`).
+    fn serialize_f32(self, v: f32) -> Result<Node, Error> {
+        if !v.is_finite() {
+            return Err(Error::new("NaN and infinities have no NML spelling"));
+        }
+        number(Number::parse_literal(&format!("{v}")))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Node, Error> {
+        if !v.is_finite() {
+            return Err(Error::new("NaN and infinities have no NML spelling"));
+        }
+        number(Number::try_from_f64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, Error> {
+        Ok(Node::Value(Value::String(v.to_string())))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node, Error> {
+        Ok(Node::Value(Value::String(v.to_owned())))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Node, Error> {
+        Err(Error::new("byte strings have no NML spelling"))
+    }
+
+    fn serialize_none(self) -> Result<Node, Error> {
+        Ok(Node::Absent)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, Error> {
+        Ok(Node::Absent)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node, Error> {
+        Ok(Node::Absent)
+    }
+
+    /// Unit variants are strings — what `de` selects variants from.
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Node, Error> {
+        Ok(Node::Value(Value::String(variant.to_owned())))
+    }
+
+    /// The exact-number handshake: [`Number`]'s `Serialize` wraps its
+    /// ladder in the private newtype, whose string rung is the exact
+    /// plain form — parsed back here, so no number ever becomes a string.
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        let node = value.serialize(NodeSerializer)?;
+        if name == NUMBER_NEWTYPE_TOKEN {
+            if let Node::Value(Value::String(text)) = &node {
+                return number(Number::parse_coercion(text));
+            }
+        }
+        Ok(node)
+    }
+
+    /// A newtype variant over a struct is an annotated block (RFC 0015);
+    /// over a scalar there is nothing for the annotation to ride on.
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        match value.serialize(NodeSerializer)? {
+            Node::Block { entries, .. } => Ok(Node::Block {
+                entries,
+                variant: Some(variant.to_owned()),
+            }),
+            other => Err(Error::new(format!(
+                "variant `{variant}` wraps {}; only struct content can carry an `as {variant}` annotation",
+                other.kind()
+            ))),
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqCollector, Error> {
+        Ok(SeqCollector(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqCollector, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqCollector, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<Node, Error>, Error> {
+        Err(Error::new(format!(
+            "tuple variant `{variant}` has no NML spelling; use a struct variant"
+        )))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapCollector, Error> {
+        Ok(MapCollector {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<StructCollector, Error> {
+        Ok(StructCollector {
+            name,
+            entries: Vec::with_capacity(len),
+            variant: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<StructCollector, Error> {
+        Ok(StructCollector {
+            name,
+            entries: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+}
+
+struct SeqCollector(Vec<Node>);
+
+impl SeqCollector {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.0.len();
+        match value
+            .serialize(NodeSerializer)
+            .map_err(|e| e.with_element(index))?
+        {
+            Node::Absent => Err(Error::new("a list cannot hold `None`").with_element(index)),
+            node => {
+                self.0.push(node);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Seq(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Seq(self.0))
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Seq(self.0))
+    }
+}
+
+struct MapCollector {
+    entries: Vec<(String, Node)>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match key.serialize(NodeSerializer)? {
+            Node::Value(Value::String(s)) => {
+                self.key = Some(s);
+                Ok(())
+            }
+            other => Err(Error::new(format!(
+                "map keys must be strings, got {}",
+                other.kind()
+            ))),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("map value serialized before its key"))?;
+        let node = value
+            .serialize(NodeSerializer)
+            .map_err(|e| e.with_field(&key))?;
+        self.entries.push((key, node));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Block {
+            entries: self.entries,
+            variant: None,
+        })
+    }
+}
+
+struct StructCollector {
+    name: &'static str,
+    entries: Vec<(String, Node)>,
+    variant: Option<&'static str>,
+}
+
+impl StructCollector {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        let node = value
+            .serialize(NodeSerializer)
+            .map_err(|e| e.with_field(key))?;
+        self.entries.push((key.to_owned(), node));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Node, Error> {
+        if self.variant.is_none() {
+            let literal = match self.name {
+                "Money" => as_money(&self.entries),
+                "Duration" => as_duration(&self.entries),
+                _ => None,
+            };
+            if let Some(value) = literal {
+                return value.map(Node::Value);
+            }
+        }
+        Ok(Node::Block {
+            entries: self.entries,
+            variant: self.variant.map(str::to_owned),
+        })
+    }
+}
+
+impl ser::SerializeStruct for StructCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructCollector {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::de::from_body;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Endpoint {
+        name: String,
+        url: String,
+        timeout: std::time::Duration,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Db {
+        backend: String,
+        pool: u32,
+    }
+
+    #[derive(Serialize, Debug)]
+    struct Service {
+        port: u16,
+        ratio: Number,
+        price: Money,
+        tags: Vec<String>,
+        db: Db,
+        endpoints: Vec<Endpoint>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    }
+
+    /// `Money` has no `Deserialize` — `de` hands money to a `String`
+    /// field as its display form — so the read side mirrors `Service`
+    /// with that one field as text.
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct ServiceView {
+        port: u16,
+        ratio: Number,
+        price: String,
+        tags: Vec<String>,
+        db: Db,
+        endpoints: Vec<Endpoint>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    }
+
+    fn sample() -> Service {
+        Service {
+            port: 8080,
+            ratio: crate::num!(0.250),
+            price: Money {
+                amount: 1999,
+                currency: "USD".into(),
+                exponent: 2,
+            },
+            tags: vec!["web".into(), "api".into()],
+            db: Db {
+                backend: "postgres".into(),
+                pool: 8,
+            },
+            endpoints: vec![
+                Endpoint {
+                    name: "Health".into(),
+                    url: "/health".into(),
+                    timeout: std::time::Duration::from_secs(120),
+                },
+                Endpoint {
+                    name: "Docs".into(),
+                    url: "/docs".into(),
+                    timeout: std::time::Duration::from_millis(1500),
+                },
+            ],
+            note: None,
+        }
+    }
+
+    /// The contract is the round trip: whatever `to_body` builds, `de`
+    /// reads back to an equal value.
+    #[test]
+    fn round_trips_through_de() {
+        let body = to_body(&sample()).unwrap();
+        let back: ServiceView = from_body(&body).unwrap();
+        let sample = sample();
+        assert_eq!(back.price, "19.99 USD");
+        assert_eq!(
+            (
+                back.port,
+                back.ratio,
+                back.tags,
+                back.db,
+                back.endpoints,
+                back.note
+            ),
+            (
+                sample.port,
+                sample.ratio,
+                sample.tags,
+                sample.db,
+                sample.endpoints,
+                sample.note
+            )
+        );
+    }
+
+    #[test]
+    fn typed_literals_and_named_items() {
+        let body = to_body(&sample()).unwrap();
+        let value = |key: &str| {
+            body.entries.iter().find_map(|e| match &e.kind {
+                BodyEntryKind::Property(p) if p.name.name == key => Some(p.value.value.clone()),
+                _ => None,
+            })
+        };
+        // Exact, scale-preserving — never through f64.
+        assert!(matches!(value("ratio"), Some(Value::Number(n)) if n.to_string() == "0.250"));
+        assert!(
+            matches!(value("price"), Some(Value::Money(m)) if m.format_display() == "19.99 USD")
+        );
+        assert!(value("note").is_none(), "None fields are omitted");
+
+        let endpoints = body
+            .entries
+            .iter()
+            .find_map(|e| match &e.kind {
+                BodyEntryKind::NestedBlock(nb) if nb.name.name == "endpoints" => Some(&nb.body),
+                _ => None,
+            })
+            .expect("struct lists become a block of items");
+        let ListItemKind::Named { name, body } = (match &endpoints.entries[0].kind {
+            BodyEntryKind::ListItem(item) => &item.kind,
+            other => panic!("expected a list item, got {other:?}"),
+        }) else {
+            panic!("expected a named item");
+        };
+        assert_eq!(name.name, "Health");
+        let timeout = body.entries.iter().find_map(|e| match &e.kind {
+            BodyEntryKind::Property(p) if p.name.name == "timeout" => Some(&p.value.value),
+            _ => None,
+        });
+        // `std::time::Duration` carries no unit: the coarsest exact one.
+        assert!(matches!(timeout, Some(Value::Duration(d)) if d.to_string() == "2m"));
+    }
+
+    #[test]
+    fn enum_variants_map_to_strings_and_annotations() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Level {
+            Debug,
+            Warn,
+        }
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        enum Store {
+            #[serde(rename = "s3")]
+            S3 { bucket: String },
+            #[serde(rename = "disk")]
+            Disk { path: String },
+        }
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Cfg {
+            level: Level,
+            store: Store,
+        }
+        let cases = [
+            (
+                Cfg {
+                    level: Level::Warn,
+                    store: Store::Disk {
+                        path: "/var/data".into(),
+                    },
+                },
+                "disk",
+            ),
+            (
+                Cfg {
+                    level: Level::Debug,
+                    store: Store::S3 {
+                        bucket: "logs".into(),
+                    },
+                },
+                "s3",
+            ),
+        ];
+        for (cfg, variant) in cases {
+            let body = to_body(&cfg).unwrap();
+            let annotation = body.entries.iter().find_map(|e| match &e.kind {
+                BodyEntryKind::NestedBlock(nb) => nb.body.type_annotation.as_ref(),
+                _ => None,
+            });
+            assert_eq!(annotation.map(|a| a.name.as_str()), Some(variant));
+            assert_eq!(from_body::<Cfg>(&body).unwrap(), cfg);
+        }
+    }
+
+    #[test]
+    fn unspellable_values_are_errors_with_paths() {
+        #[derive(Serialize)]
+        struct Anon {
+            x: u8,
+        }
+        #[derive(Serialize)]
+        struct Holder {
+            items: Vec<Anon>,
+        }
+        let err = to_body(&Holder {
+            items: vec![Anon { x: 1 }],
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "field `items`: element 0: a struct list element needs a `name` field to label its item"
+        );
+
+        #[derive(Serialize)]
+        struct Floaty {
+            f: f64,
+        }
+        assert!(to_body(&Floaty { f: f64::NAN }).is_err());
+
+        let mut bad_key = std::collections::BTreeMap::new();
+        bad_key.insert("has space", 1);
+        assert!(to_body(&bad_key).is_err());
+
+        assert!(to_body(&42).is_err(), "a scalar root is not a body");
+    }
+
+    #[test]
+    fn floats_take_the_shortest_exact_decimal() {
+        #[derive(Serialize)]
+        struct F {
+            a: f64,
+            b: f32,
+        }
+        let body = to_body(&F { a: 0.1, b: 0.1 }).unwrap();
+        for entry in &body.entries {
+            let BodyEntryKind::Property(p) = &entry.kind else {
+                panic!("expected properties");
+            };
+            assert!(
+                matches!(&p.value.value, Value::Number(n) if n.to_string() == "0.1"),
+                "{}: {:?}",
+                p.name.name,
+                p.value.value
+            );
+        }
+    }
+
+    #[test]
+    fn to_file_wraps_one_declaration() {
+        let file = to_file("service", "Api", &sample()).unwrap();
+        assert_eq!(file.declarations.len(), 1);
+        assert!(to_file("service", "not valid", &sample()).is_err());
+    }
+}
@@ -5,11 +5,15 @@
 //! reference lookup, duplicate detection, const-chain resolution, and
 //! const-cycle / unresolved-reference diagnostics.
 //!
+//! Declarations brought in by `import` (see [`crate::import`]) register
+//! beside the file's own, tagged with the file they come from.
+//!
 //! This is *static* (parse-time) resolution. Runtime value resolution --
 //! `$ENV.KEY` secrets and `a | b` fallback chains -- lives in
 //! [`crate::resolve::ValueResolver`].
 
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use crate::ast::*;
 use crate::diagnostic::{Diagnostic, codes};
@@ -27,7 +31,30 @@ pub struct SymbolTable {
 pub struct DeclInfo {
     pub keyword: String,
     pub name: String,
+    /// The declaration's span — in the importing file's source only when
+    /// [`Self::imported`] is `None`.
     pub span: Span,
+    /// Where an imported declaration comes from; `None` for the file's own.
+    pub imported: Option<ImportedFrom>,
+}
+
+/// The provenance of an imported declaration.
+#[derive(Debug, Clone)]
+pub struct ImportedFrom {
+    /// The declaring file.
+    pub path: PathBuf,
+    /// The `import` path literal in the importing file that brings it in.
+    pub import_span: Span,
+}
+
+impl DeclInfo {
+    /// Where findings about this declaration point in the importing file:
+    /// the declaration itself, or the `import` that brought it in.
+    pub fn local_span(&self) -> Span {
+        self.imported
+            .as_ref()
+            .map_or(self.span, |from| from.import_span)
+    }
 }
 
 impl SymbolTable {
@@ -37,17 +64,29 @@ impl SymbolTable {
 
     /// Register all declarations from a parsed file.
     pub fn register_file(&mut self, file: &File) {
+        self.register(file, None);
+    }
+
+    /// Register declarations another file contributes through an `import`
+    /// (already narrowed to the import's selection).
+    pub fn register_imported(&mut self, file: &File, from: ImportedFrom) {
+        self.register(file, Some(from));
+    }
+
+    fn register(&mut self, file: &File, imported: Option<ImportedFrom>) {
         for decl in &file.declarations {
             let info = match &decl.kind {
                 DeclarationKind::Block(block) => DeclInfo {
                     keyword: block.keyword.name.clone(),
                     name: block.name.name.clone(),
                     span: decl.span,
+                    imported: imported.clone(),
                 },
                 DeclarationKind::Array(arr) => DeclInfo {
                     keyword: format!("[]{}", arr.item_keyword.name),
                     name: arr.name.name.clone(),
                     span: decl.span,
+                    imported: imported.clone(),
                 },
                 DeclarationKind::Const(c) => {
                     self.const_values
@@ -56,6 +95,7 @@ impl SymbolTable {
                         keyword: "const".into(),
                         name: c.name.name.clone(),
                         span: decl.span,
+                        imported: imported.clone(),
                     }
                 }
                 DeclarationKind::Template(t) => {
@@ -65,13 +105,16 @@ impl SymbolTable {
                         keyword: "template".into(),
                         name: t.name.name.clone(),
                         span: decl.span,
+                        imported: imported.clone(),
                     }
                 }
                 DeclarationKind::OneOf(oneof) => DeclInfo {
                     keyword: "oneof".into(),
                     name: oneof.name.name.clone(),
                     span: decl.span,
+                    imported: imported.clone(),
                 },
+                DeclarationKind::Import(_) => continue,
             };
             self.declarations
                 .entry(info.name.clone())
@@ -131,10 +174,17 @@ impl SymbolTable {
         for (name, decls) in &self.declarations {
             if decls.len() > 1 {
                 for dup in &decls[1..] {
+                    let message = match &dup.imported {
+                        Some(from) => format!(
+                            "duplicate declaration: '{name}' (imported from '{}')",
+                            from.path.display()
+                        ),
+                        None => format!("duplicate declaration: '{name}'"),
+                    };
                     errors.push(
-                        Diagnostic::error(format!("duplicate declaration: '{name}'"))
+                        Diagnostic::error(message)
                             .with_code(codes::DUPLICATE_DECLARATION)
-                            .with_span(dup.span),
+                            .with_span(dup.local_span()),
                     );
                 }
             }
@@ -210,7 +260,7 @@ impl SymbolTable {
                     .declarations
                     .get(member.as_str())
                     .and_then(|v| v.first())
-                    .map(|d| d.span)
+                    .map(DeclInfo::local_span)
                     .unwrap_or(Span::empty(0));
                 errors.push(
                     Diagnostic::error(format!(
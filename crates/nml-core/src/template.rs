@@ -1,5 +1,10 @@
+//! `{{namespace.path}}` template strings: parsing into segments, and
+//! rendering them through embedder-supplied namespace providers.
+
+use crate::diagnostic::{Diagnostic, codes};
+use crate::project::ProjectConfig;
 use crate::span::Span;
-use crate::types::TemplateSegment;
+use crate::types::{TemplateSegment, Value};
 
 /// Parse a string containing `{{...}}` template expressions into segments.
 ///
@@ -72,6 +77,195 @@ pub fn segments_to_string(segments: &[TemplateSegment]) -> String {
     out
 }
 
+/// Pluggable namespace lookup: maps an expression's dotted path (the
+/// segments after the namespace — empty for a bare `{{input}}`) to its
+/// text, or `None` when the namespace has nothing at that path. `Send +
+/// Sync` for the same reason as the resolver's lookups: renderers live in
+/// shared long-lived state.
+type NamespaceLookup = Box<dyn Fn(&[String]) -> Option<String> + Send + Sync>;
+
+/// Renders template strings to text by evaluating each `{{namespace.path}}`
+/// expression through a registered provider — one per namespace (`args`,
+/// `config`, `env`, …), supplied by the embedder, which alone knows what
+/// those namespaces hold.
+///
+/// The allow-list is the project's `templateNamespaces`
+/// ([`Self::for_project`]): an expression outside it is `NML5004` — the
+/// same verdict and did-you-mean the editor shows — even when a provider
+/// happens to be registered. An empty allow-list accepts any namespace,
+/// matching the project-config contract. Rendering reports **every**
+/// failing expression, each located at its own `{{…}}` span, rather than
+/// stopping at the first.
+///
+/// # Example
+///
+/// ```rust
+/// use nml_core::template::{TemplateRenderer, parse_template_string};
+///
+/// let renderer = TemplateRenderer::new()
+///     .with_namespace("args", |path| match path {
+///         [key] if key == "name" => Some("Ada".to_string()),
+///         _ => None,
+///     });
+/// let segments = parse_template_string("Hello {{args.name}}!", 0);
+/// assert_eq!(renderer.render(&segments).unwrap(), "Hello Ada!");
+/// ```
+pub struct TemplateRenderer {
+    providers: Vec<(String, NamespaceLookup)>,
+    allowed: Vec<String>,
+}
+
+/// Structural only — providers are opaque closures (and may front
+/// secrets), so Debug reports which namespaces are wired and nothing else.
+impl std::fmt::Debug for TemplateRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TemplateRenderer")
+            .field(
+                "providers",
+                &self.providers.iter().map(|(ns, _)| ns).collect::<Vec<_>>(),
+            )
+            .field("allowed", &self.allowed)
+            .finish()
+    }
+}
+
+impl Default for TemplateRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRenderer {
+    /// A renderer with no providers and no allow-list (any namespace is
+    /// permitted; one without a provider still fails to render).
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            allowed: Vec::new(),
+        }
+    }
+
+    /// A renderer whose allow-list is the project's `templateNamespaces`.
+    pub fn for_project(config: &ProjectConfig) -> Self {
+        Self {
+            providers: Vec::new(),
+            allowed: config.template_namespaces.clone(),
+        }
+    }
+
+    /// Register the provider for `namespace`, replacing any earlier one.
+    pub fn with_namespace(
+        mut self,
+        namespace: impl Into<String>,
+        lookup: impl Fn(&[String]) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        let namespace = namespace.into();
+        self.providers.retain(|(ns, _)| *ns != namespace);
+        self.providers.push((namespace, Box::new(lookup)));
+        self
+    }
+
+    /// Render parsed segments to text, or every expression that failed:
+    /// `NML5004` for a namespace outside the allow-list or without a
+    /// provider, `NML1004` for a path its provider does not resolve.
+    pub fn render(&self, segments: &[TemplateSegment]) -> Result<String, Vec<Diagnostic>> {
+        let mut out = String::new();
+        let mut errors = Vec::new();
+        for segment in segments {
+            match segment {
+                TemplateSegment::Literal(text) => out.push_str(text),
+                TemplateSegment::Expression {
+                    namespace,
+                    path,
+                    raw,
+                    span,
+                } => {
+                    if let Some(text) = self.evaluate(namespace, path, raw, *span, &mut errors) {
+                        out.push_str(&text);
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(out)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Render a string-shaped value: a template string through
+    /// [`Self::render`], a plain string as itself. `None` for any other
+    /// value — there is no text to render.
+    pub fn render_value(&self, value: &Value) -> Option<Result<String, Vec<Diagnostic>>> {
+        match value {
+            Value::TemplateString(segments) => Some(self.render(segments)),
+            Value::String(s) => Some(Ok(s.clone())),
+            _ => None,
+        }
+    }
+
+    /// Evaluate one expression; a failure lands in `errors` (so one
+    /// render reports them all) and yields `None`.
+    fn evaluate(
+        &self,
+        namespace: &str,
+        path: &[String],
+        expr_raw: &str,
+        span: Span,
+        errors: &mut Vec<Diagnostic>,
+    ) -> Option<String> {
+        let allowed = self.allowed.is_empty() || self.allowed.iter().any(|a| a == namespace);
+        let provider = self
+            .providers
+            .iter()
+            .find_map(|(ns, lookup)| (ns == namespace).then_some(lookup));
+        let Some(lookup) = provider.filter(|_| allowed) else {
+            let message = if allowed {
+                format!("no provider for template namespace '{namespace}'")
+            } else {
+                format!("unknown template namespace '{namespace}'")
+            };
+            let diag = Diagnostic::error(message)
+                .with_code(codes::UNKNOWN_TEMPLATE_NAMESPACE)
+                .with_span(span);
+            let candidates: Vec<&str> = if allowed {
+                self.providers.iter().map(|(ns, _)| ns.as_str()).collect()
+            } else {
+                self.allowed.iter().map(String::as_str).collect()
+            };
+            // The suggestion replaces the namespace alone, not the whole
+            // `{{…}}` — so applying it keeps the path.
+            let leading = raw_leading_ws(expr_raw);
+            let ns_start = span.start + 2 + leading;
+            let ns_span = Span::new(ns_start, ns_start + namespace.len());
+            errors.push(
+                match crate::suggest::suggest(namespace, candidates.iter().copied()) {
+                    Some(s) => diag.with_suggestion(s, ns_span),
+                    None => diag,
+                },
+            );
+            return None;
+        };
+        let text = lookup(path);
+        if text.is_none() {
+            errors.push(
+                Diagnostic::error(format!(
+                    "template expression `{{{{{}}}}}` resolves to nothing in namespace '{namespace}'",
+                    expr_raw.trim()
+                ))
+                .with_code(codes::UNRESOLVED_TEMPLATE_PATH)
+                .with_span(span),
+            );
+        }
+        text
+    }
+}
+
+/// Bytes of whitespace before an expression's namespace (`{{ args.x }}`).
+fn raw_leading_ws(raw: &str) -> usize {
+    raw.len() - raw.trim_start().len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -311,4 +505,76 @@ mod tests {
         let reconstructed = segments_to_string(&segs);
         assert_eq!(reconstructed, original);
     }
+
+    // -------------------------------------------------------------------
+    // TemplateRenderer
+    // -------------------------------------------------------------------
+
+    fn renderer() -> TemplateRenderer {
+        TemplateRenderer::new()
+            .with_namespace("args", |path| match path {
+                [key] if key == "name" => Some("Ada".to_string()),
+                _ => None,
+            })
+            .with_namespace("input", |path| path.is_empty().then(|| "raw".to_string()))
+    }
+
+    #[test]
+    fn renders_every_expression_through_its_provider() {
+        let segs = parse_template_string("Hi {{ args.name }}, {{input}}.", 0);
+        assert_eq!(renderer().render(&segs).unwrap(), "Hi Ada, raw.");
+    }
+
+    /// Every failure reports, each at its own `{{…}}` — not just the first.
+    #[test]
+    fn reports_all_failures_with_spans() {
+        let text = "{{args.age}} and {{argz.name}}";
+        let errs = renderer()
+            .render(&parse_template_string(text, 0))
+            .unwrap_err();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].code, Some(codes::UNRESOLVED_TEMPLATE_PATH));
+        let span = errs[0].span.unwrap();
+        assert_eq!(&text[span.start..span.end], "{{args.age}}");
+        assert_eq!(errs[1].code, Some(codes::UNKNOWN_TEMPLATE_NAMESPACE));
+        // The did-you-mean replaces the namespace only, keeping the path.
+        let fix = &errs[1].suggestions[0];
+        assert_eq!(fix.replacement, "args");
+        assert_eq!(&text[fix.span.start..fix.span.end], "argz");
+    }
+
+    /// The project's `templateNamespaces` gates rendering even where a
+    /// provider exists — the editor's verdict and the renderer's agree.
+    #[test]
+    fn project_allow_list_gates_providers() {
+        let config = ProjectConfig {
+            template_namespaces: vec!["args".into()],
+            ..ProjectConfig::default()
+        };
+        let r = TemplateRenderer::for_project(&config)
+            .with_namespace("args", |_| Some("a".to_string()))
+            .with_namespace("env", |_| Some("e".to_string()));
+        assert_eq!(
+            r.render(&parse_template_string("{{args.x}}", 0)).unwrap(),
+            "a"
+        );
+        let errs = r
+            .render(&parse_template_string("{{env.HOME}}", 0))
+            .unwrap_err();
+        assert_eq!(errs[0].message, "unknown template namespace 'env'");
+    }
+
+    #[test]
+    fn render_value_accepts_only_strings() {
+        let r = renderer();
+        let v = Value::TemplateString(parse_template_string("{{args.name}}", 0));
+        assert_eq!(r.render_value(&v).unwrap().unwrap(), "Ada");
+        assert_eq!(
+            r.render_value(&Value::String("plain".into()))
+                .unwrap()
+                .unwrap(),
+            "plain"
+        );
+        assert!(r.render_value(&Value::Bool(true)).is_none());
+    }
 }
@@ -119,7 +119,11 @@ impl<'a> Formatter<'a> {
 
     fn format_file(mut self, file: &File) -> String {
         for (i, decl) in file.declarations.iter().enumerate() {
-            if i > 0 {
+            // Consecutive imports stay one group, like a `use` block.
+            let import_run = i > 0
+                && matches!(decl.kind, DeclarationKind::Import(_))
+                && matches!(file.declarations[i - 1].kind, DeclarationKind::Import(_));
+            if i > 0 && !import_run {
                 self.out.push('\n');
             }
             self.emit_comments_before(decl.span.start, 0);
@@ -225,6 +229,23 @@ impl<'a> Formatter<'a> {
                     self.out.push('\n');
                 }
             }
+            DeclarationKind::Import(import) => {
+                self.write_indent(depth);
+                self.out.push_str("import ");
+                self.out.push_str(&quote_string(&import.path));
+                if let Some(names) = &import.names {
+                    self.out.push_str(" (");
+                    for (i, name) in names.iter().enumerate() {
+                        if i > 0 {
+                            self.out.push_str(", ");
+                        }
+                        self.out.push_str(&name.name);
+                    }
+                    self.out.push(')');
+                }
+                self.emit_trailing_comment(decl.span.start);
+                self.out.push('\n');
+            }
         }
     }
 
@@ -667,18 +688,28 @@ fn format_string(out: &mut String, s: &str, depth: usize, escape_braces: bool) {
         }
         out.push_str("\"\"\"");
     } else {
-        out.push('"');
-        let mut chars = s.chars().peekable();
-        while let Some(ch) = chars.next() {
-            match ch {
-                '"' => out.push_str("\\\""),
-                '\t' => out.push_str("\\t"),
-                '{' if escape_braces && chars.peek() == Some(&'{') => out.push_str("\\u{7B}"),
-                c => push_value_char(out, c),
-            }
+        out.push_str(&single_line_string(s, escape_braces));
+    }
+}
+
+/// `s` as a one-line NML string literal, for displays that cannot open a
+/// block string (`nml diff`): a newline renders as `\n`. `escape_braces`
+/// is true for a literal string, false for a template's source text.
+pub fn single_line_string(s: &str, escape_braces: bool) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '{' if escape_braces && chars.peek() == Some(&'{') => out.push_str("\\u{7B}"),
+            c => push_value_char(&mut out, c),
         }
-        out.push('"');
     }
+    out.push('"');
+    out
 }
 
 /// Render one string-value character, escaping what the parser's
@@ -862,6 +893,47 @@ mod tests {
             once, twice,
             "duration facet rendering must be a fixed point"
         );
+
+        // String and list facet values (`money(currency = ...)`) render
+        // as quoted literals, lists joined with ", ".
+        let src = "model shop:\n    a money( currency=\"USD\" )\n    b money(currency = [ \"USD\",\"GBP\" ])\n";
+        let once = format(&parse(src).unwrap());
+        assert!(once.contains("a money(currency = \"USD\")"), "{once:?}");
+        assert!(
+            once.contains("b money(currency = [\"USD\", \"GBP\"])"),
+            "{once:?}"
+        );
+        let twice = format(&parse(&once).unwrap());
+        assert_eq!(once, twice, "string facet rendering must be a fixed point");
+
+        // String facets, with a pattern whose escapes survive the trip.
+        let src =
+            "model site:\n    slug string( minLength=1,pattern = \"[a-z0-9-]+\\\\.\\\"x\" )\n";
+        let once = format(&parse(src).unwrap());
+        assert!(
+            once.contains("slug string(minLength = 1, pattern = \"[a-z0-9-]+\\\\.\\\"x\")"),
+            "{once:?}"
+        );
+        let twice = format(&parse(&once).unwrap());
+        assert_eq!(once, twice, "pattern rendering must be a fixed point");
+
+        // Collection facets stay where they were authored: in the element
+        // name's parens for `[]T`, after the angles for `set<T>`.
+        let src = "model svc:\n    hosts []string(maxLength=8,minItems=1)\n    tags set<string>( nonEmpty=true )\n";
+        let once = format(&parse(src).unwrap());
+        assert!(
+            once.contains("hosts []string(maxLength = 8, minItems = 1)"),
+            "{once:?}"
+        );
+        assert!(
+            once.contains("tags set<string>(nonEmpty = true)"),
+            "{once:?}"
+        );
+        let twice = format(&parse(&once).unwrap());
+        assert_eq!(
+            once, twice,
+            "collection facet rendering must be a fixed point"
+        );
     }
 
     /// Every extreme literal the language accepts survives formatting:
@@ -1008,6 +1080,22 @@ mod tests {
         assert!(formatted.contains("\"{{args.x}}\""), "{formatted}");
     }
 
+    /// A one-line rendering of a multiline value escapes its newlines and
+    /// still re-parses to the same text.
+    #[test]
+    fn single_line_string_escapes_newlines() {
+        let literal = single_line_string("a \"b\"\n{{c}}\u{1B}", true);
+        assert_eq!(literal, "\"a \\\"b\\\"\\n\\u{7B}{c}}\\u{1B}\"");
+        let file = parse(&format!("const x = {literal}\n")).unwrap();
+        let nml_core::ast::DeclarationKind::Const(c) = &file.declarations[0].kind else {
+            panic!("expected a const");
+        };
+        assert_eq!(
+            c.value.value,
+            nml_core::types::Value::String("a \"b\"\n{{c}}\u{1B}".to_string())
+        );
+    }
+
     /// RFC 0032: directives SURVIVE formatting (rendering them is load-bearing
     /// — fmt formats from the AST, so an unrendered field would be silently
     /// deleted), in canonical one-space form, args included; and formatting is
@@ -1093,6 +1181,20 @@ mod tests {
         idempotent(source);
     }
 
+    #[test]
+    fn test_format_imports_group_and_roundtrip() {
+        let source = "import \"shared.nml\"\nimport \"db.nml\" (dbUrl,Primary)\n\nservice Api:\n    database = dbUrl\n";
+        let formatted = format(&parse(source).unwrap());
+        assert!(
+            formatted.starts_with(
+                "import \"shared.nml\"\nimport \"db.nml\" (dbUrl, Primary)\n\nservice Api:\n"
+            ),
+            "imports stay one group:\n{formatted}"
+        );
+        roundtrip(source);
+        idempotent(source);
+    }
+
     #[test]
     fn test_format_arm_set_field_types_roundtrip() {
         // RFC 0007 §5: the arm-set TYPE renders canonically as `(K -> V)`,
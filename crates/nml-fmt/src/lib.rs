@@ -1 +1,2 @@
 pub mod formatter;
+pub mod ser;
@@ -0,0 +1,84 @@
+//! Rust values out as canonical NML text.
+//!
+//! The serializer itself lives in [`nml_core::ser`] and builds the semantic
+//! AST; rendering that AST is this crate's job, and `nml-core` cannot
+//! depend on its own formatter — so the text entry point is here.
+
+use nml_core::ser::{Error, to_file};
+use serde::Serialize;
+
+/// Serialize `value` as a one-declaration NML file (`keyword name:` over
+/// the value's fields), rendered in canonical form.
+///
+/// ```rust
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Server {
+///     port: u16,
+///     host: String,
+/// }
+///
+/// let text = nml_fmt::ser::to_string(
+///     "service",
+///     "Api",
+///     &Server { port: 8080, host: "localhost".into() },
+/// )
+/// .unwrap();
+/// assert_eq!(text, "service Api:\n    port = 8080\n    host = \"localhost\"\n");
+/// ```
+pub fn to_string<T: Serialize + ?Sized>(
+    keyword: &str,
+    name: &str,
+    value: &T,
+) -> Result<String, Error> {
+    Ok(crate::formatter::format(&to_file(keyword, name, value)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nml_core::money::Money;
+
+    #[derive(Serialize)]
+    struct Route {
+        name: String,
+        path: String,
+    }
+
+    #[derive(Serialize)]
+    struct Service {
+        price: Money,
+        timeout: std::time::Duration,
+        tags: Vec<&'static str>,
+        routes: Vec<Route>,
+    }
+
+    /// The rendered text re-parses, and formatting it again is a no-op —
+    /// the serializer's output is already canonical.
+    #[test]
+    fn output_is_canonical_and_reparses() {
+        let text = to_string(
+            "service",
+            "Api",
+            &Service {
+                price: Money {
+                    amount: 1999,
+                    currency: "USD".into(),
+                    exponent: 2,
+                },
+                timeout: std::time::Duration::from_secs(30),
+                tags: vec!["web", "api"],
+                routes: vec![Route {
+                    name: "Health".into(),
+                    path: "/health".into(),
+                }],
+            },
+        )
+        .unwrap();
+        assert!(text.contains("price = 19.99 USD"), "{text}");
+        assert!(text.contains("timeout = 30s"), "{text}");
+        assert!(text.contains("- Health:"), "{text}");
+        assert_eq!(crate::formatter::format_source(&text).unwrap(), text);
+    }
+}
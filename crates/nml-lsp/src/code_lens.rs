@@ -0,0 +1,279 @@
+//! Code lenses over `.model.nml` headers: how many instance declarations
+//! each `model`, `oneof` and `enum` governs across the workspace, and how
+//! many of those currently fail validation.
+//!
+//! A model or oneof governs the instances written with its name as the
+//! keyword — top-level blocks and the items of a `[]name` array. An enum
+//! has no instances of its own; it governs the instances of every model
+//! with a field typed by it and every oneof it types the discriminator of.
+//! Instances come from the workspace symbol table; "with errors" means an
+//! error-severity diagnostic from the document's cached set falls inside
+//! the instance's declaration.
+
+use std::collections::BTreeSet;
+
+use nml_core::SchemaIndex;
+use nml_core::ast::{DeclarationKind, File, Identifier};
+use nml_core::model::FieldType;
+use serde_json::json;
+use tower_lsp::lsp_types::{
+    CodeLens, Command, Diagnostic, DiagnosticSeverity, Location, Range, Url,
+};
+
+use crate::position::LineIndex;
+
+/// One lens-bearing declaration header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaHeader {
+    pub name: String,
+    /// The declaration's name, where the lens sits.
+    pub range: Range,
+    /// The instance keywords this declaration governs.
+    pub keywords: BTreeSet<String>,
+}
+
+/// The `model`, `oneof` and `enum` headers `file` declares, in source
+/// order. Traits are skipped: they are never instantiated.
+pub fn schema_headers(file: &File, source: &str, index: &SchemaIndex) -> Vec<SchemaHeader> {
+    let line_index = LineIndex::new(source);
+    let header = |name: &Identifier, keywords: BTreeSet<String>| SchemaHeader {
+        name: name.name.clone(),
+        range: line_index.range(name.span),
+        keywords,
+    };
+    file.declarations
+        .iter()
+        .filter_map(|decl| match &decl.kind {
+            DeclarationKind::Block(block) => match block.keyword.name.as_str() {
+                "model" => Some(header(
+                    &block.name,
+                    BTreeSet::from([block.name.name.clone()]),
+                )),
+                "enum" => Some(header(&block.name, enum_governs(index, &block.name.name))),
+                _ => None,
+            },
+            DeclarationKind::OneOf(o) => {
+                Some(header(&o.name, BTreeSet::from([o.name.name.clone()])))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The models and oneofs whose instances enum `name` constrains.
+fn enum_governs(index: &SchemaIndex, name: &str) -> BTreeSet<String> {
+    let models = index
+        .models()
+        .iter()
+        .filter(|m| !m.is_trait())
+        .filter(|m| m.fields.iter().any(|f| mentions(&f.field_type, name)))
+        .map(|m| m.name.clone());
+    let oneofs = index
+        .oneofs()
+        .iter()
+        .filter(|o| o.discriminator_type.as_deref() == Some(name))
+        .map(|o| o.name.clone());
+    models.chain(oneofs).collect()
+}
+
+/// Whether `ty` names `name` anywhere — element, union variant, arm key or
+/// target.
+fn mentions(ty: &FieldType, name: &str) -> bool {
+    match ty {
+        FieldType::ModelRef(n) => n == name,
+        FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
+            mentions(inner, name)
+        }
+        FieldType::Union(variants) => variants.iter().any(|v| mentions(v, name)),
+        FieldType::Arms { key, target } => mentions(key, name) || mentions(target, name),
+        FieldType::Primitive { .. } => false,
+    }
+}
+
+/// One governed instance: where its name is, and whether it fails
+/// validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instance {
+    pub location: Location,
+    pub has_errors: bool,
+}
+
+/// Whether any error-severity diagnostic starts inside `extent`.
+pub fn has_errors_in(diagnostics: &[Diagnostic], extent: Range) -> bool {
+    diagnostics.iter().any(|d| {
+        d.severity == Some(DiagnosticSeverity::ERROR)
+            && extent.start <= d.range.start
+            && d.range.start < extent.end
+    })
+}
+
+/// `header`'s lens. With a client-declared references command the lens
+/// runs it with `[uri, position, locations]` — the argument shape of
+/// VS Code's `editor.action.showReferences` — and opens the instance list;
+/// without one the lens is a plain label (an empty command id), never an
+/// unexecutable command.
+pub fn lens(
+    uri: &Url,
+    header: &SchemaHeader,
+    instances: &[Instance],
+    references_command: Option<&str>,
+) -> CodeLens {
+    let failing = instances.iter().filter(|i| i.has_errors).count();
+    let title = format!(
+        "{} · {failing} with errors",
+        plural(instances.len(), "instance")
+    );
+    let command = match references_command {
+        Some(id) => Command {
+            title,
+            command: id.to_string(),
+            arguments: Some(vec![
+                json!(uri),
+                json!(header.range.start),
+                json!(
+                    instances
+                        .iter()
+                        .map(|i| &i.location)
+                        .collect::<Vec<&Location>>()
+                ),
+            ]),
+        },
+        None => Command {
+            title,
+            command: String::new(),
+            arguments: None,
+        },
+    };
+    CodeLens {
+        range: header.range,
+        command: Some(command),
+        data: None,
+    }
+}
+
+fn plural(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::Position;
+
+    const SCHEMA: &str = "\
+enum level:
+    - debug
+    - info
+
+trait named:
+    name string
+
+model sink is named:
+    level level
+
+model probe:
+    levels []level?
+
+model webhook:
+    url string
+
+oneof notifier by kind as level:
+    \"debug\" -> sink
+    \"info\" -> webhook
+";
+
+    fn headers() -> Vec<SchemaHeader> {
+        let (schema, errors) = nml_core::cst::extract_schema(SCHEMA);
+        assert!(errors.is_empty(), "{errors:?}");
+        let index = SchemaIndex::build(schema.models, schema.enums, schema.oneofs);
+        let file = nml_core::cst::parse_best_effort(SCHEMA);
+        schema_headers(&file, SCHEMA, &index)
+    }
+
+    #[test]
+    fn headers_cover_models_oneofs_and_enums_but_not_traits() {
+        let got: Vec<(String, Vec<String>)> = headers()
+            .into_iter()
+            .map(|h| (h.name, h.keywords.into_iter().collect()))
+            .collect();
+        let want: Vec<(String, Vec<String>)> = [
+            ("level", vec!["notifier", "probe", "sink"]),
+            ("sink", vec!["sink"]),
+            ("probe", vec!["probe"]),
+            ("webhook", vec!["webhook"]),
+            ("notifier", vec!["notifier"]),
+        ]
+        .into_iter()
+        .map(|(n, ks)| (n.to_string(), ks.into_iter().map(String::from).collect()))
+        .collect();
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn lens_counts_instances_and_failures() {
+        let uri = Url::parse("file:///w/app.nml").unwrap();
+        let header = headers().into_iter().find(|h| h.name == "sink").unwrap();
+        let at = |line| {
+            Location::new(
+                uri.clone(),
+                Range::new(Position::new(line, 5), Position::new(line, 8)),
+            )
+        };
+        let instances = [
+            Instance {
+                location: at(0),
+                has_errors: true,
+            },
+            Instance {
+                location: at(4),
+                has_errors: false,
+            },
+        ];
+
+        let label = lens(&uri, &header, &instances, None);
+        let command = label.command.unwrap();
+        assert_eq!(command.title, "2 instances · 1 with errors");
+        assert!(command.command.is_empty());
+
+        let one = lens(
+            &uri,
+            &header,
+            &instances[1..],
+            Some("editor.action.showReferences"),
+        );
+        let command = one.command.unwrap();
+        assert_eq!(command.title, "1 instance · 0 with errors");
+        assert_eq!(command.command, "editor.action.showReferences");
+        let args = command.arguments.unwrap();
+        assert_eq!(args[0], json!("file:///w/app.nml"));
+        assert_eq!(args[1], json!({ "line": 7, "character": 6 }));
+        assert_eq!(args[2].as_array().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn only_errors_inside_the_extent_count() {
+        let extent = Range::new(Position::new(2, 0), Position::new(5, 0));
+        let diag = |line, severity| Diagnostic {
+            range: Range::new(Position::new(line, 4), Position::new(line, 6)),
+            severity: Some(severity),
+            ..Default::default()
+        };
+        assert!(has_errors_in(&[diag(3, DiagnosticSeverity::ERROR)], extent));
+        assert!(!has_errors_in(
+            &[diag(3, DiagnosticSeverity::WARNING)],
+            extent
+        ));
+        assert!(!has_errors_in(
+            &[diag(5, DiagnosticSeverity::ERROR)],
+            extent
+        ));
+        assert!(!has_errors_in(
+            &[diag(1, DiagnosticSeverity::ERROR)],
+            extent
+        ));
+    }
+}
@@ -5,6 +5,7 @@ use nml_core::types::{TemplateSegment, Value};
 use nml_validate::schema::{MembershipSemantics, SchemaValidator};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
+use crate::document::ParsedDocument;
 use crate::position::LineIndex;
 
 /// Configuration for diagnostics computation.
@@ -28,6 +29,10 @@ pub struct DiagnosticConfig {
     /// judging composition would report false "unknown `is` target" errors
     /// for parents the registry validator resolves fine.
     pub load_pass_owns_composition: bool,
+    /// The document's loaded `import` closure, when it has imports: names
+    /// resolve against what they bring in, and the loader's findings for
+    /// this document (missing file, cycle, unknown name) report here.
+    pub imports: Option<std::sync::Arc<nml_core::import::ImportSet>>,
 }
 
 /// Where schema validation for a document comes from (RFC 0030).
@@ -50,13 +55,14 @@ pub enum SchemaMode<'a> {
     },
 }
 
-/// Compute diagnostics for an NML source document.
+/// Compute diagnostics for an NML source document, from its cached parse.
 pub fn compute(
-    source: &str,
+    doc: &ParsedDocument,
     mode: &SchemaMode<'_>,
     config: &DiagnosticConfig,
     uri: Option<&tower_lsp::lsp_types::Url>,
 ) -> Vec<Diagnostic> {
+    let source = doc.text();
     let mut diagnostics = Vec::new();
     let line_index = LineIndex::new(source);
 
@@ -71,8 +77,9 @@ pub fn compute(
     // includes the RFC 0018 facet definition rules (NML2058), so the
     // parse band carries them for every document with no separate walk.
     // (Previously this fn parsed the same text up to three times per
-    // keystroke: parse, facet re-extraction, merge re-extraction.)
-    let (file, own_defs, parse_errors) = nml_core::cst::parse_and_extract(source);
+    // keystroke: parse, facet re-extraction, merge re-extraction.) The
+    // parse itself is the document's own, kept in step incrementally.
+    let (file, own_defs, parse_errors) = nml_core::cst::extract_parsed(source, doc.parse());
 
     for diag in parse_errors {
         push_diagnostic(diag, None, uri, &line_index, &mut diagnostics);
@@ -85,8 +92,21 @@ pub fn compute(
     // then keeps only this buffer's findings. Nothing schema-level is
     // checked here: one engine, one findings set, no drift.
 
-    let mut symbols = nml_core::symbols::SymbolTable::new();
-    symbols.register_file(&file);
+    let symbols = match &config.imports {
+        Some(imports) => {
+            for (at, diag) in imports.diagnostics() {
+                if at == imports.root() {
+                    push_diagnostic(diag.clone(), None, uri, &line_index, &mut diagnostics);
+                }
+            }
+            imports.symbol_table(&file)
+        }
+        None => {
+            let mut symbols = nml_core::symbols::SymbolTable::new();
+            symbols.register_file(&file);
+            symbols
+        }
+    };
 
     for diag in symbols.find_duplicates() {
         push_diagnostic(diag, None, uri, &line_index, &mut diagnostics);
@@ -623,6 +643,8 @@ fn validate_templates(
             // `oneof` arms hold only discriminator literals and model names;
             // there are no template-bearing values to validate.
             DeclarationKind::OneOf(_) => {}
+            // An import is a path and names — nothing to render.
+            DeclarationKind::Import(_) => {}
         }
     }
 }
@@ -743,7 +765,10 @@ package demo:
         let binding = package.binding_for("demo.nml").unwrap();
         let validator = package.validator(binding).unwrap();
         let diags = compute(
-            "core main:\n    mode = \"lax\"\n    unknownKey = 1\n",
+            &ParsedDocument::new(
+                "core main:\n    mode = \"lax\"\n    unknownKey = 1\n".to_string(),
+                None,
+            ),
             &SchemaMode::Package {
                 validator: &validator,
                 identity: "demo blake3:12345678, store current".to_string(),
@@ -936,7 +961,7 @@ package demo:
         config: &DiagnosticConfig,
     ) -> Vec<Diagnostic> {
         compute(
-            source,
+            &ParsedDocument::new(source.to_string(), None),
             &SchemaMode::Registry {
                 models,
                 enums,
@@ -967,7 +992,7 @@ package demo:
     /// covered schema author sees the same error twice.
     #[test]
     fn covered_model_file_facet_error_is_not_double_squiggled() {
-        let source = "model m:\n    s string(min = 1)\n";
+        let source = "model m:\n    s bool(min = 1)\n";
         let mut cfg = default_config();
         cfg.uri_is_registry_source = true;
         let from_compute = compute_registry(source, &[], &[], &[], &cfg);
@@ -1213,12 +1238,12 @@ package demo:
         for (label, source, cfg) in [
             (
                 "pure .model.nml",
-                "model m:\n    s string(min = 1)\n",
+                "model m:\n    s bool(min = 1)\n",
                 &model_cfg,
             ),
             (
                 "mixed self-validating",
-                "model m:\n    s string(min = 1)\n\nm A:\n    s = \"x\"\n",
+                "model m:\n    s bool(min = 1)\n\nm A:\n    s = true\n",
                 &default_config(),
             ),
         ] {
@@ -1465,7 +1490,7 @@ package demo:
     fn related_information_is_spec_native_with_uri() {
         let uri = tower_lsp::lsp_types::Url::parse("file:///test.nml").unwrap();
         let diags = compute(
-            "service Api:\n    name = \"abc\n",
+            &ParsedDocument::new("service Api:\n    name = \"abc\n".to_string(), None),
             &SchemaMode::Registry {
                 models: &[],
                 enums: &[],
@@ -1493,7 +1518,7 @@ package demo:
     fn registry_mode_types_self_contained_documents() {
         let source = "model cache:\n    maxEntries number\n\ncache Hot:\n    enabled = false\n";
         let diags = compute(
-            source,
+            &ParsedDocument::new(source.to_string(), None),
             &SchemaMode::Registry {
                 models: &[],
                 enums: &[],
@@ -1517,7 +1542,7 @@ package demo:
         let registry = nml_core::cst::extract_schema("model cache:\n    maxEntries number\n").0;
         let source = "model cache:\n    other string?\n\ncache Hot:\n    other = \"x\"\n";
         let diags = compute(
-            source,
+            &ParsedDocument::new(source.to_string(), None),
             &SchemaMode::Registry {
                 models: &registry.models,
                 enums: &registry.enums,
@@ -1547,7 +1572,7 @@ package demo:
             ..DiagnosticConfig::default()
         };
         let diags = compute(
-            src,
+            &ParsedDocument::new(src.to_string(), None),
             &SchemaMode::Registry {
                 models: &registry.models,
                 enums: &registry.enums,
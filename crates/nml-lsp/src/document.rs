@@ -0,0 +1,177 @@
+//! One open document's text with its parse, kept in step under
+//! incremental sync.
+//!
+//! The client sends `didChange` as ranged edits (`TextDocumentSyncKind::
+//! INCREMENTAL`); each edit is spliced into the stored text and the
+//! previous [`Parse`] is updated with [`Parse::reparse`], which reparses
+//! only the top-level declaration the edit falls in. Every feature handler
+//! reads the same cached parse, and the lowered AST is built at most once
+//! per version, on first use.
+
+use std::sync::OnceLock;
+
+use nml_core::ast::File;
+use nml_core::cst::ast::{AstNode as _, Root};
+use nml_core::cst::edit::SpliceEdit;
+use nml_core::cst::{Parse, lower};
+use nml_core::span::Span;
+use tower_lsp::lsp_types::TextDocumentContentChangeEvent;
+
+use crate::position::LineIndex;
+
+/// A document version: its text, the lossless parse of that text, and the
+/// semantic AST lowered from it on demand.
+pub struct ParsedDocument {
+    /// The client's version number; `None` for text read from disk.
+    version: Option<i32>,
+    text: String,
+    parse: Parse,
+    file: OnceLock<File>,
+}
+
+impl ParsedDocument {
+    /// A full parse of `text`.
+    pub fn new(text: String, version: Option<i32>) -> Self {
+        let parse = nml_core::cst::parse(&text);
+        Self::from_parts(text, parse, version)
+    }
+
+    fn from_parts(text: String, parse: Parse, version: Option<i32>) -> Self {
+        Self {
+            version,
+            text,
+            parse,
+            file: OnceLock::new(),
+        }
+    }
+
+    pub fn version(&self) -> Option<i32> {
+        self.version
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn parse(&self) -> &Parse {
+        &self.parse
+    }
+
+    /// The best-effort semantic AST — the same tree
+    /// [`nml_core::cst::parse_best_effort`] builds, lowered once per version.
+    pub fn file(&self) -> &File {
+        self.file.get_or_init(|| {
+            let root = Root::cast(self.parse.syntax()).expect("parse always yields a Root node");
+            lower::to_ast(&root)
+        })
+    }
+
+    /// The document after `changes`, applied in order as the protocol
+    /// requires. A ranged change reparses incrementally from the previous
+    /// parse; a change without a range replaces the whole text.
+    pub fn apply_changes(
+        &self,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> ParsedDocument {
+        let mut text = self.text.clone();
+        let mut parse: Option<Parse> = None;
+        for change in changes {
+            let Some(range) = change.range else {
+                parse = Some(nml_core::cst::parse(&change.text));
+                text = change.text;
+                continue;
+            };
+            let line_index = LineIndex::new(&text);
+            let start = line_index.offset(range.start);
+            let end = line_index.offset(range.end).max(start);
+            let edit = SpliceEdit {
+                span: Span::new(start, end),
+                replacement: change.text,
+            };
+            let mut next = String::with_capacity(text.len() + edit.replacement.len());
+            next.push_str(&text[..start]);
+            next.push_str(&edit.replacement);
+            next.push_str(&text[end..]);
+            let reparsed = parse.as_ref().unwrap_or(&self.parse).reparse(&edit, &next);
+            parse = Some(reparsed);
+            text = next;
+        }
+        let parse = parse.unwrap_or_else(|| self.parse.clone());
+        Self::from_parts(text, parse, Some(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn ranged(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(start.0, start.1),
+                Position::new(end.0, end.1),
+            )),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    fn assert_matches_full_parse(doc: &ParsedDocument) {
+        let full = nml_core::cst::parse(doc.text());
+        assert_eq!(doc.parse().syntax().to_string(), full.syntax().to_string());
+        assert_eq!(
+            format!("{:#?}", doc.parse().syntax()),
+            format!("{:#?}", full.syntax())
+        );
+        assert_eq!(doc.parse().errors().len(), full.errors().len());
+        assert_eq!(
+            format!("{:?}", doc.file()),
+            format!("{:?}", nml_core::cst::parse_best_effort(doc.text()))
+        );
+    }
+
+    #[test]
+    fn ranged_edits_apply_in_order() {
+        let doc = ParsedDocument::new(
+            "service Api:\n    port = 8080\n\nservice Web:\n    port = 80\n".to_string(),
+            Some(1),
+        );
+        let next = doc.apply_changes(
+            vec![
+                // `8080` → `9090`, then a new line typed under `Web`.
+                ranged((1, 11), (1, 15), "9090"),
+                ranged((4, 13), (4, 13), "\n    host = \"web\""),
+            ],
+            2,
+        );
+        assert_eq!(
+            next.text(),
+            "service Api:\n    port = 9090\n\nservice Web:\n    port = 80\n    host = \"web\"\n"
+        );
+        assert_eq!(next.version(), Some(2));
+        assert_matches_full_parse(&next);
+    }
+
+    #[test]
+    fn utf16_columns_and_full_replacement() {
+        let doc = ParsedDocument::new("const name = \"café😀\"\n".to_string(), Some(1));
+        // Column 19 is after the emoji (two UTF-16 units).
+        let next = doc.apply_changes(vec![ranged((0, 19), (0, 19), "!")], 2);
+        assert_eq!(next.text(), "const name = \"café😀!\"\n");
+        assert_matches_full_parse(&next);
+
+        let replaced = next.apply_changes(
+            vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "service A:\n    x = = 1\n".to_string(),
+            }],
+            3,
+        );
+        assert_eq!(replaced.text(), "service A:\n    x = = 1\n");
+        assert!(!replaced.parse().errors().is_empty());
+        assert_matches_full_parse(&replaced);
+    }
+}
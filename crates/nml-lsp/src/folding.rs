@@ -0,0 +1,219 @@
+//! Folding ranges derived from the CST.
+//!
+//! Structure folds from the line of a construct's first real token to the
+//! line of its last: every declaration or entry that owns an indented
+//! `Body` (blocks, array bodies, nested blocks, list items, modifiers),
+//! `oneof` arm runs, and multi-line inline arrays. `"""` strings fold over
+//! their own lines, and runs of `//` comments on consecutive lines fold as
+//! [`FoldingRangeKind::Comment`], so "fold all comments" finds them.
+//! Folds are whole-line (no character offsets), which every client accepts.
+
+use nml_core::cst::{Parse, SyntaxKind, SyntaxNode, SyntaxToken};
+use tower_lsp::lsp_types::{FoldingRange, FoldingRangeKind};
+
+use crate::position::LineIndex;
+
+pub fn folding_ranges(parse: &Parse, source: &str) -> Vec<FoldingRange> {
+    let line_index = LineIndex::new(source);
+    let line = |offset: usize| line_index.position(offset).line;
+    let mut out = Vec::new();
+    let mut comments = CommentRun::default();
+    // Preorder: a node is visited before its tokens, tokens in source order.
+    for element in parse.syntax().descendants_with_tokens() {
+        if let Some(node) = element.as_node() {
+            if !folds(node) {
+                continue;
+            }
+            if let (Some(first), Some(last)) = (first_token(node), last_token(node)) {
+                push(
+                    &mut out,
+                    line(token_start(&first)),
+                    line(token_end(&last)),
+                    None,
+                );
+            }
+            continue;
+        }
+        let Some(token) = element.as_token() else {
+            continue;
+        };
+        match token.kind() {
+            SyntaxKind::Comment => {
+                if let Some((start, end)) = comments.comment(line(token_start(token))) {
+                    push(&mut out, start, end, Some(FoldingRangeKind::Comment));
+                }
+            }
+            kind if is_layout(kind) => {}
+            kind => {
+                if let Some((start, end)) = comments.finish() {
+                    push(&mut out, start, end, Some(FoldingRangeKind::Comment));
+                }
+                let (start, end) = (line(token_start(token)), line(token_end(token)));
+                if kind == SyntaxKind::String {
+                    push(&mut out, start, end, None);
+                }
+                comments.code_line = Some(end);
+            }
+        }
+    }
+    if let Some((start, end)) = comments.finish() {
+        push(&mut out, start, end, Some(FoldingRangeKind::Comment));
+    }
+    // One fold per start line — clients keep only one anyway, and the
+    // outermost is the one a user means.
+    out.sort_by_key(|r| (r.start_line, std::cmp::Reverse(r.end_line)));
+    out.dedup_by_key(|r| r.start_line);
+    out
+}
+
+/// Nodes that fold as a whole: anything owning an indented body, a
+/// `oneof` with its arms, and an inline array broken across lines.
+fn folds(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::OneOfDecl | SyntaxKind::ArrayValue => true,
+        SyntaxKind::Root | SyntaxKind::Body => false,
+        _ => node.children().any(|c| c.kind() == SyntaxKind::Body),
+    }
+}
+
+fn push(out: &mut Vec<FoldingRange>, start: u32, end: u32, kind: Option<FoldingRangeKind>) {
+    if end > start {
+        out.push(FoldingRange {
+            start_line: start,
+            end_line: end,
+            kind,
+            ..Default::default()
+        });
+    }
+}
+
+/// Consecutive full-line comments. A comment sharing a line with code is
+/// a trailing comment and never starts or joins a run.
+#[derive(Default)]
+struct CommentRun {
+    run: Option<(u32, u32)>,
+    code_line: Option<u32>,
+}
+
+impl CommentRun {
+    /// A comment on `line`; returns the run it ended, if it ended one.
+    /// Code closes a run through [`Self::finish`].
+    fn comment(&mut self, line: u32) -> Option<(u32, u32)> {
+        if self.code_line == Some(line) {
+            return self.finish();
+        }
+        match &mut self.run {
+            Some((_, end)) if *end + 1 == line => {
+                *end = line;
+                None
+            }
+            _ => self.run.replace((line, line)),
+        }
+    }
+
+    fn finish(&mut self) -> Option<(u32, u32)> {
+        self.run.take()
+    }
+}
+
+fn is_layout(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Whitespace | SyntaxKind::Newline | SyntaxKind::Indent | SyntaxKind::Dedent
+    )
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    is_layout(kind) || kind == SyntaxKind::Comment
+}
+
+fn first_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .find(|t| !is_trivia(t.kind()))
+}
+
+fn last_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    let mut token = node.last_token()?;
+    while is_trivia(token.kind()) {
+        token = token.prev_token()?;
+        if token.text_range().end() <= node.text_range().start() {
+            return None;
+        }
+    }
+    Some(token)
+}
+
+fn token_start(token: &SyntaxToken) -> usize {
+    usize::from(token.text_range().start())
+}
+
+fn token_end(token: &SyntaxToken) -> usize {
+    usize::from(token.text_range().end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+// Header one.
+// Header two.
+service Api:
+    host = \"x\"
+    limits:
+        rate = 1
+    doc = \"\"\"
+        hello
+        world
+        \"\"\"
+    x = 1 // trailing
+    // alone
+
+[]route routes:
+    - Home:
+        path = \"/\"
+    - Docs
+
+oneof notifier by kind = \"log\":
+    \"log\" -> logNotifier
+    \"slack\" -> slackNotifier
+";
+
+    fn ranges(source: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+        folding_ranges(&nml_core::cst::parse(source), source)
+            .into_iter()
+            .map(|r| (r.start_line, r.end_line, r.kind))
+            .collect()
+    }
+
+    #[test]
+    fn blocks_bodies_strings_and_arms_fold() {
+        let comment = || Some(FoldingRangeKind::Comment);
+        assert_eq!(
+            ranges(SOURCE),
+            vec![
+                (0, 1, comment()),
+                // `service Api:` through `x = 1`.
+                (2, 10, None),
+                (4, 5, None),
+                (6, 9, None),
+                (13, 16, None),
+                (14, 15, None),
+                (18, 20, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn comment_runs_break_on_blank_lines_and_code() {
+        let source = "// a\n// b\n\n// c\n// d\n// e\nconst x = 1 // f\n// g\n";
+        let comment = || Some(FoldingRangeKind::Comment);
+        assert_eq!(ranges(source), vec![(0, 1, comment()), (3, 5, comment())]);
+    }
+
+    #[test]
+    fn single_line_constructs_do_not_fold() {
+        assert!(ranges("const x = [1, 2]\nconst y = \"\"\"one line\"\"\"\n").is_empty());
+    }
+}
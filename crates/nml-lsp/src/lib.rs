@@ -1,10 +1,15 @@
+pub mod code_lens;
 pub mod diagnostics;
+pub mod document;
 pub mod duration_lsp;
+pub mod folding;
 pub mod packages;
 pub mod position;
 pub mod semantic_tokens;
 pub mod server;
+pub mod type_hierarchy;
 mod wasi_fs;
+pub mod workspace_symbols;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tower_lsp::Server;
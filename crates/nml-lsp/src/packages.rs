@@ -1,104 +1,24 @@
 //! Schema-package resolution for the LSP (RFC 0030).
 //!
-//! Two separate concerns, deliberately: where package **definitions** come
-//! from (workspace manifest > store `current` > builtin), and which package
-//! has **binding authority** over a file (pins > unambiguous
-//! auto-association > unbound fallback). Binding is exclusive — a bound
-//! file's validator is built from the package's sources only, never merged
-//! with the workspace scope registry — which is both what keeps strict mode
-//! sound and what makes the content hash a sound validator-cache key.
+//! Definition sourcing and binding live in [`nml_validate::resolve`], shared
+//! with `nml check`; this module adds what only the editor needs on top: the
+//! directive vocabulary for unbound model sources, and the claims walk
+//! behind it with its watcher-driven invalidation.
 
 use std::collections::HashMap;
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
-
-use nml_core::ProjectConfig;
-use nml_validate::package::{DirectiveDecl, PackageError, SchemaPackage, builtin_meta_package};
-use nml_validate::schema::SchemaValidator;
-use nml_validate::store::{Store, StoreError};
-
-/// Where a bound package's definition came from. Ordered by determinism, which
-/// is exactly the resolution precedence (RFC 0035 "delivery channels"): a
-/// committed workspace manifest (in-repo) beats a provider tool's embedded
-/// package (in-binary), which beats the machine-local cache (store), which
-/// beats the builtin.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DefinitionSource {
-    /// A `<name>.package.nml` in the workspace (the authoring path; RFC 0035
-    /// in-repo channel — the most deterministic source, shared via git).
-    WorkspaceManifest(PathBuf),
-    /// Injected in-process by an embedder that IS a schema provider — a tool
-    /// (e.g. `nudge lsp`) serving the neutral server with its own embedded
-    /// package (RFC 0035 in-binary channel). Beats the store so the editor
-    /// validates against the exact binary in front of the user, zero-sync;
-    /// yields to a committed workspace manifest, which the team chose to pin.
-    InBinary,
-    /// The per-user store's `current` slot (RFC 0035 in-cache channel).
-    Store,
-    /// Embedded in the LSP itself (today: the `package.model.nml` meta
-    /// package).
-    Builtin,
-}
-
-impl DefinitionSource {
-    pub fn label(&self) -> &'static str {
-        match self {
-            Self::WorkspaceManifest(_) => "workspace manifest",
-            Self::InBinary => "in-binary",
-            Self::Store => "store current",
-            Self::Builtin => "builtin",
-        }
-    }
-}
+use std::sync::Mutex;
+use std::sync::mpsc::SyncSender;
 
-/// Which authority step bound the file.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BindingStep {
-    Pinned,
-    AutoAssociated,
-}
-
-/// A successful binding: everything diagnostics, hover, `nml/schemaInfo`,
-/// and code actions need.
-#[derive(Clone)]
-pub struct Binding {
-    pub package_name: String,
-    pub package_version: String,
-    pub content_hash: String,
-    pub binding_name: String,
-    pub validator: Arc<SchemaValidator>,
-    pub source: DefinitionSource,
-    pub step: BindingStep,
-    /// The root the binding glob matched under.
-    pub root: PathBuf,
-    /// Set when a workspace manifest shadows a *pinned* name that the store
-    /// also holds — shadowing is visible, never silent (RFC 0030).
-    pub shadows_store: bool,
-}
-
-impl Binding {
-    /// The single owner of the human-facing binding identity used in
-    /// diagnostic suffixes and hover: `<name> blake3:<hash8>, <source>`.
-    pub fn identity(&self) -> String {
-        format!(
-            "{} blake3:{}, {}",
-            self.package_name,
-            nml_validate::store::hash8(&self.content_hash),
-            self.source.label()
-        )
-    }
-}
+use nml_validate::package::{DirectiveDecl, SchemaPackage};
+use nml_validate::resolve::{Definition, find_root, manifests_governing};
+use nml_validate::store::Store;
 
-/// One diagnostic-worthy degraded state, attached at the top of the file.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DegradedNote {
-    pub message: String,
-    pub warning: bool,
-    /// Byte span into the resolved document, when the note is about a
-    /// specific construct (a shadowed binding in a manifest); `None` pins
-    /// the note at the top of the file.
-    pub span: Option<nml_core::span::Span>,
-}
+pub use nml_validate::resolve::{
+    Binding, BindingStep, DefinitionSource, DegradedNote, Resolution, Resolved, StoreEvent,
+    WorkspaceView, display_path, nearest_project_config,
+};
 
 /// The directive vocabulary governing a `.model.nml` file (RFC 0030
 /// "Directive-vocabulary scope"): the covering package's declared
@@ -179,117 +99,15 @@ enum ClaimScan {
     Truncated,
 }
 
-/// The outcome of resolving one file.
-pub enum Resolution {
-    Bound(Box<Binding>),
-    /// No package claims the file — today's scope-token behavior applies.
-    Unbound,
-}
-
-/// Resolution result plus any degraded-state notes to surface (a file can be
-/// bound *and* carry notes — e.g. a shadow info — or unbound with a note —
-/// e.g. its pin's package failed to load and validation fell through).
-pub struct Resolved {
-    pub resolution: Resolution,
-    pub notes: Vec<DegradedNote>,
-}
-
-/// A snapshot of the workspace the resolver needs for one pass; built by the
-/// server from its own state so the resolver stays lock-free against server
-/// internals.
-pub struct WorkspaceView<'a> {
-    pub roots: &'a [PathBuf],
-    /// Open/indexed `<name>.package.nml` documents: (fs path, text). Open
-    /// text wins over disk so manifest edits resolve live.
-    pub manifests: &'a [(PathBuf, String)],
-    /// Open-document lookup for schema sources named by workspace manifests —
-    /// unsaved schema edits must flow into the package (the authoring path).
-    pub doc_text: &'a dyn Fn(&Path) -> Option<String>,
-}
-
-/// A resolved package definition: the loaded package, its content hash, and
-/// where it came from — the unit that definition precedence produces and
-/// binding authority consumes.
-#[derive(Clone)]
-pub struct Definition {
-    pub package: Arc<SchemaPackage>,
-    pub hash: String,
-    pub source: DefinitionSource,
-}
-
-/// Outcome of a store read, cached per pointer stat. Structured (not a
-/// stringly sentinel) so degraded-state wording can stay per-variant — the
-/// formatVersion contract in particular.
-#[derive(Clone)]
-enum StoreOutcome {
-    Ready(Arc<SchemaPackage>, String),
-    NotInstalled,
-    /// Human-facing degraded message, already worded per the RFC contracts.
-    Failed(String),
-}
-
-struct StoreCacheEntry {
-    /// The pointer content at load time — the exact-by-construction
-    /// freshness guard (the full hash is in it; no mtime granularity).
-    pointer: Option<String>,
-    outcome: StoreOutcome,
-}
-
-/// A store-package health transition (Ready↔Failed) or store-manifest
-/// shadow warning, surfaced once via `window/logMessage` — never as
-/// per-file diagnostics (RFC 0030: server-side conditions go to status
-/// surfaces). Push-based: the resolver `try_send`s into a bounded channel
-/// the server's notifier task drains the instant an event lands — no
-/// per-handler drain sites, no guard-across-await hazards, and overflow
-/// drops the NEWEST events (under flapping, the first transition is the
-/// informative one). Send failures (full channel, dropped receiver) are
-/// ignored by design: best-effort is the contract.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct StoreEvent {
-    pub message: String,
-    pub warning: bool,
-}
-
-/// Fingerprint of one workspace-manifest source input, for cache
-/// invalidation without re-reading content: open-document text (compared by
-/// equality) or on-disk (len, mtime).
-#[derive(Clone, PartialEq)]
-enum SourceFingerprint {
-    Doc(String),
-    Disk(u64, Option<std::time::SystemTime>),
-    Missing,
-}
-
-struct ManifestCacheEntry {
-    manifest_text: String,
-    sources: Vec<(std::path::PathBuf, SourceFingerprint)>,
-    outcome: Result<(Arc<SchemaPackage>, String), String>,
-}
-
-/// Validator-cache size at which stale entries are dropped wholesale.
-/// Entries rebuild on demand from cached packages; editing a workspace
-/// package's schema sources mints a new content hash per keystroke, so
-/// without a bound the cache grows for the length of the session.
-const VALIDATOR_CACHE_CAP: usize = 64;
+/// Bound on [`PackageResolver`]'s claims memo — cap-and-clear, like the
+/// shared resolver's validator cache.
+const CLAIMS_CACHE_CAP: usize = 64;
 
+/// The shared [`nml_validate::resolve::PackageResolver`] plus the claims
+/// memo behind [`Self::vocabulary_for`]. Derefs to the shared resolver, so
+/// `resolve`, `generation`, and the rest are called straight through.
 pub struct PackageResolver {
-    store: Option<Store>,
-    /// An embedder-supplied package served in-process (RFC 0035 in-binary
-    /// channel): precomputed once with its content hash so resolution never
-    /// re-hashes it. `None` for the neutral server; `Some` for a provider tool
-    /// like `nudge lsp`. Sits above the store in precedence, below a committed
-    /// workspace manifest.
-    injected: Option<Definition>,
-    builtin: Arc<SchemaPackage>,
-    builtin_hash: String,
-    store_cache: Mutex<HashMap<String, StoreCacheEntry>>,
-    events: tokio::sync::mpsc::Sender<StoreEvent>,
-    manifest_cache: Mutex<HashMap<std::path::PathBuf, ManifestCacheEntry>>,
-    /// Validators cached per (content hash, binding name) — sound because
-    /// binding is exclusive: the hash covers every input.
-    validator_cache: Mutex<HashMap<(String, String), Arc<SchemaValidator>>>,
-    /// Resolution generation — see [`Self::generation`].
-    generation: std::sync::atomic::AtomicU64,
+    resolver: nml_validate::resolve::PackageResolver,
     /// Memoized [`package_claims_file_under`] answers per
     /// (package content hash, root): the walk reads up to 2048 `read_dir`
     /// entries and `vocabulary_for` runs per validation pass, so an uncached
@@ -299,57 +117,60 @@ pub struct PackageResolver {
     claims_cache: Mutex<HashMap<(String, PathBuf), ClaimScan>>,
 }
 
+impl Deref for PackageResolver {
+    type Target = nml_validate::resolve::PackageResolver;
+
+    fn deref(&self) -> &Self::Target {
+        &self.resolver
+    }
+}
+
 impl PackageResolver {
-    pub fn new(store: Option<Store>, events: tokio::sync::mpsc::Sender<StoreEvent>) -> Self {
+    pub fn new(store: Option<Store>, events: SyncSender<StoreEvent>) -> Self {
         Self::with_injected(store, events, None)
     }
 
     /// Construct a resolver that also serves an embedder-supplied package
-    /// in-process (RFC 0035 in-binary channel; the seam `nudge lsp` uses). The
-    /// package's content hash is computed once here — resolution treats it like
-    /// any other [`Definition`], so binding, vocabulary, caching, and the
-    /// freshness poll all work unchanged.
+    /// in-process (RFC 0035 in-binary channel; the seam `nudge lsp` uses).
+    /// See [`nml_validate::resolve::PackageResolver::with_injected`].
     pub fn with_injected(
         store: Option<Store>,
-        events: tokio::sync::mpsc::Sender<StoreEvent>,
+        events: SyncSender<StoreEvent>,
         injected: Option<SchemaPackage>,
     ) -> Self {
-        let builtin = Arc::new(builtin_meta_package());
-        let builtin_hash = builtin.content_hash();
-        let injected = injected.map(|package| {
-            let hash = package.content_hash();
-            Definition {
-                package: Arc::new(package),
-                hash,
-                source: DefinitionSource::InBinary,
-            }
-        });
+        let mut resolver = nml_validate::resolve::PackageResolver::new(store)
+            .with_events(events)
+            .with_tool("nml-lsp");
+        if let Some(package) = injected {
+            resolver = resolver.with_injected(package);
+        }
         Self {
-            store,
-            injected,
-            builtin,
-            builtin_hash,
-            store_cache: Mutex::new(HashMap::new()),
-            events,
-            manifest_cache: Mutex::new(HashMap::new()),
-            validator_cache: Mutex::new(HashMap::new()),
+            resolver,
             claims_cache: Mutex::new(HashMap::new()),
-            generation: std::sync::atomic::AtomicU64::new(0),
         }
     }
 
-    /// Monotonic resolution generation (RFC 0010 tier 1): bumped whenever a
-    /// stat/fingerprint guard observes actual change (a store pointer
-    /// transition, a manifest rebuild). Consumers caching anything derived
-    /// from resolution compare this — an out-of-band `schema sync` then
-    /// invalidates their entries the moment any resolve notices it.
-    pub fn generation(&self) -> u64 {
-        self.generation.load(std::sync::atomic::Ordering::Relaxed)
-    }
-
-    fn bump_generation(&self) {
-        self.generation
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    /// Where a bound package's schema source `logical` (a `[]schema` entry
+    /// name, as stamped on its definitions) lives on disk: beside its
+    /// workspace manifest, or in the store's current slot. `None` for
+    /// in-binary and builtin packages, which have no file to show.
+    pub fn source_path(&self, binding: &Binding, logical: &str) -> Option<PathBuf> {
+        let (dir, manifest_path) = match &binding.source {
+            DefinitionSource::WorkspaceManifest(manifest) => {
+                (manifest.parent()?.to_path_buf(), manifest.clone())
+            }
+            DefinitionSource::Store => {
+                let dir = self.store()?.current_slot_dir(&binding.package_name)?;
+                let manifest = nml_validate::package::find_manifest(&dir).ok()?;
+                (dir, manifest)
+            }
+            DefinitionSource::InBinary | DefinitionSource::Builtin => return None,
+        };
+        let text = std::fs::read_to_string(manifest_path).ok()?;
+        let manifest = nml_validate::package::parse_manifest(&text).ok()?;
+        let entry = manifest.schemas.iter().find(|e| e.name == logical)?;
+        nml_validate::package::check_plain_file_name(&entry.file).ok()?;
+        Some(dir.join(&entry.file))
     }
 
     /// [`package_claims_file_under`] behind the claims cache: consult the
@@ -372,7 +193,7 @@ impl PackageResolver {
             // grow for the length of the session. Cap-and-clear; walks are cheap
             // to redo on demand. `Truncated` is cached AS truncated (it stays
             // "answer unknown" — see `ClaimScan`), never as a yes/no.
-            if cache.len() >= VALIDATOR_CACHE_CAP {
+            if cache.len() >= CLAIMS_CACHE_CAP {
                 cache.clear();
             }
             cache.insert(key, scan);
@@ -409,114 +230,6 @@ impl PackageResolver {
             .retain(|(_, root), _| !paths.iter().any(|path| path.starts_with(root)));
     }
 
-    /// Resolve one file. `path` must be absolute.
-    pub fn resolve(&self, path: &Path, ws: &WorkspaceView<'_>) -> Resolved {
-        let mut notes = Vec::new();
-
-        // Per-root project settings: nearest-ancestor nml-project.nml wins
-        // wholesale; lists never merge across nesting levels (RFC 0030).
-        let project = nearest_project_config(path, ws);
-        let (pins, auto_associate) = match &project {
-            // `pinned_packages()` folds in the `provider` tool as an implicit
-            // same-named pin (RFC 0035 tool→package fallback), so the neutral
-            // server validates a provider-declared project against the tool's
-            // published package without launching the tool's LSP. Each pin is
-            // charset-gated in the loop below.
-            Some((_, config)) => (config.pinned_packages(), config.auto_associate),
-            None => (Vec::new(), true),
-        };
-
-        // ── Step 1: pins, in list order, first match wins. ──
-        for pin in &pins {
-            // A pin is an external string headed for store paths and
-            // diagnostics; the charset rule guards every resolution path
-            // (RFC 0030 Security) — a hostile `../../x` pin is rejected
-            // here, never joined into a path or echoed into a "run this"
-            // hint.
-            if !nml_validate::package::valid_package_name(pin) {
-                notes.push(DegradedNote {
-                    message: format!(
-                        "schema package name {pin:?} is not a valid package name ([a-z][a-z0-9-]*) — ignored (from schemaPackages or provider.tool)"
-                    ),
-                    warning: true,
-                    span: None,
-                });
-                continue;
-            }
-            match self.definition_for(pin, path, ws, &mut notes) {
-                Some(def) => {
-                    if let Some(binding) =
-                        self.try_bind(&def, path, ws, BindingStep::Pinned, &mut notes)
-                    {
-                        let shadows_store =
-                            matches!(def.source, DefinitionSource::WorkspaceManifest(_))
-                                && self.store_has(pin);
-                        if shadows_store {
-                            notes.push(DegradedNote {
-                                message: format!(
-                                    "bound by workspace manifest for '{pin}', shadowing the store's copy"
-                                ),
-                                warning: false,
-                                span: None,
-                            });
-                        }
-                        return Resolved {
-                            resolution: Resolution::Bound(Box::new(Binding {
-                                shadows_store,
-                                ..binding
-                            })),
-                            notes,
-                        };
-                    }
-                }
-                None => {
-                    // definition_for pushed the precise note (not installed /
-                    // failed to load); the pin simply doesn't bind.
-                }
-            }
-        }
-
-        // ── Step 2: unambiguous auto-association across known packages. ──
-        if auto_associate {
-            let mut matches: Vec<Binding> = Vec::new();
-            for def in self.known_packages(path, ws, &mut notes) {
-                if let Some(binding) =
-                    self.try_bind(&def, path, ws, BindingStep::AutoAssociated, &mut notes)
-                {
-                    matches.push(binding);
-                }
-            }
-            match matches.len() {
-                1 => {
-                    let binding = matches.into_iter().next().expect("len checked");
-                    return Resolved {
-                        resolution: Resolution::Bound(Box::new(binding)),
-                        notes,
-                    };
-                }
-                0 => {}
-                _ => {
-                    let names: Vec<&str> =
-                        matches.iter().map(|b| b.package_name.as_str()).collect();
-                    notes.push(DegradedNote {
-                        message: format!(
-                            "{} schema packages claim this file ({}) — add a schemaPackages pin to choose",
-                            names.len(),
-                            names.join(", ")
-                        ),
-                        warning: true,
-                        span: None,
-                    });
-                }
-            }
-        }
-
-        Resolved {
-            resolution: Resolution::Unbound,
-            notes,
-        }
-    }
-
     /// The directive vocabulary covering `path` (RFC 0030): (a) a governing
     /// workspace manifest whose `[]schema` declares this exact file wins;
     /// else (b) the unique known package — deepest manifest first, then
@@ -531,7 +244,7 @@ impl PackageResolver {
         // (same rule as auto-association), so load notes are discarded here —
         // a failing manifest's own diagnostics surface when *it* is resolved.
         let mut quiet = Vec::new();
-        for (manifest_path, text) in Self::manifests_governing(path, ws) {
+        for (manifest_path, text) in manifests_governing(path, ws) {
             let Some(dir) = manifest_path.parent() else {
                 continue;
             };
@@ -637,480 +350,6 @@ impl PackageResolver {
             },
         }
     }
-
-    /// Workspace manifests governing `path` — a manifest defines its package
-    /// for files under its own directory subtree only (RFC 0030: "each
-    /// governs its root"; a manifest buried in a vendored dir or fixture
-    /// must not redefine validation workspace-wide). Deepest-first so the
-    /// nearest manifest wins for nesting; deduped by name downstream.
-    fn manifests_governing<'m>(
-        path: &Path,
-        ws: &'m WorkspaceView<'_>,
-    ) -> Vec<&'m (PathBuf, String)> {
-        let mut governing: Vec<&(PathBuf, String)> = ws
-            .manifests
-            .iter()
-            .filter(|(mp, _)| mp.parent().is_some_and(|dir| path.starts_with(dir)))
-            .collect();
-        // Path as the same-depth tiebreak: `ws.manifests` is built from a
-        // HashMap, so depth alone would leave two same-directory manifests
-        // in hash order — vocabulary and universe flipping between server
-        // restarts on identical inputs.
-        governing.sort_by(|(a, _), (b, _)| {
-            std::cmp::Reverse(a.components().count())
-                .cmp(&std::cmp::Reverse(b.components().count()))
-                .then_with(|| a.cmp(b))
-        });
-        governing
-    }
-
-    /// All known packages *for this file*: governing workspace manifests,
-    /// store entries, builtins — workspace definitions shadow same-named
-    /// store entries; nearest manifest shadows a farther same-named one.
-    ///
-    /// Load failures during this probing pass are deliberately quiet except
-    /// on the failing manifest itself: an unloadable package's globs are
-    /// unknowable, so the "files it would have bound" contract cannot be
-    /// met, and broadcasting the failure onto every resolved file in the
-    /// workspace is worse. Pinned resolution (which names the package
-    /// explicitly) stays loud.
-    fn known_packages(
-        &self,
-        path: &Path,
-        ws: &WorkspaceView<'_>,
-        notes: &mut Vec<DegradedNote>,
-    ) -> Vec<Definition> {
-        let mut out: Vec<Definition> = Vec::new();
-        let mut seen: Vec<String> = Vec::new();
-        for (manifest_path, text) in Self::manifests_governing(path, ws) {
-            let is_self = path == manifest_path;
-            let mut local = Vec::new();
-            if let Some((package, hash)) =
-                self.load_workspace_manifest(manifest_path, text, ws, &mut local)
-            {
-                // Shadow warnings belong to THIS manifest only — spans are
-                // byte offsets into THIS text; reading them off any other
-                // definition would squiggle arbitrary bytes.
-                if is_self {
-                    for w in package.manifest.shadow_warnings() {
-                        notes.push(DegradedNote {
-                            message: w.message,
-                            warning: true,
-                            span: w.span,
-                        });
-                    }
-                }
-                if seen.contains(&package.manifest.name) {
-                    continue; // nearer manifest already defines this name
-                }
-                seen.push(package.manifest.name.clone());
-                out.push(Definition {
-                    package,
-                    hash,
-                    source: DefinitionSource::WorkspaceManifest(manifest_path.clone()),
-                });
-            }
-            if is_self {
-                notes.extend(local);
-            }
-        }
-        // In-binary channel (RFC 0035): above the store, below a committed
-        // workspace manifest of the same name — the team's committed copy wins,
-        // but the embedded package always beats its own possibly-stale cache.
-        if let Some(def) = &self.injected {
-            if !seen.contains(&def.package.manifest.name) {
-                seen.push(def.package.manifest.name.clone());
-                out.push(def.clone());
-            }
-        }
-        if let Some(store) = &self.store {
-            for name in store.list_names() {
-                if seen.contains(&name) {
-                    continue;
-                }
-                if let StoreOutcome::Ready(package, hash) = self.load_store_package(&name) {
-                    out.push(Definition {
-                        package,
-                        hash,
-                        source: DefinitionSource::Store,
-                    });
-                }
-            }
-        }
-        if !seen.contains(&self.builtin.manifest.name) {
-            out.push(Definition {
-                package: self.builtin.clone(),
-                hash: self.builtin_hash.clone(),
-                source: DefinitionSource::Builtin,
-            });
-        }
-        out
-    }
-
-    /// Definition precedence for one named package, scoped to the file's
-    /// governing manifests: workspace manifest > store `current` > builtin.
-    fn definition_for(
-        &self,
-        name: &str,
-        path: &Path,
-        ws: &WorkspaceView<'_>,
-        notes: &mut Vec<DegradedNote>,
-    ) -> Option<Definition> {
-        for (manifest_path, text) in Self::manifests_governing(path, ws) {
-            if manifest_stem(manifest_path) == Some(name) {
-                if let Some((package, hash)) =
-                    self.load_workspace_manifest(manifest_path, text, ws, notes)
-                {
-                    return Some(Definition {
-                        package,
-                        hash,
-                        source: DefinitionSource::WorkspaceManifest(manifest_path.clone()),
-                    });
-                }
-                return None;
-            }
-        }
-        // In-binary channel (RFC 0035): a pin resolves to the embedded package
-        // before the store, so `nudge lsp` validates against the running
-        // binary's schema even when the store holds an older synced copy.
-        if let Some(def) = &self.injected {
-            if def.package.manifest.name == name {
-                return Some(def.clone());
-            }
-        }
-        match self.load_store_package(name) {
-            StoreOutcome::Ready(package, hash) => {
-                return Some(Definition {
-                    package,
-                    hash,
-                    source: DefinitionSource::Store,
-                });
-            }
-            StoreOutcome::Failed(message) => {
-                // The pin names this package explicitly — its failure is
-                // this file's business.
-                notes.push(DegradedNote {
-                    message,
-                    warning: true,
-                    span: None,
-                });
-                return None;
-            }
-            StoreOutcome::NotInstalled => {}
-        }
-        if self.builtin.manifest.name == name {
-            return Some(Definition {
-                package: self.builtin.clone(),
-                hash: self.builtin_hash.clone(),
-                source: DefinitionSource::Builtin,
-            });
-        }
-        if self.store.is_some() {
-            notes.push(DegradedNote {
-                message: format!(
-                    "pinned schema package '{name}' is not installed — run '{name} schema sync'"
-                ),
-                warning: true,
-                span: None,
-            });
-        }
-        None
-    }
-
-    fn store_has(&self, name: &str) -> bool {
-        self.store
-            .as_ref()
-            .is_some_and(|s| s.pointer_content(name).is_some())
-    }
-
-    /// Store read with a stat-guarded cache: the per-validation-pass probe is
-    /// a `stat` (microseconds); the package is re-read and re-hashed only on
-    /// a pointer transition — including absent→present, the
-    /// brand-new-operator path (RFC 0030 Freshness). Failure wording is
-    /// per-variant here so the formatVersion degradation contract survives
-    /// the store path (its primary path — a newer nudge auto-syncing).
-    fn load_store_package(&self, name: &str) -> StoreOutcome {
-        let Some(store) = self.store.as_ref() else {
-            return StoreOutcome::NotInstalled;
-        };
-        // One ~80-byte read serves as both freshness guard and load input.
-        let pointer = store.pointer_content(name);
-        let mut cache = self.store_cache.lock().unwrap_or_else(|e| e.into_inner());
-        match cache.get(name) {
-            Some(e) if e.pointer == pointer => e.outcome.clone(),
-            prior => {
-                let outcome = match &pointer {
-                    None => StoreOutcome::NotInstalled,
-                    Some(content) => match store.load_current(name, content) {
-                        Ok(slot) => {
-                            // A store manifest isn't an open file — its
-                            // shadow warnings go to the status channel,
-                            // one-shot by construction (loads happen once
-                            // per pointer transition).
-                            // One-shot by cache-miss construction; may drop
-                            // on overflow (see StoreEvent: newest-dropped).
-                            for w in slot.package.manifest.shadow_warnings() {
-                                let _ = self.events.try_send(StoreEvent {
-                                    message: format!("schema package '{name}': {}", w.message),
-                                    warning: true,
-                                });
-                            }
-                            StoreOutcome::Ready(Arc::new(slot.package), slot.content_hash)
-                        }
-                        Err(StoreError::NotInstalled) => StoreOutcome::NotInstalled,
-                        Err(StoreError::Package(
-                            nml_validate::package::PackageError::UnsupportedFormatVersion {
-                                required,
-                                supported,
-                            },
-                        )) => StoreOutcome::Failed(format!(
-                            "schema package '{name}' needs formatVersion {required}; this nml-lsp supports {supported} — update nml-lsp; using basic validation until then"
-                        )),
-                        Err(e) => StoreOutcome::Failed(format!(
-                            "schema package '{name}' in the store failed to load: {e} — falling back to basic validation"
-                        )),
-                    },
-                };
-                // Health transitions surface once, at the transition.
-                let was_failed = matches!(prior.map(|e| &e.outcome), Some(StoreOutcome::Failed(_)));
-                match (&outcome, was_failed) {
-                    (StoreOutcome::Failed(message), false) => {
-                        let _ = self.events.try_send(StoreEvent {
-                            message: message.clone(),
-                            warning: true,
-                        });
-                    }
-                    (StoreOutcome::Ready(..), true) => {
-                        let _ = self.events.try_send(StoreEvent {
-                            message: format!("schema package '{name}' in the store recovered"),
-                            warning: false,
-                        });
-                    }
-                    _ => {}
-                }
-                // A pointer transition (or first load) changes resolution
-                // output for every bound document — generation-invalidate
-                // downstream caches (RFC 0010 tier 1).
-                self.bump_generation();
-                cache.insert(
-                    name.to_string(),
-                    StoreCacheEntry {
-                        pointer,
-                        outcome: outcome.clone(),
-                    },
-                );
-                outcome
-            }
-        }
-    }
-
-    /// Load a workspace manifest package: manifest text from the live
-    /// document, sources from open documents first (unsaved edits flow into
-    /// the package — the authoring loop), disk second. Fingerprint-cached so
-    /// the steady-state per-pass cost is string compares + stats, never
-    /// re-parse/re-hash (RFC 0030 Freshness: "hash verification on
-    /// load/cache-miss only, never per keystroke").
-    fn load_workspace_manifest(
-        &self,
-        manifest_path: &Path,
-        text: &str,
-        ws: &WorkspaceView<'_>,
-        notes: &mut Vec<DegradedNote>,
-    ) -> Option<(Arc<SchemaPackage>, String)> {
-        let fingerprint_of = |full: &Path| -> SourceFingerprint {
-            if let Some(open) = (ws.doc_text)(full) {
-                return SourceFingerprint::Doc(open);
-            }
-            match std::fs::metadata(full) {
-                Ok(m) => SourceFingerprint::Disk(m.len(), m.modified().ok()),
-                Err(_) => SourceFingerprint::Missing,
-            }
-        };
-        {
-            let cache = self
-                .manifest_cache
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
-            if let Some(entry) = cache.get(manifest_path) {
-                if entry.manifest_text == text
-                    && entry.sources.iter().all(|(p, fp)| fingerprint_of(p) == *fp)
-                {
-                    match &entry.outcome {
-                        Ok((package, hash)) => return Some((package.clone(), hash.clone())),
-                        Err(message) => {
-                            notes.push(DegradedNote {
-                                message: message.clone(),
-                                warning: true,
-                                span: None,
-                            });
-                            return None;
-                        }
-                    }
-                }
-            }
-        }
-
-        let dir = manifest_path.parent()?;
-        let mut fingerprints: Vec<(PathBuf, SourceFingerprint)> = Vec::new();
-        let result = SchemaPackage::from_parts(text, |file| {
-            nml_validate::package::check_plain_file_name(file)?;
-            let full = dir.join(file);
-            let fp = fingerprint_of(&full);
-            let content = match &fp {
-                SourceFingerprint::Doc(open) => Ok(open.clone()),
-                _ => std::fs::read_to_string(&full).map_err(|e| e.to_string()),
-            };
-            fingerprints.push((full, fp));
-            content
-        });
-        let outcome: Result<(Arc<SchemaPackage>, String), String> = match result {
-            Ok(package) => {
-                // The filename stem is the pin/dedup key; the declared name
-                // is the binding identity. They must agree — the store
-                // enforces this, and a `demo.package.nml` declaring
-                // `package nudge:` must not be two different packages
-                // depending on the resolution path.
-                match manifest_stem(manifest_path) {
-                    Some(stem) if stem != package.manifest.name => Err(format!(
-                        "workspace package manifest '{}' declares package '{}' but its filename says '{stem}' — rename one; using basic validation until then",
-                        display_path(manifest_path, ws.roots),
-                        package.manifest.name
-                    )),
-                    _ => {
-                        let hash = package.content_hash();
-                        Ok((Arc::new(package), hash))
-                    }
-                }
-            }
-            Err(PackageError::UnsupportedFormatVersion {
-                required,
-                supported,
-            }) => Err(format!(
-                "package manifest '{}' needs formatVersion {required}; this nml-lsp supports {supported} — update nml-lsp; using basic validation until then",
-                display_path(manifest_path, ws.roots)
-            )),
-            Err(e) => Err(format!(
-                "workspace package manifest '{}' failed to load: {e} — falling back to basic validation",
-                display_path(manifest_path, ws.roots)
-            )),
-        };
-
-        // A manifest rebuild (fingerprint change or first load) changes
-        // resolution output — generation-invalidate downstream caches.
-        self.bump_generation();
-        self.manifest_cache
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .insert(
-                manifest_path.to_path_buf(),
-                ManifestCacheEntry {
-                    manifest_text: text.to_string(),
-                    sources: fingerprints,
-                    outcome: outcome.clone(),
-                },
-            );
-        match outcome {
-            Ok((package, hash)) => Some((package, hash)),
-            Err(message) => {
-                notes.push(DegradedNote {
-                    message,
-                    warning: true,
-                    span: None,
-                });
-                None
-            }
-        }
-    }
-
-    /// Try to bind `path` with one package: find its root, match the binding
-    /// globs, and build (or fetch) the exclusive validator.
-    fn try_bind(
-        &self,
-        def: &Definition,
-        path: &Path,
-        ws: &WorkspaceView<'_>,
-        step: BindingStep,
-        notes: &mut Vec<DegradedNote>,
-    ) -> Option<Binding> {
-        let Definition {
-            package,
-            hash,
-            source,
-        } = def;
-        let root = find_root(path, &package.manifest.root_markers, ws, source)?;
-        let rel = path.strip_prefix(&root).ok()?;
-        let rel = rel.to_string_lossy().replace('\\', "/");
-        let binding = package.binding_for(&rel)?;
-
-        let key = (hash.to_string(), binding.name.clone());
-        let cached = {
-            let cache = self
-                .validator_cache
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
-            cache.get(&key).cloned()
-        };
-        let validator = match cached {
-            Some(v) => v,
-            None => match package.validator(binding) {
-                Ok(v) => {
-                    let v = Arc::new(v);
-                    let mut cache = self
-                        .validator_cache
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner());
-                    // Bounded: schema-source edits mint a fresh hash per
-                    // keystroke; entries rebuild cheaply on demand, so a
-                    // wholesale clear at the cap beats bookkeeping.
-                    if cache.len() >= VALIDATOR_CACHE_CAP {
-                        cache.clear();
-                    }
-                    cache.insert(key, v.clone());
-                    v
-                }
-                Err(PackageError::Sources { errors }) => {
-                    let detail = errors
-                        .first()
-                        .map(|e| e.to_string())
-                        .unwrap_or_else(|| "unknown error".to_string());
-                    notes.push(DegradedNote {
-                        message: format!(
-                            "schema package '{}' failed to load: {detail} — falling back to basic validation",
-                            package.manifest.name
-                        ),
-                        warning: true,
-                        span: None,
-                    });
-                    return None;
-                }
-                Err(e) => {
-                    notes.push(DegradedNote {
-                        message: format!(
-                            "schema package '{}' failed to load: {e} — falling back to basic validation",
-                            package.manifest.name
-                        ),
-                        warning: true,
-                        span: None,
-                    });
-                    return None;
-                }
-            },
-        };
-
-        Some(Binding {
-            package_name: package.manifest.name.clone(),
-            package_version: package.manifest.version.clone(),
-            content_hash: hash.to_string(),
-            binding_name: binding.name.clone(),
-            validator,
-            source: source.clone(),
-            step,
-            root,
-            // The pinned caller overlays this after its store check; it has
-            // no meaning on other paths.
-            shadows_store: false,
-        })
-    }
 }
 
 /// Does `package` bind at least one file under `root`? Bounded filesystem
@@ -1225,108 +464,12 @@ pub(crate) fn watched_path_affects_claims(path: &Path, roots: &[PathBuf]) -> boo
     })
 }
 
-fn manifest_stem(path: &Path) -> Option<&str> {
-    path.file_name()?
-        .to_str()?
-        .strip_suffix(".package.nml")
-        .filter(|s| !s.is_empty())
-}
-
-/// A path for user-facing messages (diagnostics, hover, `nml/schemaInfo`):
-/// workspace-root-relative, with a forward-slash separator, falling back to the
-/// file name when the path sits outside every root. Never an absolute path —
-/// which keeps messages terse on every backend and, on the wasm neutral server,
-/// never leaks the `/workspace` WASI mount prefix (paths there are the mounted
-/// guest paths, not host paths).
-pub(crate) fn display_path(path: &Path, roots: &[PathBuf]) -> String {
-    roots
-        .iter()
-        .find_map(|root| path.strip_prefix(root).ok())
-        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
-        .filter(|rel| !rel.is_empty())
-        .or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()))
-        .unwrap_or_else(|| path.to_string_lossy().into_owned())
-}
-
-/// The nearest-ancestor `nml-project.nml` from `path`'s directory upward,
-/// bounded by the workspace roots. Nearest file wins wholesale.
-pub fn nearest_project_config(
-    path: &Path,
-    ws: &WorkspaceView<'_>,
-) -> Option<(PathBuf, ProjectConfig)> {
-    for dir in ancestors_within_roots(path, ws.roots) {
-        let candidate = dir.join("nml-project.nml");
-        let text = (ws.doc_text)(&candidate).or_else(|| std::fs::read_to_string(&candidate).ok());
-        if let Some(text) = text {
-            let file = nml_core::cst::parse_best_effort(&text);
-            return Some((candidate, ProjectConfig::from_file(&file)));
-        }
-    }
-    None
-}
-
-/// The project root for glob anchoring (RFC 0030 Vocabulary): nearest
-/// ancestor (inclusive) containing `nml-project.nml` or one of the package's
-/// root markers; the workspace-manifest's own directory is the root of last
-/// resort, which makes the rule total.
-fn find_root(
-    path: &Path,
-    root_markers: &[String],
-    ws: &WorkspaceView<'_>,
-    source: &DefinitionSource,
-) -> Option<PathBuf> {
-    for dir in ancestors_within_roots(path, ws.roots) {
-        if dir.join("nml-project.nml").is_file()
-            || root_markers.iter().any(|m| dir.join(m).is_file())
-        {
-            return Some(dir);
-        }
-    }
-    match source {
-        DefinitionSource::WorkspaceManifest(manifest_path) => {
-            manifest_path.parent().map(Path::to_path_buf)
-        }
-        // In-binary/store/builtin packages with no marker root: the workspace
-        // root containing the file anchors the globs. Enumerated (not a
-        // catch-all) so a new source with different anchoring can't fall
-        // through silently.
-        DefinitionSource::InBinary | DefinitionSource::Store | DefinitionSource::Builtin => ws
-            .roots
-            .iter()
-            .find(|r| path.starts_with(r))
-            .cloned()
-            .or_else(|| path.parent().map(Path::to_path_buf)),
-    }
-}
-
-/// Directories from the file's parent upward, stopping at (and including)
-/// the containing workspace root; outside any root, just the parent chain
-/// bounded to a sane depth.
-fn ancestors_within_roots(path: &Path, roots: &[PathBuf]) -> Vec<PathBuf> {
-    let mut out = Vec::new();
-    let containing_root = roots.iter().find(|r| path.starts_with(r));
-    let mut dir = path.parent();
-    let mut depth = 0;
-    while let Some(d) = dir {
-        out.push(d.to_path_buf());
-        if let Some(root) = containing_root {
-            if d == root.as_path() {
-                break;
-            }
-        }
-        depth += 1;
-        if depth >= 64 {
-            break;
-        }
-        dir = d.parent();
-    }
-    out
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::sync::Arc;
+
     use nml_validate::test_support::{DEMO_CORE as CORE, DEMO_MANIFEST as MANIFEST, publish_demo};
 
     fn temp_ws(tag: &str) -> PathBuf {
@@ -1386,10 +529,10 @@ mod tests {
     /// return; most tests drop it (send failures are the contract's
     /// no-listener case).
     fn test_events() -> (
-        tokio::sync::mpsc::Sender<StoreEvent>,
-        tokio::sync::mpsc::Receiver<StoreEvent>,
+        std::sync::mpsc::SyncSender<StoreEvent>,
+        std::sync::mpsc::Receiver<StoreEvent>,
     ) {
-        tokio::sync::mpsc::channel(64)
+        std::sync::mpsc::sync_channel(64)
     }
 
     #[test]
@@ -2011,7 +1154,7 @@ mod tests {
         publish_demo(&Store::at(&store_base));
         let pkg_dir = store_base.join("schema-packages/demo");
         std::fs::write(pkg_dir.join("current"), "0.1.0+bad00000\nblake3:wrong\n").unwrap();
-        let (tx, mut rx) = test_events();
+        let (tx, rx) = test_events();
         let resolver = PackageResolver::new(Some(Store::at(&store_base)), tx);
         std::fs::create_dir_all(ws.join("proj")).unwrap();
         std::fs::write(
@@ -2067,7 +1210,7 @@ mod tests {
         .unwrap();
         std::fs::write(project.join("x.nml"), "").unwrap();
 
-        let (tx, mut rx) = test_events();
+        let (tx, rx) = test_events();
         let resolver = PackageResolver::new(Some(Store::at(&store_base)), tx);
         let roots = vec![ws.clone()];
         let view = WorkspaceView {
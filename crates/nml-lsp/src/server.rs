@@ -8,6 +8,7 @@ use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
 use nml_core::ast::*;
+use nml_core::import::ImportSet;
 use nml_core::model::{EnumDef, FieldDef, FieldType, ModelDef, OneOfDef};
 use nml_core::schema_index::{BodyShape, NameableVariant};
 use nml_core::span::Span;
@@ -16,9 +17,12 @@ use nml_core::{FieldTarget, SchemaIndex};
 use nml_validate::schema::MembershipSemantics;
 
 use crate::diagnostics::{self, SchemaMode};
+use crate::document::ParsedDocument;
 use crate::duration_lsp::{self, DurationUnitContext};
 use crate::packages::{self, Resolution, WorkspaceView};
 use crate::position::{self, LineIndex};
+use crate::type_hierarchy::{self, HierarchyType};
+use crate::workspace_symbols::IndexedSymbol;
 
 const MAX_DIR_DEPTH: usize = 20;
 const MAX_FILE_COUNT: usize = 10_000;
@@ -46,8 +50,25 @@ struct CachedDiagnostics {
     items: Arc<Vec<tower_lsp::lsp_types::Diagnostic>>,
 }
 
+/// One document's `import` closure and the text it was loaded for.
+struct CachedImports {
+    text: String,
+    /// `None`: the document imports nothing.
+    set: Option<Arc<ImportSet>>,
+}
+
 pub struct Inner {
     documents: Mutex<HashMap<Url, String>>,
+    /// The parse of each document's current text, shared by every feature
+    /// handler. Kept in step by incremental `didChange` (reparsing only the
+    /// edited declaration) and rebuilt lazily wherever a read finds its
+    /// text behind `documents` (disk updates, indexed files).
+    parsed: Mutex<HashMap<Url, Arc<ParsedDocument>>>,
+    /// Per-document `workspace/symbol` entries, built lazily from the
+    /// cached parse. Dropped per-document wherever its text changes
+    /// (`on_change`, close, watched-file delete) and wholesale when the
+    /// store-event drain reports an out-of-band store change.
+    workspace_symbols: Mutex<HashMap<Url, Arc<Vec<IndexedSymbol>>>>,
     /// Per-document diagnostics cache (RFC 0010 tier 1), filled lazily by
     /// whichever consumer computes first — the document pull or hover's
     /// explanation lookup — so hover never recomputes per-request and the
@@ -56,6 +77,10 @@ pub struct Inner {
     /// and project-config changes (a registry edit changes OTHER documents'
     /// diagnostics without touching their text).
     diags_cache: Mutex<HashMap<Url, CachedDiagnostics>>,
+    /// Per-document `import` closures, loaded lazily for validation.
+    /// Dropped by [`Inner::invalidate_importers`] when a file in a closure
+    /// changes.
+    import_sets: Mutex<HashMap<Url, CachedImports>>,
     indexed_uris: Mutex<HashSet<Url>>,
     /// Documents currently open in the editor (didOpen without a matching
     /// didClose). Guards watched-file disk events from clobbering an open
@@ -87,6 +112,11 @@ pub struct Inner {
     /// registered no such command must never receive an unexecutable action
     /// (negotiation, not assumption). `None` = no client support declared.
     explain_command: Mutex<Option<String>>,
+    /// The client-declared command id that opens a references list, from
+    /// `initializationOptions.showReferencesCommand`. Model-file code
+    /// lenses carry it with `[uri, position, locations]`; undeclared, the
+    /// lenses are plain labels. Negotiated exactly like `explain_command`.
+    show_references_command: Mutex<Option<String>>,
 }
 
 pub struct NmlLanguageServer {
@@ -100,7 +130,7 @@ pub struct NmlLanguageServer {
     /// cannot host one, and the store cache is stat-guarded so correctness
     /// never depended on a poll. Bounded + best-effort: on overflow the
     /// newest events drop (the first transition is the informative one).
-    store_events: Mutex<tokio::sync::mpsc::Receiver<packages::StoreEvent>>,
+    store_events: Mutex<std::sync::mpsc::Receiver<packages::StoreEvent>>,
 }
 
 impl std::ops::Deref for NmlLanguageServer {
@@ -186,12 +216,15 @@ impl NmlLanguageServer {
             membership,
             injected,
         } = cfg;
-        let (store_events_tx, store_events_rx) = tokio::sync::mpsc::channel(64);
+        let (store_events_tx, store_events_rx) = std::sync::mpsc::sync_channel(64);
         Self {
             client,
             inner: Arc::new(Inner {
                 documents: Mutex::new(HashMap::new()),
+                parsed: Mutex::new(HashMap::new()),
+                workspace_symbols: Mutex::new(HashMap::new()),
                 diags_cache: Mutex::new(HashMap::new()),
+                import_sets: Mutex::new(HashMap::new()),
                 indexed_uris: Mutex::new(HashSet::new()),
                 open_docs: Mutex::new(HashSet::new()),
                 scoped_models: Mutex::new(HashMap::new()),
@@ -208,6 +241,7 @@ impl NmlLanguageServer {
                 insert_replace_support: std::sync::atomic::AtomicBool::new(false),
                 label_details_support: std::sync::atomic::AtomicBool::new(false),
                 explain_command: Mutex::new(None),
+                show_references_command: Mutex::new(None),
             }),
             store_events: Mutex::new(store_events_rx),
         }
@@ -449,6 +483,7 @@ impl Inner {
             membership,
             uri_is_registry_source: false,
             load_pass_owns_composition: false,
+            imports: None,
         }
     }
 
@@ -477,6 +512,7 @@ impl Inner {
             membership,
             uri_is_registry_source: false,
             load_pass_owns_composition: false,
+            imports: None,
         }
     }
 
@@ -560,10 +596,59 @@ impl Inner {
         }
     }
 
+    /// The document's `import` closure, read through the open buffers
+    /// first; `None` for a document with no imports (or no file path).
+    /// Cached per document until its text or a file it reaches changes.
+    fn import_set_for(&self, uri: &Url, doc: &ParsedDocument) -> Option<Arc<ImportSet>> {
+        let text = doc.text();
+        if let Some(cached) = self
+            .import_sets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(uri)
+            .filter(|cached| cached.text == text)
+        {
+            return cached.set.clone();
+        }
+        let set = self.load_import_set(uri, doc.file()).map(Arc::new);
+        self.import_sets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                uri.clone(),
+                CachedImports {
+                    text: text.to_string(),
+                    set: set.clone(),
+                },
+            );
+        set
+    }
+
+    fn load_import_set(&self, uri: &Url, file: &nml_core::ast::File) -> Option<ImportSet> {
+        nml_core::import::imports_of(file).next()?;
+        let path = uri.to_file_path().ok()?;
+        let read = |p: &Path| -> std::io::Result<String> {
+            let buffered = Url::from_file_path(p).ok().and_then(|u| {
+                self.documents
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(&u)
+                    .cloned()
+            });
+            buffered.map_or_else(|| fs::read_to_string(p), Ok)
+        };
+        Some(ImportSet::load(&path, file, read))
+    }
+
     /// Full validation of one document: package-bound (exclusive validator +
     /// binding identity) when a package claims it, the scope-registry path
     /// otherwise, plus any degraded-state notes pinned to the top of file.
-    fn validate_document(&self, uri: &Url, text: &str) -> Vec<tower_lsp::lsp_types::Diagnostic> {
+    fn validate_document(
+        &self,
+        uri: &Url,
+        doc: &ParsedDocument,
+    ) -> Vec<tower_lsp::lsp_types::Diagnostic> {
+        let text = doc.text();
         let mut dc = self.diagnostic_config_for(uri);
         let resolved = self.resolve_document(uri);
         let bound = matches!(
@@ -665,11 +750,12 @@ impl Inner {
             (outcome, sources, own_name, owns_composition)
         });
         dc.load_pass_owns_composition = model_pass.as_ref().is_some_and(|(_, _, _, owns)| *owns);
+        dc.imports = self.import_set_for(uri, doc);
         let mut diags = match resolved.as_ref().map(|r| &r.resolution) {
             Some(Resolution::Bound(b)) => {
                 let identity = b.identity();
                 diagnostics::compute(
-                    text,
+                    doc,
                     &SchemaMode::Package {
                         validator: &b.validator,
                         identity,
@@ -681,7 +767,7 @@ impl Inner {
             _ => {
                 let (models, enums, oneofs) = self.models_for_file(uri);
                 diagnostics::compute(
-                    text,
+                    doc,
                     &SchemaMode::Registry {
                         models: &models,
                         enums: &enums,
@@ -797,12 +883,7 @@ impl NmlLanguageServer {
         &self,
         uri: &Url,
     ) -> Option<Arc<Vec<tower_lsp::lsp_types::Diagnostic>>> {
-        let text = self
-            .documents
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .get(uri)
-            .cloned()?;
+        let doc = self.parsed_document(uri)?;
         // Let the stat-guarded resolver notice out-of-band changes (a store
         // `schema sync`, a manifest edit on disk) — cheap when nothing
         // changed, and it advances the generation when something did.
@@ -814,11 +895,11 @@ impl NmlLanguageServer {
             .unwrap_or_else(|e| e.into_inner())
             .get(uri)
         {
-            if entry.text == text && entry.generation == generation {
+            if entry.text == doc.text() && entry.generation == generation {
                 return Some(Arc::clone(&entry.items));
             }
         }
-        let items = Arc::new(self.validate_document(uri, &text));
+        let items = Arc::new(self.validate_document(uri, &doc));
         // Store-health events queued during this resolution surface promptly
         // on whichever path computed (the drain's charter).
         self.drain_store_events().await;
@@ -828,7 +909,7 @@ impl NmlLanguageServer {
             .insert(
                 uri.clone(),
                 CachedDiagnostics {
-                    text,
+                    text: doc.text().to_string(),
                     generation,
                     items: Arc::clone(&items),
                 },
@@ -851,9 +932,18 @@ impl NmlLanguageServer {
 
     async fn drain_store_events(&self) {
         let events: Vec<packages::StoreEvent> = {
-            let mut rx = self.store_events.lock().unwrap_or_else(|e| e.into_inner());
+            let rx = self.store_events.lock().unwrap_or_else(|e| e.into_inner());
             std::iter::from_fn(|| rx.try_recv().ok()).collect()
         };
+        // Out-of-band store changes surface only through this drain: drop
+        // the symbol index so the next query rebuilds it from the current
+        // buffers rather than trusting entries built before the change.
+        if !events.is_empty() {
+            self.workspace_symbols
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+        }
         for ev in events {
             let level = if ev.warning {
                 MessageType::WARNING
@@ -864,6 +954,133 @@ impl NmlLanguageServer {
         }
     }
 
+    /// Drop the cached diagnostics and import closures of documents that
+    /// import `uri`, directly or through another file: what it declares
+    /// decides which of their names resolve. A cheap text filter on the
+    /// file name runs before any parse.
+    fn invalidate_importers(&self, uri: &Url) {
+        let Some(name) = uri
+            .to_file_path()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        else {
+            return;
+        };
+        let importers: Vec<Url> = {
+            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+            docs.iter()
+                .filter(|(u, text)| *u != uri && text.contains("import") && text.contains(&name))
+                .filter(|(u, text)| {
+                    let Ok(path) = u.to_file_path() else {
+                        return false;
+                    };
+                    let file = nml_core::cst::parse_best_effort(text);
+                    nml_core::import::imports_of(&file).any(|i| {
+                        Url::from_file_path(nml_core::import::resolve_path(&path, &i.path))
+                            .is_ok_and(|target| target == *uri)
+                    })
+                })
+                .map(|(u, _)| u.clone())
+                .collect()
+        };
+        let path = uri.to_file_path().ok();
+        let mut sets = self.import_sets.lock().unwrap_or_else(|e| e.into_inner());
+        let reaching: Vec<Url> = sets
+            .iter()
+            .filter(|(_, cached)| {
+                cached
+                    .set
+                    .as_ref()
+                    .zip(path.as_deref())
+                    .is_some_and(|(set, path)| set.file(path).is_some())
+            })
+            .map(|(u, _)| u.clone())
+            .collect();
+        let mut cache = self.diags_cache.lock().unwrap_or_else(|e| e.into_inner());
+        for importer in importers.into_iter().chain(reaching) {
+            sets.remove(&importer);
+            cache.remove(&importer);
+        }
+    }
+
+    /// The cached parse of `uri`'s current text, or a fresh full parse
+    /// (then cached) when the text moved on without one. The parse runs
+    /// with no lock held, so a large file never stalls other handlers, and
+    /// is cached only if its text is still current — a newer version's
+    /// parse is never overwritten. Lock order: `documents` before `parsed`.
+    fn parsed_document(&self, uri: &Url) -> Option<Arc<ParsedDocument>> {
+        let text = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(uri)?
+            .clone();
+        if let Some(doc) = self
+            .parsed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(uri)
+            .filter(|doc| doc.text() == text)
+        {
+            return Some(Arc::clone(doc));
+        }
+        let doc = Arc::new(ParsedDocument::new(text, None));
+        let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+        if docs.get(uri).map(String::as_str) == Some(doc.text()) {
+            self.parsed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(uri.clone(), Arc::clone(&doc));
+        }
+        Some(doc)
+    }
+
+    /// Every open document's cached parse, in no particular order.
+    fn parsed_documents(&self) -> Vec<Arc<ParsedDocument>> {
+        let uris: Vec<Url> = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        uris.iter()
+            .filter_map(|uri| self.parsed_document(uri))
+            .collect()
+    }
+
+    /// `uri`'s workspace symbols, from the index or built from its parse.
+    fn symbols_for(&self, uri: &Url) -> Option<Arc<Vec<IndexedSymbol>>> {
+        if let Some(symbols) = self
+            .workspace_symbols
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(uri)
+        {
+            return Some(Arc::clone(symbols));
+        }
+        let doc = self.parsed_document(uri)?;
+        let symbols = Arc::new(crate::workspace_symbols::file_symbols(
+            doc.file(),
+            doc.text(),
+        ));
+        self.workspace_symbols
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(uri.clone(), Arc::clone(&symbols));
+        Some(symbols)
+    }
+
+    /// Install an editor-supplied version of `uri`, parse included.
+    fn on_document_change(&self, uri: Url, doc: ParsedDocument) {
+        let text = doc.text().to_string();
+        self.parsed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(uri.clone(), Arc::new(doc));
+        self.on_change(uri, text);
+    }
+
     /// Update server state for a changed document. Diagnostics are NOT pushed:
     /// under the pull model (RFC 0035) the client re-pulls this document (a
     /// `didChange` triggers a document pull) and re-pulls dependents when they
@@ -874,6 +1091,10 @@ impl NmlLanguageServer {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .insert(uri.clone(), text.clone());
+        self.workspace_symbols
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&uri);
         // This document's cached diagnostics are stale (text changed). The
         // project-config and registry branches below clear wholesale — those
         // changes affect every document.
@@ -881,6 +1102,7 @@ impl NmlLanguageServer {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .remove(&uri);
+        self.invalidate_importers(&uri);
 
         // Segment-anchored: `foo-nml-project.nml` is an ordinary document,
         // not project config — a bare suffix match would let it clobber the
@@ -958,6 +1180,106 @@ impl Inner {
         None
     }
 
+    /// The index a type hierarchy for `uri` walks, plus the binding when a
+    /// package claims the document — the binding is what locates the
+    /// package's definitions on disk. Same exclusivity as
+    /// [`Self::schema_index_for`]: a bound document never sees workspace
+    /// models.
+    fn hierarchy_scope(&self, uri: &Url) -> (IndexHandle, Option<Box<packages::Binding>>) {
+        match self.resolve_document(uri).map(|r| r.resolution) {
+            Some(Resolution::Bound(b)) => (IndexHandle::Bound(b.validator.clone()), Some(b)),
+            _ => {
+                let (models, enums, oneofs) = self.models_for_file(uri);
+                let index = SchemaIndex::build(models, enums, oneofs);
+                (IndexHandle::Registry(Box::new(index)), None)
+            }
+        }
+    }
+
+    /// `ty` as a hierarchy item. A bound package's definition is found in
+    /// the schema file its manifest names (workspace or store slot); a
+    /// registry definition in the workspace's `.model.nml` files, `origin`'s
+    /// own file and scope first. `origin` rides along in `data` so the
+    /// follow-up supertypes/subtypes requests resolve in the same scope.
+    /// `None` when the definition has no file to point at.
+    fn type_hierarchy_item(
+        &self,
+        docs: &HashMap<Url, String>,
+        origin: &Url,
+        binding: Option<&packages::Binding>,
+        ty: HierarchyType<'_>,
+    ) -> Option<TypeHierarchyItem> {
+        let candidates: Vec<(Url, String)> = match binding {
+            Some(binding) => {
+                let path = self.resolver.source_path(binding, ty.source()?)?;
+                doc_or_disk(docs, &path).into_iter().collect()
+            }
+            None => {
+                let file_scope = extract_file_scope(origin.as_str());
+                let mut uris: Vec<&Url> = docs
+                    .keys()
+                    .filter(|u| u.as_str().ends_with(".model.nml"))
+                    .collect();
+                uris.sort_by_key(|u| {
+                    let rank = if *u == origin {
+                        0
+                    } else if file_scope.as_deref() == Some(&extract_schema_scope(u.as_str())) {
+                        1
+                    } else {
+                        2
+                    };
+                    (rank, u.as_str())
+                });
+                uris.into_iter()
+                    .map(|u| (u.clone(), docs[u].clone()))
+                    .collect()
+            }
+        };
+        candidates.into_iter().find_map(|(uri, text)| {
+            let file = nml_core::cst::parse_best_effort(&text);
+            let (range, selection_range) = type_hierarchy::declaration_ranges(&file, &text, ty)?;
+            Some(TypeHierarchyItem {
+                name: ty.name().to_string(),
+                kind: ty.kind(),
+                tags: None,
+                detail: Some(ty.detail()),
+                uri,
+                range,
+                selection_range,
+                data: Some(serde_json::json!({ "origin": origin })),
+            })
+        })
+    }
+
+    /// The items `related` reaches from `item`, resolved in the scope of the
+    /// document the hierarchy was prepared from.
+    fn type_hierarchy_related(
+        &self,
+        item: &TypeHierarchyItem,
+        related: for<'i> fn(&'i SchemaIndex, HierarchyType<'_>) -> Vec<HierarchyType<'i>>,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let origin = item
+            .data
+            .as_ref()
+            .and_then(|d| d.get("origin"))
+            .and_then(|o| o.as_str())
+            .and_then(|o| Url::parse(o).ok())
+            .unwrap_or_else(|| item.uri.clone());
+        let (index, binding) = self.hierarchy_scope(&origin);
+        let ty = type_hierarchy::lookup(index.index(), &item.name)?;
+        let docs = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        Some(
+            related(index.index(), ty)
+                .into_iter()
+                .filter_map(|t| self.type_hierarchy_item(&docs, &origin, binding.as_deref(), t))
+                .collect(),
+        )
+    }
+
     fn find_tagged_ref_definition(&self, role_ref: &str) -> Option<Location> {
         let docs: HashMap<Url, String> = self
             .documents
@@ -1001,6 +1323,7 @@ impl Inner {
                     DeclarationKind::OneOf(o) => {
                         names.push((o.name.name.clone(), "oneof".into()));
                     }
+                    DeclarationKind::Import(_) => {}
                 }
             }
         }
@@ -1277,6 +1600,42 @@ fn find_enclosing_block_keyword(
 
 // ── Definition resolution ─────────────────────────────────────
 
+/// The text of `path`: its open (or indexed) buffer, else the disk.
+fn doc_or_disk(docs: &HashMap<Url, String>, path: &Path) -> Option<(Url, String)> {
+    let uri = Url::from_file_path(path).ok()?;
+    let text = docs
+        .get(&uri)
+        .cloned()
+        .or_else(|| fs::read_to_string(path).ok())?;
+    Some((uri, text))
+}
+
+/// The files `current_uri`'s `import`s name, with each import and the
+/// imported file's text. Unreadable targets are skipped (the diagnostics
+/// report them).
+fn imported_docs(docs: &HashMap<Url, String>, current_uri: &Url) -> Vec<(ImportDecl, Url, String)> {
+    let (Some(source), Ok(path)) = (docs.get(current_uri), current_uri.to_file_path()) else {
+        return Vec::new();
+    };
+    let file = nml_core::cst::parse_best_effort(source);
+    nml_core::import::imports_of(&file)
+        .filter_map(|import| {
+            let target = nml_core::import::resolve_path(&path, &import.path);
+            let (uri, text) = doc_or_disk(docs, &target)?;
+            Some((import.clone(), uri, text))
+        })
+        .collect()
+}
+
+/// The file an `import` path literal at `offset` names.
+fn import_target_at(source: &str, offset: usize, current_uri: &Url) -> Option<Url> {
+    let path = current_uri.to_file_path().ok()?;
+    let file = nml_core::cst::parse_best_effort(source);
+    let import = nml_core::import::imports_of(&file)
+        .find(|i| i.path_span.start <= offset && offset < i.path_span.end)?;
+    Url::from_file_path(nml_core::import::resolve_path(&path, &import.path)).ok()
+}
+
 fn find_definition_in_docs(
     docs: &HashMap<Url, String>,
     name: &str,
@@ -1349,7 +1708,24 @@ fn find_definition_in_docs(
         }
     }
 
-    // Priority 4: Top-level declarations in other files
+    // Priority 4: Declarations the current file imports (whole-file imports,
+    // or a selection naming it) — read from disk when not open.
+    for (import, uri, source) in imported_docs(docs, current_uri) {
+        let selected = import
+            .names
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|n| n.name == name));
+        if !selected {
+            continue;
+        }
+        let file = nml_core::cst::parse_best_effort(&source);
+        let line_index = LineIndex::new(&source);
+        if let Some(range) = find_top_level_decl(&file, name, &line_index) {
+            return Some((uri, range));
+        }
+    }
+
+    // Priority 5: Top-level declarations in other files
     for (uri, source) in docs.iter() {
         if uri == current_uri {
             continue;
@@ -1460,6 +1836,8 @@ fn find_top_level_decl(file: &File, name: &str, line_index: &LineIndex) -> Optio
                     return Some(span_to_range(o.name.span, line_index));
                 }
             }
+            // An import's selection names declarations of another file.
+            DeclarationKind::Import(_) => {}
         }
     }
     None
@@ -1501,6 +1879,8 @@ fn find_name_in_file(file: &File, name: &str, line_index: &LineIndex) -> Option<
                     return Some(span_to_range(o.name.span, line_index));
                 }
             }
+            // An import's selection names declarations of another file.
+            DeclarationKind::Import(_) => {}
         }
     }
     None
@@ -1739,6 +2119,16 @@ fn build_document_symbols(file: &File, line_index: &LineIndex) -> Vec<DocumentSy
                     arms,
                 ));
             }
+            DeclarationKind::Import(import) => {
+                symbols.push(document_symbol(
+                    import.path.clone(),
+                    Some("import".into()),
+                    SymbolKind::MODULE,
+                    span_to_range(decl.span, line_index),
+                    span_to_range(import.path_span, line_index),
+                    Vec::new(),
+                ));
+            }
         }
     }
     symbols
@@ -1905,6 +2295,14 @@ fn collect_references(file: &File, name: &str, line_index: &LineIndex, ranges: &
                     }
                 }
             }
+            // A selective import names the declarations it brings in.
+            DeclarationKind::Import(import) => {
+                for selected in import.names.iter().flatten() {
+                    if selected.name == name {
+                        ranges.push(span_to_range(selected.span, line_index));
+                    }
+                }
+            }
         }
     }
 }
@@ -2097,7 +2495,7 @@ fn find_model_ref_types_at(
                     out.push(name.clone());
                 }
             }
-            FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
+            FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
                 ref_names_of(inner, index, out)
             }
             FieldType::Union(members) => {
@@ -2170,7 +2568,7 @@ fn find_value_completions_at(
                     out.extend(e.variants.iter().cloned());
                 }
             }
-            FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
+            FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
                 variants_of(inner, index, out)
             }
             FieldType::Union(members) => {
@@ -2203,7 +2601,7 @@ pub(crate) fn governs_duration(ty: &FieldType) -> bool {
             ty: nml_core::types::PrimitiveType::Duration,
             ..
         } => true,
-        FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
+        FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
             governs_duration(inner)
         }
         FieldType::Union(members) => members.iter().any(governs_duration),
@@ -2212,6 +2610,78 @@ pub(crate) fn governs_duration(ty: &FieldType) -> bool {
     }
 }
 
+/// The currencies a money value position admits: the union of the
+/// governing `money` fields' `currency` facets. `None` when no money field
+/// governs the position, or when one is unrestricted — completion offers
+/// only codes the validator would accept, and an open field has no list to
+/// narrow to.
+pub(crate) fn governed_currencies(fields: &[&FieldDef]) -> Option<Vec<String>> {
+    fn collect(ty: &FieldType, out: &mut Vec<String>, open: &mut bool) {
+        match ty {
+            FieldType::Primitive {
+                ty: nml_core::types::PrimitiveType::Money,
+                facets,
+            } => match facets {
+                nml_core::model::PrimitiveFacets::Money(fs) => {
+                    for code in fs.currency.iter().flat_map(|c| &c.codes) {
+                        if !out.contains(code) {
+                            out.push(code.clone());
+                        }
+                    }
+                }
+                _ => *open = true,
+            },
+            FieldType::List(inner, _) | FieldType::Set(inner, _) | FieldType::Modifier(inner) => {
+                collect(inner, out, open)
+            }
+            FieldType::Union(members) => {
+                for m in members {
+                    collect(m, out, open);
+                }
+            }
+            FieldType::Arms { target, .. } => collect(target, out, open),
+            _ => {}
+        }
+    }
+    let mut codes = Vec::new();
+    let mut open = false;
+    for f in fields {
+        collect(&f.field_type, &mut codes, &mut open);
+    }
+    (!open && !codes.is_empty()).then_some(codes)
+}
+
+/// The currency slot of a money literal at `pos` (`price = 19.99 U⌖`):
+/// the insert range covers the typed code letters up to the cursor, the
+/// replace range the whole letter run. `None` unless the value so far is
+/// a bare amount followed by whitespace and an optional partial code.
+fn currency_edit_ranges(source: &str, pos: Position) -> Option<(Range, Range)> {
+    let line = position::line_at(source, pos.line)?;
+    let cursor = position::utf16_to_byte(line, pos.character);
+    let eq = line[..cursor].find('=')?;
+    let value = line[eq + 1..cursor].trim_start();
+    let amount_len = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '_' | '-')))
+        .unwrap_or(value.len());
+    let rest = &value[amount_len..];
+    let code = rest.trim_start();
+    if amount_len == 0 || code.len() == rest.len() || !code.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    let code_start = cursor - code.len();
+    let code_end = cursor
+        + line[cursor..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(line.len() - cursor);
+    let col = |b: usize| position::byte_to_utf16(line, b);
+    let start = Position::new(pos.line, col(code_start));
+    Some((
+        Range::new(start, pos),
+        Range::new(start, Position::new(pos.line, col(code_end))),
+    ))
+}
+
 // ── Schema-driven field completion (RFC 0003) ─────────────────────────────────
 
 /// Resolve the model whose fields are valid at the cursor's body, **and that body** (so the
@@ -2397,7 +2867,7 @@ fn find_union_list_field_at<'i>(
         FieldType::Modifier(inner) => inner.as_ref(),
         t => t,
     };
-    if let FieldType::List(inner) | FieldType::Set(inner) = base {
+    if let FieldType::List(inner, _) | FieldType::Set(inner, _) = base {
         // An item body the cursor sits strictly INSIDE (below its header):
         // descend through the item's body-aware resolved variant, so a union
         // list nested in a `[]model` item — or in another union's item — is
@@ -2979,7 +3449,7 @@ fn descend_to_cursor<'i, 'f>(
                     FieldType::Modifier(inner) => inner.as_ref(),
                     t => t,
                 };
-                let (FieldType::List(elem_ty) | FieldType::Set(elem_ty)) = base else {
+                let (FieldType::List(elem_ty, _) | FieldType::Set(elem_ty, _)) = base else {
                     return None;
                 };
                 let item_idx =
@@ -3407,11 +3877,9 @@ fn present_field_names_in(model: &ModelDef, body: &Body) -> HashSet<String> {
         .iter()
         .any(|e| matches!(e.kind, BodyEntryKind::ListItem(_)))
     {
-        if let Some(field) = model
-            .fields
-            .iter()
-            .find(|f| f.shorthand && matches!(f.field_type, FieldType::List(_) | FieldType::Set(_)))
-        {
+        if let Some(field) = model.fields.iter().find(|f| {
+            f.shorthand && matches!(f.field_type, FieldType::List(_, _) | FieldType::Set(_, _))
+        }) {
             present.insert(field.name.clone());
         }
     }
@@ -3737,10 +4205,11 @@ struct SimplifyNumber {
     new_text: String,
 }
 
-fn simplify_number_action(source: &str, offset: usize) -> Option<SimplifyNumber> {
-    let root = nml_core::cst::parse(source).syntax();
+fn simplify_number_action(parse: &nml_core::cst::Parse, offset: usize) -> Option<SimplifyNumber> {
+    let root = parse.syntax();
+    let len = usize::from(root.text_range().end());
     let tok = root
-        .token_at_offset((offset.min(source.len()) as u32).into())
+        .token_at_offset((offset.min(len) as u32).into())
         .find(|t| t.kind() == nml_core::cst::SyntaxKind::Number)?;
     // Money literals (`19.90 USD`) are Number + currency Ident inside one
     // Value node. Duration literals wrap in `DurationLiteral`. Simplifying
@@ -4313,32 +4782,12 @@ fn project_file_insertion(text: &str, edit: &ProjectEdit) -> Option<String> {
         nml_core::ProjectConfig::from_file(&file)
     };
     match edit {
-        ProjectEdit::Pin(name) => {
-            if config.schema_packages.iter().any(|p| p == name) {
-                return None;
-            }
-            // Append to the `schemaPackages:` block nested under the `project`
-            // block; failing that (no such nested block yet), create it (with
-            // its first item) directly under the `project <Name>:` header —
-            // the same shapes the plain-text writer produced, now
-            // indentation-adaptive and comment-safe. Path addressing means a
-            // `schemaPackages:` under some other top-level block can never
-            // receive the pin, and duplicates refuse rather than misdirect.
-            insert_entry_at_path(
-                text,
-                &["project", "schemaPackages"],
-                &format!("- {name}"),
-                EntryPosition::Last,
-            )
-            .or_else(|| {
-                insert_entry_at_path(
-                    text,
-                    &["project"],
-                    &format!("schemaPackages:\n    - {name}"),
-                    EntryPosition::AfterHeader,
-                )
-            })
-        }
+        // Appended to the `schemaPackages:` block nested under the `project`
+        // block, or created with its first item under the header. Path
+        // addressing means a `schemaPackages:` under some other top-level
+        // block can never receive the pin, and duplicates refuse rather than
+        // misdirect. Shared with `nml init`.
+        ProjectEdit::Pin(name) => nml_core::project::insert_pin(text, name),
         ProjectEdit::OptOut => {
             // `auto_associate` defaults true; a `false` already present means
             // the opt-out is redundant.
@@ -4392,6 +4841,16 @@ impl LanguageServer for NmlLanguageServer {
             .and_then(|c| c.as_str())
             .filter(|c| !c.is_empty())
             .map(str::to_string);
+        *self
+            .show_references_command
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = params
+            .initialization_options
+            .as_ref()
+            .and_then(|o| o.get("showReferencesCommand"))
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+            .map(str::to_string);
         let roots: Vec<Url> = params
             .workspace_folders
             .as_ref()
@@ -4419,7 +4878,7 @@ impl LanguageServer for NmlLanguageServer {
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 completion_provider: Some(CompletionOptions {
                     trigger_characters: Some(vec![
@@ -4474,6 +4933,11 @@ impl LanguageServer for NmlLanguageServer {
                 position_encoding: Some(PositionEncodingKind::UTF16),
                 inlay_hint_provider: Some(OneOf::Left(true)),
                 selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
                 semantic_tokens_provider: Some(crate::semantic_tokens::server_capabilities()),
                 ..Default::default()
             },
@@ -4501,7 +4965,29 @@ impl LanguageServer for NmlLanguageServer {
                     .unwrap_or_default(),
                 ),
             };
-            let _ = self.client.register_capability(vec![registration]).await;
+            // lsp-types 0.94 has no static `typeHierarchyProvider` field, so
+            // the type hierarchy (LSP 3.17) is offered by registration.
+            let type_hierarchy = Registration {
+                id: "nml-type-hierarchy".to_string(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+                register_options: Some(
+                    serde_json::to_value(TypeHierarchyRegistrationOptions {
+                        text_document_registration_options: TextDocumentRegistrationOptions {
+                            document_selector: Some(vec![DocumentFilter {
+                                language: None,
+                                scheme: Some("file".to_string()),
+                                pattern: Some("**/*.nml".to_string()),
+                            }]),
+                        },
+                        ..Default::default()
+                    })
+                    .unwrap_or_default(),
+                ),
+            };
+            let _ = self
+                .client
+                .register_capability(vec![registration, type_hierarchy])
+                .await;
         }
         self.client
             .log_message(MessageType::INFO, "NML language server initialized")
@@ -4561,13 +5047,24 @@ impl LanguageServer for NmlLanguageServer {
             .insert(params.text_document.uri.clone());
         // State only; the client pulls this document's diagnostics (didOpen
         // triggers a pull under the diagnostic-provider capability).
-        self.on_change(params.text_document.uri, params.text_document.text);
+        let doc = ParsedDocument::new(
+            params.text_document.text,
+            Some(params.text_document.version),
+        );
+        self.on_document_change(params.text_document.uri, doc);
     }
 
+    /// Incremental sync: each ranged change is spliced into the stored
+    /// text and reparsed from the previous parse, one declaration at a time.
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        if let Some(change) = params.content_changes.into_iter().last() {
-            self.on_change(params.text_document.uri, change.text);
-        }
+        let uri = params.text_document.uri;
+        let doc = match self.parsed_document(&uri) {
+            Some(prev) => prev.apply_changes(params.content_changes, params.text_document.version),
+            // Never opened: only a full-text change carries usable content.
+            None => ParsedDocument::new(String::new(), None)
+                .apply_changes(params.content_changes, params.text_document.version),
+        };
+        self.on_document_change(uri, doc);
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -4576,6 +5073,14 @@ impl LanguageServer for NmlLanguageServer {
             .lock()
             .unwrap_or_else(|e| e.into_inner())
             .remove(&uri);
+        self.parsed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&uri);
+        self.workspace_symbols
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&uri);
         self.diags_cache
             .lock()
             .unwrap_or_else(|e| e.into_inner())
@@ -4591,6 +5096,8 @@ impl LanguageServer for NmlLanguageServer {
                 .lock()
                 .unwrap_or_else(|e| e.into_inner())
                 .remove(&uri);
+            // Importers now read the file from disk.
+            self.invalidate_importers(&uri);
         }
 
         if was_model {
@@ -4694,10 +5201,19 @@ impl LanguageServer for NmlLanguageServer {
                         .lock()
                         .unwrap_or_else(|e| e.into_inner())
                         .remove(&change.uri);
+                    self.parsed
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .remove(&change.uri);
+                    self.workspace_symbols
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .remove(&change.uri);
                     self.diags_cache
                         .lock()
                         .unwrap_or_else(|e| e.into_inner())
                         .remove(&change.uri);
+                    self.invalidate_importers(&change.uri);
                     self.indexed_uris
                         .lock()
                         .unwrap_or_else(|e| e.into_inner())
@@ -4813,38 +5329,41 @@ impl LanguageServer for NmlLanguageServer {
             // than re-cloning it per detector. A package-bound document (RFC
             // 0030) completes against its package's exclusive definitions —
             // the same exclusivity rule diagnostics apply.
-            let (model_ref_types, discriminator_values, value_completions, duration_context): (
+            let (
+                model_ref_types,
+                discriminator_values,
+                value_completions,
+                duration_context,
+                currency_context,
+            ): (
                 Vec<String>,
                 Option<Vec<String>>,
                 Option<ValueCompletions>,
                 Option<DurationUnitContext>,
+                Option<(Vec<String>, (Range, Range))>,
             ) = {
-                let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-                match docs.get(&uri).map(|source| {
-                    let (file, parse) = nml_core::cst::parse_best_effort_with_tree(source);
-                    (source, file, parse)
-                }) {
-                    // Parse once and build one schema index, shared by all detectors.
-                    Some((source, file, parse)) => {
+                match self.parsed_document(&uri) {
+                    // One cached parse and one schema index, shared by all detectors.
+                    Some(doc) => {
+                        let (source, file, parse) = (doc.text(), doc.file(), doc.parse());
                         let index: &SchemaIndex = handle.index();
                         let line_index = LineIndex::new(source);
                         let model_refs =
-                            find_model_ref_types_at(&file, source, pos, index, &line_index);
+                            find_model_ref_types_at(file, source, pos, index, &line_index);
                         let discriminator =
-                            find_oneof_discriminator_at(&file, source, pos, index, &line_index)
-                                .map(|o| {
-                                    o.variants.iter().map(|(value, _)| value.clone()).collect()
-                                });
+                            find_oneof_discriminator_at(file, source, pos, index, &line_index).map(
+                                |o| o.variants.iter().map(|(value, _)| value.clone()).collect(),
+                            );
                         let values =
-                            find_value_completions_at(&file, source, pos, index, &line_index);
+                            find_value_completions_at(file, source, pos, index, &line_index);
                         let duration = duration_lsp::find_duration_unit_completions_at(
-                            &parse,
+                            parse,
                             pos,
                             &line_index,
                             || {
                                 value_position_prop_name(source, pos)
                                     .map(|prop| {
-                                        value_governors_at(&file, pos, index, &line_index, prop)
+                                        value_governors_at(file, pos, index, &line_index, prop)
                                             .fields
                                             .iter()
                                             .any(|f| governs_duration(&f.field_type))
@@ -4852,9 +5371,17 @@ impl LanguageServer for NmlLanguageServer {
                                     .unwrap_or(false)
                             },
                         );
-                        (model_refs, discriminator, values, duration)
+                        // Money fields restricted by a `currency` facet
+                        // complete the code after the amount — only the
+                        // admitted codes, so the pick cannot be rejected.
+                        let currency = currency_edit_ranges(source, pos).and_then(|ranges| {
+                            let prop = value_position_prop_name(source, pos)?;
+                            let governors = value_governors_at(file, pos, index, &line_index, prop);
+                            governed_currencies(&governors.fields).map(|codes| (codes, ranges))
+                        });
+                        (model_refs, discriminator, values, duration, currency)
                     }
-                    None => (Vec::new(), None, None, None),
+                    None => (Vec::new(), None, None, None, None),
                 }
             };
 
@@ -4907,6 +5434,31 @@ impl LanguageServer for NmlLanguageServer {
                 }
             }
 
+            if let Some((codes, (insert, replace))) = currency_context {
+                for (i, code) in codes.iter().enumerate() {
+                    let text_edit = if insert_replace {
+                        CompletionTextEdit::InsertAndReplace(InsertReplaceEdit {
+                            new_text: code.clone(),
+                            insert,
+                            replace,
+                        })
+                    } else {
+                        CompletionTextEdit::Edit(TextEdit {
+                            range: replace,
+                            new_text: code.clone(),
+                        })
+                    };
+                    items.push(CompletionItem {
+                        label: code.clone(),
+                        kind: Some(CompletionItemKind::UNIT),
+                        detail: Some("allowed currency".to_string()),
+                        sort_text: Some(format!("0_{i:03}")),
+                        text_edit: Some(text_edit),
+                        ..Default::default()
+                    });
+                }
+            }
+
             // Inside a `oneof` block, offer the arm keys as discriminator values.
             if let Some(values) = discriminator_values {
                 for (i, value) in values.iter().enumerate() {
@@ -4953,9 +5505,11 @@ impl LanguageServer for NmlLanguageServer {
             // Property position (no `=` before the cursor): schema-driven FIELD completion
             // (RFC 0003) — the dual of the value-position completions above. Offer the
             // enclosing model's not-yet-present fields, type-aware insertion, required-first.
+            let doc = self.parsed_document(&uri);
             let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            if let Some(source) = docs.get(&uri) {
-                let file = nml_core::cst::parse_best_effort(source);
+            if let Some(doc) = &doc {
+                let source = doc.text();
+                let file = doc.file();
                 let index: &SchemaIndex = handle.index();
                 let line_index = LineIndex::new(source);
                 let line_ctx = position::line_at(source, pos.line).map(|line| {
@@ -4967,7 +5521,7 @@ impl LanguageServer for NmlLanguageServer {
                 // `@keyword/name` references for model-keyed K, and `else`.
                 if let Some((line, end)) = line_ctx {
                     if !cursor_past_arm_arrow(line, end) {
-                        if let Some(key) = find_arm_set_key_at(&file, pos, index, &line_index) {
+                        if let Some(key) = find_arm_set_key_at(file, pos, index, &line_index) {
                             let tagged_refs = collect_tagged_ref_candidates(&docs);
                             let selector_items =
                                 arm_selector_completion_items(&key, index, &tagged_refs);
@@ -4981,7 +5535,7 @@ impl LanguageServer for NmlLanguageServer {
                 if let Some((line, end)) = line_ctx {
                     if cursor_past_arm_arrow(line, end) {
                         if let Some(target_keywords) =
-                            find_arm_target_types_at(&file, pos, index, &line_index)
+                            find_arm_target_types_at(file, pos, index, &line_index)
                         {
                             if let Some(snippet) =
                                 inline_arm_target_snippet_item(&target_keywords, index)
@@ -5035,10 +5589,10 @@ impl LanguageServer for NmlLanguageServer {
                     // list field (the mid-typing item need not parse, and the
                     // full descent would fail on a list-of-union).
                     let field = match &slot {
-                        AsSlot::Field(name) => find_model_body_at(&file, pos, index, &line_index)
+                        AsSlot::Field(name) => find_model_body_at(file, pos, index, &line_index)
                             .and_then(|(model, _)| model.fields.iter().find(|f| f.name == *name)),
                         AsSlot::Item => {
-                            enclosing_top_block(&file, pos, &line_index).and_then(|block| {
+                            enclosing_top_block(file, pos, &line_index).and_then(|block| {
                                 let Some(FieldTarget::Model(model)) =
                                     index.resolve_ref(&block.keyword.name)
                                 else {
@@ -5064,7 +5618,7 @@ impl LanguageServer for NmlLanguageServer {
                                 t => t,
                             };
                             let variants = base.union_variants().or_else(|| match base {
-                                FieldType::List(inner) | FieldType::Set(inner) => {
+                                FieldType::List(inner, _) | FieldType::Set(inner, _) => {
                                     inner.union_variants()
                                 }
                                 _ => None,
@@ -5088,7 +5642,7 @@ impl LanguageServer for NmlLanguageServer {
                     }
                     return Ok(Some(CompletionResponse::Array(items)));
                 }
-                match find_candidates_at(&file, pos, index, &line_index) {
+                match find_candidates_at(file, pos, index, &line_index) {
                     Some(DescentTarget::One {
                         model,
                         body,
@@ -5191,9 +5745,8 @@ impl LanguageServer for NmlLanguageServer {
                     .chain(index.oneofs().iter().map(|o| o.name.clone()))
                     .collect();
                 if matches!(handle, IndexHandle::Registry(_)) {
-                    let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-                    if let Some(source) = docs.get(&uri) {
-                        let (own, _) = nml_core::cst::extract_schema(source);
+                    if let Some(doc) = self.parsed_document(&uri) {
+                        let (_, own, _) = nml_core::cst::extract_parsed(doc.text(), doc.parse());
                         names.extend(
                             own.models
                                 .iter()
@@ -5231,10 +5784,8 @@ impl LanguageServer for NmlLanguageServer {
             }
             drop(pc);
 
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            for source in docs.values() {
-                let file = nml_core::cst::parse_best_effort(source);
-                for decl in &file.declarations {
+            for doc in self.parsed_documents() {
+                for decl in &doc.file().declarations {
                     if let nml_core::ast::DeclarationKind::Block(block) = &decl.kind {
                         let kw = &block.keyword.name;
                         if seen.insert(kw.clone()) {
@@ -5263,11 +5814,9 @@ impl LanguageServer for NmlLanguageServer {
 
         {
             let member_kws = &self.membership.member_keywords;
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
             let mut seen_refs = HashSet::new();
-            for source in docs.values() {
-                let file = nml_core::cst::parse_best_effort(source);
-                for decl in &file.declarations {
+            for doc in self.parsed_documents() {
+                for decl in &doc.file().declarations {
                     if let DeclarationKind::Block(block) = &decl.kind {
                         let kw = &block.keyword.name;
                         let name = &block.name.name;
@@ -5301,14 +5850,10 @@ impl LanguageServer for NmlLanguageServer {
         let uri = params.text_document.uri;
         let mut actions: Vec<CodeActionOrCommand> = Vec::new();
 
-        let source = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            docs.get(&uri).cloned()
-        };
-        let Some(source) = source else {
+        let Some(doc) = self.parsed_document(&uri) else {
             return Ok(None);
         };
-        let line_index = LineIndex::new(&source);
+        let line_index = LineIndex::new(doc.text());
 
         // 1. Machine-applicable suggestions the validator derived — never
         //    re-derived, never parsed out of message text.
@@ -5395,7 +5940,8 @@ impl LanguageServer for NmlLanguageServer {
         //    preserves written scale. Refactor-kind, never a quickfix:
         //    authored precision like `2.50` is intent until the author
         //    says otherwise, so nothing auto-applies.
-        if let Some(action) = simplify_number_action(&source, line_index.offset(params.range.start))
+        if let Some(action) =
+            simplify_number_action(doc.parse(), line_index.offset(params.range.start))
         {
             let edit = TextEdit {
                 range: line_index.range(action.span),
@@ -5510,25 +6056,22 @@ impl LanguageServer for NmlLanguageServer {
                 }
             }
 
-            let source_clone = {
-                let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-                match docs.get(&uri) {
-                    Some(s) => s.clone(),
-                    None => return Ok(None),
-                }
+            let Some(doc) = self.parsed_document(&uri) else {
+                return Ok(None);
             };
+            let source = doc.text();
 
-            let Some(line) = position::line_at(&source_clone, pos.line) else {
+            let Some(line) = position::line_at(source, pos.line) else {
                 return Ok(None);
             };
             let byte_col = position::utf16_to_byte(line, pos.character);
 
-            // One lex+parse for every hover surface: the CST feeds the
+            // One cached parse for every hover surface: the CST feeds the
             // duration query, the lowered AST feeds the field-hover walk.
-            let (file, parse) = nml_core::cst::parse_best_effort_with_tree(&source_clone);
-            let line_index = LineIndex::new(&source_clone);
+            let (file, parse) = (doc.file(), doc.parse());
+            let line_index = LineIndex::new(source);
             if let Some(hover) =
-                duration_lsp::duration_hover(&parse, &source_clone, pos, &line_index)
+                duration_lsp::duration_hover(parse, source, pos, &line_index)
             {
                 return Ok(Some(hover));
             }
@@ -5587,7 +6130,7 @@ impl LanguageServer for NmlLanguageServer {
             let is_prop = is_property_name_position(line, &word, byte_col);
 
             if is_prop && !word.is_empty() {
-                if let Some(keyword) = find_enclosing_block_keyword(&file, pos, &line_index) {
+                if let Some(keyword) = find_enclosing_block_keyword(file, pos, &line_index) {
                     let handle = self.schema_index_for(&uri);
                     if let Some(model) = handle.index().model(&keyword) {
                         if let Some(field) = model.fields.iter().find(|f| f.name == word) {
@@ -5657,7 +6200,7 @@ impl LanguageServer for NmlLanguageServer {
             if !word.is_empty() {
                 let model_ref_types = if !is_prop {
                     let handle = self.schema_index_for(&uri);
-                    find_model_ref_types_at(&file, &source_clone, pos, handle.index(), &line_index)
+                    find_model_ref_types_at(file, source, pos, handle.index(), &line_index)
                 } else {
                     Vec::new()
                 };
@@ -5689,10 +6232,18 @@ impl LanguageServer for NmlLanguageServer {
         let uri = params.text_document_position_params.text_document.uri;
 
         let (word, enclosing_keyword, is_prop) = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            let Some(source) = docs.get(&uri) else {
+            let Some(doc) = self.parsed_document(&uri) else {
                 return Ok(None);
             };
+            let source = doc.text();
+            // On an `import` path: the imported file itself.
+            let offset = LineIndex::new(source).offset(pos);
+            if let Some(target) = import_target_at(source, offset, &uri) {
+                return Ok(Some(GotoDefinitionResponse::Scalar(Location {
+                    uri: target,
+                    range: Range::default(),
+                })));
+            }
             let Some(line) = position::line_at(source, pos.line) else {
                 return Ok(None);
             };
@@ -5701,9 +6252,8 @@ impl LanguageServer for NmlLanguageServer {
             let is_prop = is_property_name_position(line, &word, byte_col);
 
             let enclosing = {
-                let file = nml_core::cst::parse_best_effort(source);
                 let line_index = LineIndex::new(source);
-                find_enclosing_block_keyword(&file, pos, &line_index)
+                find_enclosing_block_keyword(doc.file(), pos, &line_index)
             };
 
             (word, enclosing, is_prop)
@@ -5780,6 +6330,20 @@ impl LanguageServer for NmlLanguageServer {
                 });
             }
         }
+        // Imported files outside the workspace are not in the document set
+        // — they still hold the definitions this file refers to.
+        for (_, doc_uri, source) in imported_docs(&docs, &uri) {
+            if docs.contains_key(&doc_uri) {
+                continue;
+            }
+            let line_index = LineIndex::new(&source);
+            for range in find_references_in_source(&source, &word, &line_index) {
+                locations.push(Location {
+                    uri: doc_uri.clone(),
+                    range,
+                });
+            }
+        }
 
         if locations.is_empty() {
             Ok(None)
@@ -5793,20 +6357,14 @@ impl LanguageServer for NmlLanguageServer {
         params: DocumentSymbolParams,
     ) -> Result<Option<DocumentSymbolResponse>> {
         let uri = params.text_document.uri;
-        let source_clone = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            match docs.get(&uri) {
-                Some(s) => s.clone(),
-                None => return Ok(None),
-            }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
 
         // Resilient parse keeps the document outline populated mid-edit instead
         // of collapsing to empty on the first syntax error.
-        let file = nml_core::cst::parse_best_effort(&source_clone);
-
-        let line_index = LineIndex::new(&source_clone);
-        let symbols = build_document_symbols(&file, &line_index);
+        let line_index = LineIndex::new(doc.text());
+        let symbols = build_document_symbols(doc.file(), &line_index);
         Ok(Some(DocumentSymbolResponse::Nested(symbols)))
     }
 
@@ -5817,17 +6375,13 @@ impl LanguageServer for NmlLanguageServer {
         let pos = params.text_document_position_params.position;
         let uri = params.text_document_position_params.text_document.uri;
 
-        let source_clone = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            let Some(source) = docs.get(&uri) else {
-                return Ok(None);
-            };
-            source.clone()
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
-        let parse = nml_core::cst::parse(&source_clone);
+        let (source, parse) = (doc.text(), doc.parse());
 
-        let line_index = LineIndex::new(&source_clone);
-        if let Some(range) = duration_lsp::duration_highlight_range(&parse, pos, &line_index) {
+        let line_index = LineIndex::new(source);
+        if let Some(range) = duration_lsp::duration_highlight_range(parse, pos, &line_index) {
             return Ok(Some(vec![DocumentHighlight {
                 range,
                 kind: Some(DocumentHighlightKind::READ),
@@ -5835,7 +6389,7 @@ impl LanguageServer for NmlLanguageServer {
         }
 
         let word = {
-            let Some(line) = position::line_at(&source_clone, pos.line) else {
+            let Some(line) = position::line_at(source, pos.line) else {
                 return Ok(None);
             };
             extract_word_at(line, position::utf16_to_byte(line, pos.character))
@@ -5845,7 +6399,7 @@ impl LanguageServer for NmlLanguageServer {
             return Ok(None);
         }
 
-        let refs = find_references_in_source(&source_clone, &word, &line_index);
+        let refs = find_references_in_source(source, &word, &line_index);
 
         if refs.is_empty() {
             Ok(None)
@@ -5866,15 +6420,10 @@ impl LanguageServer for NmlLanguageServer {
         params: SemanticTokensParams,
     ) -> Result<Option<SemanticTokensResult>> {
         let uri = params.text_document.uri;
-        let source = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            match docs.get(&uri) {
-                Some(s) => s.clone(),
-                None => return Ok(None),
-            }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
-        let parse = nml_core::cst::parse(&source);
-        Ok(Some(crate::semantic_tokens::full(&parse, &source)))
+        Ok(Some(crate::semantic_tokens::full(doc.parse(), doc.text())))
     }
 
     async fn semantic_tokens_range(
@@ -5882,38 +6431,32 @@ impl LanguageServer for NmlLanguageServer {
         params: SemanticTokensRangeParams,
     ) -> Result<Option<SemanticTokensRangeResult>> {
         let uri = params.text_document.uri;
-        let source = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            match docs.get(&uri) {
-                Some(s) => s.clone(),
-                None => return Ok(None),
-            }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
-        let line_index = LineIndex::new(&source);
+        let line_index = LineIndex::new(doc.text());
         let span = Span::new(
             line_index.offset(params.range.start),
             line_index.offset(params.range.end),
         );
-        let parse = nml_core::cst::parse(&source);
-        Ok(Some(crate::semantic_tokens::range(&parse, &source, span)))
+        Ok(Some(crate::semantic_tokens::range(
+            doc.parse(),
+            doc.text(),
+            span,
+        )))
     }
 
     async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
         let uri = params.text_document.uri;
-        let source = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            match docs.get(&uri) {
-                Some(s) => s.clone(),
-                None => return Ok(None),
-            }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
-        let line_index = LineIndex::new(&source);
+        let line_index = LineIndex::new(doc.text());
         let span = Span::new(
             line_index.offset(params.range.start),
             line_index.offset(params.range.end),
         );
-        let parse = nml_core::cst::parse(&source);
-        let hints = nml_core::cst::duration_literals_in(&parse, span)
+        let hints = nml_core::cst::duration_literals_in(doc.parse(), span)
             .into_iter()
             .filter_map(|at| {
                 // Signed literals are domain-invalid (durations are
@@ -5941,20 +6484,177 @@ impl LanguageServer for NmlLanguageServer {
         Ok((!hints.is_empty()).then_some(hints))
     }
 
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        self.drain_store_events().await;
+        let uris: Vec<Url> = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        let files: Vec<(Url, Arc<Vec<IndexedSymbol>>)> = uris
+            .into_iter()
+            .filter_map(|uri| {
+                let symbols = self.symbols_for(&uri)?;
+                Some((uri, symbols))
+            })
+            .collect();
+        Ok(Some(crate::workspace_symbols::search(
+            &params.query,
+            files.iter().map(|(uri, symbols)| (uri, symbols.as_slice())),
+        )))
+    }
+
+    /// Instance counts and validation status on each `model`, `oneof` and
+    /// `enum` header of a `.model.nml` file. Instances are read from every
+    /// other document's symbol table; their failures from the same
+    /// diagnostics cache the pull fills, so a document already pulled costs
+    /// nothing here.
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let uri = params.text_document.uri;
+        if !uri.as_str().ends_with(".model.nml") {
+            return Ok(None);
+        }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
+        };
+        let index = self.schema_index_for(&uri);
+        let headers = crate::code_lens::schema_headers(doc.file(), doc.text(), index.index());
+        let keywords: HashSet<&str> = headers
+            .iter()
+            .flat_map(|h| h.keywords.iter().map(String::as_str))
+            .collect();
+
+        let mut uris: Vec<Url> = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .filter(|u| !u.as_str().ends_with(".model.nml"))
+            .cloned()
+            .collect();
+        uris.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        // Per keyword: each instance with whether it fails validation.
+        let mut by_keyword: HashMap<String, Vec<crate::code_lens::Instance>> = HashMap::new();
+        for doc_uri in uris {
+            let Some(symbols) = self.symbols_for(&doc_uri) else {
+                continue;
+            };
+            let governed: Vec<&IndexedSymbol> = symbols
+                .iter()
+                .filter(|s| {
+                    s.instance_of
+                        .as_deref()
+                        .is_some_and(|k| keywords.contains(k))
+                })
+                .collect();
+            if governed.is_empty() {
+                continue;
+            }
+            let diagnostics = self.cached_diagnostics(&doc_uri).await.unwrap_or_default();
+            for symbol in governed {
+                let Some(keyword) = symbol.instance_of.clone() else {
+                    continue;
+                };
+                by_keyword
+                    .entry(keyword)
+                    .or_default()
+                    .push(crate::code_lens::Instance {
+                        location: Location::new(doc_uri.clone(), symbol.range),
+                        has_errors: crate::code_lens::has_errors_in(&diagnostics, symbol.extent),
+                    });
+            }
+        }
+
+        let command = self
+            .show_references_command
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let lenses = headers
+            .iter()
+            .map(|header| {
+                let instances: Vec<crate::code_lens::Instance> = header
+                    .keywords
+                    .iter()
+                    .filter_map(|k| by_keyword.get(k))
+                    .flatten()
+                    .cloned()
+                    .collect();
+                crate::code_lens::lens(&uri, header, &instances, command.as_deref())
+            })
+            .collect();
+        Ok(Some(lenses))
+    }
+
+    async fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let pos = params.text_document_position_params.position;
+        let uri = params.text_document_position_params.text_document.uri;
+        let word = {
+            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(line) = docs.get(&uri).and_then(|s| position::line_at(s, pos.line)) else {
+                return Ok(None);
+            };
+            extract_word_at(line, position::utf16_to_byte(line, pos.character))
+        };
+        if word.is_empty() {
+            return Ok(None);
+        }
+        let (index, binding) = self.hierarchy_scope(&uri);
+        let Some(ty) = type_hierarchy::lookup(index.index(), &word) else {
+            return Ok(None);
+        };
+        let docs = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        Ok(self
+            .type_hierarchy_item(&docs, &uri, binding.as_deref(), ty)
+            .map(|item| vec![item]))
+    }
+
+    async fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        Ok(self.type_hierarchy_related(&params.item, type_hierarchy::supertypes))
+    }
+
+    async fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        Ok(self.type_hierarchy_related(&params.item, type_hierarchy::subtypes))
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let Some(doc) = self.parsed_document(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(Some(crate::folding::folding_ranges(
+            doc.parse(),
+            doc.text(),
+        )))
+    }
+
     async fn selection_range(
         &self,
         params: SelectionRangeParams,
     ) -> Result<Option<Vec<SelectionRange>>> {
         let uri = params.text_document.uri;
-        let source = {
-            let docs = self.documents.lock().unwrap_or_else(|e| e.into_inner());
-            match docs.get(&uri) {
-                Some(s) => s.clone(),
-                None => return Ok(None),
-            }
+        let Some(doc) = self.parsed_document(&uri) else {
+            return Ok(None);
         };
-        let parse = nml_core::cst::parse(&source);
-        let line_index = LineIndex::new(&source);
+        let (source, parse) = (doc.text(), doc.parse());
+        let line_index = LineIndex::new(source);
         let mut out = Vec::new();
         for pos in params.positions {
             let byte = line_index.offset(pos);
@@ -6202,7 +6902,7 @@ mod tests {
     fn simplify_number_action_edges() {
         let src = "service App:\n    x = 8080.000\n";
         let off = src.find("8080").unwrap();
-        let a = super::simplify_number_action(src, off).expect("action");
+        let a = super::simplify_number_action(&nml_core::cst::parse(src), off).expect("action");
         assert_eq!(a.title, "Simplify number to `8080`");
         assert_eq!(a.new_text, "8080");
         assert_eq!(&src[a.span.start..a.span.end], "8080.000");
@@ -6211,27 +6911,27 @@ mod tests {
         let src = "service App:\n    price = 19.90 USD\n";
         let off = src.find("19.90").unwrap();
         assert!(
-            super::simplify_number_action(src, off).is_none(),
+            super::simplify_number_action(&nml_core::cst::parse(src), off).is_none(),
             "money literals must not offer simplify — fmt would revert it"
         );
 
         // Negative literal: edit replaces digits only, title shows sign.
         let src = "service App:\n    x = -8080.000\n";
         let off = src.find("8080").unwrap();
-        let a = super::simplify_number_action(src, off).expect("action");
+        let a = super::simplify_number_action(&nml_core::cst::parse(src), off).expect("action");
         assert_eq!(a.title, "Simplify number to `-8080`");
         assert_eq!(a.new_text, "8080");
 
         // A list marker's dash is NOT a sign (space-separated token).
         let src = "service App:\n    ports:\n        - 8080.0\n";
         let off = src.find("8080").unwrap();
-        let a = super::simplify_number_action(src, off).expect("action");
+        let a = super::simplify_number_action(&nml_core::cst::parse(src), off).expect("action");
         assert_eq!(a.title, "Simplify number to `8080`");
 
         // Already minimal: no action.
         let src = "service App:\n    x = 2.5\n";
         let off = src.find("2.5").unwrap();
-        assert!(super::simplify_number_action(src, off).is_none());
+        assert!(super::simplify_number_action(&nml_core::cst::parse(src), off).is_none());
 
         // Inline arrays: elements are their own nodes, so an Ident
         // NEIGHBOR (a reference) must not trip the money check — while
@@ -6239,13 +6939,13 @@ mod tests {
         let src = "service App:\n    ports = [8080.000, OtherRef]\n";
         let off = src.find("8080").unwrap();
         assert!(
-            super::simplify_number_action(src, off).is_some(),
+            super::simplify_number_action(&nml_core::cst::parse(src), off).is_some(),
             "reference neighbor must not suppress simplify"
         );
         let src = "service App:\n    prices = [19.90 USD, 20.00 USD]\n";
         let off = src.find("19.90").unwrap();
         assert!(
-            super::simplify_number_action(src, off).is_none(),
+            super::simplify_number_action(&nml_core::cst::parse(src), off).is_none(),
             "array money must suppress"
         );
     }
@@ -7440,6 +8140,42 @@ workflow VoiceAgent:
         assert!(detect(src, 1, 14).is_none());
     }
 
+    /// A money field restricted by `currency` completes exactly its codes
+    /// in the currency slot after the amount; an unrestricted money field
+    /// and non-money fields offer nothing there.
+    #[test]
+    fn currency_completion_offers_only_allowed_codes() {
+        let idx = field_index(
+            "model shop:\n    price money(currency = [\"USD\", \"GBP\"])?\n    tip money?\n    qty number?\n",
+        );
+        let detect = |source: &str, line: u32, character: u32| {
+            let file = nml_core::cst::parse_best_effort_with_tree(source).0;
+            let li = LineIndex::new(source);
+            let pos = Position::new(line, character);
+            let ranges = currency_edit_ranges(source, pos)?;
+            let prop = value_position_prop_name(source, pos)?;
+            let governors = value_governors_at(&file, pos, &idx, &li, prop);
+            governed_currencies(&governors.fields).map(|codes| (codes, ranges))
+        };
+        let span_of = |r: Range| (r.start.character, r.end.character);
+        let src = "shop Main:\n    price = 19.99 U\n";
+        let (codes, (insert, replace)) = detect(src, 1, 19).expect("currency slot completes");
+        assert_eq!(codes, ["USD", "GBP"]);
+        assert_eq!(span_of(insert), (18, 19));
+        assert_eq!(span_of(replace), (18, 19));
+        // Re-triggering inside an existing code replaces all of it.
+        let src = "shop Main:\n    price = 19.99 EUR\n";
+        let (_, (insert, replace)) = detect(src, 1, 19).expect("mid-code retrigger");
+        assert_eq!(span_of(insert), (18, 19));
+        assert_eq!(span_of(replace), (18, 21));
+        // No amount yet, or no space after it: not a currency slot.
+        assert!(detect("shop Main:\n    price = \n", 1, 12).is_none());
+        assert!(detect("shop Main:\n    price = 19\n", 1, 14).is_none());
+        // Unrestricted money and number fields have nothing to narrow to.
+        assert!(detect("shop Main:\n    tip = 5.00 \n", 1, 15).is_none());
+        assert!(detect("shop Main:\n    qty = 5 \n", 1, 12).is_none());
+    }
+
     /// RFC 0017: the hover's normalized total — the coarsest of ms/us/ns
     /// that divides the total exactly, grouped with the language's own
     /// `_` separator (pasteable NML), skipped when it would restate the
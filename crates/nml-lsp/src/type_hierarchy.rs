@@ -0,0 +1,250 @@
+//! Type hierarchy (`textDocument/prepareTypeHierarchy`,
+//! `typeHierarchy/supertypes`, `typeHierarchy/subtypes`) over a
+//! [`SchemaIndex`].
+//!
+//! The graph has two kinds of edges. Composition: a model or trait points up
+//! at every definition it names with `is` / `extends` (RFC 0011), so a
+//! trait's subtypes are the models that mix it in. Discrimination: a `oneof`
+//! points down at its variants, so a model's supertypes include every oneof
+//! that lists it as an arm. Unresolved names are dropped — the diagnostics
+//! already report them.
+
+use nml_core::SchemaIndex;
+use nml_core::ast::{DeclarationKind, File};
+use nml_core::model::{ModelDef, OneOfDef};
+use nml_core::span::Span;
+use tower_lsp::lsp_types::{Range, SymbolKind};
+
+use crate::position::LineIndex;
+
+/// One node of the hierarchy: a model or trait, or a oneof.
+#[derive(Debug, Clone, Copy)]
+pub enum HierarchyType<'i> {
+    Model(&'i ModelDef),
+    OneOf(&'i OneOfDef),
+}
+
+impl<'i> HierarchyType<'i> {
+    pub fn name(&self) -> &'i str {
+        match self {
+            HierarchyType::Model(m) => &m.name,
+            HierarchyType::OneOf(o) => &o.name,
+        }
+    }
+
+    pub fn kind(&self) -> SymbolKind {
+        match self {
+            HierarchyType::Model(m) if m.is_trait() => SymbolKind::INTERFACE,
+            HierarchyType::Model(_) => SymbolKind::CLASS,
+            HierarchyType::OneOf(_) => SymbolKind::ENUM,
+        }
+    }
+
+    /// The declaration as a reader would write its header: `trait auditable`,
+    /// `oneof notifier by kind`.
+    pub fn detail(&self) -> String {
+        match self {
+            HierarchyType::Model(m) => m.kind.label().to_string(),
+            HierarchyType::OneOf(o) => format!("oneof by {}", o.discriminator),
+        }
+    }
+
+    /// The declaring schema source, when the loader stamped one.
+    pub fn source(&self) -> Option<&'i str> {
+        match self {
+            HierarchyType::Model(m) => m.source.as_deref(),
+            HierarchyType::OneOf(o) => o.source.as_deref(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            HierarchyType::Model(m) => m.span,
+            HierarchyType::OneOf(o) => o.span,
+        }
+    }
+
+    fn is_oneof(&self) -> bool {
+        matches!(self, HierarchyType::OneOf(_))
+    }
+}
+
+/// The model, trait or oneof named `name`.
+pub fn lookup<'i>(index: &'i SchemaIndex, name: &str) -> Option<HierarchyType<'i>> {
+    index
+        .model(name)
+        .map(HierarchyType::Model)
+        .or_else(|| index.oneof(name).map(HierarchyType::OneOf))
+}
+
+/// What `ty` composes (`is` / `extends`), then the oneofs it is a variant
+/// of, in declaration order.
+pub fn supertypes<'i>(index: &'i SchemaIndex, ty: HierarchyType<'_>) -> Vec<HierarchyType<'i>> {
+    let mut out = Vec::new();
+    if let HierarchyType::Model(m) = ty {
+        out.extend(m.extends.iter().filter_map(|r| lookup(index, &r.name)));
+    }
+    out.extend(
+        index
+            .oneofs()
+            .iter()
+            .filter(|o| o.variants.iter().any(|(_, variant)| variant == ty.name()))
+            .map(HierarchyType::OneOf),
+    );
+    dedup(out)
+}
+
+/// What composes `ty`, then — for a oneof — its variants in arm order.
+pub fn subtypes<'i>(index: &'i SchemaIndex, ty: HierarchyType<'_>) -> Vec<HierarchyType<'i>> {
+    let mut out: Vec<HierarchyType<'i>> = index
+        .models()
+        .iter()
+        .filter(|m| m.extends.iter().any(|r| r.name == ty.name()))
+        .map(HierarchyType::Model)
+        .collect();
+    if let HierarchyType::OneOf(o) = ty {
+        out.extend(
+            o.variants
+                .iter()
+                .filter_map(|(_, variant)| lookup(index, variant)),
+        );
+    }
+    dedup(out)
+}
+
+/// First occurrence wins: a model listed under two arms of one oneof, or
+/// naming the same trait twice, is one edge.
+fn dedup(types: Vec<HierarchyType<'_>>) -> Vec<HierarchyType<'_>> {
+    let mut seen = std::collections::HashSet::new();
+    types
+        .into_iter()
+        .filter(|t| seen.insert((t.is_oneof(), t.name())))
+        .collect()
+}
+
+/// Where `ty` is declared in `file` (parsed from `source`): the whole
+/// declaration, trailing blank lines excluded, and its name. A declaration
+/// whose span equals the definition's wins over a same-name one elsewhere
+/// in the file (a shadowed duplicate).
+pub fn declaration_ranges(
+    file: &File,
+    source: &str,
+    ty: HierarchyType<'_>,
+) -> Option<(Range, Range)> {
+    let line_index = LineIndex::new(source);
+    let mut found = None;
+    for decl in &file.declarations {
+        let name = match (&decl.kind, ty) {
+            (DeclarationKind::Block(block), HierarchyType::Model(_))
+                if matches!(block.keyword.name.as_str(), "model" | "trait") =>
+            {
+                &block.name
+            }
+            (DeclarationKind::OneOf(o), HierarchyType::OneOf(_)) => &o.name,
+            _ => continue,
+        };
+        if name.name != ty.name() {
+            continue;
+        }
+        let text = source
+            .get(decl.span.start..decl.span.end)
+            .unwrap_or_default();
+        let end = decl.span.start + text.trim_end().len();
+        let ranges = (
+            line_index.range(Span::new(decl.span.start, end)),
+            line_index.range(name.span),
+        );
+        if decl.span == ty.span() {
+            return Some(ranges);
+        }
+        found.get_or_insert(ranges);
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "\
+trait auditable:
+    owner string
+
+trait named:
+    name string
+
+model logNotifier is auditable, named:
+    level string
+
+model slackNotifier is auditable:
+    channel string
+
+model webhook:
+    url string
+
+oneof notifier by kind:
+    \"log\" -> logNotifier
+    \"slack\" -> slackNotifier
+    \"hook\" -> webhook
+";
+
+    fn index() -> SchemaIndex {
+        let (schema, errors) = nml_core::cst::extract_schema(SCHEMA);
+        assert!(errors.is_empty(), "{errors:?}");
+        SchemaIndex::build(schema.models, schema.enums, schema.oneofs)
+    }
+
+    fn names(types: Vec<HierarchyType<'_>>) -> Vec<&str> {
+        types.iter().map(|t| t.name()).collect()
+    }
+
+    #[test]
+    fn traits_reach_the_models_that_mix_them_in() {
+        let index = index();
+        let auditable = lookup(&index, "auditable").unwrap();
+        assert_eq!(auditable.kind(), SymbolKind::INTERFACE);
+        assert_eq!(
+            names(subtypes(&index, auditable)),
+            ["logNotifier", "slackNotifier"]
+        );
+        assert!(supertypes(&index, auditable).is_empty());
+    }
+
+    #[test]
+    fn models_reach_their_traits_and_oneofs() {
+        let index = index();
+        let log = lookup(&index, "logNotifier").unwrap();
+        assert_eq!(
+            names(supertypes(&index, log)),
+            ["auditable", "named", "notifier"]
+        );
+        let webhook = lookup(&index, "webhook").unwrap();
+        assert_eq!(names(supertypes(&index, webhook)), ["notifier"]);
+        assert!(subtypes(&index, webhook).is_empty());
+    }
+
+    #[test]
+    fn oneofs_reach_their_variants() {
+        let index = index();
+        let notifier = lookup(&index, "notifier").unwrap();
+        assert_eq!(notifier.detail(), "oneof by kind");
+        assert_eq!(
+            names(subtypes(&index, notifier)),
+            ["logNotifier", "slackNotifier", "webhook"]
+        );
+    }
+
+    #[test]
+    fn declaration_ranges_select_the_name() {
+        let index = index();
+        let file = nml_core::cst::parse_best_effort(SCHEMA);
+        let notifier = lookup(&index, "notifier").unwrap();
+        let (range, selection) = declaration_ranges(&file, SCHEMA, notifier).unwrap();
+        assert_eq!(range.start.line, 15);
+        assert_eq!(range.end.line, 18);
+        assert_eq!((selection.start.line, selection.start.character), (15, 6));
+        let named = lookup(&index, "named").unwrap();
+        let (_, selection) = declaration_ranges(&file, SCHEMA, named).unwrap();
+        assert_eq!((selection.start.line, selection.start.character), (3, 6));
+    }
+}
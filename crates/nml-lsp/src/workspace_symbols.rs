@@ -0,0 +1,322 @@
+//! Workspace symbol search (`workspace/symbol`).
+//!
+//! Every document the server knows — the workspace roots' `.nml` files
+//! indexed at startup plus whatever the editor has open — contributes its
+//! named declarations: instances, models, traits, enums, oneofs, consts,
+//! templates, arrays, and named list items at any depth. Each symbol
+//! carries its container: the declaring keyword for top-level names, the
+//! enclosing block or array for list items. Queries match fuzzily — the
+//! query's characters in order, case-insensitively — and rank word-start
+//! and contiguous hits first, so `bilsvc` finds `billingService`.
+//!
+//! The same table feeds the model-file code lenses: each instance symbol
+//! records the keyword it instantiates and the extent of its declaration.
+
+use nml_core::ast::{
+    Body, BodyEntryKind, DeclarationKind, File, Identifier, ListItem, ListItemKind, ModifierValue,
+};
+use nml_core::span::Span;
+use tower_lsp::lsp_types::{Location, Range, SymbolInformation, SymbolKind, Url};
+
+use crate::position::LineIndex;
+
+/// Upper bound on one response — an empty query matches everything, and
+/// clients re-query as the user types.
+const MAX_RESULTS: usize = 256;
+
+/// One named declaration, positioned at its name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub container: String,
+    pub range: Range,
+    /// The whole declaration, name through body.
+    pub extent: Range,
+    /// The keyword this symbol instantiates: a top-level instance block's
+    /// keyword, or the item keyword of the `[]array` listing it. `None` for
+    /// schema declarations and for list items nested in a body, whose type
+    /// only the schema knows.
+    pub instance_of: Option<String>,
+}
+
+/// The symbols `file` declares, in source order.
+pub fn file_symbols(file: &File, source: &str) -> Vec<IndexedSymbol> {
+    let line_index = LineIndex::new(source);
+    let mut out = Vec::new();
+    let mut push = |name: &Identifier, kind, container: &str, extent, instance_of: Option<&str>| {
+        out.push(IndexedSymbol {
+            name: name.name.clone(),
+            kind,
+            container: container.to_string(),
+            range: line_index.range(name.span),
+            extent: line_index.range(extent),
+            instance_of: instance_of.map(str::to_string),
+        });
+    };
+    for decl in &file.declarations {
+        match &decl.kind {
+            DeclarationKind::Block(block) => {
+                let keyword = block.keyword.name.as_str();
+                let (kind, instance_of) = match keyword {
+                    "model" => (SymbolKind::CLASS, None),
+                    "trait" => (SymbolKind::INTERFACE, None),
+                    "enum" => (SymbolKind::ENUM, None),
+                    _ => (SymbolKind::OBJECT, Some(keyword)),
+                };
+                push(&block.name, kind, keyword, decl.span, instance_of);
+                body_items(&block.body, &block.name.name, &mut push);
+            }
+            DeclarationKind::Array(arr) => {
+                push(
+                    &arr.name,
+                    SymbolKind::ARRAY,
+                    &format!("[]{}", arr.item_keyword.name),
+                    decl.span,
+                    None,
+                );
+                for modifier in &arr.body.modifiers {
+                    if let ModifierValue::Block(items) = &modifier.value {
+                        list_items(items, &arr.name.name, None, &mut push);
+                    }
+                }
+                let item_keyword = Some(arr.item_keyword.name.as_str());
+                list_items(&arr.body.items, &arr.name.name, item_keyword, &mut push);
+            }
+            DeclarationKind::Const(c) => {
+                push(&c.name, SymbolKind::CONSTANT, "const", decl.span, None)
+            }
+            DeclarationKind::Template(t) => {
+                push(&t.name, SymbolKind::STRING, "template", decl.span, None)
+            }
+            DeclarationKind::OneOf(o) => push(&o.name, SymbolKind::ENUM, "oneof", decl.span, None),
+            DeclarationKind::Import(_) => {}
+        }
+    }
+    out
+}
+
+type Push<'a> = dyn FnMut(&Identifier, SymbolKind, &str, Span, Option<&str>) + 'a;
+
+/// Named list items anywhere under `body`, each contained by the nearest
+/// enclosing named block.
+fn body_items(body: &Body, container: &str, push: &mut Push<'_>) {
+    for entry in &body.entries {
+        match &entry.kind {
+            BodyEntryKind::NestedBlock(nb) => body_items(&nb.body, &nb.name.name, push),
+            BodyEntryKind::ListItem(item) => {
+                list_items(std::slice::from_ref(item), container, None, push)
+            }
+            BodyEntryKind::Modifier(m) => {
+                if let ModifierValue::Block(items) = &m.value {
+                    list_items(items, container, None, push);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn list_items(items: &[ListItem], container: &str, instance_of: Option<&str>, push: &mut Push<'_>) {
+    for item in items {
+        if let ListItemKind::Named { name, body } = &item.kind {
+            push(name, SymbolKind::OBJECT, container, item.span, instance_of);
+            body_items(body, &name.name, push);
+        }
+    }
+}
+
+/// The best matches for `query` across `files`, best first.
+pub fn search<'a>(
+    query: &str,
+    files: impl IntoIterator<Item = (&'a Url, &'a [IndexedSymbol])>,
+) -> Vec<SymbolInformation> {
+    let mut hits: Vec<(u32, &Url, &IndexedSymbol)> = files
+        .into_iter()
+        .flat_map(|(uri, symbols)| symbols.iter().map(move |s| (uri, s)))
+        .filter_map(|(uri, s)| Some((fuzzy_score(query, &s.name)?, uri, s)))
+        .collect();
+    hits.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| a.2.name.len().cmp(&b.2.name.len()))
+            .then_with(|| a.2.name.cmp(&b.2.name))
+            .then_with(|| a.1.as_str().cmp(b.1.as_str()))
+    });
+    hits.truncate(MAX_RESULTS);
+    hits.into_iter()
+        .map(|(_, uri, s)| symbol_information(uri, s))
+        .collect()
+}
+
+/// `SymbolInformation` still carries the deprecated `deprecated` field;
+/// this is the one place that initializes it.
+fn symbol_information(uri: &Url, symbol: &IndexedSymbol) -> SymbolInformation {
+    #[allow(deprecated)]
+    SymbolInformation {
+        name: symbol.name.clone(),
+        kind: symbol.kind,
+        tags: None,
+        deprecated: None,
+        location: Location::new(uri.clone(), symbol.range),
+        container_name: Some(symbol.container.clone()),
+    }
+}
+
+/// Subsequence match score of `query` against `name`, or `None` when the
+/// query's characters do not all appear in order. Case-insensitive. Each
+/// matched character scores; a match at a word start (the first
+/// character, after `_`/`-`/`.`, or a lower→upper camel hump) or right
+/// after the previous match scores more, and an exact or prefix match
+/// outranks everything else. The empty query matches every name.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<u32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = name.chars().collect();
+    let mut score = 0u32;
+    let mut at = 0usize;
+    let mut prev_match: Option<usize> = None;
+    for q in query.chars() {
+        let found = (at..chars.len()).find(|&i| eq_fold(chars[i], q))?;
+        score += 1;
+        if is_word_start(&chars, found) {
+            score += 8;
+        }
+        if prev_match.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        prev_match = Some(found);
+        at = found + 1;
+    }
+    if name.eq_ignore_ascii_case(query) {
+        score += 100;
+    } else if name
+        .get(..query.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(query))
+    {
+        score += 50;
+    }
+    Some(score)
+}
+
+fn eq_fold(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+fn is_word_start(chars: &[char], i: usize) -> bool {
+    match i.checked_sub(1).map(|p| chars[p]) {
+        None => true,
+        Some('_' | '-' | '.') => true,
+        Some(prev) => prev.is_lowercase() && chars[i].is_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+model service:
+    host string
+
+trait auditable:
+    owner string
+
+enum logLevel:
+    - debug
+    - info
+
+oneof notifier by kind:
+    \"log\" -> logNotifier
+
+const region = \"eu-west-1\"
+
+template greeting = \"Hello {{name}}\"
+
+service billingService:
+    host = \"billing\"
+    routes:
+        - Invoices:
+            path = \"/invoices\"
+
+[]route routes:
+    - Home:
+        path = \"/\"
+";
+
+    fn symbols() -> Vec<IndexedSymbol> {
+        let file = nml_core::cst::parse_best_effort(SOURCE);
+        file_symbols(&file, SOURCE)
+    }
+
+    #[test]
+    fn every_declaration_form_is_indexed_with_its_container() {
+        let got: Vec<(String, SymbolKind, String)> = symbols()
+            .into_iter()
+            .map(|s| (s.name, s.kind, s.container))
+            .collect();
+        let want = [
+            ("service", SymbolKind::CLASS, "model"),
+            ("auditable", SymbolKind::INTERFACE, "trait"),
+            ("logLevel", SymbolKind::ENUM, "enum"),
+            ("notifier", SymbolKind::ENUM, "oneof"),
+            ("region", SymbolKind::CONSTANT, "const"),
+            ("greeting", SymbolKind::STRING, "template"),
+            ("billingService", SymbolKind::OBJECT, "service"),
+            ("Invoices", SymbolKind::OBJECT, "routes"),
+            ("routes", SymbolKind::ARRAY, "[]route"),
+            ("Home", SymbolKind::OBJECT, "routes"),
+        ];
+        let want: Vec<(String, SymbolKind, String)> = want
+            .iter()
+            .map(|(n, k, c)| (n.to_string(), *k, c.to_string()))
+            .collect();
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn fuzzy_matches_rank_word_starts_and_exact_names_first() {
+        assert!(fuzzy_score("bilsvc", "billingService").is_some());
+        assert!(fuzzy_score("svcbil", "billingService").is_none());
+        assert!(fuzzy_score("BILLING", "billingService").is_some());
+        // Word-start hits beat scattered ones, exact beats prefix.
+        assert!(fuzzy_score("bs", "billingService") > fuzzy_score("bs", "absent"));
+        assert!(fuzzy_score("routes", "routes") > fuzzy_score("routes", "routesV2"));
+
+        let uri = Url::parse("file:///w/app.nml").unwrap();
+        let symbols = symbols();
+        let names: Vec<String> = search("serv", [(&uri, symbols.as_slice())])
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["service", "billingService"]);
+    }
+
+    #[test]
+    fn instances_record_their_keyword_and_extent() {
+        let symbols = symbols();
+        let instance_of = |name: &str| {
+            let s = symbols.iter().find(|s| s.name == name).unwrap();
+            s.instance_of.clone()
+        };
+        assert_eq!(instance_of("billingService").as_deref(), Some("service"));
+        assert_eq!(instance_of("Home").as_deref(), Some("route"));
+        // Nested list items and schema declarations instantiate nothing
+        // the symbol table can name.
+        assert_eq!(instance_of("Invoices"), None);
+        assert_eq!(instance_of("service"), None);
+
+        let billing = symbols.iter().find(|s| s.name == "billingService").unwrap();
+        assert_eq!(billing.extent.start.line, 17);
+        assert!(billing.extent.end.line >= 21);
+    }
+
+    #[test]
+    fn locations_point_at_the_name() {
+        let symbols = symbols();
+        let home = symbols.iter().find(|s| s.name == "Home").unwrap();
+        let line = SOURCE.lines().nth(home.range.start.line as usize).unwrap();
+        let start = home.range.start.character as usize;
+        assert_eq!(&line[start..start + 4], "Home");
+    }
+}
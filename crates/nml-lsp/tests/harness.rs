@@ -1161,6 +1161,44 @@ async fn watched_change_of_open_document_is_ignored() {
     );
 }
 
+/// String facets render in the field hover's signature exactly as the
+/// canonical type spelling, pattern quoting included.
+#[tokio::test]
+async fn string_facets_render_in_field_hover() {
+    let base = temp_dir("string-facet-hover");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let ws = base.join("ws");
+    fs::create_dir_all(&ws).expect("create workspace");
+    fs::write(
+        ws.join("core.model.nml"),
+        "model site:\n    slug string(pattern = \"[a-z-]+\", minLength = 1)\n",
+    )
+    .expect("write model");
+    let app = ws.join("app.nml");
+    let app_text = "site main:\n    slug = \"docs\"\n";
+    fs::write(&app, app_text).expect("write app");
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    harness.initialize(&ws).await;
+    harness.open(&app, app_text).await;
+
+    let hover = harness
+        .request(
+            "textDocument/hover",
+            json!({
+                "textDocument": { "uri": file_uri(&app) },
+                "position": { "line": 1, "character": 5 },
+            }),
+        )
+        .await;
+    let value = hover["contents"]["value"].as_str().expect("markdown hover");
+    assert!(
+        value.contains("slug string(minLength = 1, pattern = \"[a-z-]+\")"),
+        "facets missing from the signature: {value}"
+    );
+}
+
 /// TEST B — a field's leading comment block (RFC 0004 §4.3) rides extraction
 /// into both editor surfaces: hover renders it as a markdown paragraph under
 /// the signature, and field completion carries it as the item documentation.
@@ -2175,3 +2213,409 @@ async fn universe_provenance_matrix() {
         );
     }
 }
+
+/// `import` end to end: a file outside the workspace (so never indexed)
+/// resolves names, answers go-to-definition and find-references, and the
+/// import path itself navigates to the file; a missing import is NML1005.
+#[tokio::test]
+async fn imports_resolve_across_files_outside_the_workspace() {
+    let base = temp_dir("imports");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let shared = base.join("shared");
+    fs::create_dir_all(&shared).expect("create shared dir");
+    let common = shared.join("common.nml");
+    fs::write(&common, "const dbUrl = \"postgres://db\"\n").expect("write common");
+    let ws = base.join("ws");
+    fs::create_dir_all(&ws).expect("create workspace");
+    let app = ws.join("app.nml");
+    let app_text = "import \"../shared/common.nml\" (dbUrl)\nimport \"missing.nml\"\n\nservice Api:\n    database = dbUrl\n";
+    fs::write(&app, app_text).expect("write app");
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    harness.initialize(&ws).await;
+    let report = harness.open(&app, app_text).await;
+    let codes: Vec<&str> = report["diagnostics"]
+        .as_array()
+        .expect("diagnostics")
+        .iter()
+        .filter_map(|d| d["code"].as_str())
+        .collect();
+    assert_eq!(codes, ["NML1005"], "{report}");
+
+    let definition = harness
+        .request(
+            "textDocument/definition",
+            json!({
+                "textDocument": { "uri": file_uri(&app) },
+                "position": { "line": 4, "character": 18 },
+            }),
+        )
+        .await;
+    assert_eq!(definition["uri"], file_uri(&common), "{definition}");
+    assert_eq!(definition["range"]["start"]["line"], 0);
+
+    let on_path = harness
+        .request(
+            "textDocument/definition",
+            json!({
+                "textDocument": { "uri": file_uri(&app) },
+                "position": { "line": 0, "character": 12 },
+            }),
+        )
+        .await;
+    assert_eq!(on_path["uri"], file_uri(&common), "{on_path}");
+
+    let references = harness
+        .request(
+            "textDocument/references",
+            json!({
+                "textDocument": { "uri": file_uri(&app) },
+                "position": { "line": 4, "character": 18 },
+                "context": { "includeDeclaration": true },
+            }),
+        )
+        .await;
+    let mut uris: Vec<&str> = references
+        .as_array()
+        .expect("references")
+        .iter()
+        .filter_map(|l| l["uri"].as_str())
+        .collect();
+    uris.sort_unstable();
+    let (app_uri, common_uri) = (file_uri(&app), file_uri(&common));
+    let mut expected = vec![app_uri.as_str(), app_uri.as_str(), common_uri.as_str()];
+    expected.sort_unstable();
+    // The selection in the import, the use, and the declaration.
+    assert_eq!(uris, expected, "{references}");
+
+    // The importer's cached closure follows the imported buffer: an unsaved
+    // rename reaches it, and closing the buffer restores the disk text.
+    let codes = |report: &Value| -> Vec<String> {
+        report["diagnostics"]
+            .as_array()
+            .expect("diagnostics")
+            .iter()
+            .filter_map(|d| d["code"].as_str().map(String::from))
+            .collect()
+    };
+    harness
+        .open(&common, "const otherUrl = \"postgres://db\"\n")
+        .await;
+    let renamed = harness.diagnostics(&app_uri).await;
+    // The selection names nothing the buffer declares any more, and the
+    // use it bound goes unresolved with it.
+    assert_eq!(
+        codes(&renamed),
+        ["NML1007", "NML1005", "NML1001"],
+        "{renamed}"
+    );
+    harness
+        .notify(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": common_uri } }),
+        )
+        .await;
+    let restored = harness.diagnostics(&app_uri).await;
+    assert_eq!(codes(&restored), ["NML1005"], "{restored}");
+}
+
+/// Folding is advertised, and served from the open buffer: a block and
+/// its nested body, a `"""` string, and a `//` comment run (as a comment
+/// fold, so "fold all comments" finds it).
+#[tokio::test]
+async fn folding_ranges_cover_blocks_strings_and_comment_runs() {
+    let base = temp_dir("folding");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let ws = base.join("ws");
+    fs::create_dir_all(&ws).expect("create workspace");
+    let app = ws.join("app.nml");
+    let text = "// About the service.\n// Second line.\nservice Api:\n    limits:\n        rate = 1\n    doc = \"\"\"\n        hello\n        \"\"\"\n";
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    let caps = harness
+        .request(
+            "initialize",
+            json!({ "capabilities": {}, "rootUri": file_uri(&ws) }),
+        )
+        .await;
+    assert_eq!(
+        caps["capabilities"]["foldingRangeProvider"],
+        json!(true),
+        "{caps}"
+    );
+    harness.notify("initialized", json!({})).await;
+    harness.open(&app, text).await;
+
+    let ranges = harness
+        .request(
+            "textDocument/foldingRange",
+            json!({ "textDocument": { "uri": file_uri(&app) } }),
+        )
+        .await;
+    let folds: Vec<(u64, u64, Option<&str>)> = ranges
+        .as_array()
+        .expect("ranges")
+        .iter()
+        .map(|r| {
+            (
+                r["startLine"].as_u64().expect("startLine"),
+                r["endLine"].as_u64().expect("endLine"),
+                r["kind"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        folds,
+        [
+            (0, 1, Some("comment")),
+            (2, 7, None),
+            (3, 4, None),
+            (5, 7, None)
+        ],
+        "{ranges}"
+    );
+}
+
+/// `workspace/symbol` is advertised and searches every indexed file, open
+/// or not, fuzzily; an edit to an open buffer replaces its entries.
+#[tokio::test]
+async fn workspace_symbols_search_indexed_and_open_files() {
+    let base = temp_dir("workspace-symbol");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let ws = base.join("ws");
+    fs::create_dir_all(&ws).expect("create workspace");
+    fs::write(
+        ws.join("svc.model.nml"),
+        "model service:\n    host string\n",
+    )
+    .expect("write svc.model.nml");
+    let billing = ws.join("billing.nml");
+    fs::write(&billing, "service billingService:\n    host = \"b\"\n").expect("write billing.nml");
+    let app = ws.join("app.nml");
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    let caps = harness
+        .request(
+            "initialize",
+            json!({ "capabilities": {}, "rootUri": file_uri(&ws) }),
+        )
+        .await;
+    assert_eq!(
+        caps["capabilities"]["workspaceSymbolProvider"],
+        json!(true),
+        "{caps}"
+    );
+    harness.notify("initialized", json!({})).await;
+    harness
+        .open(&app, "service billingSvcEdge:\n    host = \"e\"\n")
+        .await;
+
+    let search = |result: &Value| -> Vec<(String, String, String)> {
+        result
+            .as_array()
+            .expect("symbols")
+            .iter()
+            .map(|s| {
+                (
+                    s["name"].as_str().expect("name").to_string(),
+                    s["containerName"].as_str().unwrap_or_default().to_string(),
+                    s["location"]["uri"].as_str().expect("uri").to_string(),
+                )
+            })
+            .collect()
+    };
+    let found = harness
+        .request("workspace/symbol", json!({ "query": "bilsvc" }))
+        .await;
+    let mut hits = search(&found);
+    hits.sort();
+    assert_eq!(
+        hits,
+        [
+            (
+                "billingService".into(),
+                "service".into(),
+                file_uri(&billing)
+            ),
+            ("billingSvcEdge".into(), "service".into(), file_uri(&app)),
+        ],
+        "{found}"
+    );
+
+    harness
+        .notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": file_uri(&app), "version": 2 },
+                "contentChanges": [{ "text": "service edge:\n    host = \"e\"\n" }],
+            }),
+        )
+        .await;
+    let found = harness
+        .request("workspace/symbol", json!({ "query": "bilsvc" }))
+        .await;
+    assert_eq!(
+        search(&found),
+        [(
+            "billingService".into(),
+            "service".into(),
+            file_uri(&billing)
+        )],
+        "{found}"
+    );
+}
+
+/// Type hierarchy across both schema channels: a store-bound document walks
+/// its package's index and lands in the store slot's schema file, while an
+/// unbound document in the same workspace walks the `.model.nml` registry.
+#[tokio::test]
+async fn type_hierarchy_spans_store_packages_and_workspace_models() {
+    let base = temp_dir("type-hierarchy");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let core = "trait named:\n    name string+\n\nmodel core is named:\n    mode string?\n";
+    let package = nml_validate::package::SchemaPackage::from_parts(
+        nml_validate::test_support::DEMO_MANIFEST,
+        |_| Ok(core.to_string()),
+    )
+    .expect("package loads");
+    Store::at(&store_base).publish(&package).expect("publish");
+
+    let ws = demo_workspace(&base);
+    let demo = ws.join("demo.nml");
+    let demo_text = "core Api:\n    name = \"api\"\n";
+    fs::write(&demo, demo_text).expect("write demo.nml");
+    let models = ws.join("svc.model.nml");
+    let models_text = "trait auditable:\n    owner string\n\nmodel logSink is auditable:\n    level string\n\noneof sink by kind:\n    \"log\" -> logSink\n";
+    fs::write(&models, models_text).expect("write svc.model.nml");
+    let app = ws.join("app.svc.nml");
+    let app_text = "logSink Main:\n    owner = \"ops\"\n    level = \"info\"\n";
+    fs::write(&app, app_text).expect("write app.svc.nml");
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    harness.initialize(&ws).await;
+    harness.open(&demo, demo_text).await;
+    harness.open(&app, app_text).await;
+
+    let prepare = |uri: String| {
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 0, "character": 2 },
+        })
+    };
+    let names = |items: &Value| -> Vec<String> {
+        items
+            .as_array()
+            .expect("items")
+            .iter()
+            .map(|i| i["name"].as_str().unwrap_or_default().to_string())
+            .collect()
+    };
+
+    // Store-bound: `core` resolves to the slot's `core.model.nml`.
+    let items = harness
+        .request(
+            "textDocument/prepareTypeHierarchy",
+            prepare(file_uri(&demo)),
+        )
+        .await;
+    assert_eq!(names(&items), ["core"], "{items}");
+    let item = items[0].clone();
+    let uri = item["uri"].as_str().unwrap_or_default();
+    assert!(uri.ends_with("/core.model.nml"), "{item}");
+    assert!(!uri.starts_with(&file_uri(&ws)), "{item}");
+    assert_eq!(item["selectionRange"]["start"]["line"], 3, "{item}");
+    let supers = harness
+        .request("typeHierarchy/supertypes", json!({ "item": item }))
+        .await;
+    assert_eq!(names(&supers), ["named"], "{supers}");
+    let subs = harness
+        .request("typeHierarchy/subtypes", json!({ "item": supers[0] }))
+        .await;
+    assert_eq!(names(&subs), ["core"], "{subs}");
+
+    // Workspace registry: the model's trait and the oneof it is an arm of.
+    let items = harness
+        .request("textDocument/prepareTypeHierarchy", prepare(file_uri(&app)))
+        .await;
+    assert_eq!(names(&items), ["logSink"], "{items}");
+    assert_eq!(items[0]["uri"], file_uri(&models), "{items}");
+    let supers = harness
+        .request("typeHierarchy/supertypes", json!({ "item": items[0] }))
+        .await;
+    assert_eq!(names(&supers), ["auditable", "sink"], "{supers}");
+    assert_eq!(supers[1]["kind"], 10, "oneofs are enum-kind: {supers}");
+    let subs = harness
+        .request("typeHierarchy/subtypes", json!({ "item": supers[1] }))
+        .await;
+    assert_eq!(names(&subs), ["logSink"], "{subs}");
+}
+
+/// Model-file code lenses count each header's instances across the
+/// workspace and how many fail validation; with a declared references
+/// command the lens opens exactly those instances.
+#[tokio::test]
+async fn model_code_lenses_count_instances_and_failures() {
+    let base = temp_dir("code-lens");
+    let store_base = base.join("store");
+    fs::create_dir_all(&store_base).expect("create store dir");
+    let ws = base.join("ws");
+    fs::create_dir_all(&ws).expect("create workspace");
+    let models = ws.join("svc.model.nml");
+    let models_text = "enum level:\n    - debug\n    - info\n\nmodel sink:\n    level level\n    path string\n\nmodel probe:\n    url string\n";
+    fs::write(&models, models_text).expect("write svc.model.nml");
+    let app = ws.join("app.svc.nml");
+    let app_text = "sink Main:\n    level = \"info\"\n    path = \"/var/log\"\n\nsink Audit:\n    level = \"info\"\n";
+    fs::write(&app, app_text).expect("write app.svc.nml");
+
+    let mut harness = Harness::new(Store::at(&store_base));
+    harness
+        .initialize_with_options(
+            &ws,
+            json!({ "showReferencesCommand": "test.showReferences" }),
+        )
+        .await;
+    harness.open(&models, models_text).await;
+
+    let lenses = harness
+        .request(
+            "textDocument/codeLens",
+            json!({ "textDocument": { "uri": file_uri(&models) } }),
+        )
+        .await;
+    let titles: Vec<&str> = lenses
+        .as_array()
+        .expect("lenses")
+        .iter()
+        .filter_map(|l| l["command"]["title"].as_str())
+        .collect();
+    // `level` governs `sink` through its field; `Audit` lacks `path`.
+    assert_eq!(
+        titles,
+        [
+            "2 instances · 1 with errors",
+            "2 instances · 1 with errors",
+            "0 instances · 0 with errors",
+        ],
+        "{lenses}"
+    );
+    let sink = &lenses[1]["command"];
+    assert_eq!(sink["command"], "test.showReferences", "{sink}");
+    assert_eq!(sink["arguments"][0], file_uri(&models), "{sink}");
+    assert_eq!(sink["arguments"][1], json!({ "line": 4, "character": 6 }));
+    let targets = sink["arguments"][2].as_array().expect("locations");
+    assert_eq!(targets.len(), 2, "{sink}");
+    assert!(targets.iter().all(|t| t["uri"] == file_uri(&app)), "{sink}");
+
+    // Instance files get no lenses.
+    let none = harness
+        .request(
+            "textDocument/codeLens",
+            json!({ "textDocument": { "uri": file_uri(&app) } }),
+        )
+        .await;
+    assert!(none.is_null(), "{none}");
+}
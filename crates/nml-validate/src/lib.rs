@@ -10,6 +10,7 @@
 pub mod glob;
 pub mod loader;
 pub mod package;
+pub mod resolve;
 pub mod schema;
 pub mod store;
 #[cfg(any(test, feature = "test-support"))]
@@ -383,7 +383,9 @@ pub fn valid_package_name(name: &str) -> bool {
         && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '-'))
 }
 
-fn find_manifest(dir: &Path) -> Result<std::path::PathBuf, PackageError> {
+/// The single `<name>.package.nml` in a package directory (a bare
+/// `package.nml` is not a manifest).
+pub fn find_manifest(dir: &Path) -> Result<std::path::PathBuf, PackageError> {
     let entries = std::fs::read_dir(dir).map_err(|e| PackageError::MissingSource {
         file: dir.display().to_string(),
         detail: e.to_string(),
@@ -0,0 +1,982 @@
+//! Schema-package resolution (RFC 0030), shared by `nml check` and the
+//! language server so the command line and the editor never disagree.
+//!
+//! Two separate concerns, deliberately: where package **definitions** come
+//! from (workspace manifest > in-binary > store `current` > builtin), and
+//! which package has **binding authority** over a file (pins > unambiguous
+//! auto-association > unbound fallback). Binding is exclusive — a bound
+//! file's validator is built from the package's sources only, never merged
+//! with any other schema — which is both what keeps strict mode sound and
+//! what makes the content hash a sound validator-cache key.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::SyncSender;
+use std::sync::{Arc, Mutex};
+
+use nml_core::ProjectConfig;
+
+use crate::package::{PackageError, SchemaPackage, builtin_meta_package};
+use crate::schema::SchemaValidator;
+use crate::store::{Store, StoreError};
+
+/// Where a bound package's definition came from. Ordered by determinism, which
+/// is exactly the resolution precedence (RFC 0035 "delivery channels"): a
+/// committed workspace manifest (in-repo) beats a provider tool's embedded
+/// package (in-binary), which beats the machine-local cache (store), which
+/// beats the builtin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionSource {
+    /// A `<name>.package.nml` in the workspace (the authoring path; RFC 0035
+    /// in-repo channel — the most deterministic source, shared via git).
+    WorkspaceManifest(PathBuf),
+    /// Injected in-process by an embedder that IS a schema provider — a tool
+    /// (e.g. `nudge lsp`) serving the neutral server with its own embedded
+    /// package (RFC 0035 in-binary channel). Beats the store so the editor
+    /// validates against the exact binary in front of the user, zero-sync;
+    /// yields to a committed workspace manifest, which the team chose to pin.
+    InBinary,
+    /// The per-user store's `current` slot (RFC 0035 in-cache channel).
+    Store,
+    /// Embedded in nml itself (today: the `package.model.nml` meta
+    /// package).
+    Builtin,
+}
+
+impl DefinitionSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WorkspaceManifest(_) => "workspace manifest",
+            Self::InBinary => "in-binary",
+            Self::Store => "store current",
+            Self::Builtin => "builtin",
+        }
+    }
+}
+
+/// Which authority step bound the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingStep {
+    Pinned,
+    AutoAssociated,
+}
+
+/// A successful binding: everything diagnostics, hover, `nml/schemaInfo`,
+/// and code actions need.
+#[derive(Clone)]
+pub struct Binding {
+    pub package_name: String,
+    pub package_version: String,
+    pub content_hash: String,
+    pub binding_name: String,
+    pub validator: Arc<SchemaValidator>,
+    pub source: DefinitionSource,
+    pub step: BindingStep,
+    /// The root the binding glob matched under.
+    pub root: PathBuf,
+    /// Set when a workspace manifest shadows a *pinned* name that the store
+    /// also holds — shadowing is visible, never silent (RFC 0030).
+    pub shadows_store: bool,
+}
+
+impl Binding {
+    /// The single owner of the human-facing binding identity used in
+    /// diagnostic suffixes and hover: `<name> blake3:<hash8>, <source>`.
+    pub fn identity(&self) -> String {
+        format!(
+            "{} blake3:{}, {}",
+            self.package_name,
+            crate::store::hash8(&self.content_hash),
+            self.source.label()
+        )
+    }
+}
+
+/// One diagnostic-worthy degraded state, attached at the top of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DegradedNote {
+    pub message: String,
+    pub warning: bool,
+    /// Byte span into the resolved document, when the note is about a
+    /// specific construct (a shadowed binding in a manifest); `None` pins
+    /// the note at the top of the file.
+    pub span: Option<nml_core::span::Span>,
+}
+
+/// The outcome of resolving one file.
+pub enum Resolution {
+    Bound(Box<Binding>),
+    /// No package claims the file — today's scope-token behavior applies.
+    Unbound,
+}
+
+/// Resolution result plus any degraded-state notes to surface (a file can be
+/// bound *and* carry notes — e.g. a shadow info — or unbound with a note —
+/// e.g. its pin's package failed to load and validation fell through).
+pub struct Resolved {
+    pub resolution: Resolution,
+    pub notes: Vec<DegradedNote>,
+}
+
+/// A snapshot of the workspace the resolver needs for one pass; built by the
+/// caller from its own state (the server's open documents, or `nml check`'s
+/// directory scan) so the resolver stays lock-free against caller internals.
+pub struct WorkspaceView<'a> {
+    pub roots: &'a [PathBuf],
+    /// Open/indexed `<name>.package.nml` documents: (fs path, text). Open
+    /// text wins over disk so manifest edits resolve live.
+    pub manifests: &'a [(PathBuf, String)],
+    /// Open-document lookup for schema sources named by workspace manifests —
+    /// unsaved schema edits must flow into the package (the authoring path).
+    pub doc_text: &'a dyn Fn(&Path) -> Option<String>,
+}
+
+/// A resolved package definition: the loaded package, its content hash, and
+/// where it came from — the unit that definition precedence produces and
+/// binding authority consumes.
+#[derive(Clone)]
+pub struct Definition {
+    pub package: Arc<SchemaPackage>,
+    pub hash: String,
+    pub source: DefinitionSource,
+}
+
+/// Outcome of a store read, cached per pointer stat. Structured (not a
+/// stringly sentinel) so degraded-state wording can stay per-variant — the
+/// formatVersion contract in particular.
+#[derive(Clone)]
+enum StoreOutcome {
+    Ready(Arc<SchemaPackage>, String),
+    NotInstalled,
+    /// Human-facing degraded message, already worded per the RFC contracts.
+    Failed(String),
+}
+
+struct StoreCacheEntry {
+    /// The pointer content at load time — the exact-by-construction
+    /// freshness guard (the full hash is in it; no mtime granularity).
+    pointer: Option<String>,
+    outcome: StoreOutcome,
+}
+
+/// A store-package health transition (Ready↔Failed) or store-manifest
+/// shadow warning, surfaced once on a status surface (the language
+/// server's `window/logMessage`) — never as per-file diagnostics (RFC 0030:
+/// server-side conditions go to status surfaces). Push-based: the resolver
+/// `try_send`s into the bounded channel given to
+/// [`PackageResolver::with_events`], so overflow drops the NEWEST events
+/// (under flapping, the first transition is the informative one). Send
+/// failures (full channel, dropped receiver) are ignored by design:
+/// best-effort is the contract; without a channel, events are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreEvent {
+    pub message: String,
+    pub warning: bool,
+}
+
+/// Fingerprint of one workspace-manifest source input, for cache
+/// invalidation without re-reading content: open-document text (compared by
+/// equality) or on-disk (len, mtime).
+#[derive(Clone, PartialEq)]
+enum SourceFingerprint {
+    Doc(String),
+    Disk(u64, Option<std::time::SystemTime>),
+    Missing,
+}
+
+struct ManifestCacheEntry {
+    manifest_text: String,
+    sources: Vec<(std::path::PathBuf, SourceFingerprint)>,
+    outcome: Result<(Arc<SchemaPackage>, String), String>,
+}
+
+/// Validator-cache size at which stale entries are dropped wholesale.
+/// Entries rebuild on demand from cached packages; editing a workspace
+/// package's schema sources mints a new content hash per keystroke, so
+/// without a bound the cache grows for the length of the session.
+const VALIDATOR_CACHE_CAP: usize = 64;
+
+pub struct PackageResolver {
+    store: Option<Store>,
+    /// An embedder-supplied package served in-process (RFC 0035 in-binary
+    /// channel): precomputed once with its content hash so resolution never
+    /// re-hashes it. `None` for the neutral server and the CLI; `Some` for a
+    /// provider tool like `nudge lsp`. Sits above the store in precedence,
+    /// below a committed workspace manifest.
+    injected: Option<Definition>,
+    builtin: Arc<SchemaPackage>,
+    builtin_hash: String,
+    store_cache: Mutex<HashMap<String, StoreCacheEntry>>,
+    events: Option<SyncSender<StoreEvent>>,
+    manifest_cache: Mutex<HashMap<PathBuf, ManifestCacheEntry>>,
+    /// Validators cached per (content hash, binding name) — sound because
+    /// binding is exclusive: the hash covers every input.
+    validator_cache: Mutex<HashMap<(String, String), Arc<SchemaValidator>>>,
+    /// Resolution generation — see [`Self::generation`].
+    generation: std::sync::atomic::AtomicU64,
+    /// Layer `--strict` over every binding's own profile.
+    strict: bool,
+    /// The program named in "update …" degradation messages.
+    tool: &'static str,
+}
+
+impl PackageResolver {
+    /// A resolver over `store` (`None` runs storeless).
+    pub fn new(store: Option<Store>) -> Self {
+        let builtin = Arc::new(builtin_meta_package());
+        let builtin_hash = builtin.content_hash();
+        Self {
+            store,
+            injected: None,
+            builtin,
+            builtin_hash,
+            store_cache: Mutex::new(HashMap::new()),
+            events: None,
+            manifest_cache: Mutex::new(HashMap::new()),
+            validator_cache: Mutex::new(HashMap::new()),
+            generation: std::sync::atomic::AtomicU64::new(0),
+            strict: false,
+            tool: "nml",
+        }
+    }
+
+    /// Also serve an embedder-supplied package in-process (RFC 0035
+    /// in-binary channel; the seam `nudge lsp` uses). The package's content
+    /// hash is computed once here — resolution treats it like any other
+    /// [`Definition`], so binding, caching, and the freshness poll all work
+    /// unchanged.
+    pub fn with_injected(mut self, package: SchemaPackage) -> Self {
+        let hash = package.content_hash();
+        self.injected = Some(Definition {
+            package: Arc::new(package),
+            hash,
+            source: DefinitionSource::InBinary,
+        });
+        self
+    }
+
+    /// Push [`StoreEvent`]s into `events`.
+    pub fn with_events(mut self, events: SyncSender<StoreEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Make every bound validator strict (`nml check --strict`).
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Name `tool` in degradation messages asking the user to update.
+    pub fn with_tool(mut self, tool: &'static str) -> Self {
+        self.tool = tool;
+        self
+    }
+
+    /// The store definitions are read from, if any.
+    pub fn store(&self) -> Option<&Store> {
+        self.store.as_ref()
+    }
+
+    /// Monotonic resolution generation (RFC 0010 tier 1): bumped whenever a
+    /// stat/fingerprint guard observes actual change (a store pointer
+    /// transition, a manifest rebuild). Consumers caching anything derived
+    /// from resolution compare this — an out-of-band `schema sync` then
+    /// invalidates their entries the moment any resolve notices it.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn bump_generation(&self) {
+        self.generation
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn emit(&self, event: StoreEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.try_send(event);
+        }
+    }
+
+    /// Resolve one file. `path` must be absolute.
+    pub fn resolve(&self, path: &Path, ws: &WorkspaceView<'_>) -> Resolved {
+        let mut notes = Vec::new();
+
+        // Per-root project settings: nearest-ancestor nml-project.nml wins
+        // wholesale; lists never merge across nesting levels (RFC 0030).
+        let project = nearest_project_config(path, ws);
+        let (pins, auto_associate) = match &project {
+            // `pinned_packages()` folds in the `provider` tool as an implicit
+            // same-named pin (RFC 0035 tool→package fallback), so the neutral
+            // server validates a provider-declared project against the tool's
+            // published package without launching the tool's LSP. Each pin is
+            // charset-gated in the loop below.
+            Some((_, config)) => (config.pinned_packages(), config.auto_associate),
+            None => (Vec::new(), true),
+        };
+
+        // ── Step 1: pins, in list order, first match wins. ──
+        for pin in &pins {
+            // A pin is an external string headed for store paths and
+            // diagnostics; the charset rule guards every resolution path
+            // (RFC 0030 Security) — a hostile `../../x` pin is rejected
+            // here, never joined into a path or echoed into a "run this"
+            // hint.
+            if !crate::package::valid_package_name(pin) {
+                notes.push(DegradedNote {
+                    message: format!(
+                        "schema package name {pin:?} is not a valid package name ([a-z][a-z0-9-]*) — ignored (from schemaPackages or provider.tool)"
+                    ),
+                    warning: true,
+                    span: None,
+                });
+                continue;
+            }
+            match self.definition_for(pin, path, ws, &mut notes) {
+                Some(def) => {
+                    if let Some(binding) =
+                        self.try_bind(&def, path, ws, BindingStep::Pinned, &mut notes)
+                    {
+                        let shadows_store =
+                            matches!(def.source, DefinitionSource::WorkspaceManifest(_))
+                                && self.store_has(pin);
+                        if shadows_store {
+                            notes.push(DegradedNote {
+                                message: format!(
+                                    "bound by workspace manifest for '{pin}', shadowing the store's copy"
+                                ),
+                                warning: false,
+                                span: None,
+                            });
+                        }
+                        return Resolved {
+                            resolution: Resolution::Bound(Box::new(Binding {
+                                shadows_store,
+                                ..binding
+                            })),
+                            notes,
+                        };
+                    }
+                }
+                None => {
+                    // definition_for pushed the precise note (not installed /
+                    // failed to load); the pin simply doesn't bind.
+                }
+            }
+        }
+
+        // ── Step 2: unambiguous auto-association across known packages. ──
+        if auto_associate {
+            let mut matches: Vec<Binding> = Vec::new();
+            for def in self.known_packages(path, ws, &mut notes) {
+                if let Some(binding) =
+                    self.try_bind(&def, path, ws, BindingStep::AutoAssociated, &mut notes)
+                {
+                    matches.push(binding);
+                }
+            }
+            match matches.len() {
+                1 => {
+                    let binding = matches.into_iter().next().expect("len checked");
+                    return Resolved {
+                        resolution: Resolution::Bound(Box::new(binding)),
+                        notes,
+                    };
+                }
+                0 => {}
+                _ => {
+                    let names: Vec<&str> =
+                        matches.iter().map(|b| b.package_name.as_str()).collect();
+                    notes.push(DegradedNote {
+                        message: format!(
+                            "{} schema packages claim this file ({}) — add a schemaPackages pin to choose",
+                            names.len(),
+                            names.join(", ")
+                        ),
+                        warning: true,
+                        span: None,
+                    });
+                }
+            }
+        }
+
+        Resolved {
+            resolution: Resolution::Unbound,
+            notes,
+        }
+    }
+
+    /// All known packages *for this file*: governing workspace manifests,
+    /// store entries, builtins — workspace definitions shadow same-named
+    /// store entries; nearest manifest shadows a farther same-named one.
+    ///
+    /// Load failures during this probing pass are deliberately quiet except
+    /// on the failing manifest itself: an unloadable package's globs are
+    /// unknowable, so the "files it would have bound" contract cannot be
+    /// met, and broadcasting the failure onto every resolved file in the
+    /// workspace is worse. Pinned resolution (which names the package
+    /// explicitly) stays loud.
+    pub fn known_packages(
+        &self,
+        path: &Path,
+        ws: &WorkspaceView<'_>,
+        notes: &mut Vec<DegradedNote>,
+    ) -> Vec<Definition> {
+        let mut out: Vec<Definition> = Vec::new();
+        let mut seen: Vec<String> = Vec::new();
+        for (manifest_path, text) in manifests_governing(path, ws) {
+            let is_self = path == manifest_path;
+            let mut local = Vec::new();
+            if let Some((package, hash)) =
+                self.load_workspace_manifest(manifest_path, text, ws, &mut local)
+            {
+                // Shadow warnings belong to THIS manifest only — spans are
+                // byte offsets into THIS text; reading them off any other
+                // definition would squiggle arbitrary bytes.
+                if is_self {
+                    for w in package.manifest.shadow_warnings() {
+                        notes.push(DegradedNote {
+                            message: w.message,
+                            warning: true,
+                            span: w.span,
+                        });
+                    }
+                }
+                if seen.contains(&package.manifest.name) {
+                    continue; // nearer manifest already defines this name
+                }
+                seen.push(package.manifest.name.clone());
+                out.push(Definition {
+                    package,
+                    hash,
+                    source: DefinitionSource::WorkspaceManifest(manifest_path.clone()),
+                });
+            }
+            if is_self {
+                notes.extend(local);
+            }
+        }
+        // In-binary channel (RFC 0035): above the store, below a committed
+        // workspace manifest of the same name — the team's committed copy wins,
+        // but the embedded package always beats its own possibly-stale cache.
+        if let Some(def) = &self.injected {
+            if !seen.contains(&def.package.manifest.name) {
+                seen.push(def.package.manifest.name.clone());
+                out.push(def.clone());
+            }
+        }
+        if let Some(store) = &self.store {
+            for name in store.list_names() {
+                if seen.contains(&name) {
+                    continue;
+                }
+                if let StoreOutcome::Ready(package, hash) = self.load_store_package(&name) {
+                    out.push(Definition {
+                        package,
+                        hash,
+                        source: DefinitionSource::Store,
+                    });
+                }
+            }
+        }
+        if !seen.contains(&self.builtin.manifest.name) {
+            out.push(Definition {
+                package: self.builtin.clone(),
+                hash: self.builtin_hash.clone(),
+                source: DefinitionSource::Builtin,
+            });
+        }
+        out
+    }
+
+    /// Definition precedence for one named package, scoped to the file's
+    /// governing manifests: workspace manifest > store `current` > builtin.
+    fn definition_for(
+        &self,
+        name: &str,
+        path: &Path,
+        ws: &WorkspaceView<'_>,
+        notes: &mut Vec<DegradedNote>,
+    ) -> Option<Definition> {
+        for (manifest_path, text) in manifests_governing(path, ws) {
+            if manifest_stem(manifest_path) == Some(name) {
+                if let Some((package, hash)) =
+                    self.load_workspace_manifest(manifest_path, text, ws, notes)
+                {
+                    return Some(Definition {
+                        package,
+                        hash,
+                        source: DefinitionSource::WorkspaceManifest(manifest_path.clone()),
+                    });
+                }
+                return None;
+            }
+        }
+        // In-binary channel (RFC 0035): a pin resolves to the embedded package
+        // before the store, so `nudge lsp` validates against the running
+        // binary's schema even when the store holds an older synced copy.
+        if let Some(def) = &self.injected {
+            if def.package.manifest.name == name {
+                return Some(def.clone());
+            }
+        }
+        match self.load_store_package(name) {
+            StoreOutcome::Ready(package, hash) => {
+                return Some(Definition {
+                    package,
+                    hash,
+                    source: DefinitionSource::Store,
+                });
+            }
+            StoreOutcome::Failed(message) => {
+                // The pin names this package explicitly — its failure is
+                // this file's business.
+                notes.push(DegradedNote {
+                    message,
+                    warning: true,
+                    span: None,
+                });
+                return None;
+            }
+            StoreOutcome::NotInstalled => {}
+        }
+        if self.builtin.manifest.name == name {
+            return Some(Definition {
+                package: self.builtin.clone(),
+                hash: self.builtin_hash.clone(),
+                source: DefinitionSource::Builtin,
+            });
+        }
+        if self.store.is_some() {
+            notes.push(DegradedNote {
+                message: format!(
+                    "pinned schema package '{name}' is not installed — run '{name} schema sync'"
+                ),
+                warning: true,
+                span: None,
+            });
+        }
+        None
+    }
+
+    fn store_has(&self, name: &str) -> bool {
+        self.store
+            .as_ref()
+            .is_some_and(|s| s.pointer_content(name).is_some())
+    }
+
+    /// Store read with a stat-guarded cache: the per-validation-pass probe is
+    /// a `stat` (microseconds); the package is re-read and re-hashed only on
+    /// a pointer transition — including absent→present, the
+    /// brand-new-operator path (RFC 0030 Freshness). Failure wording is
+    /// per-variant here so the formatVersion degradation contract survives
+    /// the store path (its primary path — a newer nudge auto-syncing).
+    fn load_store_package(&self, name: &str) -> StoreOutcome {
+        let Some(store) = self.store.as_ref() else {
+            return StoreOutcome::NotInstalled;
+        };
+        let tool = self.tool;
+        // One ~80-byte read serves as both freshness guard and load input.
+        let pointer = store.pointer_content(name);
+        let mut cache = self.store_cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get(name) {
+            Some(e) if e.pointer == pointer => e.outcome.clone(),
+            prior => {
+                let outcome = match &pointer {
+                    None => StoreOutcome::NotInstalled,
+                    Some(content) => match store.load_current(name, content) {
+                        Ok(slot) => {
+                            // A store manifest isn't an open file — its
+                            // shadow warnings go to the status channel,
+                            // one-shot by construction (loads happen once
+                            // per pointer transition).
+                            // One-shot by cache-miss construction; may drop
+                            // on overflow (see StoreEvent: newest-dropped).
+                            for w in slot.package.manifest.shadow_warnings() {
+                                self.emit(StoreEvent {
+                                    message: format!("schema package '{name}': {}", w.message),
+                                    warning: true,
+                                });
+                            }
+                            StoreOutcome::Ready(Arc::new(slot.package), slot.content_hash)
+                        }
+                        Err(StoreError::NotInstalled) => StoreOutcome::NotInstalled,
+                        Err(StoreError::Package(
+                            crate::package::PackageError::UnsupportedFormatVersion {
+                                required,
+                                supported,
+                            },
+                        )) => StoreOutcome::Failed(format!(
+                            "schema package '{name}' needs formatVersion {required}; this {tool} supports {supported} — update {tool}; using basic validation until then"
+                        )),
+                        Err(e) => StoreOutcome::Failed(format!(
+                            "schema package '{name}' in the store failed to load: {e} — falling back to basic validation"
+                        )),
+                    },
+                };
+                // Health transitions surface once, at the transition.
+                let was_failed = matches!(prior.map(|e| &e.outcome), Some(StoreOutcome::Failed(_)));
+                match (&outcome, was_failed) {
+                    (StoreOutcome::Failed(message), false) => {
+                        self.emit(StoreEvent {
+                            message: message.clone(),
+                            warning: true,
+                        });
+                    }
+                    (StoreOutcome::Ready(..), true) => {
+                        self.emit(StoreEvent {
+                            message: format!("schema package '{name}' in the store recovered"),
+                            warning: false,
+                        });
+                    }
+                    _ => {}
+                }
+                // A pointer transition (or first load) changes resolution
+                // output for every bound document — generation-invalidate
+                // downstream caches (RFC 0010 tier 1).
+                self.bump_generation();
+                cache.insert(
+                    name.to_string(),
+                    StoreCacheEntry {
+                        pointer,
+                        outcome: outcome.clone(),
+                    },
+                );
+                outcome
+            }
+        }
+    }
+
+    /// Load a workspace manifest package: manifest text from the live
+    /// document, sources from open documents first (unsaved edits flow into
+    /// the package — the authoring loop), disk second. Fingerprint-cached so
+    /// the steady-state per-pass cost is string compares + stats, never
+    /// re-parse/re-hash (RFC 0030 Freshness: "hash verification on
+    /// load/cache-miss only, never per keystroke").
+    pub fn load_workspace_manifest(
+        &self,
+        manifest_path: &Path,
+        text: &str,
+        ws: &WorkspaceView<'_>,
+        notes: &mut Vec<DegradedNote>,
+    ) -> Option<(Arc<SchemaPackage>, String)> {
+        let fingerprint_of = |full: &Path| -> SourceFingerprint {
+            if let Some(open) = (ws.doc_text)(full) {
+                return SourceFingerprint::Doc(open);
+            }
+            match std::fs::metadata(full) {
+                Ok(m) => SourceFingerprint::Disk(m.len(), m.modified().ok()),
+                Err(_) => SourceFingerprint::Missing,
+            }
+        };
+        {
+            let cache = self
+                .manifest_cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if let Some(entry) = cache.get(manifest_path) {
+                if entry.manifest_text == text
+                    && entry.sources.iter().all(|(p, fp)| fingerprint_of(p) == *fp)
+                {
+                    match &entry.outcome {
+                        Ok((package, hash)) => return Some((package.clone(), hash.clone())),
+                        Err(message) => {
+                            notes.push(DegradedNote {
+                                message: message.clone(),
+                                warning: true,
+                                span: None,
+                            });
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+
+        let tool = self.tool;
+        let dir = manifest_path.parent()?;
+        let mut fingerprints: Vec<(PathBuf, SourceFingerprint)> = Vec::new();
+        let result = SchemaPackage::from_parts(text, |file| {
+            crate::package::check_plain_file_name(file)?;
+            let full = dir.join(file);
+            let fp = fingerprint_of(&full);
+            let content = match &fp {
+                SourceFingerprint::Doc(open) => Ok(open.clone()),
+                _ => std::fs::read_to_string(&full).map_err(|e| e.to_string()),
+            };
+            fingerprints.push((full, fp));
+            content
+        });
+        let outcome: Result<(Arc<SchemaPackage>, String), String> = match result {
+            Ok(package) => {
+                // The filename stem is the pin/dedup key; the declared name
+                // is the binding identity. They must agree — the store
+                // enforces this, and a `demo.package.nml` declaring
+                // `package nudge:` must not be two different packages
+                // depending on the resolution path.
+                match manifest_stem(manifest_path) {
+                    Some(stem) if stem != package.manifest.name => Err(format!(
+                        "workspace package manifest '{}' declares package '{}' but its filename says '{stem}' — rename one; using basic validation until then",
+                        display_path(manifest_path, ws.roots),
+                        package.manifest.name
+                    )),
+                    _ => {
+                        let hash = package.content_hash();
+                        Ok((Arc::new(package), hash))
+                    }
+                }
+            }
+            Err(PackageError::UnsupportedFormatVersion {
+                required,
+                supported,
+            }) => Err(format!(
+                "package manifest '{}' needs formatVersion {required}; this {tool} supports {supported} — update {tool}; using basic validation until then",
+                display_path(manifest_path, ws.roots)
+            )),
+            Err(e) => Err(format!(
+                "workspace package manifest '{}' failed to load: {e} — falling back to basic validation",
+                display_path(manifest_path, ws.roots)
+            )),
+        };
+
+        // A manifest rebuild (fingerprint change or first load) changes
+        // resolution output — generation-invalidate downstream caches.
+        self.bump_generation();
+        self.manifest_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                manifest_path.to_path_buf(),
+                ManifestCacheEntry {
+                    manifest_text: text.to_string(),
+                    sources: fingerprints,
+                    outcome: outcome.clone(),
+                },
+            );
+        match outcome {
+            Ok((package, hash)) => Some((package, hash)),
+            Err(message) => {
+                notes.push(DegradedNote {
+                    message,
+                    warning: true,
+                    span: None,
+                });
+                None
+            }
+        }
+    }
+
+    /// Try to bind `path` with one package: find its root, match the binding
+    /// globs, and build (or fetch) the exclusive validator.
+    fn try_bind(
+        &self,
+        def: &Definition,
+        path: &Path,
+        ws: &WorkspaceView<'_>,
+        step: BindingStep,
+        notes: &mut Vec<DegradedNote>,
+    ) -> Option<Binding> {
+        let Definition {
+            package,
+            hash,
+            source,
+        } = def;
+        let root = find_root(path, &package.manifest.root_markers, ws, source)?;
+        let rel = path.strip_prefix(&root).ok()?;
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        let binding = package.binding_for(&rel)?;
+
+        let key = (hash.to_string(), binding.name.clone());
+        let cached = {
+            let cache = self
+                .validator_cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            cache.get(&key).cloned()
+        };
+        let validator = match cached {
+            Some(v) => v,
+            None => match package.validator(binding) {
+                Ok(v) => {
+                    let v = Arc::new(if self.strict { v.strict() } else { v });
+                    let mut cache = self
+                        .validator_cache
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner());
+                    // Bounded: schema-source edits mint a fresh hash per
+                    // keystroke; entries rebuild cheaply on demand, so a
+                    // wholesale clear at the cap beats bookkeeping.
+                    if cache.len() >= VALIDATOR_CACHE_CAP {
+                        cache.clear();
+                    }
+                    cache.insert(key, v.clone());
+                    v
+                }
+                Err(PackageError::Sources { errors }) => {
+                    let detail = errors
+                        .first()
+                        .map(|e| e.to_string())
+                        .unwrap_or_else(|| "unknown error".to_string());
+                    notes.push(DegradedNote {
+                        message: format!(
+                            "schema package '{}' failed to load: {detail} — falling back to basic validation",
+                            package.manifest.name
+                        ),
+                        warning: true,
+                        span: None,
+                    });
+                    return None;
+                }
+                Err(e) => {
+                    notes.push(DegradedNote {
+                        message: format!(
+                            "schema package '{}' failed to load: {e} — falling back to basic validation",
+                            package.manifest.name
+                        ),
+                        warning: true,
+                        span: None,
+                    });
+                    return None;
+                }
+            },
+        };
+
+        Some(Binding {
+            package_name: package.manifest.name.clone(),
+            package_version: package.manifest.version.clone(),
+            content_hash: hash.to_string(),
+            binding_name: binding.name.clone(),
+            validator,
+            source: source.clone(),
+            step,
+            root,
+            // The pinned caller overlays this after its store check; it has
+            // no meaning on other paths.
+            shadows_store: false,
+        })
+    }
+}
+
+/// Workspace manifests governing `path` — a manifest defines its package
+/// for files under its own directory subtree only (RFC 0030: "each
+/// governs its root"; a manifest buried in a vendored dir or fixture
+/// must not redefine validation workspace-wide). Deepest-first so the
+/// nearest manifest wins for nesting; deduped by name downstream.
+pub fn manifests_governing<'m>(
+    path: &Path,
+    ws: &'m WorkspaceView<'_>,
+) -> Vec<&'m (PathBuf, String)> {
+    let mut governing: Vec<&(PathBuf, String)> = ws
+        .manifests
+        .iter()
+        .filter(|(mp, _)| mp.parent().is_some_and(|dir| path.starts_with(dir)))
+        .collect();
+    // Path as the same-depth tiebreak: `ws.manifests` is built from a
+    // HashMap, so depth alone would leave two same-directory manifests
+    // in hash order — vocabulary and universe flipping between server
+    // restarts on identical inputs.
+    governing.sort_by(|(a, _), (b, _)| {
+        std::cmp::Reverse(a.components().count())
+            .cmp(&std::cmp::Reverse(b.components().count()))
+            .then_with(|| a.cmp(b))
+    });
+    governing
+}
+
+fn manifest_stem(path: &Path) -> Option<&str> {
+    path.file_name()?
+        .to_str()?
+        .strip_suffix(".package.nml")
+        .filter(|s| !s.is_empty())
+}
+
+/// A path for user-facing messages (diagnostics, hover, `nml/schemaInfo`):
+/// workspace-root-relative, with a forward-slash separator, falling back to the
+/// file name when the path sits outside every root. Never an absolute path —
+/// which keeps messages terse on every backend and, on the wasm neutral server,
+/// never leaks the `/workspace` WASI mount prefix (paths there are the mounted
+/// guest paths, not host paths).
+pub fn display_path(path: &Path, roots: &[PathBuf]) -> String {
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .filter(|rel| !rel.is_empty())
+        .or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
+/// The nearest-ancestor `nml-project.nml` from `path`'s directory upward,
+/// bounded by the workspace roots. Nearest file wins wholesale.
+pub fn nearest_project_config(
+    path: &Path,
+    ws: &WorkspaceView<'_>,
+) -> Option<(PathBuf, ProjectConfig)> {
+    for dir in ancestors_within_roots(path, ws.roots) {
+        let candidate = dir.join("nml-project.nml");
+        let text = (ws.doc_text)(&candidate).or_else(|| std::fs::read_to_string(&candidate).ok());
+        if let Some(text) = text {
+            let file = nml_core::cst::parse_best_effort(&text);
+            return Some((candidate, ProjectConfig::from_file(&file)));
+        }
+    }
+    None
+}
+
+/// The project root for glob anchoring (RFC 0030 Vocabulary): nearest
+/// ancestor (inclusive) containing `nml-project.nml` or one of the package's
+/// root markers; the workspace-manifest's own directory is the root of last
+/// resort, which makes the rule total.
+pub fn find_root(
+    path: &Path,
+    root_markers: &[String],
+    ws: &WorkspaceView<'_>,
+    source: &DefinitionSource,
+) -> Option<PathBuf> {
+    for dir in ancestors_within_roots(path, ws.roots) {
+        if dir.join("nml-project.nml").is_file()
+            || root_markers.iter().any(|m| dir.join(m).is_file())
+        {
+            return Some(dir);
+        }
+    }
+    match source {
+        DefinitionSource::WorkspaceManifest(manifest_path) => {
+            manifest_path.parent().map(Path::to_path_buf)
+        }
+        // In-binary/store/builtin packages with no marker root: the workspace
+        // root containing the file anchors the globs. Enumerated (not a
+        // catch-all) so a new source with different anchoring can't fall
+        // through silently.
+        DefinitionSource::InBinary | DefinitionSource::Store | DefinitionSource::Builtin => ws
+            .roots
+            .iter()
+            .find(|r| path.starts_with(r))
+            .cloned()
+            .or_else(|| path.parent().map(Path::to_path_buf)),
+    }
+}
+
+/// Directories from the file's parent upward, stopping at (and including)
+/// the containing workspace root; outside any root, just the parent chain
+/// bounded to a sane depth.
+fn ancestors_within_roots(path: &Path, roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let containing_root = roots.iter().find(|r| path.starts_with(r));
+    let mut dir = path.parent();
+    let mut depth = 0;
+    while let Some(d) = dir {
+        out.push(d.to_path_buf());
+        if let Some(root) = containing_root {
+            if d == root.as_path() {
+                break;
+            }
+        }
+        depth += 1;
+        if depth >= 64 {
+            break;
+        }
+        dir = d.parent();
+    }
+    out
+}
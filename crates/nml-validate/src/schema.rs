@@ -133,7 +133,7 @@ impl<'a> ElemLabel<'a> {
     /// can mislabel a `set<T>` as an array.
     fn for_type(field: &'a str, ty: &FieldType) -> Self {
         let base = Self::array(field);
-        if matches!(ty, FieldType::Set(_)) {
+        if matches!(ty, FieldType::Set(_, _)) {
             base.in_set()
         } else {
             base
@@ -729,7 +729,9 @@ impl SchemaValidator {
                         diagnostics.push(self.ineffective_definition("oneof", o.name.span));
                     }
                 }
-                DeclarationKind::Const(_) | DeclarationKind::Template(_) => {}
+                DeclarationKind::Const(_)
+                | DeclarationKind::Template(_)
+                | DeclarationKind::Import(_) => {}
             }
         }
 
@@ -1340,7 +1342,7 @@ impl SchemaValidator {
         // a shape mismatch — the same fail-loud stance D2 takes for the keyed
         // case, closing the model-only-union hole.
         let has_arms_variant = variants.iter().any(|v| matches!(v, FieldType::Arms { .. }));
-        let has_list_variant = variants.iter().any(|v| matches!(v, FieldType::List(_)));
+        let has_list_variant = variants.iter().any(|v| matches!(v, FieldType::List(_, _)));
         if (shape.has_arms && !has_arms_variant) || (shape.has_list_items && !has_list_variant) {
             let shape_name = if shape.has_arms {
                 "routing arms"
@@ -1851,7 +1853,7 @@ impl SchemaValidator {
                                     diags,
                                 );
                             }
-                            FieldType::List(inner) => {
+                            FieldType::List(inner, facets) => {
                                 // Each item resolves its inner type against its own
                                 // body (so a `(a | b)` union variant is picked per
                                 // item; a `ModelRef` inner resolves body-independently),
@@ -1859,6 +1861,13 @@ impl SchemaValidator {
                                 // item's identity into the body before validating —
                                 // so a required `name` supplied by the item key
                                 // (`- classify:`) is seen, not reported missing.
+                                validate_collection_facets(
+                                    facets,
+                                    count_list_items(&nb.body),
+                                    &field_def.name,
+                                    nb.name.span,
+                                    diags,
+                                );
                                 let empty = Body::fresh(Vec::new());
                                 self.validate_body_shared_properties(&nb.body, inner, depth, diags);
                                 for entry in &nb.body.entries {
@@ -1886,10 +1895,17 @@ impl SchemaValidator {
                                     }
                                 }
                             }
-                            FieldType::Set(inner) => {
+                            FieldType::Set(inner, facets) => {
                                 // Items validate exactly like a list's (same
                                 // per-item variant resolution + identity
                                 // materialization as the `List` arm above)…
+                                validate_collection_facets(
+                                    facets,
+                                    count_list_items(&nb.body),
+                                    &field_def.name,
+                                    nb.name.span,
+                                    diags,
+                                );
                                 let empty = Body::fresh(Vec::new());
                                 self.validate_body_shared_properties(&nb.body, inner, depth, diags);
                                 let mut items: Vec<&ListItem> = Vec::new();
@@ -1981,11 +1997,11 @@ impl SchemaValidator {
                 BodyEntryKind::ListItem(item) => {
                     if let Some(field_def) = model.fields.iter().find(|f| {
                         f.shorthand
-                            && matches!(f.field_type, FieldType::List(_) | FieldType::Set(_))
+                            && matches!(f.field_type, FieldType::List(_, _) | FieldType::Set(_, _))
                     }) {
                         seen_fields.push(&field_def.name);
                         let inner = match &field_def.field_type {
-                            FieldType::List(i) | FieldType::Set(i) => i,
+                            FieldType::List(i, _) | FieldType::Set(i, _) => i,
                             _ => unreachable!("guarded by the find predicate"),
                         };
                         let empty = Body::fresh(Vec::new());
@@ -2009,6 +2025,31 @@ impl SchemaValidator {
             }
         }
 
+        // The bare items above are one body-positional list: its
+        // collection facets count them together, against the instance.
+        let positional = body.entries.iter().find_map(|e| match &e.kind {
+            BodyEntryKind::ListItem(_) => Some(e.span),
+            _ => None,
+        });
+        if let Some(first_item) = positional {
+            if let Some((field_def, facets)) =
+                model.fields.iter().find_map(|f| match &f.field_type {
+                    FieldType::List(_, facets) | FieldType::Set(_, facets) if f.shorthand => {
+                        Some((f, facets))
+                    }
+                    _ => None,
+                })
+            {
+                validate_collection_facets(
+                    facets,
+                    count_list_items(body),
+                    &field_def.name,
+                    header_span.unwrap_or(first_item),
+                    diags,
+                );
+            }
+        }
+
         for field in &model.fields {
             if !field.optional
                 && field.default_value.is_none()
@@ -2192,8 +2233,8 @@ impl SchemaValidator {
                 // declaration (RFC 0032 — e.g. `|block set<string>?` written
                 // as `|block:` + items); sets additionally enforce uniqueness.
                 let (inner, is_set) = match declared.as_ref() {
-                    FieldType::List(inner) => (inner, false),
-                    FieldType::Set(inner) => (inner, true),
+                    FieldType::List(inner, _) => (inner, false),
+                    FieldType::Set(inner, _) => (inner, true),
                     _ => {
                         diags.push(
                             Diagnostic::error(format!(
@@ -2305,6 +2346,18 @@ impl SchemaValidator {
                     (PrimitiveFacets::Duration(fs), Value::Duration(d)) => {
                         validate_facets(fs, d, field_name, span, diags);
                     }
+                    (PrimitiveFacets::Money(fs), Value::Money(m)) => {
+                        validate_money_facets(fs, m, field_name, span, diags);
+                    }
+                    (PrimitiveFacets::String(fs), Value::String(s)) => {
+                        for tail in fs.violations(s) {
+                            diags.push(
+                                Diagnostic::error(format!("'{field_name}' {tail}"))
+                                    .with_code(codes::FACET_VIOLATION)
+                                    .with_span(span),
+                            );
+                        }
+                    }
                     // The resolved lane (RFC 0047): a deferred value on a
                     // faceted field is checkable here only when this
                     // validator owns the file's resolution (boot; the
@@ -2312,7 +2365,9 @@ impl SchemaValidator {
                     // never enter this arm — it carries no facets — so
                     // credentials are structurally outside the lane.
                     (
-                        PrimitiveFacets::Number(_) | PrimitiveFacets::Duration(_),
+                        PrimitiveFacets::Number(_)
+                        | PrimitiveFacets::Duration(_)
+                        | PrimitiveFacets::String(_),
                         Value::Secret(_) | Value::Reference(_),
                     ) => {
                         if let Some(resolver) = &self.env_resolution {
@@ -2329,8 +2384,9 @@ impl SchemaValidator {
                     self.validate_model_ref_value(value, ref_name, field_name, span, diags);
                 }
             }
-            FieldType::List(inner) => match value {
+            FieldType::List(inner, facets) => match value {
                 Value::Array(items) => {
+                    validate_collection_facets(facets, items.len(), field_name, span, diags);
                     for item in items {
                         self.validate_value_against_type(
                             &item.value,
@@ -2355,8 +2411,9 @@ impl SchemaValidator {
                     );
                 }
             },
-            FieldType::Set(inner) => match value {
+            FieldType::Set(inner, facets) => match value {
                 Value::Array(items) => {
+                    validate_collection_facets(facets, items.len(), field_name, span, diags);
                     for item in items {
                         self.validate_value_against_type(
                             &item.value,
@@ -2449,6 +2506,10 @@ impl SchemaValidator {
                                 (PrimitiveFacets::Duration(fs), Value::Duration(d)) => {
                                     Some(fs.admits(d))
                                 }
+                                (PrimitiveFacets::Money(fs), Value::Money(m)) => Some(fs.admits(m)),
+                                (PrimitiveFacets::String(fs), Value::String(s)) => {
+                                    Some(fs.admits(s))
+                                }
                                 _ => None,
                             };
                             if let Some(admits) = verdict {
@@ -2529,7 +2590,7 @@ impl SchemaValidator {
                     matches!(value, Value::String(_) | Value::TemplateString(_))
                 }
             }
-            FieldType::List(inner) => match value {
+            FieldType::List(inner, _) => match value {
                 Value::Array(items) => items
                     .iter()
                     .all(|item| self.value_matches_type(&item.value, inner)),
@@ -2537,7 +2598,7 @@ impl SchemaValidator {
             },
             // Matching is shape-only; uniqueness is enforced (with spans) in
             // `validate_value_against_type`, not here.
-            FieldType::Set(inner) => match value {
+            FieldType::Set(inner, _) => match value {
                 Value::Array(items) => items
                     .iter()
                     .all(|item| self.value_matches_type(&item.value, inner)),
@@ -3057,10 +3118,10 @@ fn field_type_shape_errors(
 ) {
     match field_type {
         FieldTypeExpr::Named { .. } => {}
-        FieldTypeExpr::Array(inner) => {
+        FieldTypeExpr::Array(inner, _) => {
             field_type_shape_errors(inner, Some("an array element"), span, diags);
         }
-        FieldTypeExpr::Set(inner) => {
+        FieldTypeExpr::Set(inner, _) => {
             // Same positional rules as an array element (an arm set nested in a
             // collection element is unreachable — RFC 0007's placement rule).
             field_type_shape_errors(inner, Some("a set element"), span, diags);
@@ -3144,9 +3205,10 @@ fn set_item_label(item: &nml_core::ast::ListItem) -> String {
 fn type_has_facets(t: &FieldType) -> bool {
     match t {
         FieldType::Primitive { facets, .. } => !facets.is_none(),
-        FieldType::List(inner) | FieldType::Set(inner) | FieldType::Modifier(inner) => {
-            type_has_facets(inner)
+        FieldType::List(inner, facets) | FieldType::Set(inner, facets) => {
+            !facets.is_none() || type_has_facets(inner)
         }
+        FieldType::Modifier(inner) => type_has_facets(inner),
         FieldType::Union(vs) => vs.iter().any(type_has_facets),
         FieldType::Arms { key, target } => type_has_facets(key) || type_has_facets(target),
         FieldType::ModelRef(_) => false,
@@ -3208,6 +3270,33 @@ pub fn default_diagnostics(schema: &ExtractedSchema) -> Vec<Diagnostic> {
     out
 }
 
+/// Collection facet enforcement (NML2057): the element count against
+/// `minItems`/`maxItems`/`nonEmpty`, reported on the list itself — the
+/// elements carry their own findings.
+fn validate_collection_facets(
+    facets: &nml_core::model::CollectionFacets,
+    count: usize,
+    field_name: &str,
+    span: Span,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for tail in facets.violations(count) {
+        diags.push(
+            Diagnostic::error(format!("'{field_name}' {tail}"))
+                .with_code(codes::FACET_VIOLATION)
+                .with_span(span),
+        );
+    }
+}
+
+/// The number of `- item` entries in a block-bodied list or set.
+fn count_list_items(body: &Body) -> usize {
+    body.entries
+        .iter()
+        .filter(|e| matches!(e.kind, BodyEntryKind::ListItem(_)))
+        .count()
+}
+
 /// RFC 0018 facet enforcement (NML2057). Exact comparisons through
 /// `Number`'s numeric `Ord` and `is_multiple_of` — a boundary can never
 /// lie the way an f64 comparison does. Values echoed are authored
@@ -3229,6 +3318,38 @@ fn validate_facets<T: nml_core::model::FacetDomain>(
     }
 }
 
+/// The `currency` facet (NML2057). When an allowed code with the same
+/// minor unit is a near miss for the value's currency, the finding carries
+/// it as a did-you-mean over the currency code — same-exponent only, so
+/// applying the fix can never reinterpret the amount's decimal places.
+fn validate_money_facets(
+    facets: &nml_core::model::MoneyFacets,
+    value: &nml_core::money::Money,
+    field_name: &str,
+    span: Span,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for tail in facets.violations(value) {
+        let diag = Diagnostic::error(format!("'{field_name}' {tail}"))
+            .with_code(codes::FACET_VIOLATION)
+            .with_span(span);
+        let allowed = facets
+            .currency
+            .iter()
+            .flat_map(|c| &c.codes)
+            .filter(|code| nml_core::money::currency_exponent(code) == Some(value.exponent));
+        // The literal is `amount CODE`, so the code is the span's tail.
+        let code_len = value.currency.len();
+        let diag = match nml_core::suggest::suggest(&value.currency, allowed.map(String::as_str)) {
+            Some(s) if span.end - span.start > code_len => {
+                diag.with_suggestion(s, Span::new(span.end - code_len, span.end))
+            }
+            _ => diag,
+        };
+        diags.push(diag);
+    }
+}
+
 /// The RFC 0047 resolved-lane leaf, shared by the whole-file walk (the
 /// facet hook above, via [`SchemaValidator::with_env_resolution`]) and the
 /// per-model shallow walk ([`SchemaValidator::validate_resolved_facets`]).
@@ -3267,7 +3388,21 @@ fn check_resolved_facets(
                 validate_facets_resolved(fs, &d, text.var(), field_name, span, diags);
             }
         }
-        PrimitiveFacets::None => {}
+        PrimitiveFacets::String(fs) => {
+            for desc in fs.violation_descriptions(text.as_str()) {
+                diags.push(
+                    Diagnostic::error(format!(
+                        "'{field_name}' from {} resolved to a value {desc}",
+                        text.var()
+                    ))
+                    .with_code(codes::FACET_VIOLATION)
+                    .with_span(span),
+                );
+            }
+        }
+        // Env text never coerces to money (deserialization reads money
+        // as its display string), so there is no resolved value to judge.
+        PrimitiveFacets::Money(_) | PrimitiveFacets::None => {}
     }
 }
 
@@ -3733,6 +3868,148 @@ mod tests {
         );
     }
 
+    /// The `currency` facet admits only its codes, in lists and unions
+    /// too; a near-miss code with the same minor unit carries a did-you-mean
+    /// over the currency code alone.
+    #[test]
+    fn currency_facets_are_enforced() {
+        let schema = "model svc:\n    name string+\n    price money(currency = [\"USD\", \"GBP\"])?\n    fees []money(currency = \"EUR\")?\n";
+        let clean = diags(
+            schema,
+            "svc A:\n    price = 19.99 GBP\n    fees = [1.00 EUR]\n",
+        );
+        assert!(
+            clean.iter().all(|x| x.severity != Severity::Error),
+            "{clean:?}"
+        );
+        let src = "svc A:\n    price = 19.99 GIP\n";
+        let d = diags(schema, src);
+        let hit = d
+            .iter()
+            .find(|x| x.code == Some(nml_core::diagnostic::codes::FACET_VIOLATION))
+            .unwrap_or_else(|| panic!("{d:?}"));
+        assert!(
+            hit.rendered_message().contains(
+                "in GIP, which the schema's currency = [\"USD\", \"GBP\"] does not admit"
+            ),
+            "{hit:?}"
+        );
+        let fix = hit.suggestions.first().expect("did-you-mean");
+        assert_eq!(fix.replacement, "GBP");
+        assert_eq!(&src[fix.span.start..fix.span.end], "GIP");
+        // A different minor unit never gets a suggestion: JPY has none.
+        let d = diags(schema, "svc A:\n    price = 1000 JPY\n");
+        let hit = d
+            .iter()
+            .find(|x| x.code == Some(nml_core::diagnostic::codes::FACET_VIOLATION))
+            .unwrap_or_else(|| panic!("{d:?}"));
+        assert!(hit.suggestions.is_empty(), "{hit:?}");
+        let d = diags(schema, "svc A:\n    fees = [1.00 EUR, 2.00 USD]\n");
+        assert!(
+            d.iter()
+                .any(|x| x.code == Some(nml_core::diagnostic::codes::FACET_VIOLATION)),
+            "{d:?}"
+        );
+    }
+
+    /// String facets count characters, match the whole value, and report
+    /// over the value's own span; union variants admit by their facets.
+    #[test]
+    fn string_facets_are_enforced() {
+        let schema = "model svc:\n    name string+\n    slug string(minLength = 2, maxLength = 4, pattern = \"[a-z-]+\")?\n    tags []string(maxLength = 3)?\n    label (string(pattern = \"[a-z]+\") | string(pattern = \"[0-9]+\"))?\n";
+        let clean = diags(
+            schema,
+            "svc A:\n    slug = \"a-b\"\n    tags = [\"x\", \"éüñ\"]\n    label = \"42\"\n",
+        );
+        assert!(
+            clean.iter().all(|x| x.severity != Severity::Error),
+            "{clean:?}"
+        );
+        let violations = |src: &str| -> Vec<(String, String)> {
+            diags(schema, src)
+                .into_iter()
+                .filter(|d| d.code == Some(nml_core::diagnostic::codes::FACET_VIOLATION))
+                .map(|d| {
+                    let span = d.span.unwrap();
+                    (d.rendered_message(), src[span.start..span.end].to_string())
+                })
+                .collect()
+        };
+        let v = violations("svc A:\n    slug = \"Abcde\"\n");
+        assert_eq!(v.len(), 2, "{v:?}");
+        assert!(
+            v[0].0
+                .contains("'slug' has 5 characters, more than the schema's maxLength = 4")
+        );
+        assert!(
+            v[1].0
+                .contains("'slug' does not match the schema's pattern = \"[a-z-]+\"")
+        );
+        assert_eq!(v[0].1, "\"Abcde\"");
+        let v = violations("svc A:\n    tags = [\"ok\", \"long\"]\n");
+        assert_eq!(v.len(), 1, "{v:?}");
+        assert_eq!(v[0].1, "\"long\"");
+        let v = violations("svc A:\n    label = \"a1\"\n");
+        assert_eq!(v.len(), 1, "{v:?}");
+    }
+
+    /// Collection facets count a list's elements wherever the list is
+    /// written — a `[...]` value, a `field:` block of items, or bare
+    /// items filling a body-positional list — and point at the list.
+    #[test]
+    fn collection_facets_are_enforced() {
+        let schema = "model endpoint:\n    url string+\n\nmodel svc:\n    endpoints []endpoint(minItems = 1)?\n    \
+                      regions set<string>(maxItems = 2)?\n    hosts []string(nonEmpty = true)?\n\n\
+                      model pool:\n    members []string(minItems = 2)+\n";
+        let violations = |src: &str| -> Vec<(String, String)> {
+            diags(schema, src)
+                .into_iter()
+                .filter(|d| d.code == Some(nml_core::diagnostic::codes::FACET_VIOLATION))
+                .map(|d| {
+                    let span = d.span.unwrap();
+                    (d.rendered_message(), src[span.start..span.end].to_string())
+                })
+                .collect()
+        };
+        let clean = "svc A:\n    endpoints:\n        - \"/a\"\n    regions = [\"eu\", \"us\"]\n    hosts = [\"h\"]\n";
+        assert_eq!(violations(clean), Vec::new());
+
+        let v = violations("svc A:\n    endpoints:\n        |url = \"/a\"\n");
+        assert_eq!(
+            v,
+            [(
+                "'endpoints' has 0 items, fewer than the schema's minItems = 1".to_string(),
+                "endpoints".to_string()
+            )]
+        );
+        let v = violations("svc A:\n    regions = [\"eu\", \"us\", \"ap\"]\n");
+        assert_eq!(
+            v,
+            [(
+                "'regions' has 3 items, more than the schema's maxItems = 2".to_string(),
+                "[\"eu\", \"us\", \"ap\"]".to_string()
+            )]
+        );
+        let v = violations("svc A:\n    hosts = []\n");
+        assert_eq!(v.len(), 1, "{v:?}");
+        assert_eq!(
+            v[0].0,
+            "'hosts' is empty, but the schema requires nonEmpty = true"
+        );
+        let v = violations("pool P:\n    - \"a\"\n");
+        assert_eq!(
+            v,
+            [(
+                "'members' has 1 item, fewer than the schema's minItems = 2".to_string(),
+                "P".to_string()
+            )]
+        );
+        assert_eq!(
+            violations("pool P:\n    - \"a\"\n    - \"b\"\n"),
+            Vec::new()
+        );
+    }
+
     /// RFC 0018 §2 definition-side fixtures (NML2058) — plus the
     /// violating-default case, which reports through the SHARED
     /// enforcement pass as NML2057.
@@ -3753,7 +4030,7 @@ mod tests {
             );
         };
         bad_def(
-            "model m:\n    s string(min = 1)\n",
+            "model m:\n    s bool(min = 1)\n",
             "facets attach only to `number`",
         );
         bad_def(
@@ -6653,7 +6930,7 @@ workflow W:
         let FieldType::Modifier(inner) = &model.fields[0].field_type else {
             panic!("expected modifier type for |allow");
         };
-        let FieldType::List(elem) = inner.as_ref() else {
+        let FieldType::List(elem, _) = inner.as_ref() else {
             panic!("expected list inside modifier");
         };
         assert!(matches!(
@@ -6672,7 +6949,7 @@ workflow W:
         };
         assert_eq!(variants.len(), 2);
         assert!(matches!(&variants[0], FieldType::ModelRef(n) if n == "step"));
-        assert!(matches!(&variants[1], FieldType::List(_)));
+        assert!(matches!(&variants[1], FieldType::List(_, _)));
     }
 
     #[test]
@@ -7576,6 +7853,28 @@ mod resolved_facet_tests {
         );
     }
 
+    /// String facets judge env text as-is, with the same redaction: the
+    /// message names the variable and the rule, never the text.
+    #[test]
+    fn resolved_env_string_facets_are_redacted() {
+        let diags = diags_with_env(
+            "model svc:\n    host string(pattern = \"[a-z.]+\")\n",
+            "svc A:\n    host = $ENV.HOST\n",
+            &[("HOST", "Bad_Host")],
+        );
+        let errs = facet_errors(&diags);
+        assert_eq!(errs.len(), 1, "{diags:?}");
+        let msg = &errs[0].message;
+        assert!(
+            msg.contains("$ENV.HOST") && msg.contains("that does not match the schema's pattern"),
+            "{msg}"
+        );
+        assert!(
+            !msg.contains("Bad_Host"),
+            "must never echo the value: {msg}"
+        );
+    }
+
     /// Number lane, coercion-grammar parity: "1e-6" is exactly what the
     /// de-layer's `parse_coercion` will accept at runtime, so validation
     /// must judge the same value — not reject the spelling (literal
@@ -75,6 +75,11 @@ impl Store {
         Self { base: base.into() }
     }
 
+    /// The store root, for messages naming where packages live.
+    pub fn base(&self) -> &std::path::Path {
+        &self.base
+    }
+
     /// The per-user store: `NML_SCHEMA_STORE_DIR` when set (an operational
     /// override for sandboxed CI, read-only homes, hermetic caches — and the
     /// process-e2e seam; the `CARGO_HOME` pattern), else
@@ -134,25 +139,7 @@ impl Store {
     /// Load the slot a pointer names (callers hold the pointer content they
     /// already read as the freshness guard).
     pub fn load_current(&self, name: &str, pointer: &str) -> Result<CurrentSlot, StoreError> {
-        let mut lines = pointer.lines();
-        let (slot, hash) = match (lines.next(), lines.next()) {
-            (Some(slot), Some(hash)) if !slot.is_empty() && hash.starts_with("blake3:") => {
-                (slot.to_string(), hash.to_string())
-            }
-            _ => {
-                return Err(StoreError::Corrupt {
-                    detail: "pointer must hold a slot name and a blake3 hash".to_string(),
-                });
-            }
-        };
-        // Slot names are single path components; a pointer must not be able
-        // to walk the filesystem.
-        if slot.contains('/') || slot.contains('\\') || slot.contains("..") {
-            return Err(StoreError::Corrupt {
-                detail: "pointer slot name is not a plain directory name".to_string(),
-            });
-        }
-        let slot_dir = self.package_dir(name).expect("validated above").join(&slot);
+        let (slot_dir, hash) = self.slot_dir(name, pointer)?;
         let package = SchemaPackage::from_dir(&slot_dir).map_err(StoreError::Package)?;
         let actual = package.content_hash();
         if actual != hash {
@@ -174,6 +161,40 @@ impl Store {
         })
     }
 
+    /// The directory of `name`'s `current` slot, holding its manifest and
+    /// schema files — for pointing an editor at a stored definition.
+    ///
+    /// The slot's contents are not hash-verified here;
+    /// [`Self::read_current`] is the loading path.
+    pub fn current_slot_dir(&self, name: &str) -> Option<PathBuf> {
+        let pointer = self.pointer_content(name)?;
+        self.slot_dir(name, &pointer).ok().map(|(dir, _)| dir)
+    }
+
+    /// Parse a pointer into its slot directory and recorded hash.
+    fn slot_dir(&self, name: &str, pointer: &str) -> Result<(PathBuf, String), StoreError> {
+        let mut lines = pointer.lines();
+        let (slot, hash) = match (lines.next(), lines.next()) {
+            (Some(slot), Some(hash)) if !slot.is_empty() && hash.starts_with("blake3:") => {
+                (slot.to_string(), hash.to_string())
+            }
+            _ => {
+                return Err(StoreError::Corrupt {
+                    detail: "pointer must hold a slot name and a blake3 hash".to_string(),
+                });
+            }
+        };
+        // Slot names are single path components; a pointer must not be able
+        // to walk the filesystem.
+        if slot.contains('/') || slot.contains('\\') || slot.contains("..") {
+            return Err(StoreError::Corrupt {
+                detail: "pointer slot name is not a plain directory name".to_string(),
+            });
+        }
+        let package_dir = self.package_dir(name).ok_or(StoreError::NotInstalled)?;
+        Ok((package_dir.join(&slot), hash))
+    }
+
     /// Package names present in the store (a directory with a `current`
     /// pointer). Feeds auto-association's "known package" set (RFC 0030) —
     /// the store-inclusive reading is what makes zero-config work.
@@ -200,17 +221,23 @@ impl Store {
     /// Inventory the store for human display (RFC 0030 `schema list`).
     /// Derived from pointer files and directory names only — no package
     /// loads, no hash recomputation — so listing stays cheap and a corrupt
-    /// slot cannot fail it. A corrupt/unreadable pointer yields a row with
-    /// `"?"` identity fields rather than being dropped: an operator listing
-    /// the store to debug it must SEE the broken entry, not miss it.
+    /// slot cannot fail it. A corrupt/unreadable pointer yields a
+    /// [`ListingStatus::CorruptPointer`] row with `"?"` identity fields
+    /// rather than being dropped: an operator listing the store to debug it
+    /// must SEE the broken entry, not miss it.
     pub fn list(&self) -> Vec<PackageListing> {
         self.list_names()
             .into_iter()
             .map(|name| {
-                let (version, short) = self
+                let identity = self
                     .pointer_content(&name)
-                    .and_then(|p| pointer_identity(&p))
-                    .unwrap_or_else(|| ("?".to_string(), "?".to_string()));
+                    .and_then(|p| pointer_identity(&p));
+                let status = match identity {
+                    Some(_) => ListingStatus::Current,
+                    None => ListingStatus::CorruptPointer,
+                };
+                let (version, short) =
+                    identity.unwrap_or_else(|| ("?".to_string(), "?".to_string()));
                 // Non-dot directories only: `.staging-*` / `.pointer-*` temp
                 // artifacts are publish mechanics, not slots.
                 let slot_count = self
@@ -237,6 +264,7 @@ impl Store {
                     hash8: short,
                     slot_count,
                     published,
+                    status,
                 }
             })
             .collect()
@@ -244,7 +272,8 @@ impl Store {
 }
 
 /// One row of [`Store::list`]. Identity fields are `"?"` when the pointer
-/// is corrupt — visible degradation, never silent omission.
+/// is corrupt — visible degradation, never silent omission; branch on
+/// [`Self::status`], not on the placeholder.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageListing {
     pub name: String,
@@ -255,6 +284,16 @@ pub struct PackageListing {
     pub slot_count: usize,
     /// Pointer-file mtime — the last publish. `None` when unreadable.
     pub published: Option<std::time::SystemTime>,
+    pub status: ListingStatus,
+}
+
+/// Whether a [`PackageListing`]'s `current` pointer could be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingStatus {
+    /// The pointer names a slot; the identity fields are real.
+    Current,
+    /// The pointer is missing, unreadable, or malformed.
+    CorruptPointer,
 }
 
 /// Parse `(version, hash8)` out of a two-line pointer without touching the
@@ -360,16 +399,33 @@ impl Store {
         Ok(PublishOutcome::Published { slot })
     }
 
+    /// Run the publish-time GC for one installed package on demand — for
+    /// stores whose publisher has stopped publishing, where the pass would
+    /// otherwise never run again. Same retention rule (see `gc`); refuses
+    /// a package whose pointer cannot be read, since the pointed slot is
+    /// the one thing GC must never touch. Returns the directories removed.
+    pub fn gc_package(&self, name: &str) -> Result<usize, StoreError> {
+        let pointer = self.pointer_content(name).ok_or(StoreError::NotInstalled)?;
+        let slot = pointer
+            .lines()
+            .next()
+            .filter(|_| pointer_identity(&pointer).is_some())
+            .ok_or_else(|| StoreError::Corrupt {
+                detail: format!("'{name}' has a malformed current pointer"),
+            })?;
+        Ok(self.gc(name, slot))
+    }
+
     /// Keep the pointed slot plus the 4 most recent others by mtime; skip
     /// slots younger than an hour (closes the create→flip race against
     /// concurrent publishers). Best-effort — GC failures never fail a
-    /// publish.
-    fn gc(&self, name: &str, pointed: &str) {
+    /// publish. Returns the directories removed.
+    fn gc(&self, name: &str, pointed: &str) -> usize {
         let Some(dir) = self.package_dir(name) else {
-            return;
+            return 0;
         };
         let Ok(entries) = std::fs::read_dir(&dir) else {
-            return;
+            return 0;
         };
         let now = std::time::SystemTime::now();
         let mut slots: Vec<(std::time::SystemTime, PathBuf, String)> = entries
@@ -389,6 +445,7 @@ impl Store {
             .collect();
         slots.sort_by_key(|s| std::cmp::Reverse(s.0));
         let mut kept = 0;
+        let mut removed = 0;
         for (modified, path, file_name) in slots {
             let temp = file_name.starts_with('.');
             if !temp {
@@ -401,10 +458,11 @@ impl Store {
                 .duration_since(modified)
                 .map(|d| d.as_secs() >= 3600)
                 .unwrap_or(false);
-            if aged {
-                let _ = std::fs::remove_dir_all(path);
+            if aged && std::fs::remove_dir_all(path).is_ok() {
+                removed += 1;
             }
         }
+        removed
     }
 }
 
@@ -612,6 +670,40 @@ package demo:
         let _ = std::fs::remove_dir_all(&base);
     }
 
+    /// `gc_package` runs the same pass without a publish, and refuses a
+    /// package it cannot safely identify the pointed slot of.
+    #[test]
+    fn gc_package_prunes_on_demand() {
+        let base = temp_base("gc-on-demand");
+        let store = Store::at(&base);
+        assert!(matches!(
+            store.gc_package("demo"),
+            Err(StoreError::NotInstalled)
+        ));
+        crate::test_support::publish_demo(&store);
+        let dir = base.join("schema-packages/demo");
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(7200);
+        for i in 0..6 {
+            let slot = dir.join(format!("0.0.{i}+aaaaaaa{i}"));
+            std::fs::create_dir_all(&slot).unwrap();
+            filetime::set_file_mtime(&slot, filetime::FileTime::from_system_time(old)).unwrap();
+        }
+        assert_eq!(
+            store.gc_package("demo").unwrap(),
+            2,
+            "4 newest unpointed kept"
+        );
+        assert_eq!(store.gc_package("demo").unwrap(), 0, "idempotent");
+        assert!(store.read_current("demo").is_ok(), "pointed slot untouched");
+
+        std::fs::write(dir.join(CURRENT_POINTER), "garbage\n").unwrap();
+        assert!(matches!(
+            store.gc_package("demo"),
+            Err(StoreError::Corrupt { .. })
+        ));
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
     /// `list` over a healthy published package and a corrupt-pointer one:
     /// the healthy row carries real identity, the corrupt row survives with
     /// visible `"?"` fields (never dropped from the inventory).
@@ -637,12 +729,14 @@ package demo:
             "corrupt pointer is visible, not hidden"
         );
         assert_eq!(broken.hash8, "?");
+        assert_eq!(broken.status, ListingStatus::CorruptPointer);
         assert_eq!(broken.slot_count, 1, "slot dirs still counted");
         let demo = &listings[1];
         assert_eq!(demo.name, "demo");
         assert_eq!(demo.version, "0.1.0");
         assert_eq!(demo.hash8, hash8(&hash));
         assert_eq!(demo.slot_count, 1);
+        assert_eq!(demo.status, ListingStatus::Current);
         assert!(
             demo.published.is_some(),
             "pointer mtime is readable on a just-published package"
@@ -118,7 +118,10 @@ export class NmlClientManager {
       traceOutputChannel: this.logs.trace,
       revealOutputChannelOn: RevealOutputChannelOn.Error,
       progressOnInitialization: true,
-      initializationOptions: { explainCommand: "nml.explain" },
+      initializationOptions: {
+        explainCommand: "nml.explain",
+        showReferencesCommand: "nml.showReferences",
+      },
       initializationFailedHandler: (error) => {
         void this.failStart(resolution.label, String(error));
         return false;
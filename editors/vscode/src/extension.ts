@@ -2,6 +2,7 @@ import { commands, ExtensionContext, languages, window, workspace } from "vscode
 import { NmlClientManager } from "./clientManager";
 import { registerExplain } from "./explain";
 import { createNmlLogs } from "./logging";
+import { registerShowReferences } from "./references";
 import { createStatusBar, NmlStatusBar } from "./statusBar";
 
 let clientManager: NmlClientManager | undefined;
@@ -18,6 +19,7 @@ export async function activate(context: ExtensionContext): Promise<void> {
   clientManager = manager;
 
   registerExplain(context, () => manager.getClient());
+  registerShowReferences(context);
 
   context.subscriptions.push(
     commands.registerCommand("nml.restartServer", () =>
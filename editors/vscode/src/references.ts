@@ -0,0 +1,54 @@
+import { commands, ExtensionContext, Location, Position, Range, Uri } from "vscode";
+import { isRecord } from "./contracts/wire";
+
+// ─────────────────────────────────────────────────────────────────────────
+// Model-file code lenses ("3 instances · 1 with errors") open the instance
+// list through `nml.showReferences`. The server attaches the command only
+// because activation declared `initializationOptions.showReferencesCommand`,
+// and passes `[uri, position, locations]` as plain JSON; VS Code's
+// `editor.action.showReferences` needs real `Uri`/`Position`/`Location`
+// values, so this converts — skipping anything malformed rather than
+// throwing — and opens the references peek at the lens.
+// ─────────────────────────────────────────────────────────────────────────
+
+export function registerShowReferences(context: ExtensionContext): void {
+  context.subscriptions.push(
+    commands.registerCommand(
+      "nml.showReferences",
+      async (uri: unknown, position: unknown, locations: unknown) => {
+        const at = toPosition(position);
+        if (typeof uri !== "string" || !at || !Array.isArray(locations)) {
+          return;
+        }
+        const targets = locations
+          .map(toLocation)
+          .filter((l): l is Location => l !== undefined);
+        await commands.executeCommand(
+          "editor.action.showReferences",
+          Uri.parse(uri),
+          at,
+          targets
+        );
+      }
+    )
+  );
+}
+
+function toPosition(v: unknown): Position | undefined {
+  if (!isRecord(v)) return undefined;
+  const { line, character } = v;
+  return typeof line === "number" && typeof character === "number"
+    ? new Position(line, character)
+    : undefined;
+}
+
+function toLocation(v: unknown): Location | undefined {
+  if (!isRecord(v) || typeof v.uri !== "string" || !isRecord(v.range)) {
+    return undefined;
+  }
+  const start = toPosition(v.range.start);
+  const end = toPosition(v.range.end);
+  return start && end
+    ? new Location(Uri.parse(v.uri), new Range(start, end))
+    : undefined;
+}
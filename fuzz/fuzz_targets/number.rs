@@ -1,11 +1,12 @@
 //! Fuzz the exact-decimal core (RFC 0016 §2): both grammars must never
 //! panic, and every accepted value must satisfy the round-trip, equality,
-//! and hashing invariants.
+//! and hashing invariants. An input of two space-separated numbers also
+//! drives the checked arithmetic: never a panic, and every `Ok` is exact.
 
 #![no_main]
 
 use libfuzzer_sys::fuzz_target;
-use nml_core::decimal::Number;
+use nml_core::decimal::{Number, RoundingMode};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -15,10 +16,65 @@ fn h(n: &Number) -> u64 {
     s.finish()
 }
 
+const MODES: [RoundingMode; 7] = [
+    RoundingMode::Down,
+    RoundingMode::Up,
+    RoundingMode::Ceiling,
+    RoundingMode::Floor,
+    RoundingMode::HalfUp,
+    RoundingMode::HalfDown,
+    RoundingMode::HalfEven,
+];
+
+/// Exactness oracles that need no wider arithmetic: an `Ok` sum or
+/// product must undo exactly, and operand order must not matter.
+fn arithmetic(a: &Number, b: &Number) {
+    let sum = a.checked_add(b);
+    match (&sum, &b.checked_add(a)) {
+        (Ok(x), Ok(y)) => assert_eq!(x.total_cmp(y), std::cmp::Ordering::Equal),
+        (x, y) => assert_eq!(x.is_ok(), y.is_ok(), "add commutativity of {a} and {b}"),
+    }
+    if let Ok(sum) = sum {
+        let back = sum
+            .checked_sub(b)
+            .unwrap_or_else(|e| panic!("({a}) + ({b}) - ({b}): {e:?}"));
+        assert_eq!(back, *a, "add/sub round-trip of {a} and {b}");
+    }
+    let prod = a.checked_mul(b);
+    assert_eq!(
+        prod.is_ok(),
+        b.checked_mul(a).is_ok(),
+        "mul commutativity of {a} and {b}"
+    );
+    if let Ok(prod) = prod {
+        if *b != Number::ZERO {
+            let back = prod
+                .checked_div_rounded(b, a.scale(), RoundingMode::Down)
+                .unwrap_or_else(|e| panic!("({a}) * ({b}) / ({b}): {e:?}"));
+            assert_eq!(back, *a, "mul/div round-trip of {a} and {b}");
+        }
+    }
+    // Division never panics; floor ≤ every mode ≤ ceiling, and the modes
+    // agree on whether the quotient fits.
+    let scale = b.scale();
+    let floor = a.checked_div_rounded(b, scale, RoundingMode::Floor);
+    let ceil = a.checked_div_rounded(b, scale, RoundingMode::Ceiling);
+    for mode in MODES {
+        if let (Ok(f), Ok(q), Ok(c)) = (&floor, a.checked_div_rounded(b, scale, mode), &ceil) {
+            assert!(*f <= q && q <= *c, "{a} / {b} under {mode:?}");
+        }
+    }
+}
+
 fuzz_target!(|data: &[u8]| {
     let Ok(s) = std::str::from_utf8(data) else {
         return;
     };
+    if let Some((l, r)) = s.split_once(' ') {
+        if let (Ok(a), Ok(b)) = (Number::parse_coercion(l), Number::parse_coercion(r)) {
+            arithmetic(&a, &b);
+        }
+    }
     for parse in [Number::parse_literal, Number::parse_coercion] {
         if let Ok(n) = parse(s) {
             // Display output reparses to an Eq-equal value with an equal
@@ -0,0 +1,153 @@
+//! Schema-package resolution for `nml check` (RFC 0030) — the editor's
+//! resolver ([`nml_validate::resolve`]), run from the command line so CI
+//! and the editor never disagree.
+//!
+//! The workspace root is the current directory, standing in for the
+//! editor's workspace folder: manifest and project lookups stop there, and
+//! a marker-less store or builtin package anchors its globs there. The
+//! manifests the editor would have indexed are the `*.package.nml` files in
+//! each checked file's ancestor directories.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use nml_validate::resolve::{
+    Binding, BindingStep, DefinitionSource, DegradedNote, PackageResolver, Resolution,
+    WorkspaceView, display_path,
+};
+use nml_validate::store::{Store, hash8};
+
+/// A file's binding, with the workspace roots its manifest path is shown
+/// relative to.
+pub struct Bound {
+    pub binding: Binding,
+    roots: Vec<PathBuf>,
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.binding;
+        let step = match b.step {
+            BindingStep::Pinned => "pinned",
+            BindingStep::AutoAssociated => "auto-associated",
+        };
+        let source = match &b.source {
+            DefinitionSource::WorkspaceManifest(path) => {
+                format!("workspace manifest {}", display_path(path, &self.roots))
+            }
+            source => source.label().to_string(),
+        };
+        write!(
+            f,
+            "package '{}' blake3:{} ({source}), validator '{}', {step}",
+            b.package_name,
+            hash8(&b.content_hash),
+            b.binding_name
+        )
+    }
+}
+
+/// What resolution found for one file, plus its degraded-state notes.
+pub struct Resolved {
+    pub bound: Option<Bound>,
+    pub notes: Vec<DegradedNote>,
+}
+
+/// Resolution state shared across a run's worker threads: the shared
+/// resolver caches each manifest, store slot, and validator, and each
+/// directory's manifests are read once however many files sit under it.
+pub struct Resolver {
+    roots: Vec<PathBuf>,
+    resolver: PackageResolver,
+    manifests: Mutex<HashMap<PathBuf, Vec<(PathBuf, String)>>>,
+}
+
+impl Resolver {
+    /// A resolver over the user store ([`Store::user`]) rooted at the
+    /// current directory. `strict` layers `--strict` over every binding's
+    /// own profile.
+    pub fn new(strict: bool) -> Self {
+        let mut resolver = PackageResolver::new(Store::user());
+        if strict {
+            resolver = resolver.strict();
+        }
+        Self {
+            roots: std::env::current_dir()
+                .ok()
+                .and_then(|d| d.canonicalize().ok())
+                .into_iter()
+                .collect(),
+            resolver,
+            manifests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve one file: pins in list order, first match wins; then, unless
+    /// the project opts out, the one package whose globs claim the file.
+    pub fn resolve(&self, path: &Path) -> Resolved {
+        let Ok(path) = path.canonicalize() else {
+            return Resolved {
+                bound: None,
+                notes: Vec::new(),
+            };
+        };
+        let manifests = self.manifests_above(&path);
+        let ws = WorkspaceView {
+            roots: &self.roots,
+            manifests: &manifests,
+            doc_text: &|_| None,
+        };
+        let resolved = self.resolver.resolve(&path, &ws);
+        Resolved {
+            bound: match resolved.resolution {
+                Resolution::Bound(binding) => Some(Bound {
+                    binding: *binding,
+                    roots: self.roots.clone(),
+                }),
+                Resolution::Unbound => None,
+            },
+            notes: resolved.notes,
+        }
+    }
+
+    /// The `*.package.nml` manifests in `path`'s ancestor directories, up
+    /// to the workspace root when the file is inside it.
+    fn manifests_above(&self, path: &Path) -> Vec<(PathBuf, String)> {
+        let root = self.roots.iter().find(|r| path.starts_with(r));
+        let mut cache = self.manifests.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = Vec::new();
+        for dir in path.ancestors().skip(1).take(64) {
+            out.extend(
+                cache
+                    .entry(dir.to_path_buf())
+                    .or_insert_with(|| manifests_in(dir))
+                    .iter()
+                    .cloned(),
+            );
+            if Some(dir) == root.map(PathBuf::as_path) {
+                break;
+            }
+        }
+        out
+    }
+}
+
+/// The readable `*.package.nml` manifests directly in `dir`, with their text.
+fn manifests_in(dir: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".package.nml"))
+                .is_some_and(|stem| !stem.is_empty())
+        })
+        .filter_map(|p| std::fs::read_to_string(&p).ok().map(|text| (p, text)))
+        .collect()
+}
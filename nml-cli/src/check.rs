@@ -0,0 +1,456 @@
+//! `nml check` — parse, validate and schema-check any number of files and
+//! directories in one run.
+//!
+//! The `--schema` directory is read and loaded once; files are then checked
+//! in parallel against it (or against the schema package that binds them,
+//! see [`crate::binding`]) and reported in path order, so the output is the
+//! same however the work was scheduled. Every file gets a summary line and
+//! the run fails when any file does.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use nml_core::diagnostic::{Code, Diagnostic, Severity};
+use nml_validate::schema::SchemaValidator;
+
+use crate::binding::Resolver;
+use crate::output::Reporter;
+use crate::pipeline;
+
+const USAGE: &str = "usage: nml check [--schema <dir>] [--strict] [--explain-binding] <path>...";
+
+pub fn cmd_check(args: &[String], reporter: &mut Reporter) -> Result<(), String> {
+    let mut schema_dir: Option<PathBuf> = None;
+    let mut strict = false;
+    let mut explain_binding = false;
+    let mut path_args: Vec<&String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--schema" {
+            i += 1;
+            if i >= args.len() {
+                return Err("--schema requires a path argument".to_string());
+            }
+            schema_dir = Some(PathBuf::from(&args[i]));
+        } else if args[i] == "--strict" {
+            strict = true;
+        } else if args[i] == "--explain-binding" {
+            explain_binding = true;
+        } else {
+            path_args.push(&args[i]);
+        }
+        i += 1;
+    }
+
+    if path_args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let files = pipeline::collect_nml_files(&path_args)?;
+    if files.is_empty() {
+        return Err("no .nml files found under the given paths".to_string());
+    }
+
+    let schema_sources = match &schema_dir {
+        Some(dir) => pipeline::read_schema_dir(dir)?,
+        None => Vec::new(),
+    };
+    let run = Run::new(schema_dir.as_deref(), &schema_sources, strict);
+    let checks = run.check_all(&files);
+    run.report(reporter, &files, &checks, explain_binding)
+}
+
+/// Everything a run shares across files. Read-only apart from the
+/// resolver's caches, which lock internally — so one `&Run` serves every
+/// worker thread.
+pub(crate) struct Run<'a> {
+    schema_dir: Option<&'a Path>,
+    schema_sources: &'a [(PathBuf, String)],
+    /// The `--schema` directory alone, loaded once: the universe of every
+    /// file that declares no definitions of its own.
+    base: Universe,
+    resolver: Resolver,
+    strict: bool,
+}
+
+/// The `--schema` directory loaded alone: its definition findings and,
+/// when it defines anything, the instance validator it composes into.
+struct Universe {
+    findings: Vec<Finding>,
+    validator: Option<Arc<SchemaValidator>>,
+}
+
+impl Universe {
+    fn load(schema_dir: Option<&Path>, sources: &[(PathBuf, String)], strict: bool) -> Self {
+        let named = pipeline::named_schema_sources(sources);
+        let loaded = pipeline::load_universe(&named);
+        let unlocated = schema_dir.unwrap_or(Path::new("."));
+        let findings = loaded
+            .findings
+            .into_iter()
+            .map(|(diag, attributed)| match attributed {
+                Some((at, text)) => Finding::Elsewhere(at.to_path_buf(), text.to_string(), diag),
+                None => Finding::Unlocated(unlocated.to_path_buf(), diag),
+            })
+            .collect();
+        Self {
+            findings,
+            validator: instance_validator(loaded.schema, strict),
+        }
+    }
+}
+
+/// The instance validator a loaded universe composes into, or `None` when
+/// it defines nothing. Definition composition is covered by the load
+/// itself — instance-only here, so no finding is ever reported twice.
+/// `--strict` makes unknown properties and unmodeled keywords errors (CI
+/// posture; the same profile package bindings can set).
+fn instance_validator(
+    schema: nml_core::schema::ExtractedSchema,
+    strict: bool,
+) -> Option<Arc<SchemaValidator>> {
+    if schema.is_empty() {
+        return None;
+    }
+    let validator = SchemaValidator::from(schema).composition_checked_at_load();
+    Some(Arc::new(if strict {
+        validator.strict()
+    } else {
+        validator
+    }))
+}
+
+/// One file's results, gathered off-thread and reported in path order.
+#[derive(Clone)]
+pub(crate) struct FileCheck {
+    source: String,
+    /// Which package and validator claimed the file, for
+    /// `--explain-binding`.
+    binding: String,
+    findings: Vec<Finding>,
+    errors: usize,
+    declarations: usize,
+    /// Why the file could not be checked at all (unreadable, unparseable,
+    /// `--strict` with nothing to enforce) — its summary line.
+    failure: Option<String>,
+    /// The files its `import`s reached: a change to any of them changes
+    /// this file's verdict (what `nml watch` re-checks on).
+    pub(crate) imports: Vec<PathBuf>,
+}
+
+impl FileCheck {
+    fn failed(source: String, binding: String, failure: String) -> Self {
+        Self {
+            source,
+            binding,
+            findings: Vec::new(),
+            errors: 0,
+            declarations: 0,
+            failure: Some(failure),
+            imports: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, finding: Finding) {
+        if matches!(finding.diag().severity, Severity::Error) {
+            self.errors += 1;
+        }
+        self.findings.push(finding);
+    }
+
+    fn verdict(&self) -> Result<(), String> {
+        match &self.failure {
+            Some(why) => Err(why.clone()),
+            None if self.errors > 0 => Err(format!("{} error(s)", self.errors)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Where a finding is located: in the checked file, in another file with
+/// its own text (an import, a `--schema` source), or nowhere in particular.
+#[derive(Clone)]
+enum Finding {
+    Here(Diagnostic),
+    Elsewhere(PathBuf, String, Diagnostic),
+    Unlocated(PathBuf, Diagnostic),
+}
+
+impl Finding {
+    fn diag(&self) -> &Diagnostic {
+        match self {
+            Self::Here(d) | Self::Elsewhere(_, _, d) | Self::Unlocated(_, d) => d,
+        }
+    }
+
+    fn report(&self, reporter: &mut Reporter, path: &Path, source: &str) -> Option<Code> {
+        match self {
+            Self::Here(d) => reporter.report(path, source, d),
+            Self::Elsewhere(at, text, d) => reporter.report(at, text, d),
+            Self::Unlocated(at, d) => reporter.report_unlocated(at, d),
+        }
+    }
+}
+
+impl<'a> Run<'a> {
+    pub(crate) fn new(
+        schema_dir: Option<&'a Path>,
+        schema_sources: &'a [(PathBuf, String)],
+        strict: bool,
+    ) -> Self {
+        Run {
+            schema_dir,
+            base: Universe::load(schema_dir, schema_sources, strict),
+            schema_sources,
+            resolver: Resolver::new(strict),
+            strict,
+        }
+    }
+
+    /// Report a run's findings and summary lines: the `--schema`
+    /// directory's own findings once, then each file's in path order.
+    /// The verdict fails when any file or the directory does.
+    pub(crate) fn report(
+        &self,
+        reporter: &mut Reporter,
+        files: &[PathBuf],
+        checks: &[FileCheck],
+        explain_binding: bool,
+    ) -> Result<(), String> {
+        // Findings in the `--schema` directory itself belong to no checked
+        // file: reported once, ahead of the per-file findings.
+        let mut first_code = None;
+        let mut schema_errors = 0;
+        for finding in &self.base.findings {
+            first_code = first_code.or(finding.report(reporter, Path::new("."), ""));
+            if matches!(finding.diag().severity, Severity::Error) {
+                schema_errors += 1;
+            }
+        }
+
+        let mut failed: Vec<String> = Vec::new();
+        for (path, check) in files.iter().zip(checks) {
+            if explain_binding {
+                reporter.status(format_args!("{}: {}", path.display(), check.binding));
+            }
+            for finding in &check.findings {
+                first_code = first_code.or(finding.report(reporter, path, &check.source));
+            }
+            match check.verdict() {
+                Ok(()) => reporter.status(format_args!(
+                    "{}: ok ({} declaration(s))",
+                    path.display(),
+                    check.declarations
+                )),
+                Err(why) => {
+                    reporter.status(format_args!("{}: {why}", path.display()));
+                    failed.push(why);
+                }
+            }
+        }
+        reporter.explain_hint(first_code);
+
+        if schema_errors > 0 {
+            let dir = self.schema_dir.unwrap_or(Path::new("--schema"));
+            reporter.status(format_args!("{}: {schema_errors} error(s)", dir.display()));
+        }
+        if files.len() > 1 {
+            reporter.status(format_args!(
+                "checked {} file(s): {} ok, {} failed",
+                files.len(),
+                files.len() - failed.len(),
+                failed.len()
+            ));
+        }
+
+        match (files.len(), failed.pop()) {
+            (_, None) if schema_errors == 0 => Ok(()),
+            (_, None) => Err(format!("{schema_errors} error(s) in the schema directory")),
+            // One file keeps its own verdict as the run's, as before.
+            (1, Some(why)) if schema_errors == 0 => Err(why),
+            _ => Err(format!(
+                "{} of {} file(s) failed{}",
+                failed.len() + 1,
+                files.len(),
+                if schema_errors > 0 {
+                    format!("; {schema_errors} error(s) in the schema directory")
+                } else {
+                    String::new()
+                }
+            )),
+        }
+    }
+
+    /// Check every file, fanned out over the available cores. Results land
+    /// in input order.
+    pub(crate) fn check_all(&self, files: &[PathBuf]) -> Vec<FileCheck> {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(files.len());
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, FileCheck)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(path) = files.get(i) else {
+                                break done;
+                            };
+                            done.push((i, self.check_file(path)));
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("check worker panicked"))
+                .collect()
+        });
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, check)| check).collect()
+    }
+
+    pub(crate) fn check_file(&self, path: &Path) -> FileCheck {
+        // Binding depends on where the file sits, never on what it says.
+        let resolved = self.resolver.resolve(path);
+        let binding = match &resolved.bound {
+            Some(bound) => format!("bound to {bound}"),
+            None if self.schema_dir.is_some() => {
+                "unbound — checked against --schema and its own definitions".to_string()
+            }
+            None => "unbound — checked against its own definitions".to_string(),
+        };
+
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                return FileCheck::failed(
+                    String::new(),
+                    binding,
+                    format!("failed to read {}: {e}", path.display()),
+                );
+            }
+        };
+
+        let (file, parse_errors) = nml_core::cst::parse_to_ast_all(&source);
+        if !parse_errors.is_empty() {
+            let why = format!("{} parse error(s)", parse_errors.len());
+            let mut check = FileCheck::failed(source, binding, why);
+            check
+                .findings
+                .extend(parse_errors.into_iter().map(Finding::Here));
+            return check;
+        }
+        let mut check = FileCheck {
+            source,
+            binding,
+            findings: Vec::new(),
+            errors: 0,
+            declarations: file.declarations.len(),
+            failure: None,
+            imports: Vec::new(),
+        };
+
+        // Names resolve against the file plus what its `import`s bring in.
+        let (symbols, imports) = pipeline::symbols_with_imports(path, &file);
+        check.imports = imports.files().iter().map(|f| f.path.clone()).collect();
+        for err in symbols
+            .find_duplicates()
+            .into_iter()
+            .chain(symbols.find_unresolved_references(&file))
+            .chain(symbols.find_const_cycles())
+        {
+            check.push(Finding::Here(err));
+        }
+        for (at, diag) in imports.diagnostics() {
+            check.push(if at == path {
+                Finding::Here(diag.clone())
+            } else {
+                let text = imports.file(at).map_or("", |f| f.source.as_str());
+                Finding::Elsewhere(at.clone(), text.to_string(), diag.clone())
+            });
+        }
+
+        // A package binding is exclusive: its composed schemas are the
+        // whole authority for the file, never merged with `--schema`.
+        for note in resolved.notes {
+            let diag = if note.warning {
+                Diagnostic::warning(note.message)
+            } else {
+                Diagnostic::info(note.message)
+            };
+            check.push(Finding::Unlocated(path.to_path_buf(), diag));
+        }
+        let validator = match resolved.bound {
+            Some(bound) => Some(bound.binding.validator),
+            None => self.universe_for(path, &mut check),
+        };
+
+        match validator {
+            Some(validator) => {
+                for diag in validator.validate(&file) {
+                    check.push(Finding::Here(diag));
+                }
+            }
+            // `--strict` promises enforcement; with an empty schema universe
+            // there is nothing to enforce, and silently degrading to
+            // parse-only checking is how a CI pipeline points at the wrong
+            // path and stays green forever. Fail the file, naming the
+            // actual mistake.
+            None if self.strict => {
+                check.failure = Some(
+                    "--strict has nothing to enforce: no schema definitions found \
+                     (no --schema directory given and none declared in the file)"
+                        .to_string(),
+                );
+            }
+            None => {}
+        }
+        check
+    }
+
+    /// The unbound file's validator. One schema universe per check (RFC
+    /// 0012): the `--schema` directory's sources plus the file itself
+    /// (unless it *is* one of them), so a self-contained file validates
+    /// with no flags and a name declared in both the file and the directory
+    /// is NML2009 — never a silent shadow. A file declaring no definitions
+    /// adds nothing to the universe and shares the one loaded up front;
+    /// only a file with definitions of its own pays for a load, whose
+    /// findings already reported for the directory are dropped here.
+    fn universe_for(&self, path: &Path, check: &mut FileCheck) -> Option<Arc<SchemaValidator>> {
+        if nml_core::cst::extract_schema(&check.source).0.is_empty() {
+            return self.base.validator.clone();
+        }
+        let named = pipeline::assemble_universe(path, &check.source, self.schema_sources);
+        let loaded = pipeline::load_universe(&named);
+
+        // Attributed findings print `path:line:col` against their declaring
+        // source; a finding no single definition owns falls back to a
+        // location-less line under the schema dir (or the file).
+        for (diag, attributed) in loaded.findings {
+            if self
+                .base
+                .findings
+                .iter()
+                .any(|seen| same_finding(seen.diag(), &diag))
+            {
+                continue;
+            }
+            check.push(match attributed {
+                Some((at, _)) if at == path => Finding::Here(diag),
+                Some((at, text)) => Finding::Elsewhere(at.to_path_buf(), text.to_string(), diag),
+                None => Finding::Unlocated(self.schema_dir.unwrap_or(path).to_path_buf(), diag),
+            });
+        }
+        instance_validator(loaded.schema, self.strict)
+    }
+}
+
+/// Whether two definition findings are the same one, as loaded from the
+/// directory alone and again with a checked file alongside.
+fn same_finding(a: &Diagnostic, b: &Diagnostic) -> bool {
+    a.code == b.code && a.source == b.source && a.span == b.span && a.message == b.message
+}
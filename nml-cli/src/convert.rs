@@ -0,0 +1,792 @@
+//! `nml convert` — bring TOML, JSON and YAML configs into NML, and export
+//! NML back out as JSON.
+//!
+//! Into NML, the document's shape picks the spelling: a top-level
+//! `keyword` table of `Name` tables is one `keyword Name:` block per name
+//! (an array of tables under `Name` is `[]keyword Name:`), nested tables
+//! are nested blocks, and arrays of tables are named list items labeled by
+//! each element's `name`. `--block '<keyword> <Name>'` instead wraps the
+//! whole document as that one block — the usual shape of a TOML file.
+//! Numbers are exact [`Number`]s: JSON numbers as written, TOML and YAML
+//! integers as written and their floats as the shortest decimal that reads
+//! back to the same value.
+//!
+//! `--schema` reads the document against the block's model: `duration`
+//! and `money` fields written as strings (`"30s"`, `"19.99 USD"`) become
+//! typed literals, and list items of a model with a positional (`+`)
+//! field are written in shorthand (`- "/api"`).
+//!
+//! Out of NML, `--to json` prints the file the way `nml query --resolve`
+//! reads it — positional items materialized, shared properties merged,
+//! schema defaults applied, consts and `$ENV` resolved — as one
+//! `{"keyword": {"Name": …}}` document, numbers encoded as `nml parse`
+//! encodes them. Secrets print resolved: the caller asked for the values.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use nml_core::ast::{
+    ArrayBody, ArrayDecl, BlockDecl, Body, BodyEntry, BodyEntryKind, Declaration, DeclarationKind,
+    File, Identifier, ListItem, ListItemKind, NestedBlock, Property,
+};
+use nml_core::duration::Duration;
+use nml_core::model::{FieldDef, FieldType, ModelDef};
+use nml_core::query::Selected;
+use nml_core::span::Span;
+use nml_core::types::{Number, PrimitiveType, SpannedValue, Value};
+use nml_core::{FieldTarget, SchemaIndex};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::value::RawValue;
+
+use crate::output::{Format, Reporter};
+use crate::{pipeline, query};
+
+const USAGE: &str = "usage: nml convert [--from toml|json|yaml|nml] [--to nml|json] \
+                     [--schema <dir>] [--block '<keyword> <Name>'] <file>";
+
+/// The field `toml` reads a datetime into — its only non-JSON value.
+const TOML_DATETIME: &str = "$__toml_private_datetime";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Nml,
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Syntax {
+    fn from_name(name: &str) -> Result<Syntax, String> {
+        match name {
+            "nml" => Ok(Syntax::Nml),
+            "json" => Ok(Syntax::Json),
+            "toml" => Ok(Syntax::Toml),
+            "yaml" | "yml" => Ok(Syntax::Yaml),
+            other => Err(format!("unknown format '{other}'; {USAGE}")),
+        }
+    }
+
+    fn from_extension(path: &Path) -> Option<Syntax> {
+        Syntax::from_name(path.extension()?.to_str()?).ok()
+    }
+}
+
+pub fn cmd_convert(args: &[String]) -> Result<(), String> {
+    let mut from = None;
+    let mut to = None;
+    let mut schema_dir = None;
+    let mut block = None;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--from" => from = Some(Syntax::from_name(iter.next().ok_or(USAGE)?)?),
+            "--to" => to = Some(Syntax::from_name(iter.next().ok_or(USAGE)?)?),
+            "--schema" => {
+                let dir = iter.next().ok_or("--schema requires a directory")?;
+                schema_dir = Some(PathBuf::from(dir));
+            }
+            "--block" => block = Some(iter.next().ok_or("--block requires '<keyword> <Name>'")?),
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [path] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+    let path = PathBuf::from(path);
+    let from = from
+        .or_else(|| Syntax::from_extension(&path))
+        .ok_or_else(|| format!("cannot tell the format of {}; pass --from", path.display()))?;
+    let to = to.unwrap_or(if from == Syntax::Nml {
+        Syntax::Json
+    } else {
+        Syntax::Nml
+    });
+    match (from, to) {
+        (Syntax::Nml, Syntax::Json) => {
+            if block.is_some() {
+                return Err("--block only applies when converting into nml".to_string());
+            }
+            export_json(&path, schema_dir.as_deref())
+        }
+        (Syntax::Json | Syntax::Toml | Syntax::Yaml, Syntax::Nml) => convert_to_nml(
+            &path,
+            from,
+            schema_dir.as_deref(),
+            block.map(String::as_str),
+        ),
+        _ => Err("nml convert reads toml, json or yaml into nml, or nml into json".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Into NML
+// ---------------------------------------------------------------------------
+
+fn convert_to_nml(
+    path: &Path,
+    from: Syntax,
+    schema_dir: Option<&Path>,
+    block: Option<&str>,
+) -> Result<(), String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let data = match from {
+        Syntax::Json => serde_json::from_str::<&RawValue>(&source)
+            .map_err(|e| e.to_string())
+            .and_then(|raw| json_data(raw, "")),
+        Syntax::Toml => toml::from_str::<Read<false>>(&source)
+            .map(|read| read.0)
+            .map_err(|e| e.to_string()),
+        Syntax::Yaml => serde_norway::from_str::<Read<true>>(&source)
+            .map(|read| read.0)
+            .map_err(|e| e.to_string()),
+        Syntax::Nml => unreachable!("nml input is exported, not converted"),
+    }
+    .map_err(|e| format!("{}: {e}", path.display()))?;
+    let Data::Table(root) = data else {
+        return Err(format!(
+            "{}: the document is {}, not a table",
+            path.display(),
+            data.kind()
+        ));
+    };
+
+    let index = match schema_dir {
+        Some(dir) => {
+            let named: Vec<(String, PathBuf, String)> = pipeline::read_schema_dir(dir)?
+                .into_iter()
+                .map(|(p, text)| (pipeline::schema_load_name(&p), p, text))
+                .collect();
+            let mut reporter = Reporter::new(Format::Human);
+            let index = pipeline::load_index(&mut reporter, &named, dir);
+            reporter.finish();
+            Some(index?)
+        }
+        None => None,
+    };
+    let builder = Builder {
+        index: index.as_ref(),
+    };
+    let declarations = match block {
+        Some(head) => {
+            let (keyword, name) = head
+                .split_once(' ')
+                .map(|(k, n)| (k.trim(), n.trim()))
+                .ok_or("--block takes '<keyword> <Name>', e.g. --block 'service Api'")?;
+            vec![builder.declaration(keyword, name, &Data::Table(root))?]
+        }
+        None => builder.declarations(&root)?,
+    };
+    print!("{}", nml_fmt::formatter::format(&File { declarations }));
+    Ok(())
+}
+
+/// A foreign document as read: key order kept, so the converted file
+/// reads in the order the original did, and numbers already exact.
+#[derive(Clone)]
+enum Data {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    List(Vec<Data>),
+    Table(Vec<(String, Data)>),
+}
+
+impl Data {
+    fn kind(&self) -> &'static str {
+        match self {
+            Data::Null => "null",
+            Data::Bool(_) => "a bool",
+            Data::Number(_) => "a number",
+            Data::String(_) => "a string",
+            Data::List(_) => "an array",
+            Data::Table(_) => "a table",
+        }
+    }
+}
+
+/// A [`Data`] document read from a format that hands integers past its
+/// integer range over as floats (`FLOAT_INTS`: YAML, past 128 bits) or
+/// never does (TOML; JSON numbers never reach a visitor, see
+/// [`json_data`]).
+struct Read<const FLOAT_INTS: bool>(Data);
+
+impl<'de, const FLOAT_INTS: bool> Deserialize<'de> for Read<FLOAT_INTS> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(DataVisitor::<FLOAT_INTS>)
+            .map(Read)
+    }
+}
+
+struct DataVisitor<const FLOAT_INTS: bool>;
+
+impl<'de, const FLOAT_INTS: bool> Visitor<'de> for DataVisitor<FLOAT_INTS> {
+    type Value = Data;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a configuration value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Data, E> {
+        Ok(Data::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Data, E> {
+        Ok(Data::Number(Number::from(v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Data, E> {
+        Ok(Data::Number(Number::from_u64(v)))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Data, E> {
+        Number::try_from(v).map(Data::Number).map_err(E::custom)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Data, E> {
+        Number::try_from(v).map(Data::Number).map_err(E::custom)
+    }
+
+    /// A float written as one reads exactly as its shortest decimal. YAML
+    /// hands integers past 128 bits over as floats too, and their digits
+    /// are already gone, so an integral float that large is refused rather
+    /// than guessed at. Messages show `v` in exponent form: a float's
+    /// positional digits can run to hundreds.
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Data, E> {
+        if FLOAT_INTS && v.fract() == 0.0 && v.abs() >= i128::MAX as f64 {
+            return Err(E::custom(format!(
+                "{v:e} is too large to read exactly; quote it as a string"
+            )));
+        }
+        Number::try_from_f64(v)
+            .map(Data::Number)
+            .map_err(|e| E::custom(format!("{v:e} has no NML number: {e}")))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Data, E> {
+        Ok(Data::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Data, E> {
+        Ok(Data::String(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Data, E> {
+        Ok(Data::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Data, E> {
+        Ok(Data::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Data, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Data, A::Error> {
+        let mut items = Vec::new();
+        while let Some(Read::<FLOAT_INTS>(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Data::List(items))
+    }
+
+    /// YAML keys may be numbers or bools; they name fields by their text.
+    /// A TOML datetime arrives as a one-field map and has no NML literal,
+    /// so it is kept as its RFC 3339 text.
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Data, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(Read::<FLOAT_INTS>(key)) = map.next_key()? {
+            let key = match key {
+                Data::String(s) => s,
+                Data::Number(n) => n.to_string(),
+                Data::Bool(b) => b.to_string(),
+                other => {
+                    return Err(de::Error::custom(format!(
+                        "a key must be a string, not {}",
+                        other.kind()
+                    )));
+                }
+            };
+            let Read::<FLOAT_INTS>(value) = map.next_value()?;
+            if key == TOML_DATETIME && entries.is_empty() {
+                if let Data::String(text) = value {
+                    return Ok(Data::String(text));
+                }
+            }
+            entries.push((key, value));
+        }
+        Ok(Data::Table(entries))
+    }
+}
+
+/// A JSON value as [`Data`], numbers read from their text — a JSON number
+/// past `u64` or with more digits than `f64` keeps every one of them, or is
+/// refused when NML cannot hold it exactly. `raw` is already valid JSON,
+/// so only a number can fail; `at` is its document path, for the message.
+fn json_data(raw: &RawValue, at: &str) -> Result<Data, String> {
+    let text = raw.get();
+    let nested = |key: &str| match at {
+        "" => key.to_string(),
+        _ => format!("{at}.{key}"),
+    };
+    match text.as_bytes().first() {
+        Some(b'{') => {
+            let JsonTable(entries) = serde_json::from_str(text).map_err(|e| e.to_string())?;
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = json_data(value, &nested(&key))?;
+                    Ok((key, value))
+                })
+                .collect::<Result<_, String>>()
+                .map(Data::Table)
+        }
+        Some(b'[') => {
+            let items: Vec<&RawValue> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| json_data(item, &format!("{at}[{i}]")))
+                .collect::<Result<_, String>>()
+                .map(Data::List)
+        }
+        Some(b'-' | b'0'..=b'9') => {
+            Number::parse_coercion(text)
+                .map(Data::Number)
+                .map_err(|e| match at {
+                    "" => format!("{text} has no NML number: {e}"),
+                    _ => format!("{at}: {text} has no NML number: {e}"),
+                })
+        }
+        _ => serde_json::from_str::<Read<false>>(text)
+            .map(|read| read.0)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// A JSON object's members in document order, values still unread.
+struct JsonTable<'a>(Vec<(String, &'a RawValue)>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for JsonTable<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TableVisitor;
+
+        impl<'de> Visitor<'de> for TableVisitor {
+            type Value = JsonTable<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(JsonTable(entries))
+            }
+        }
+
+        deserializer.deserialize_map(TableVisitor)
+    }
+}
+
+/// Builds declarations from a [`Data`] document, reading it against the
+/// schema when there is one. Errors name the document path they are at.
+struct Builder<'a> {
+    index: Option<&'a SchemaIndex>,
+}
+
+/// The span every converted node carries: the tree has no NML source
+/// until it is formatted.
+const CONVERTED: Span = Span { start: 0, end: 0 };
+
+impl<'a> Builder<'a> {
+    /// One declaration per `keyword.Name` of the document root.
+    fn declarations(&self, root: &[(String, Data)]) -> Result<Vec<Declaration>, String> {
+        let mut out = Vec::new();
+        for (keyword, value) in root {
+            let Data::Table(names) = value else {
+                return Err(format!(
+                    "{keyword}: the top level holds `keyword Name` tables, not {} \
+                     (use --block '<keyword> <Name>' to wrap the whole document)",
+                    value.kind()
+                ));
+            };
+            for (name, body) in names {
+                out.push(self.declaration(keyword, name, body)?);
+            }
+        }
+        Ok(out)
+    }
+
+    fn declaration(&self, keyword: &str, name: &str, data: &Data) -> Result<Declaration, String> {
+        let path = format!("{keyword}.{name}");
+        let keyword_ident = identifier(&path, keyword)?;
+        let name_ident = identifier(&path, name)?;
+        let model = match self.index {
+            Some(index) => match (index.resolve_ref(keyword), data) {
+                (Some(target), Data::Table(entries)) => self.model_of(target, entries),
+                (Some(FieldTarget::Model(model)), _) => Some(model),
+                _ => return Err(format!("'{keyword}' has no model in the schema")),
+            },
+            None => None,
+        };
+        let kind = match data {
+            Data::Table(entries) => DeclarationKind::Block(BlockDecl {
+                keyword: keyword_ident,
+                name: name_ident,
+                extends: Vec::new(),
+                body: self.body(&path, entries, model)?,
+            }),
+            Data::List(items) => DeclarationKind::Array(ArrayDecl {
+                item_keyword: keyword_ident,
+                name: name_ident,
+                body: ArrayBody {
+                    modifiers: Vec::new(),
+                    shared_properties: Vec::new(),
+                    properties: Vec::new(),
+                    items: self.items(&path, items, model)?,
+                },
+            }),
+            other => {
+                return Err(format!(
+                    "{path}: a declaration is a table or an array of tables, not {}",
+                    other.kind()
+                ));
+            }
+        };
+        Ok(Declaration {
+            kind,
+            span: CONVERTED,
+        })
+    }
+
+    /// The model a table is an instance of: a `oneof` picks its variant by
+    /// the table's discriminator (or the union's default).
+    fn model_of(
+        &self,
+        target: FieldTarget<'a>,
+        entries: &[(String, Data)],
+    ) -> Option<&'a ModelDef> {
+        match target {
+            FieldTarget::Model(model) => Some(model),
+            FieldTarget::OneOf(oneof) => {
+                let chosen = entries
+                    .iter()
+                    .find(|(k, _)| *k == oneof.discriminator)
+                    .and_then(|(_, v)| match v {
+                        Data::String(s) => Some(s.as_str()),
+                        _ => None,
+                    })
+                    .or(oneof.default_discriminator.as_deref())?;
+                let (_, variant) = oneof.variants.iter().find(|(v, _)| v == chosen)?;
+                self.index?.model(variant)
+            }
+            _ => None,
+        }
+    }
+
+    fn body(
+        &self,
+        path: &str,
+        entries: &[(String, Data)],
+        model: Option<&'a ModelDef>,
+    ) -> Result<Body, String> {
+        let mut out = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            let path = format!("{path}.{key}");
+            let field = model.and_then(|m| m.fields.iter().find(|f| f.name == *key));
+            if let Some(kind) = self.entry(&path, key, value, field)? {
+                out.push(BodyEntry {
+                    kind,
+                    span: CONVERTED,
+                });
+            }
+        }
+        Ok(Body::fresh(out))
+    }
+
+    /// Place one field: tables are nested blocks, arrays holding tables
+    /// (or scalars of a model with a positional field) are blocks of list
+    /// items, everything else is a property. Nulls are omitted.
+    fn entry(
+        &self,
+        path: &str,
+        key: &str,
+        value: &Data,
+        field: Option<&'a FieldDef>,
+    ) -> Result<Option<BodyEntryKind>, String> {
+        let name = identifier(path, key)?;
+        let target = field
+            .zip(self.index)
+            .map(|(f, index)| index.resolve_field(f));
+        Ok(Some(match value {
+            Data::Null => return Ok(None),
+            Data::Table(entries) => {
+                let model = target.and_then(|t| self.model_of(t, entries));
+                BodyEntryKind::NestedBlock(NestedBlock {
+                    name,
+                    body: self.body(path, entries, model)?,
+                })
+            }
+            Data::List(items) => {
+                let element = match target {
+                    Some(FieldTarget::ListOf(_, element) | FieldTarget::SetOf(_, element)) => {
+                        Some(*element)
+                    }
+                    _ => None,
+                };
+                let shorthand = match &element {
+                    Some(FieldTarget::Model(model)) => model.fields.iter().any(|f| f.shorthand),
+                    _ => false,
+                };
+                if shorthand || items.iter().any(|i| matches!(i, Data::Table(_))) {
+                    let model = match element {
+                        Some(FieldTarget::Model(model)) => Some(model),
+                        _ => None,
+                    };
+                    let items = self.items(path, items, model)?;
+                    let entries = items
+                        .into_iter()
+                        .map(|item| BodyEntry {
+                            kind: BodyEntryKind::ListItem(item),
+                            span: CONVERTED,
+                        })
+                        .collect();
+                    BodyEntryKind::NestedBlock(NestedBlock {
+                        name,
+                        body: Body::fresh(entries),
+                    })
+                } else {
+                    BodyEntryKind::Property(Property {
+                        name,
+                        value: spanned(self.value(path, value, field.map(|f| &f.field_type))?),
+                    })
+                }
+            }
+            scalar => BodyEntryKind::Property(Property {
+                name,
+                value: spanned(self.value(path, scalar, field.map(|f| &f.field_type))?),
+            }),
+        }))
+    }
+
+    fn items(
+        &self,
+        path: &str,
+        items: &[Data],
+        model: Option<&'a ModelDef>,
+    ) -> Result<Vec<ListItem>, String> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                self.item(&format!("{path}[{i}]"), item, model)
+                    .map(|kind| ListItem {
+                        kind,
+                        span: CONVERTED,
+                    })
+            })
+            .collect()
+    }
+
+    /// A list element: labeled by its `name` when it has one, otherwise in
+    /// shorthand when its model declares a positional field and the element
+    /// sets it.
+    fn item(
+        &self,
+        path: &str,
+        item: &Data,
+        model: Option<&'a ModelDef>,
+    ) -> Result<ListItemKind, String> {
+        let positional = model.and_then(|m| m.fields.iter().find(|f| f.shorthand));
+        let entries = match item {
+            Data::Table(entries) => entries,
+            Data::List(_) | Data::Null => {
+                return Err(format!(
+                    "{path}: a list of tables cannot also hold {}",
+                    item.kind()
+                ));
+            }
+            scalar => {
+                let Some(field) = positional else {
+                    return Err(format!(
+                        "{path}: a list of tables cannot also hold {}",
+                        scalar.kind()
+                    ));
+                };
+                return Ok(ListItemKind::Shorthand {
+                    value: spanned(self.value(path, scalar, Some(&field.field_type))?),
+                    body: None,
+                });
+            }
+        };
+        let key_of = |field: &str| entries.iter().position(|(k, _)| k == field);
+        // The label wins over the shorthand: `name` is the item's identity,
+        // not a field, so a shorthand item would carry it as an unknown
+        // property and lose it on the way back.
+        if let Some(at) = key_of("name") {
+            let Data::String(label) = &entries[at].1 else {
+                return Err(format!("{path}: a list element's `name` must be a string"));
+            };
+            return Ok(ListItemKind::Named {
+                name: identifier(path, label)?,
+                body: self.body(path, &rest_of(entries, at), model)?,
+            });
+        }
+        let Some((field, at)) = positional.and_then(|field| {
+            key_of(&field.name)
+                .filter(|&at| is_scalar(&entries[at].1))
+                .map(|at| (field, at))
+        }) else {
+            return Err(format!(
+                "{path}: a table in a list needs a `name` to label its item"
+            ));
+        };
+        let value = self.value(path, &entries[at].1, Some(&field.field_type))?;
+        let rest: Vec<(String, Data)> = rest_of(entries, at);
+        Ok(ListItemKind::Shorthand {
+            value: spanned(value),
+            body: if rest.is_empty() {
+                None
+            } else {
+                Some(self.body(path, &rest, model)?)
+            },
+        })
+    }
+
+    /// A scalar or array of scalars. With a schema, strings in `duration`
+    /// and `money` fields become typed literals when they read as one.
+    fn value(&self, path: &str, data: &Data, ty: Option<&FieldType>) -> Result<Value, String> {
+        let ty = match ty {
+            Some(FieldType::Modifier(inner)) => Some(&**inner),
+            other => other,
+        };
+        Ok(match data {
+            Data::Bool(b) => Value::Bool(*b),
+            Data::Number(n) => Value::Number(*n),
+            Data::String(s) => typed_string(s, ty).unwrap_or_else(|| Value::String(s.clone())),
+            Data::List(items) => {
+                let element = match ty {
+                    Some(FieldType::List(inner, _) | FieldType::Set(inner, _)) => Some(&**inner),
+                    _ => None,
+                };
+                let mut out = Vec::with_capacity(items.len());
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{path}[{i}]");
+                    out.push(spanned(self.value(&path, item, element)?));
+                }
+                Value::Array(out)
+            }
+            Data::Null => return Err(format!("{path}: null has no NML spelling in an array")),
+            Data::Table(_) => return Err(format!("{path}: an array cannot hold a table")),
+        })
+    }
+}
+
+fn typed_string(text: &str, ty: Option<&FieldType>) -> Option<Value> {
+    let Some(FieldType::Primitive { ty, .. }) = ty else {
+        return None;
+    };
+    match ty {
+        PrimitiveType::Duration => Duration::parse_text(text).ok().map(Value::Duration),
+        PrimitiveType::Money => {
+            let (amount, currency) = text.trim().split_once(' ')?;
+            nml_core::money::parse_money(amount, currency.trim(), CONVERTED)
+                .ok()
+                .map(Value::Money)
+        }
+        _ => None,
+    }
+}
+
+fn is_scalar(data: &Data) -> bool {
+    matches!(data, Data::Bool(_) | Data::Number(_) | Data::String(_))
+}
+
+/// A table's entries without the one at `at` (the label an item spelled).
+fn rest_of(entries: &[(String, Data)], at: usize) -> Vec<(String, Data)> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != at)
+        .map(|(_, (k, v))| (k.clone(), v.clone()))
+        .collect()
+}
+
+fn spanned(value: Value) -> SpannedValue {
+    SpannedValue::new(value, CONVERTED)
+}
+
+/// An identifier per the lexer's rule (`[A-Za-z_][A-Za-z0-9_-]*`).
+fn identifier(path: &str, text: &str) -> Result<Identifier, String> {
+    let mut bytes = text.bytes();
+    let valid = bytes
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if valid {
+        Ok(Identifier::new(text, CONVERTED))
+    } else {
+        Err(format!("{path}: `{text}` is not an NML identifier"))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Out of NML
+// ---------------------------------------------------------------------------
+
+fn export_json(path: &Path, schema_dir: Option<&Path>) -> Result<(), String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let mut reporter = Reporter::new(Format::Human);
+    let file = match crate::parse_or_report_all(&mut reporter, path, &source) {
+        Ok(file) => file,
+        Err(e) => {
+            reporter.finish();
+            return Err(e);
+        }
+    };
+    let index = query::schema_index(&mut reporter, schema_dir, path, &source);
+    reporter.finish();
+    let resolver = query::file_resolver(&file);
+    let file = query::resolve_file(&file, &index?, &resolver);
+
+    let mut doc = serde_json::Map::new();
+    for decl in &file.declarations {
+        let (keyword, name, node) = match &decl.kind {
+            DeclarationKind::Block(b) => (&b.keyword, &b.name, Selected::Body(&b.body)),
+            DeclarationKind::Array(a) => (&a.item_keyword, &a.name, Selected::Array(&a.body)),
+            _ => continue,
+        };
+        if matches!(keyword.name.as_str(), "model" | "enum" | "trait") {
+            continue;
+        }
+        if let Some(Err(e)) = query::unresolved(&node).map(|v| resolver.resolve(v)) {
+            return Err(format!(
+                "{}: cannot resolve {} {}: {e}",
+                path.display(),
+                keyword.name,
+                name.name
+            ));
+        }
+        let names = doc
+            .entry(keyword.name.clone())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let serde_json::Value::Object(names) = names {
+            names.insert(name.name.clone(), query::node_json(&node));
+        }
+    }
+    let text =
+        serde_json::to_string_pretty(&serde_json::Value::Object(doc)).map_err(|e| e.to_string())?;
+    println!("{text}");
+    Ok(())
+}
@@ -0,0 +1,520 @@
+//! `nml diff` — the schema-driven semantic diff (RFC 0032) behind a verb:
+//! the same `diff_config` over a synthesized config root that a reload
+//! classifier embeds, so an operator can ask "what would this deploy
+//! change, and does it need a restart?" without writing Rust.
+//!
+//! Each side is one or more files in precedence order (later overrides
+//! earlier, property-level — the layering the library applies). Changes
+//! are classified by the nearest `#live`/`#restart` directive along the
+//! field path, leaf to root; a path carrying neither is `unclassified`.
+//! Secret-typed paths are always reported, never their values.
+//!
+//! The exit status is the verdict a deploy script branches on: 0 when no
+//! change is `#restart`-classified, [`RESTART_EXIT`] when one is, 1 when
+//! the diff could not be computed.
+
+use std::path::{Path, PathBuf};
+
+use nml_core::ast::{BlockDecl, Body, BodyEntryKind, DeclarationKind};
+use nml_core::diff::{
+    ChangeKind, ConfigFieldKind, ConfigRootField, FieldChange, Origin, diff_config,
+    synthesize_config_root, wrap_file_as_body,
+};
+use nml_core::span::{Location, SourceMap};
+use nml_core::types::{Directive, Value};
+use nml_core::{File, SchemaIndex};
+use nml_fmt::formatter::single_line_string;
+use serde::Serialize;
+
+use crate::output::{self, Format, Reporter};
+use crate::pipeline;
+
+const USAGE: &str = "usage: nml diff [--schema <dir>] [--format human|json] <old> <new>\n       \
+                     nml diff [--schema <dir>] [--format human|json] <old>... -- <new>...";
+
+/// The exit status when at least one change is `#restart`-classified.
+pub const RESTART_EXIT: i32 = 2;
+
+/// The synthesized root model's name. Not an identifier, so it can never
+/// collide with a model the schema defines.
+const ROOT: &str = "<config>";
+
+/// Run the diff; `Ok(true)` means a `#restart`-classified change exists.
+pub fn cmd_diff(args: &[String]) -> Result<bool, String> {
+    let (format, rest) = output::split_format_flag(args)?;
+    if matches!(format, Format::Sarif | Format::Github) {
+        return Err("nml diff supports --format human|json".to_string());
+    }
+    let mut schema_dir = None;
+    let mut paths = Vec::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--schema" => {
+                let dir = iter.next().ok_or("--schema requires a directory")?;
+                schema_dir = Some(PathBuf::from(dir));
+            }
+            flag if flag.starts_with("--") && flag != "--" => {
+                return Err(format!("unknown flag: {flag}\n{USAGE}"));
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    let (old_paths, new_paths) = split_sides(&paths)?;
+
+    // Diagnostics go to stderr in the human format whatever `--format`
+    // says: the JSON document on stdout is the diff, and a run that
+    // reports diagnostics produces no diff.
+    let mut reporter = Reporter::new(Format::Human);
+    let result = load(&mut reporter, schema_dir.as_deref(), old_paths, new_paths);
+    reporter.finish();
+    let diff = result?;
+
+    let rows: Vec<Row> = diff.changes.iter().map(|c| diff.row(c)).collect();
+    let restart = rows.iter().any(|r| r.class == Class::Restart);
+    match format {
+        Format::Json => print_json(&diff, &rows, restart)?,
+        _ => print_human(&rows, restart),
+    }
+    Ok(restart)
+}
+
+/// `<old> <new>`, or `<old>... -- <new>...` for layered sides.
+fn split_sides<'a>(paths: &[&'a str]) -> Result<(Vec<&'a str>, Vec<&'a str>), String> {
+    match paths.iter().position(|p| *p == "--") {
+        Some(at) if at > 0 && at + 1 < paths.len() => {
+            let new = &paths[at + 1..];
+            if new.contains(&"--") {
+                return Err(format!("'--' may appear only once\n{USAGE}"));
+            }
+            Ok((paths[..at].to_vec(), new.to_vec()))
+        }
+        None if paths.len() == 2 => Ok((vec![paths[0]], vec![paths[1]])),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// One file of a side, parsed.
+struct Layer {
+    path: PathBuf,
+    source: String,
+    file: File,
+}
+
+/// Both sides loaded against one schema, diffed.
+struct Diff {
+    old: Vec<Layer>,
+    new: Vec<Layer>,
+    changes: Vec<FieldChange>,
+}
+
+fn load(
+    reporter: &mut Reporter,
+    schema_dir: Option<&Path>,
+    old_paths: Vec<&str>,
+    new_paths: Vec<&str>,
+) -> Result<Diff, String> {
+    let old = read_side(reporter, &old_paths)?;
+    let new = read_side(reporter, &new_paths)?;
+
+    // The schema is the `--schema` directory plus the definitions the new
+    // side carries: a change is classified by the schema it ships with.
+    let mut named = match schema_dir {
+        Some(dir) => pipeline::named_schema_sources(&pipeline::read_schema_dir(dir)?),
+        None => Vec::new(),
+    };
+    for layer in &new {
+        if !nml_core::cst::extract_schema(&layer.source).0.is_empty() {
+            named.push((
+                layer.path.display().to_string(),
+                layer.path.clone(),
+                layer.source.clone(),
+            ));
+        }
+    }
+    let schema = pipeline::load_index(reporter, &named, schema_dir.unwrap_or(Path::new(".")))?;
+
+    // Every top-level declaration on either side is a field of the
+    // synthesized root, so a block present on one side only still diffs.
+    let files: Vec<&File> = old.iter().chain(&new).map(|l| &l.file).collect();
+    let fields = root_fields(&files);
+    let mut models = schema.models().to_vec();
+    models.push(synthesize_config_root(ROOT, &fields));
+    let index = SchemaIndex::build(models, schema.enums().to_vec(), schema.oneofs().to_vec());
+    if let Some(field) = fields
+        .iter()
+        .find(|f| index.model(&f.model).is_none() && index.oneof(&f.model).is_none())
+    {
+        let hint = if schema_dir.is_none() {
+            " (pass --schema <dir>)"
+        } else {
+            ""
+        };
+        return Err(format!(
+            "'{}' has no model in the schema{hint}",
+            field.model
+        ));
+    }
+
+    let old_bodies: Vec<_> = old.iter().map(|l| root_body(&l.file)).collect();
+    let new_bodies: Vec<_> = new.iter().map(|l| root_body(&l.file)).collect();
+    let changes = diff_config(
+        &index,
+        ROOT,
+        &pairs(&old, &old_bodies),
+        &pairs(&new, &new_bodies),
+    );
+    Ok(Diff { old, new, changes })
+}
+
+/// The synthesized root's field for a top-level block: keyword and name,
+/// as a query path spells it (`service Api`). Keyed by keyword alone, two
+/// instances of one model would overlay into one field, and a `#restart`
+/// change to one could hide behind the other's unchanged value.
+fn block_field(block: &BlockDecl) -> String {
+    format!("{} {}", block.keyword.name, block.name.name)
+}
+
+/// The root's fields across both sides: one per block instance
+/// ([`block_field`]) and one per array, first occurrence wins. `const`,
+/// `template` and `oneof` declarations are not config instances.
+fn root_fields(files: &[&File]) -> Vec<ConfigRootField> {
+    let mut fields: Vec<ConfigRootField> = Vec::new();
+    for decl in files.iter().flat_map(|f| &f.declarations) {
+        let field = match &decl.kind {
+            DeclarationKind::Block(b) => ConfigRootField {
+                name: block_field(b),
+                model: b.keyword.name.clone(),
+                kind: ConfigFieldKind::Block,
+            },
+            DeclarationKind::Array(a) => ConfigRootField {
+                name: a.name.name.clone(),
+                model: a.item_keyword.name.clone(),
+                kind: ConfigFieldKind::Array,
+            },
+            _ => continue,
+        };
+        if !fields.iter().any(|f| f.name == field.name) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+/// [`wrap_file_as_body`] with each block's entry keyed by its
+/// [`block_field`], matching [`root_fields`].
+fn root_body(file: &File) -> Body {
+    let mut body = wrap_file_as_body(file);
+    // One entry per block or array declaration, in declaration order.
+    let instances = file.declarations.iter().filter_map(|d| match &d.kind {
+        DeclarationKind::Block(b) => Some(Some(b)),
+        DeclarationKind::Array(_) => Some(None),
+        _ => None,
+    });
+    for (entry, block) in body.entries.iter_mut().zip(instances) {
+        if let (BodyEntryKind::NestedBlock(nested), Some(block)) = (&mut entry.kind, block) {
+            nested.name.name = block_field(block);
+        }
+    }
+    body
+}
+
+/// A side's `(file, root body)` pairs in precedence order.
+fn pairs<'a>(layers: &[Layer], bodies: &'a [Body]) -> Vec<(PathBuf, &'a Body)> {
+    layers
+        .iter()
+        .zip(bodies)
+        .map(|(l, b)| (l.path.clone(), b))
+        .collect()
+}
+
+/// Read and parse a side's files, reporting every parse error across all
+/// of them before failing.
+fn read_side(reporter: &mut Reporter, paths: &[&str]) -> Result<Vec<Layer>, String> {
+    let mut layers = Vec::with_capacity(paths.len());
+    let mut failed = 0;
+    for path in paths {
+        let path = PathBuf::from(path);
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        match crate::parse_or_report_all(reporter, &path, &source) {
+            Ok(file) => layers.push(Layer { path, source, file }),
+            Err(_) => failed += 1,
+        }
+    }
+    match failed {
+        0 => Ok(layers),
+        n => Err(format!("{n} file(s) failed to parse")),
+    }
+}
+
+/// How a change lands on a running process, per the schema's directives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Live,
+    Restart,
+    Unclassified,
+}
+
+impl Class {
+    fn as_str(self) -> &'static str {
+        match self {
+            Class::Live => "live",
+            Class::Restart => "restart",
+            Class::Unclassified => "unclassified",
+        }
+    }
+}
+
+/// A change with everything the report shows resolved.
+struct Row<'a> {
+    change: &'a FieldChange,
+    class: Class,
+    /// Every directive along the path, root to leaf, as written.
+    directives: Vec<String>,
+    /// `None` for a value the schema default supplied.
+    origin: Option<(&'a Path, Location)>,
+}
+
+impl Diff {
+    fn row<'a>(&'a self, change: &'a FieldChange) -> Row<'a> {
+        let steps: Vec<_> = change.path.field_steps().collect();
+        let class = steps
+            .iter()
+            .rev()
+            .flat_map(|s| &s.directives)
+            .find_map(|d| match d.name.as_str() {
+                "live" => Some(Class::Live),
+                "restart" => Some(Class::Restart),
+                _ => None,
+            })
+            .unwrap_or(Class::Unclassified);
+        let directives = steps
+            .iter()
+            .flat_map(|s| &s.directives)
+            .map(render_directive)
+            .collect();
+        // `origin` is the new side's, except for a removal.
+        let side = match change.kind {
+            ChangeKind::Removed { .. } => &self.old,
+            _ => &self.new,
+        };
+        let origin = match &change.origin {
+            Origin::File { file, span } => side.iter().find(|l| &l.path == file).map(|l| {
+                (
+                    l.path.as_path(),
+                    SourceMap::new(&l.source).location(span.start),
+                )
+            }),
+            Origin::Default => None,
+        };
+        Row {
+            change,
+            class,
+            directives,
+            origin,
+        }
+    }
+}
+
+fn render_directive(directive: &Directive) -> String {
+    match &directive.arg {
+        Some(arg) => format!("#{}({})", directive.name, render_value(&arg.value)),
+        None => format!("#{}", directive.name),
+    }
+}
+
+/// A value as the report shows it — NML literal syntax where there is one.
+/// Secret-typed paths never reach here; a `$ENV` reference inside any
+/// other value still renders as a marker, never by name, matching the
+/// differ's own path rendering.
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => single_line_string(s, true),
+        Value::TemplateString(segments) => {
+            single_line_string(&nml_core::template::segments_to_string(segments), false)
+        }
+        Value::Number(n) => n.to_string(),
+        Value::Money(m) => m.format_display(),
+        Value::Duration(d) => d.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Role(r) | Value::Reference(r) => r.clone(),
+        Value::Array(items) => {
+            let items: Vec<_> = items.iter().map(|i| render_value(&i.value)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Fallback(primary, fallback) => format!(
+            "{} | {}",
+            render_value(&primary.value),
+            render_value(&fallback.value)
+        ),
+        Value::Secret(_) => "‹secret›".to_string(),
+        Value::Resolved(_) => "‹resolved›".to_string(),
+    }
+}
+
+fn kind_name(kind: &ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added { .. } => "added",
+        ChangeKind::Removed { .. } => "removed",
+        ChangeKind::Modified { .. } => "modified",
+        ChangeKind::SetDelta { .. } => "setDelta",
+        ChangeKind::OpaqueChanged => "opaqueChanged",
+        ChangeKind::ObjectChanged => "objectChanged",
+    }
+}
+
+fn describe(change: &FieldChange) -> String {
+    if change.is_secret() {
+        let verb = match change.kind {
+            ChangeKind::Added { .. } => "added",
+            ChangeKind::Removed { .. } => "removed",
+            _ => "changed",
+        };
+        return format!("{verb} (secret — value not shown)");
+    }
+    match &change.kind {
+        ChangeKind::Added { new } => format!("added {}", render_value(new)),
+        ChangeKind::Removed { old } => format!("removed {}", render_value(old)),
+        ChangeKind::Modified { old, new } => {
+            format!("{} -> {}", render_value(old), render_value(new))
+        }
+        ChangeKind::SetDelta { added, removed } => {
+            let mut parts: Vec<_> = added
+                .iter()
+                .map(|v| format!("+{}", render_value(v)))
+                .collect();
+            parts.extend(removed.iter().map(|v| format!("-{}", render_value(v))));
+            parts.join(", ")
+        }
+        ChangeKind::OpaqueChanged => "changed (a shape the schema does not describe)".to_string(),
+        ChangeKind::ObjectChanged => "changed (object-typed field)".to_string(),
+    }
+}
+
+fn print_human(rows: &[Row], restart: bool) {
+    if rows.is_empty() {
+        println!("no semantic changes");
+        return;
+    }
+    let width = rows
+        .iter()
+        .map(|r| r.class.as_str().len())
+        .max()
+        .unwrap_or(0);
+    for row in rows {
+        let mut notes = vec![match row.origin {
+            Some((path, loc)) => format!("{}:{}", path.display(), loc.line),
+            None => "schema default".to_string(),
+        }];
+        notes.extend(row.directives.iter().cloned());
+        println!(
+            "{:width$}  {}: {}  ({})",
+            row.class.as_str(),
+            row.change.path,
+            describe(row.change),
+            notes.join(", ")
+        );
+    }
+    let count = |class| rows.iter().filter(|r| r.class == class).count();
+    println!(
+        "{} change(s): {} live, {} restart, {} unclassified{}",
+        rows.len(),
+        count(Class::Live),
+        count(Class::Restart),
+        count(Class::Unclassified),
+        if restart { " — restart required" } else { "" }
+    );
+}
+
+/// The `json` document. `version` bumps on any breaking shape change.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonDocument<'a> {
+    version: u32,
+    old: Vec<&'a Path>,
+    new: Vec<&'a Path>,
+    restart_required: bool,
+    changes: Vec<JsonChange>,
+}
+
+/// One change — the `json` wire shape. Values are rendered as in the
+/// human report and are `null` on a secret path.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonChange {
+    path: String,
+    kind: &'static str,
+    class: &'static str,
+    directives: Vec<String>,
+    secret: bool,
+    old: Option<String>,
+    new: Option<String>,
+    added: Vec<String>,
+    removed: Vec<String>,
+    /// `null` when the schema default supplied the value.
+    origin: Option<JsonOrigin>,
+}
+
+#[derive(Serialize)]
+struct JsonOrigin {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+}
+
+fn print_json(diff: &Diff, rows: &[Row], restart: bool) -> Result<(), String> {
+    let changes = rows
+        .iter()
+        .map(|row| {
+            let secret = row.change.is_secret();
+            let shown = |v: &Value| (!secret).then(|| render_value(v));
+            let shown_all = |vs: &[Value]| {
+                if secret {
+                    Vec::new()
+                } else {
+                    vs.iter().map(render_value).collect()
+                }
+            };
+            let (old, new, added, removed) = match &row.change.kind {
+                ChangeKind::Added { new } => (None, shown(new), Vec::new(), Vec::new()),
+                ChangeKind::Removed { old } => (shown(old), None, Vec::new(), Vec::new()),
+                ChangeKind::Modified { old, new } => {
+                    (shown(old), shown(new), Vec::new(), Vec::new())
+                }
+                ChangeKind::SetDelta { added, removed } => {
+                    (None, None, shown_all(added), shown_all(removed))
+                }
+                ChangeKind::OpaqueChanged | ChangeKind::ObjectChanged => {
+                    (None, None, Vec::new(), Vec::new())
+                }
+            };
+            JsonChange {
+                path: row.change.path.to_string(),
+                kind: kind_name(&row.change.kind),
+                class: row.class.as_str(),
+                directives: row.directives.clone(),
+                secret,
+                old,
+                new,
+                added,
+                removed,
+                origin: row.origin.map(|(file, loc)| JsonOrigin {
+                    file: file.to_path_buf(),
+                    line: loc.line,
+                    column: loc.column,
+                }),
+            }
+        })
+        .collect();
+    let doc = JsonDocument {
+        version: 1,
+        old: diff.old.iter().map(|l| l.path.as_path()).collect(),
+        new: diff.new.iter().map(|l| l.path.as_path()).collect(),
+        restart_required: restart,
+        changes,
+    };
+    let text = serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())?;
+    println!("{text}");
+    Ok(())
+}
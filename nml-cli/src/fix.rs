@@ -33,12 +33,14 @@ use nml_core::cst::edit::{SpliceEdit, splice};
 use nml_core::diagnostic::Diagnostic;
 use nml_validate::schema::SchemaValidator;
 
+use crate::output::{Format, Reporter};
+
 /// Bound on fix rounds per file. Two layers (parse, then validation) plus
 /// headroom for fixes that reveal fixes; a file needing more is beyond
 /// mechanical repair and keeps its remaining diagnostics reported.
 const MAX_ROUNDS: usize = 8;
 
-pub fn cmd_fix(args: &[String]) -> Result<(), String> {
+pub fn cmd_fix(args: &[String], reporter: &mut Reporter) -> Result<(), String> {
     let mut schema_dir: Option<PathBuf> = None;
     let mut dry_run = false;
     let mut path_args: Vec<&String> = Vec::new();
@@ -67,7 +69,7 @@ pub fn cmd_fix(args: &[String]) -> Result<(), String> {
         return Err("usage: nml fix [--schema <dir>] [--dry-run] <path>...".to_string());
     }
 
-    let files = collect_nml_files(&path_args)?;
+    let files = crate::pipeline::collect_nml_files(&path_args)?;
     if files.is_empty() {
         return Err("no .nml files found under the given paths".to_string());
     }
@@ -76,73 +78,27 @@ pub fn cmd_fix(args: &[String]) -> Result<(), String> {
     let mut total_edits = 0usize;
     let mut remaining = 0usize;
     for path in &files {
-        let outcome = fix_file(path, schema_dir.as_ref(), dry_run)?;
+        let outcome = fix_file(reporter, path, schema_dir.as_ref(), dry_run)?;
         if outcome.applied > 0 {
             fixed_files += 1;
             total_edits += outcome.applied;
             let verb = if dry_run { "would fix" } else { "fixed" };
-            println!("{verb} {} ({} edit(s))", path.display(), outcome.applied);
+            reporter.status(format_args!(
+                "{verb} {} ({} edit(s))",
+                path.display(),
+                outcome.applied
+            ));
         }
         remaining += outcome.remaining;
     }
     let noun = if dry_run { "appliable" } else { "applied" };
-    println!(
+    reporter.status(format_args!(
         "{total_edits} edit(s) {noun} across {fixed_files} of {} file(s); {remaining} diagnostic(s) not auto-fixable",
         files.len()
-    );
+    ));
     Ok(())
 }
 
-/// Expand path arguments to `.nml` files: files pass through (whatever
-/// their extension — the user named them deliberately), directories are
-/// walked recursively for `*.nml`, skipping dot-directories and symlinks
-/// (a link cycle would otherwise recurse forever, and a link pointing
-/// outside the named tree would silently widen what the user asked to
-/// rewrite — follow-nothing is the safe default for a tool that writes).
-/// Sorted and deduplicated for deterministic output.
-fn collect_nml_files(paths: &[&String]) -> Result<Vec<PathBuf>, String> {
-    fn walk(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
-        let entries =
-            std::fs::read_dir(dir).map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
-        for entry in entries.flatten() {
-            let p = entry.path();
-            let hidden = p
-                .file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|n| n.starts_with('.'));
-            // `file_type()` reads the entry itself (lstat semantics), so a
-            // symlinked directory never recurses and a symlinked file is
-            // never rewritten through the link.
-            let Ok(file_type) = entry.file_type() else {
-                continue;
-            };
-            if hidden || file_type.is_symlink() {
-                continue;
-            }
-            if file_type.is_dir() {
-                walk(&p, out)?;
-            } else if p.extension().and_then(|e| e.to_str()) == Some("nml") {
-                out.push(p);
-            }
-        }
-        Ok(())
-    }
-    let mut files = Vec::new();
-    for arg in paths {
-        let p = PathBuf::from(arg);
-        if p.is_dir() {
-            walk(&p, &mut files)?;
-        } else if p.is_file() {
-            files.push(p);
-        } else {
-            return Err(format!("no such file or directory: {arg}"));
-        }
-    }
-    files.sort();
-    files.dedup();
-    Ok(files)
-}
-
 struct FixOutcome {
     /// Edits applied (or, dry-run, that would be).
     applied: usize,
@@ -151,6 +107,7 @@ struct FixOutcome {
 }
 
 fn fix_file(
+    reporter: &mut Reporter,
     path: &Path,
     schema_dir: Option<&PathBuf>,
     dry_run: bool,
@@ -160,6 +117,8 @@ fn fix_file(
     let mut text = original.clone();
     let mut applied = 0usize;
     let mut analysis = analyze(path, &text, schema_dir);
+    let structured = reporter.format() != Format::Human;
+    let untouched = (structured && dry_run).then(|| analysis.diags.clone());
 
     for _ in 0..MAX_ROUNDS {
         let edits = sole_candidate_edits(&analysis.diags);
@@ -185,10 +144,23 @@ fn fix_file(
     }
 
     if applied > 0 {
-        if dry_run {
-            print!("{}", unified_diff(&original, &text, path));
-        } else {
+        if !dry_run {
             crate::write_file_atomically(&path.to_path_buf(), &text)?;
+        } else if !structured {
+            print!("{}", unified_diff(&original, &text, path));
+        }
+    }
+    // Under `--format`, findings replace the dry-run diff and are located
+    // in the file as it now stands on disk: untouched under `--dry-run`
+    // (every finding, its suggestions the would-be edits), rewritten
+    // otherwise (what the fixer could not resolve).
+    if structured {
+        let (on_disk, diags) = match &untouched {
+            Some(diags) => (&original, diags),
+            None => (&text, &analysis.diags),
+        };
+        for diag in diags {
+            reporter.report(path, on_disk, diag);
         }
     }
     Ok(FixOutcome {
@@ -217,11 +189,18 @@ fn analyze(path: &Path, source: &str, schema_dir: Option<&PathBuf>) -> Analysis
     }
 
     let mut diags: Vec<Diagnostic> = Vec::new();
-    let mut symbols = nml_core::symbols::SymbolTable::new();
-    symbols.register_file(&file);
+    let (symbols, imports) = crate::pipeline::symbols_with_imports(path, &file);
     diags.extend(symbols.find_duplicates());
     diags.extend(symbols.find_unresolved_references(&file));
     diags.extend(symbols.find_const_cycles());
+    // Findings inside imported files are theirs to fix, not this file's.
+    diags.extend(
+        imports
+            .diagnostics()
+            .iter()
+            .filter(|(at, _)| at == path)
+            .map(|(_, d)| d.clone()),
+    );
 
     // The fixer only rewrites THIS file, so foreign-source findings are
     // context, not fix candidates — but a schema universe that fails to
@@ -336,11 +315,11 @@ fn sole_candidate_edits(diags: &[Diagnostic]) -> Vec<SpliceEdit> {
     kept
 }
 
-/// A minimal unified diff (3 lines of context) for `--dry-run`. Line-based
-/// LCS — fix targets are configuration files, small by nature; a
+/// A minimal unified diff (3 lines of context) for `--dry-run` and `nml fmt
+/// --diff`. Line-based LCS — targets are configuration files, small by nature; a
 /// pathological pair falls back to one whole-file hunk rather than
 /// quadratic work.
-fn unified_diff(old: &str, new: &str, path: &Path) -> String {
+pub(crate) fn unified_diff(old: &str, new: &str, path: &Path) -> String {
     let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
     let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
 
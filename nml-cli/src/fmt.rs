@@ -0,0 +1,127 @@
+//! `nml fmt` — canonical formatting for files, directory trees and pipes.
+//!
+//! Files are rewritten in place (atomically, and only when formatting
+//! changes them). `--check` writes nothing and lists the files that would
+//! change, failing when there are any — the pre-commit and CI mode.
+//! `--diff` writes nothing and prints what would change as a unified
+//! diff. `-` reads stdin and prints the formatted text on stdout, for
+//! editors and hooks that pipe.
+//!
+//! A file that cannot be read or does not parse is never touched: it is
+//! reported, skipped, and counted in the summary. Once every other file
+//! has been handled the run exits [`SKIPPED_EXIT`] — distinct from the
+//! `--check` failure, which takes precedence when both apply.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: nml fmt [--check] [--diff] <path>... | nml fmt [--check] [--diff] -";
+
+/// The exit status when files were skipped and nothing else failed.
+pub const SKIPPED_EXIT: i32 = 3;
+
+/// Run the formatter; `Ok(true)` means files were skipped.
+pub fn cmd_fmt(args: &[String]) -> Result<bool, String> {
+    let mut check = false;
+    let mut diff = false;
+    let mut path_args: Vec<&String> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--diff" => diff = true,
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag {flag}; {USAGE}"));
+            }
+            _ => path_args.push(arg),
+        }
+    }
+    match path_args[..] {
+        [] => Err(USAGE.to_string()),
+        [stdin] if stdin == "-" => format_stdin(check, diff).map(|()| false),
+        _ if path_args.iter().any(|p| *p == "-") => Err(format!(
+            "`-` (stdin) cannot be combined with paths; {USAGE}"
+        )),
+        _ => format_paths(&path_args, check, diff),
+    }
+}
+
+/// The formatted text, or the parse error located in `path`.
+fn format_text(path: &Path, source: &str) -> Result<String, String> {
+    nml_fmt::formatter::format_source(source).map_err(|e| {
+        let source_map = nml_core::span::SourceMap::new(source);
+        let loc = source_map.location(e.span().start);
+        format!("{}:{}:{}: {}", path.display(), loc.line, loc.column, e)
+    })
+}
+
+fn format_stdin(check: bool, diff: bool) -> Result<(), String> {
+    let mut source = String::new();
+    std::io::stdin()
+        .read_to_string(&mut source)
+        .map_err(|e| format!("failed to read stdin: {e}"))?;
+    let stdin = Path::new("<stdin>");
+    let formatted = format_text(stdin, &source)?;
+    if diff && formatted != source {
+        print!("{}", crate::fix::unified_diff(&source, &formatted, stdin));
+    }
+    if !check && !diff {
+        print!("{formatted}");
+    }
+    if check && formatted != source {
+        return Err("<stdin> is not formatted".to_string());
+    }
+    Ok(())
+}
+
+fn format_paths(path_args: &[&String], check: bool, diff: bool) -> Result<bool, String> {
+    let files: Vec<PathBuf> = crate::pipeline::collect_nml_files(path_args)?;
+    if files.is_empty() {
+        return Err("no .nml files found under the given paths".to_string());
+    }
+    let write = !check && !diff;
+    let mut changed = 0usize;
+    let mut skipped = 0usize;
+    for path in &files {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("error: failed to read {}: {e}", path.display());
+                skipped += 1;
+                continue;
+            }
+        };
+        let formatted = match format_text(path, &source) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("error: {e}");
+                skipped += 1;
+                continue;
+            }
+        };
+        if formatted == source {
+            continue;
+        }
+        changed += 1;
+        if diff {
+            print!("{}", crate::fix::unified_diff(&source, &formatted, path));
+        }
+        if check && !diff {
+            println!("{}", path.display());
+        }
+        if write {
+            crate::write_file_atomically(path, &formatted)?;
+            println!("formatted {}", path.display());
+        }
+    }
+
+    let verb = if write { "formatted" } else { "would reformat" };
+    let skipped_note = match skipped {
+        0 => String::new(),
+        n => format!("; {n} skipped (read or parse errors)"),
+    };
+    eprintln!("{verb} {changed} of {} file(s){skipped_note}", files.len());
+    if check && changed > 0 {
+        return Err(format!("{changed} file(s) not formatted"));
+    }
+    Ok(skipped > 0)
+}
@@ -0,0 +1,400 @@
+//! `nml init` — scaffold an NML-configured project.
+//!
+//! Writes the files a new tool otherwise starts by hand: `nml-project.nml`,
+//! a starter `<stem>.model.nml`, an `app.nml` instance it validates, and
+//! with `--package <name>` a `<name>.package.nml` manifest whose validator
+//! binds `app.nml`, pinned from the project file. Every generated file
+//! checks clean as written.
+//!
+//! Each file is a list of declarations and block entries it must contain,
+//! not a template: a missing declaration is appended at the end of the
+//! file, a missing entry is inserted into its block through the structural
+//! CST edit (RFC 0030 P2), and the pin goes in exactly as the editor's
+//! "pin this package" action writes it. A fresh directory gets the full
+//! skeleton; re-running over an existing project adds only what is
+//! missing and leaves every other byte alone. A file that does not parse
+//! is refused rather than edited.
+
+use std::path::{Path, PathBuf};
+
+use nml_core::ast::{BodyEntryKind, DeclarationKind, File};
+use nml_core::cst::edit::{EntryPosition, SpliceEdit, insert_entry_at_path, splice};
+use nml_core::span::Span;
+
+const USAGE: &str = "usage: nml init [--package <name>] [<dir>]";
+
+/// A top-level declaration a generated file must contain.
+struct Decl {
+    /// `model`, or `[]schema` for an array declaration.
+    keyword: &'static str,
+    /// `None` matches any name: a project keeps its own `project` and
+    /// `package` block names.
+    name: Option<String>,
+    /// The declaration as appended when missing.
+    text: String,
+}
+
+/// A property or nested block the (single) block declared with `keyword`
+/// must contain.
+struct Entry {
+    keyword: &'static str,
+    name: &'static str,
+    /// The entry as inserted when missing, at zero indentation.
+    snippet: String,
+}
+
+/// What one generated file must contain.
+struct Plan {
+    path: PathBuf,
+    decls: Vec<Decl>,
+    entries: Vec<Entry>,
+    /// A schema package `nml-project.nml` pins.
+    pin: Option<String>,
+}
+
+pub fn cmd_init(args: &[String]) -> Result<(), String> {
+    let mut package: Option<String> = None;
+    let mut dir: Option<PathBuf> = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--package" => {
+                let name = iter.next().ok_or("--package requires a name argument")?;
+                package = Some(name.clone());
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag {flag}; {USAGE}"));
+            }
+            _ if dir.is_some() => return Err(USAGE.to_string()),
+            _ => dir = Some(PathBuf::from(arg)),
+        }
+    }
+    if let Some(name) = &package {
+        // The name becomes a file name and a written pin entry.
+        if !nml_validate::package::valid_package_name(name) {
+            return Err(format!(
+                "invalid package name {name:?}: expected a lowercase identifier ([a-z][a-z0-9-]*)"
+            ));
+        }
+    }
+    let dir = dir.unwrap_or_else(|| PathBuf::from("."));
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+
+    let project = project_name(package.as_deref(), &dir);
+    let stem = package.as_deref().unwrap_or("app");
+    let model = model_plan(&dir, stem);
+    let model_file = model
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut plans = vec![
+        project_plan(&dir, &project, package.as_deref()),
+        model,
+        instance_plan(&dir),
+    ];
+    if let Some(name) = &package {
+        plans.push(package_plan(&dir, name, &model_file));
+    }
+
+    for plan in &plans {
+        let status = apply(plan)?;
+        println!("{status} {}", plan.path.display());
+    }
+    match &package {
+        Some(_) => println!("next: nml check {}", dir.join("app.nml").display()),
+        None => println!(
+            "next: nml check --schema {} {}",
+            dir.display(),
+            dir.join("app.nml").display()
+        ),
+    }
+    Ok(())
+}
+
+/// The `project <Name>:` block name: the package name or the directory's,
+/// in PascalCase (`my-tool` → `MyTool`).
+fn project_name(package: Option<&str>, dir: &Path) -> String {
+    let base = match package {
+        Some(name) => name.to_string(),
+        None => std::path::absolute(dir)
+            .ok()
+            .and_then(|d| d.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default(),
+    };
+    let name: String = base
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first.into_iter().chain(chars).collect::<String>()
+        })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => "Project".to_string(),
+    }
+}
+
+fn project_plan(dir: &Path, project: &str, package: Option<&str>) -> Plan {
+    Plan {
+        path: dir.join("nml-project.nml"),
+        decls: vec![Decl {
+            keyword: "project",
+            name: None,
+            text: format!("project {project}:\n"),
+        }],
+        entries: Vec::new(),
+        pin: package.map(str::to_string),
+    }
+}
+
+/// The starter model, in `<stem>.model.nml` — unless that file is absent
+/// and another model file in `dir` already declares `model service` (an
+/// earlier `nml init` without `--package`): a second file would declare
+/// it twice (NML2009), so the existing one is reused.
+fn model_plan(dir: &Path, stem: &str) -> Plan {
+    let service = Decl {
+        keyword: "model",
+        name: Some("service".to_string()),
+        text: "model service:\n    host string\n    port number(min = 1, max = 65535)\n    timeout duration = 30s\n".to_string(),
+    };
+    let own = dir.join(format!("{stem}.model.nml"));
+    let path = if own.exists() {
+        own
+    } else {
+        model_files(dir)
+            .into_iter()
+            .find(|path| {
+                std::fs::read_to_string(path)
+                    .ok()
+                    .and_then(|text| nml_core::parse(&text).ok())
+                    .is_some_and(|file| has_decl(&file, &service))
+            })
+            .unwrap_or(own)
+    };
+    Plan {
+        path,
+        decls: vec![service],
+        entries: Vec::new(),
+        pin: None,
+    }
+}
+
+/// The `*.model.nml` files directly in `dir`, sorted.
+fn model_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p.is_file()
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.ends_with(".model.nml"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn instance_plan(dir: &Path) -> Plan {
+    Plan {
+        path: dir.join("app.nml"),
+        decls: vec![Decl {
+            keyword: "service",
+            name: Some("Api".to_string()),
+            text: "service Api:\n    host = \"localhost\"\n    port = 8080\n".to_string(),
+        }],
+        entries: Vec::new(),
+        pin: None,
+    }
+}
+
+/// The manifest for package `name`, whose one schema source is
+/// `model_file`.
+fn package_plan(dir: &Path, name: &str, model_file: &str) -> Plan {
+    let version = "version = \"0.1.0\"";
+    let format_version = "formatVersion = 1";
+    let root_markers = "rootMarkers:\n    - \"nml-project.nml\"";
+    let indented = |entry: &str| {
+        entry
+            .lines()
+            .map(|line| format!("    {line}\n"))
+            .collect::<String>()
+    };
+    Plan {
+        path: dir.join(format!("{name}.package.nml")),
+        decls: vec![
+            Decl {
+                keyword: "package",
+                name: None,
+                text: format!(
+                    "package {name}:\n{}{}{}",
+                    indented(version),
+                    indented(format_version),
+                    indented(root_markers)
+                ),
+            },
+            Decl {
+                keyword: "[]schema",
+                name: Some("schemas".to_string()),
+                text: format!(
+                    "[]schema schemas:\n    - {name}:\n        file = \"{model_file}\"\n"
+                ),
+            },
+            Decl {
+                keyword: "[]validator",
+                name: Some("validators".to_string()),
+                text: format!(
+                    "[]validator validators:\n    - service:\n        files:\n            - \"**/app.nml\"\n        schemas:\n            - {name}\n"
+                ),
+            },
+        ],
+        entries: vec![
+            Entry {
+                keyword: "package",
+                name: "version",
+                snippet: version.to_string(),
+            },
+            Entry {
+                keyword: "package",
+                name: "formatVersion",
+                snippet: format_version.to_string(),
+            },
+            Entry {
+                keyword: "package",
+                name: "rootMarkers",
+                snippet: root_markers.to_string(),
+            },
+        ],
+        pin: None,
+    }
+}
+
+/// Bring one file up to its plan, writing it only when something was
+/// missing. Returns the status line's verb.
+fn apply(plan: &Plan) -> Result<String, String> {
+    let path = &plan.path;
+    let existing = if path.exists() {
+        Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        )
+    } else {
+        None
+    };
+    let mut text = existing.clone().unwrap_or_default();
+    let mut added: Vec<String> = Vec::new();
+
+    let file = parse_clean(path, &text)?;
+    for decl in &plan.decls {
+        if has_decl(&file, decl) {
+            continue;
+        }
+        text = append(&text, &decl.text);
+        added.push(match &decl.name {
+            Some(name) => format!("{} {name}", decl.keyword),
+            None => decl.keyword.to_string(),
+        });
+    }
+
+    for entry in &plan.entries {
+        if has_entry(&parse_clean(path, &text)?, entry) {
+            continue;
+        }
+        text = insert_entry_at_path(&text, &[entry.keyword], &entry.snippet, EntryPosition::Last)
+            .ok_or_else(|| {
+            format!(
+                "{}: cannot add `{}` to the `{}` block (is there exactly one?)",
+                path.display(),
+                entry.name,
+                entry.keyword
+            )
+        })?;
+        added.push(entry.name.to_string());
+    }
+
+    if let Some(pin) = &plan.pin {
+        if let Some(pinned) = nml_core::project::insert_pin(&text, pin) {
+            text = pinned;
+            added.push(format!("schemaPackages pin {pin}"));
+        }
+    }
+
+    if existing.as_deref() == Some(text.as_str()) {
+        return Ok("up to date".to_string());
+    }
+    // The appended and inserted text must leave the file as clean as it was.
+    parse_clean(path, &text)?;
+    crate::write_file_atomically(path, &text)?;
+    Ok(match existing {
+        None => "created".to_string(),
+        Some(_) => format!("updated (added {})", added.join(", ")),
+    })
+}
+
+/// The file's AST, or the first parse error located in `path` — a file
+/// that does not parse cleanly is never edited.
+fn parse_clean(path: &Path, text: &str) -> Result<File, String> {
+    nml_core::parse(text).map_err(|e| {
+        let loc = nml_core::span::SourceMap::new(text).location(e.span().start);
+        format!(
+            "{}:{}:{}: {e} (fix the file, then re-run nml init)",
+            path.display(),
+            loc.line,
+            loc.column
+        )
+    })
+}
+
+fn has_decl(file: &File, decl: &Decl) -> bool {
+    file.declarations.iter().any(|d| {
+        let (keyword, name) = match &d.kind {
+            DeclarationKind::Block(block) => (block.keyword.name.clone(), &block.name.name),
+            DeclarationKind::Array(array) => {
+                (format!("[]{}", array.item_keyword.name), &array.name.name)
+            }
+            _ => return false,
+        };
+        keyword == decl.keyword && decl.name.as_ref().is_none_or(|n| n == name)
+    })
+}
+
+fn has_entry(file: &File, entry: &Entry) -> bool {
+    file.declarations.iter().any(|d| match &d.kind {
+        DeclarationKind::Block(block) if block.keyword.name == entry.keyword => {
+            block.body.entries.iter().any(|e| match &e.kind {
+                BodyEntryKind::Property(prop) => prop.name.name == entry.name,
+                BodyEntryKind::NestedBlock(nested) => nested.name.name == entry.name,
+                _ => false,
+            })
+        }
+        _ => false,
+    })
+}
+
+/// Append a declaration at the end of `text`, one blank line below the
+/// last.
+fn append(text: &str, decl: &str) -> String {
+    let separator = if text.is_empty() || text.ends_with("\n\n") {
+        ""
+    } else if text.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    let end = Span::new(text.len(), text.len());
+    splice(
+        text,
+        &[SpliceEdit {
+            span: end,
+            replacement: format!("{separator}{decl}"),
+        }],
+    )
+    .expect("an end-of-file insertion is always in bounds")
+}
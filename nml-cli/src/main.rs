@@ -1,73 +1,55 @@
 use std::path::{Path, PathBuf};
 use std::process;
 
-use nml_core::diagnostic::{Code, Diagnostic, Severity};
+use nml_core::diagnostic::{Code, Diagnostic};
 use nml_validate::schema::SchemaValidator;
+use output::{Format, Reporter};
 
+mod binding;
+mod check;
+mod convert;
+mod diff;
 mod fix;
+mod fmt;
+mod init;
+mod output;
+mod package;
 mod pipeline;
+mod query;
+mod watch;
 
 /// Parse a file via the CST, reporting **every** syntactic and semantic error
 /// at once (not just the first — exceeding the legacy one-at-a-time UX). Returns
 /// the AST when the input is fully valid.
-fn parse_or_report_all(path: &Path, source: &str) -> Result<nml_core::ast::File, String> {
+fn parse_or_report_all(
+    reporter: &mut Reporter,
+    path: &Path,
+    source: &str,
+) -> Result<nml_core::ast::File, String> {
     let (file, errors) = nml_core::cst::parse_to_ast_all(source);
     if errors.is_empty() {
         return Ok(file);
     }
-    let source_map = nml_core::span::SourceMap::new(source);
     let mut first_code = None;
     for e in &errors {
-        first_code = first_code.or(report(path, &source_map, e));
+        first_code = first_code.or(reporter.report(path, source, e));
     }
-    explain_hint(first_code);
+    reporter.explain_hint(first_code);
     Err(format!("{} parse error(s)", errors.len()))
 }
 
-/// The one diagnostic printer: `path:line:col: <Display>` — `Display` renders
-/// severity, the stable `[NML0000]` code when assigned, and the did-you-mean
-/// hint derived from the structured suggestion (RFC 0008).
-/// After a run's diagnostics, point at the offline explanation for the first
-/// coded finding — rustc's "for more information" pattern, printed once.
-fn explain_hint(first_code: Option<Code>) {
-    if let Some(code) = first_code {
-        eprintln!("for more information, run: nml explain {code}");
-    }
-}
-
-fn report(path: &Path, source_map: &nml_core::span::SourceMap, diag: &Diagnostic) -> Option<Code> {
-    let (line, column) = match diag.span {
-        Some(span) => {
-            let loc = source_map.location(span.start);
-            (loc.line, loc.column)
-        }
-        None => (0, 0),
-    };
-    // `line:col` already locates the finding — the raw byte-span suffix that
-    // `Display` adds for span-less contexts would be noise here.
-    let code = diag.code.map(|c| format!("[{c}]")).unwrap_or_default();
-    eprintln!(
-        "{}:{}:{}: {}{}: {}",
-        path.display(),
-        line,
-        column,
-        diag.severity,
-        code,
-        diag.rendered()
-    );
-    // Secondary locations (RFC 0009) — rustc's `note:` shape, from the one
-    // shared model, so every consumer prints the same explanation.
-    for rel in &diag.related {
-        let loc = source_map.location(rel.span.start);
-        eprintln!(
-            "{}:{}:{}: note: {}",
-            path.display(),
-            loc.line,
-            loc.column,
-            rel.message
-        );
-    }
-    diag.code
+/// Run a diagnostic-producing verb: peel off `--format`, hand the verb its
+/// [`Reporter`], and flush the document even when the verb fails — a CI
+/// consumer needs the findings most exactly then.
+fn with_reporter(
+    args: &[String],
+    verb: fn(&[String], &mut Reporter) -> Result<(), String>,
+) -> Result<(), String> {
+    let (format, rest) = output::split_format_flag(args)?;
+    let mut reporter = Reporter::new(format);
+    let result = verb(&rest, &mut reporter);
+    reporter.finish();
+    result
 }
 
 fn main() {
@@ -80,11 +62,29 @@ fn main() {
 
     let result = match args[1].as_str() {
         "parse" => cmd_parse(&args[2..]),
-        "validate" => cmd_validate(&args[2..]),
-        "fmt" => cmd_fmt(&args[2..]),
-        "check" => cmd_check(&args[2..]),
-        "fix" => fix::cmd_fix(&args[2..]),
+        "validate" => with_reporter(&args[2..], cmd_validate),
+        // Skipped files are reported as they happen; the status tells a
+        // script they were left alone, apart from a `--check` failure.
+        "fmt" => fmt::cmd_fmt(&args[2..]).map(|skipped| {
+            if skipped {
+                process::exit(fmt::SKIPPED_EXIT);
+            }
+        }),
+        "check" => with_reporter(&args[2..], check::cmd_check),
+        "fix" => with_reporter(&args[2..], fix::cmd_fix),
+        // A restart-classified change is a verdict, not an error: its own
+        // exit status, after the report has printed.
+        "diff" => diff::cmd_diff(&args[2..]).map(|restart| {
+            if restart {
+                process::exit(diff::RESTART_EXIT);
+            }
+        }),
         "explain" => cmd_explain(&args[2..]),
+        "package" => package::cmd_package(&args[2..]),
+        "query" => query::cmd_query(&args[2..]),
+        "convert" => convert::cmd_convert(&args[2..]),
+        "watch" => watch::cmd_watch(&args[2..]),
+        "init" => init::cmd_init(&args[2..]),
         "help" | "--help" | "-h" => {
             print_usage();
             Ok(())
@@ -117,19 +117,64 @@ COMMANDS:
     parse                           Parse an NML file and dump the AST as JSON
                                     (numbers: JSON number for integer-form
                                     values within u64, else exact string)
-    validate                        Validate an NML file for duplicates and unresolved references
-    fmt                             Format NML files in canonical style
-    check [--schema <dir>] [--strict] <file>
+    validate [--format <fmt>] <file>
+                                    Validate an NML file for duplicates and unresolved references
+    fmt [--check] [--diff] <path>...
+                                    Format NML files in canonical style; directories
+                                    are walked for .nml files; --check lists unformatted
+                                    files and fails, --diff prints the changes; files
+                                    that fail to read or parse are skipped (exit 3)
+    fmt [--check] [--diff] -        Format stdin to stdout
+    check [--schema <dir>] [--strict] [--explain-binding] [--format <fmt>] <path>...
                                     Parse + validate + schema check (CI-friendly);
-                                    --strict makes unknown properties/keywords errors
-    fix [--schema <dir>] [--dry-run] <path>...
+                                    directories are walked for .nml files, with a
+                                    summary line per file; schema packages bind
+                                    files as in the editor (--explain-binding shows
+                                    which); --strict makes unknown properties/keywords
+                                    errors
+    fix [--schema <dir>] [--dry-run] [--format <fmt>] <path>...
                                     Apply machine-applicable fixes (migrations,
                                     sole-candidate suggestions) in bulk; directories
                                     are walked for .nml files; --dry-run prints a diff
+    watch [--schema <dir>] [--strict] <path>...
+                                    Re-run check whenever a file, an import or the
+                                    schema directory changes (polls; Ctrl-C to stop)
+    diff [--schema <dir>] [--format human|json] <old>... [--] <new>...
+                                    Semantic diff of two configs (each side one file,
+                                    or layered files split by --): changes with their
+                                    origin and #live/#restart class, secrets redacted;
+                                    exits 2 when any change is #restart
+    query [--resolve] [--schema <dir>] [--format human|json] <path> <file>
+                                    Print the values a path selects
+                                    (service Api.endpoints[Docs].url; * wildcards);
+                                    --resolve applies shared properties, schema
+                                    defaults, consts and $ENV first
+    convert [--from <fmt>] [--to nml|json] [--schema <dir>] [--block '<keyword> <Name>'] <file>
+                                    Convert toml, json or yaml into NML (typed
+                                    duration/money literals and positional items
+                                    with --schema), or NML into resolved,
+                                    defaulted JSON; formats default from the
+                                    file extension
+    init [--package <name>] [<dir>]
+                                    Scaffold a project: nml-project.nml, a starter
+                                    model and an app.nml it validates, plus with
+                                    --package a pinned package manifest; re-running
+                                    adds only what is missing
+    package build <dir>             Check a schema package and print its content hash
+    package publish <dir>           Build, then install into the user schema store
+    package list                    List installed packages (corrupt pointers included)
+    package verify [<name>...]      Re-hash installed packages against their pointers
+    package gc [<name>...]          Prune retired store slots
     explain <code>                  Explain a diagnostic code (e.g. nml explain NML2007)
     explain --list                  List every diagnostic code with its summary
     help                            Show this help message
-    version                         Show version information"
+    version                         Show version information
+
+OPTIONS:
+    --format human|json|sarif|github
+                                    Diagnostic output for validate/check/fix: json and
+                                    sarif print one document on stdout, github prints
+                                    Actions workflow commands (default: human)"
     );
 }
 
@@ -159,25 +204,34 @@ fn cmd_parse(args: &[String]) -> Result<(), String> {
     let path = require_file_arg(args, "parse")?;
     let source = read_file(&path)?;
 
-    let file = parse_or_report_all(&path, &source)?;
+    let file = parse_or_report_all(&mut Reporter::new(Format::Human), &path, &source)?;
     let json =
         serde_json::to_string_pretty(&file).map_err(|e| format!("serialization error: {e}"))?;
     println!("{json}");
     Ok(())
 }
 
-fn cmd_validate(args: &[String]) -> Result<(), String> {
+fn cmd_validate(args: &[String], reporter: &mut Reporter) -> Result<(), String> {
     let path = require_file_arg(args, "validate")?;
     let source = read_file(&path)?;
 
-    let file = parse_or_report_all(&path, &source)?;
+    let file = parse_or_report_all(reporter, &path, &source)?;
 
-    let mut symbols = nml_core::symbols::SymbolTable::new();
-    symbols.register_file(&file);
+    let (symbols, imports) = pipeline::symbols_with_imports(&path, &file);
 
     let mut errors = symbols.find_duplicates();
     errors.extend(symbols.find_unresolved_references(&file));
     errors.extend(symbols.find_const_cycles());
+    let mut foreign_errors = 0;
+    let mut foreign_code = None;
+    for (at, diag) in imports.diagnostics() {
+        if at == &path {
+            errors.push(diag.clone());
+        } else {
+            foreign_code = foreign_code.or(report_imported(reporter, &imports, at, diag));
+            foreign_errors += usize::from(diag.severity == nml_core::diagnostic::Severity::Error);
+        }
+    }
     // Schema definitions in the file get the full loader pipeline (RFC 0011):
     // reserved/duplicate definition names, `is` composition, trait usage,
     // oneof integrity, positional arity, cycles — the same findings loading
@@ -196,24 +250,24 @@ fn cmd_validate(args: &[String]) -> Result<(), String> {
                 .validate_definitions(&file),
         );
     }
-    if errors.is_empty() {
-        println!("{}: ok", path.display());
+    if errors.is_empty() && foreign_errors == 0 {
+        reporter.status(format_args!("{}: ok", path.display()));
         Ok(())
     } else {
-        let source_map = nml_core::span::SourceMap::new(&source);
-        let mut first_code = None;
+        let mut first_code = foreign_code;
         for err in &errors {
-            first_code = first_code.or(report(&path, &source_map, err));
+            first_code = first_code.or(reporter.report(&path, &source, err));
         }
-        explain_hint(first_code);
+        reporter.explain_hint(first_code);
         // Warnings (e.g. advisory model-reference cycles) report but do not
         // fail the file — same posture as `check`.
-        let error_count = errors
-            .iter()
-            .filter(|d| d.severity == nml_core::diagnostic::Severity::Error)
-            .count();
+        let error_count = foreign_errors
+            + errors
+                .iter()
+                .filter(|d| d.severity == nml_core::diagnostic::Severity::Error)
+                .count();
         if error_count == 0 {
-            println!("{}: ok", path.display());
+            reporter.status(format_args!("{}: ok", path.display()));
             return Ok(());
         }
         Err(format!("{error_count} validation error(s)"))
@@ -251,156 +305,16 @@ fn cmd_explain(args: &[String]) -> Result<(), String> {
     }
 }
 
-fn cmd_fmt(args: &[String]) -> Result<(), String> {
-    let path = require_file_arg(args, "fmt")?;
-    let source = read_file(&path)?;
-
-    let formatted = nml_fmt::formatter::format_source(&source).map_err(|e| {
-        let source_map = nml_core::span::SourceMap::new(&source);
-        let loc = source_map.location(e.span().start);
-        format!("{}:{}:{}: {}", path.display(), loc.line, loc.column, e)
-    })?;
-    write_file_atomically(&path, &formatted)?;
-
-    println!("formatted {}", path.display());
-    Ok(())
-}
-
-fn cmd_check(args: &[String]) -> Result<(), String> {
-    let mut schema_dir: Option<PathBuf> = None;
-    let mut strict = false;
-    let mut file_args: Vec<&String> = Vec::new();
-
-    let mut i = 0;
-    while i < args.len() {
-        if args[i] == "--schema" {
-            i += 1;
-            if i >= args.len() {
-                return Err("--schema requires a path argument".to_string());
-            }
-            schema_dir = Some(PathBuf::from(&args[i]));
-        } else if args[i] == "--strict" {
-            strict = true;
-        } else {
-            file_args.push(&args[i]);
-        }
-        i += 1;
-    }
-
-    if file_args.is_empty() {
-        return Err("usage: nml check [--schema <dir>] [--strict] <file>".to_string());
-    }
-    if file_args.len() > 1 {
-        return Err(format!(
-            "usage: nml check [--schema <dir>] [--strict] <file> (got {} files)",
-            file_args.len()
-        ));
-    }
-    let path = PathBuf::from(file_args[0]);
-    let source = read_file(&path)?;
-
-    let file = parse_or_report_all(&path, &source)?;
-
-    let mut symbols = nml_core::symbols::SymbolTable::new();
-    symbols.register_file(&file);
-
-    let source_map = nml_core::span::SourceMap::new(&source);
-    let mut error_count = 0;
-
-    let mut first_code = None;
-    for err in symbols
-        .find_duplicates()
-        .into_iter()
-        .chain(symbols.find_unresolved_references(&file))
-        .chain(symbols.find_const_cycles())
-    {
-        first_code = first_code.or(report(&path, &source_map, &err));
-        error_count += 1;
-    }
-
-    // One schema universe per check (RFC 0012): the `--schema` directory's
-    // sources plus the checked file itself (unless it *is* one of them). A
-    // single load runs every definition pass — reserved/duplicate names,
-    // `is` composition, trait usage, oneof integrity, positional arity,
-    // cycles — with per-file attribution, and the composed schema then
-    // types instances. A self-contained file (`model cache` above
-    // `cache Foo:`) validates with no flags, and a name declared in both
-    // the file and the directory is NML2009 — never a silent shadow.
-    // Assembly is shared with `nml fix` (pipeline module), so the fixer
-    // can never judge a file differently than this verb does.
-    let named_sources = pipeline::schema_universe(&path, &source, schema_dir.as_ref())?;
-    let source_refs: Vec<(&str, &str)> = named_sources
-        .iter()
-        .map(|(n, _, t)| (n.as_str(), t.as_str()))
-        .collect();
-    let (schema, schema_diags) = nml_validate::loader::load_schema(&source_refs);
-
-    // Attributed findings print `path:line:col` against their declaring
-    // source; a finding no single definition owns falls back to a
-    // location-less line under the schema dir (or the file).
-    for diag in &schema_diags {
-        let attributed = diag
-            .source
-            .as_deref()
-            .and_then(|name| named_sources.iter().find(|(n, _, _)| n == name));
-        match attributed {
-            Some((_, src_path, text)) => {
-                first_code = first_code.or(report(
-                    src_path,
-                    &nml_core::span::SourceMap::new(text),
-                    diag,
-                ));
-            }
-            None => {
-                match &schema_dir {
-                    Some(sd) => eprintln!("{}: {}", sd.display(), diag),
-                    None => eprintln!("{}: {}", path.display(), diag),
-                }
-                first_code = first_code.or(diag.code);
-            }
-        }
-        if matches!(diag.severity, Severity::Error) {
-            error_count += 1;
-        }
-    }
-
-    // `--strict` promises enforcement; with an empty schema universe there
-    // is nothing to enforce, and silently degrading to parse-only checking
-    // is how a CI pipeline points at the wrong path and stays green
-    // forever. Fail the *invocation*, naming the actual mistake.
-    if strict && schema.is_empty() {
-        return Err(
-            "--strict has nothing to enforce: no schema definitions found \
-             (no --schema directory given and none declared in the file)"
-                .to_string(),
-        );
-    }
-
-    if !schema.is_empty() {
-        // Definition composition is covered by the single load above —
-        // instance-only here, so no finding is ever reported twice.
-        let mut validator = SchemaValidator::from(schema).composition_checked_at_load();
-        // --strict: unknown properties and unmodeled keywords become
-        // errors (CI posture; the same profile package bindings can set).
-        if strict {
-            validator = validator.strict();
-        }
-        for diag in validator.validate(&file) {
-            first_code = first_code.or(report(&path, &source_map, &diag));
-            if matches!(diag.severity, Severity::Error) {
-                error_count += 1;
-            }
-        }
-    }
-
-    explain_hint(first_code);
-    let decl_count = file.declarations.len();
-    if error_count == 0 {
-        println!("{}: ok ({decl_count} declaration(s))", path.display());
-        Ok(())
-    } else {
-        Err(format!("{error_count} error(s)"))
-    }
+/// [`Reporter::report`] for a finding in a file the checked one imports,
+/// against that file's own source.
+fn report_imported(
+    reporter: &mut Reporter,
+    imports: &nml_core::import::ImportSet,
+    at: &Path,
+    diag: &Diagnostic,
+) -> Option<Code> {
+    let source = imports.file(at).map_or("", |f| f.source.as_str());
+    reporter.report(at, source, diag)
 }
 
 fn require_file_arg(args: &[String], cmd: &str) -> Result<PathBuf, String> {
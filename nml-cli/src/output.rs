@@ -0,0 +1,479 @@
+//! Diagnostic output for the reporting verbs (`check`, `validate`, `fix`):
+//! the human `path:line:col` printer plus the machine-readable formats
+//! selected with `--format json|sarif|github`. Every format renders the
+//! same [`Diagnostic`] model — code, severity, span, `related` notes,
+//! suggestions — so a CI dashboard sees exactly what a terminal does.
+//!
+//! * `json` — one document on stdout: `{"version": 1, "diagnostics": […]}`.
+//! * `sarif` — a SARIF 2.1.0 log on stdout; suggestions become `fixes`.
+//! * `github` — GitHub Actions workflow commands (`::error file=…::…`),
+//!   streamed as findings arrive, so annotations land on the PR diff.
+//!
+//! The document formats own stdout; status lines (`app.nml: ok`, fix
+//! tallies) move to stderr under them, so stdout always parses.
+//!
+//! Structured positions are 1-based lines and **character** columns (the
+//! unit SARIF and GitHub expect), with the raw byte offsets alongside in
+//! `json`. The human printer keeps its byte columns.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use nml_core::diagnostic::{Code, Diagnostic, Severity, SuggestionKind};
+use nml_core::span::{SourceMap, Span};
+use serde::Serialize;
+
+/// Where findings go — see the module docs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+    Sarif,
+    Github,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "sarif" => Ok(Format::Sarif),
+            "github" => Ok(Format::Github),
+            other => Err(format!(
+                "unknown --format {other:?}; expected one of: human, json, sarif, github"
+            )),
+        }
+    }
+}
+
+/// Strip `--format <name>` / `--format=<name>` from a verb's arguments,
+/// leaving the rest for the verb's own parser. The last occurrence wins.
+pub fn split_format_flag(args: &[String]) -> Result<(Format, Vec<String>), String> {
+    let mut format = Format::Human;
+    let mut rest = Vec::with_capacity(args.len());
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--format" {
+            i += 1;
+            let name = args.get(i).ok_or_else(|| {
+                "--format requires one of: human, json, sarif, github".to_string()
+            })?;
+            format = Format::parse(name)?;
+        } else if let Some(name) = args[i].strip_prefix("--format=") {
+            format = Format::parse(name)?;
+        } else {
+            rest.push(args[i].clone());
+        }
+        i += 1;
+    }
+    Ok((format, rest))
+}
+
+/// The one diagnostic sink for a verb run. Human and GitHub output stream;
+/// JSON and SARIF buffer until [`Reporter::finish`].
+pub struct Reporter {
+    format: Format,
+    findings: Vec<Finding>,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Self {
+        Reporter {
+            format,
+            findings: Vec::new(),
+        }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Report `diag`, whose spans index `source` (the text of `path`).
+    /// Returns the code, so callers can remember the first one for
+    /// [`Reporter::explain_hint`].
+    pub fn report(&mut self, path: &Path, source: &str, diag: &Diagnostic) -> Option<Code> {
+        match self.format {
+            Format::Human => report_human(path, &SourceMap::new(source), diag),
+            _ => {
+                let lines = LineIndex::new(source);
+                self.emit(Finding {
+                    file: path.to_path_buf(),
+                    code: diag.code.map(|c| c.to_string()),
+                    severity: severity_name(&diag.severity),
+                    message: diag.message.clone(),
+                    rendered: diag.rendered_message(),
+                    span: diag.span.map(|s| lines.region(s)),
+                    related: diag
+                        .related
+                        .iter()
+                        .map(|r| RelatedOut {
+                            message: r.message.clone(),
+                            span: lines.region(r.span),
+                        })
+                        .collect(),
+                    suggestions: diag
+                        .suggestions
+                        .iter()
+                        .map(|s| SuggestionOut {
+                            kind: match s.kind {
+                                SuggestionKind::DidYouMean => "didYouMean",
+                                SuggestionKind::Fix => "fix",
+                            },
+                            replacement: s.replacement.clone(),
+                            span: lines.region(s.span),
+                        })
+                        .collect(),
+                });
+            }
+        }
+        diag.code
+    }
+
+    /// Report a finding no single source location owns (a cross-file
+    /// schema finding), attributed to `path` as a whole.
+    pub fn report_unlocated(&mut self, path: &Path, diag: &Diagnostic) -> Option<Code> {
+        if self.format == Format::Human {
+            eprintln!("{}: {}", path.display(), diag);
+            return diag.code;
+        }
+        self.report(path, "", &without_spans(diag))
+    }
+
+    /// A status line (`app.nml: ok`, fix tallies): stdout, except under a
+    /// document format, where stdout belongs to the document.
+    pub fn status(&self, line: impl Display) {
+        match self.format {
+            Format::Json | Format::Sarif => eprintln!("{line}"),
+            Format::Human | Format::Github => println!("{line}"),
+        }
+    }
+
+    /// After a run's diagnostics, point at the offline explanation for the
+    /// first coded finding — rustc's "for more information" pattern,
+    /// printed once. Human output only: the structured formats carry the
+    /// code itself.
+    pub fn explain_hint(&self, first_code: Option<Code>) {
+        if let (Format::Human, Some(code)) = (self.format, first_code) {
+            eprintln!("for more information, run: nml explain {code}");
+        }
+    }
+
+    /// Print the buffered document (JSON, SARIF); a no-op for the
+    /// streaming formats.
+    pub fn finish(self) {
+        let document = match self.format {
+            Format::Human | Format::Github => return,
+            Format::Json => serde_json::to_string_pretty(&JsonDocument {
+                version: 1,
+                diagnostics: &self.findings,
+            }),
+            Format::Sarif => serde_json::to_string_pretty(&sarif(&self.findings)),
+        };
+        println!("{}", document.expect("findings always serialize"));
+    }
+
+    fn emit(&mut self, finding: Finding) {
+        if self.format == Format::Github {
+            println!("{}", github_command(&finding));
+        } else {
+            self.findings.push(finding);
+        }
+    }
+}
+
+/// The human printer: `path:line:col: <Display>` — `Display` renders
+/// severity, the stable `[NML0000]` code when assigned, and the
+/// did-you-mean hint derived from the structured suggestion (RFC 0008).
+fn report_human(path: &Path, source_map: &SourceMap, diag: &Diagnostic) {
+    let (line, column) = match diag.span {
+        Some(span) => {
+            let loc = source_map.location(span.start);
+            (loc.line, loc.column)
+        }
+        None => (0, 0),
+    };
+    // `line:col` already locates the finding — the raw byte-span suffix that
+    // `Display` adds for span-less contexts would be noise here.
+    let code = diag.code.map(|c| format!("[{c}]")).unwrap_or_default();
+    eprintln!(
+        "{}:{}:{}: {}{}: {}",
+        path.display(),
+        line,
+        column,
+        diag.severity,
+        code,
+        diag.rendered()
+    );
+    // Secondary locations (RFC 0009) — rustc's `note:` shape, from the one
+    // shared model, so every consumer prints the same explanation.
+    for rel in &diag.related {
+        let loc = source_map.location(rel.span.start);
+        eprintln!(
+            "{}:{}:{}: note: {}",
+            path.display(),
+            loc.line,
+            loc.column,
+            rel.message
+        );
+    }
+}
+
+fn without_spans(diag: &Diagnostic) -> Diagnostic {
+    let mut d = diag.clone();
+    d.span = None;
+    d.related.clear();
+    d.suggestions.clear();
+    d
+}
+
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        _ => "info",
+    }
+}
+
+/// The `json` document. `version` bumps on any breaking shape change.
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    version: u32,
+    diagnostics: &'a [Finding],
+}
+
+/// One reported diagnostic, positions resolved — the `json` wire shape.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Finding {
+    file: PathBuf,
+    code: Option<String>,
+    severity: &'static str,
+    /// The finding's prose alone.
+    message: String,
+    /// `message` plus the derived hint — what the human printer shows.
+    rendered: String,
+    span: Option<Region>,
+    related: Vec<RelatedOut>,
+    suggestions: Vec<SuggestionOut>,
+}
+
+#[derive(Serialize)]
+struct RelatedOut {
+    message: String,
+    span: Region,
+}
+
+#[derive(Serialize)]
+struct SuggestionOut {
+    kind: &'static str,
+    replacement: String,
+    span: Region,
+}
+
+/// A span with its resolved start and end positions.
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// Byte offsets into the file.
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+/// Byte offset → (line, character column), both 1-based.
+struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex {
+            source,
+            line_starts,
+        }
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&s| s <= offset) - 1;
+        let start = self.line_starts[line];
+        let column = self
+            .source
+            .get(start..offset)
+            .map_or(offset - start, |text| text.chars().count());
+        (line + 1, column + 1)
+    }
+
+    fn region(&self, span: Span) -> Region {
+        let (line, column) = self.position(span.start);
+        let (end_line, end_column) = self.position(span.end);
+        Region {
+            start: span.start,
+            end: span.end,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// `::error file=…,line=…::message` — properties and message escaped per
+/// the workflow-command rules, so file content can never inject a second
+/// command. `related` notes ride along as extra message lines.
+fn github_command(f: &Finding) -> String {
+    fn escape_data(s: &str) -> String {
+        s.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+    fn escape_property(s: &str) -> String {
+        escape_data(s).replace(':', "%3A").replace(',', "%2C")
+    }
+    let level = match f.severity {
+        "error" => "error",
+        "warning" => "warning",
+        _ => "notice",
+    };
+    let mut props = vec![format!(
+        "file={}",
+        escape_property(&f.file.display().to_string())
+    )];
+    if let Some(r) = &f.span {
+        props.push(format!("line={}", r.line));
+        props.push(format!("col={}", r.column));
+        props.push(format!("endLine={}", r.end_line));
+        props.push(format!("endColumn={}", r.end_column));
+    }
+    if let Some(code) = &f.code {
+        props.push(format!("title={}", escape_property(code)));
+    }
+    let mut message = f.rendered.clone();
+    for rel in &f.related {
+        message.push_str(&format!(
+            "\nnote ({}:{}): {}",
+            rel.span.line, rel.span.column, rel.message
+        ));
+    }
+    format!("::{level} {}::{}", props.join(","), escape_data(&message))
+}
+
+/// A SARIF 2.1.0 log with one run. Rules are the codes that occur, each
+/// with its error-index summary; suggestions become `fixes` (one per
+/// alternative) with `insertedContent` replacements.
+fn sarif(findings: &[Finding]) -> serde_json::Value {
+    use serde_json::json;
+
+    let mut rule_ids: Vec<&str> = findings.iter().filter_map(|f| f.code.as_deref()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    let rules: Vec<_> = rule_ids
+        .iter()
+        .map(|id| {
+            // The error-index summary is markdown; `text` gets it plain.
+            let summary = nml_core::diagnostic::explain_summary(id).unwrap_or_default();
+            json!({
+                "id": id,
+                "shortDescription": {
+                    "text": summary.replace("**", "").replace('`', ""),
+                    "markdown": summary,
+                },
+                "helpUri": format!(
+                    "{}/blob/main/crates/nml-core/assets/error-index.md#{}",
+                    env!("CARGO_PKG_REPOSITORY"),
+                    id.to_ascii_lowercase()
+                ),
+            })
+        })
+        .collect();
+
+    let region = |r: &Region| {
+        json!({
+            "startLine": r.line,
+            "startColumn": r.column,
+            "endLine": r.end_line,
+            "endColumn": r.end_column,
+        })
+    };
+    let results: Vec<_> = findings
+        .iter()
+        .map(|f| {
+            let uri = f.file.display().to_string().replace('\\', "/");
+            let mut physical = json!({ "artifactLocation": { "uri": uri } });
+            if let Some(r) = &f.span {
+                physical["region"] = region(r);
+            }
+            let mut result = json!({
+                "level": match f.severity {
+                    "error" => "error",
+                    "warning" => "warning",
+                    _ => "note",
+                },
+                "message": { "text": f.rendered },
+                "locations": [{ "physicalLocation": physical }],
+            });
+            if let Some(code) = &f.code {
+                result["ruleId"] = json!(code);
+            }
+            if !f.related.is_empty() {
+                result["relatedLocations"] = f
+                    .related
+                    .iter()
+                    .enumerate()
+                    .map(|(id, rel)| {
+                        json!({
+                            "id": id,
+                            "message": { "text": rel.message },
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": uri },
+                                "region": region(&rel.span),
+                            },
+                        })
+                    })
+                    .collect();
+            }
+            if !f.suggestions.is_empty() {
+                result["fixes"] = f
+                    .suggestions
+                    .iter()
+                    .map(|s| {
+                        json!({
+                            "description": { "text": format!("replace with {:?}", s.replacement) },
+                            "artifactChanges": [{
+                                "artifactLocation": { "uri": uri },
+                                "replacements": [{
+                                    "deletedRegion": region(&s.span),
+                                    "insertedContent": { "text": s.replacement },
+                                }],
+                            }],
+                        })
+                    })
+                    .collect();
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "nml",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
@@ -0,0 +1,278 @@
+//! `nml package` — build, publish and maintain schema packages (RFC 0030)
+//! without custom Rust: the same `SchemaPackage` loading and `Store` write
+//! side a publisher embeds, behind a verb.
+//!
+//! `build` and `publish` share one gate, so nothing reaches the store that
+//! `build` would reject. Store verbs work on [`Store::user`], which honors
+//! `NML_SCHEMA_STORE_DIR` — the store the editor and `nml check` read.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use nml_core::diagnostic::{Code, Diagnostic};
+use nml_validate::package::{PackageError, SchemaPackage, find_manifest};
+use nml_validate::store::{ListingStatus, PublishOutcome, Store, StoreError, hash8};
+
+use crate::output::{Format, Reporter};
+
+const USAGE: &str =
+    "usage: nml package build <dir> | publish <dir> | list | verify [<name>...] | gc [<name>...]";
+
+pub fn cmd_package(args: &[String]) -> Result<(), String> {
+    let Some((verb, rest)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    match verb.as_str() {
+        "build" => build(rest).map(|_| ()),
+        "publish" => publish(rest),
+        "list" => list(rest),
+        "verify" => verify(rest),
+        "gc" => gc(rest),
+        other => Err(format!("unknown package command: {other}; {USAGE}")),
+    }
+}
+
+/// Load and check a package directory, printing its identity. Every
+/// finding is reported against the file it belongs to; any error refuses
+/// the package.
+fn build(args: &[String]) -> Result<SchemaPackage, String> {
+    let [dir] = args else {
+        return Err("usage: nml package {build|publish} <dir>".to_string());
+    };
+    let dir = PathBuf::from(dir);
+    let mut reporter = Reporter::new(Format::Human);
+    let result = check_package(&mut reporter, &dir);
+    reporter.finish();
+    let package = result?;
+    println!(
+        "{} {} {}",
+        package.manifest.name,
+        package.manifest.version,
+        package.content_hash()
+    );
+    Ok(package)
+}
+
+fn check_package(reporter: &mut Reporter, dir: &Path) -> Result<SchemaPackage, String> {
+    let manifest_path = find_manifest(dir).map_err(|e| e.to_string())?;
+    // Located findings need the manifest text back; a package only hands it
+    // over when it loads.
+    let manifest_text = || std::fs::read_to_string(&manifest_path).unwrap_or_default();
+    let package = match SchemaPackage::from_dir(dir) {
+        Ok(package) => package,
+        // Manifest findings are located in the manifest; everything else
+        // already names what it is about.
+        Err(PackageError::Manifest { errors }) => {
+            let manifest_text = manifest_text();
+            let mut first_code = None;
+            for diag in &errors {
+                first_code = first_code.or(reporter.report(&manifest_path, &manifest_text, diag));
+            }
+            reporter.explain_hint(first_code);
+            return Err(format!(
+                "{}: {} error(s)",
+                manifest_path.display(),
+                errors.len()
+            ));
+        }
+        Err(PackageError::Inconsistent {
+            message,
+            span: Some(span),
+        }) => {
+            let diag = Diagnostic::error(message).with_span(span);
+            reporter.report(&manifest_path, &manifest_text(), &diag);
+            return Err(format!(
+                "{}: inconsistent manifest",
+                manifest_path.display()
+            ));
+        }
+        Err(e) => return Err(format!("{}: {e}", manifest_path.display())),
+    };
+
+    // The filename stem is what workspace pins resolve by; the declared
+    // name is the identity. The editor refuses a disagreement, so a
+    // package carrying one must never ship.
+    let stem = manifest_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_suffix(".package.nml"));
+    if stem != Some(package.manifest.name.as_str()) {
+        return Err(format!(
+            "{} declares package '{}' but its filename says '{}' — rename one",
+            manifest_path.display(),
+            package.manifest.name,
+            stem.unwrap_or_default()
+        ));
+    }
+
+    // Manifest loading reads the sources; composing each binding is what
+    // loads them, so do it here rather than in the first editor that binds.
+    let mut errors = 0;
+    let mut first_code = None;
+    for binding in &package.manifest.validators {
+        let Err(e) = package.composed_schema(binding) else {
+            continue;
+        };
+        let PackageError::Sources { errors: diags } = e else {
+            return Err(format!("binding '{}': {e}", binding.name));
+        };
+        for diag in &diags {
+            first_code = first_code.or(report_source_diag(reporter, dir, &package, diag));
+            errors += 1;
+        }
+    }
+    for warning in package.manifest.shadow_warnings() {
+        first_code =
+            first_code.or(reporter.report(&manifest_path, &package.manifest_text, &warning));
+    }
+    reporter.explain_hint(first_code);
+    if errors > 0 {
+        return Err(format!("{errors} error(s) in the package's schema sources"));
+    }
+    Ok(package)
+}
+
+/// Source findings carry the logical schema name they belong to; report
+/// them against the declared file.
+fn report_source_diag(
+    reporter: &mut Reporter,
+    dir: &Path,
+    package: &SchemaPackage,
+    diag: &Diagnostic,
+) -> Option<Code> {
+    let logical = diag.source.as_deref();
+    let entry = package
+        .manifest
+        .schemas
+        .iter()
+        .find(|e| Some(e.name.as_str()) == logical);
+    let text = package
+        .sources
+        .iter()
+        .find(|(name, _)| Some(name.as_str()) == logical)
+        .map(|(_, text)| text.as_str());
+    match (entry, text) {
+        (Some(entry), Some(text)) => reporter.report(&dir.join(&entry.file), text, diag),
+        _ => reporter.report_unlocated(dir, diag),
+    }
+}
+
+fn user_store() -> Result<Store, String> {
+    Store::user().ok_or_else(|| {
+        "no schema store location: this platform reports no data directory \
+         (set NML_SCHEMA_STORE_DIR to an absolute path)"
+            .to_string()
+    })
+}
+
+fn publish(args: &[String]) -> Result<(), String> {
+    let package = build(args)?;
+    let store = user_store()?;
+    let name = &package.manifest.name;
+    match store.publish(&package).map_err(|e| e.to_string())? {
+        PublishOutcome::Unchanged => println!("{name}: already current"),
+        PublishOutcome::Published { slot } => {
+            println!("{name}: published {slot} to {}", store.base().display())
+        }
+    }
+    Ok(())
+}
+
+/// The store inventory — a corrupt pointer is listed, never dropped.
+fn list(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err("usage: nml package list".to_string());
+    }
+    let store = user_store()?;
+    let listings = store.list();
+    if listings.is_empty() {
+        println!("no schema packages in {}", store.base().display());
+        return Ok(());
+    }
+    let name_width = listings.iter().map(|l| l.name.len()).max().unwrap_or(0);
+    let version_width = listings.iter().map(|l| l.version.len()).max().unwrap_or(0);
+    for l in &listings {
+        let state = match l.status {
+            ListingStatus::Current => format!("blake3:{}", l.hash8),
+            ListingStatus::CorruptPointer => "corrupt pointer".to_string(),
+        };
+        let published = l
+            .published
+            .map(|t| format!(", published {}", age(t)))
+            .unwrap_or_default();
+        println!(
+            "{:name_width$}  {:version_width$}  {state}  ({} slot(s){published})",
+            l.name, l.version, l.slot_count
+        );
+    }
+    Ok(())
+}
+
+/// Re-hash installed packages against their pointers: all of them, or
+/// the ones named.
+fn verify(args: &[String]) -> Result<(), String> {
+    let store = user_store()?;
+    let names = names_or_all(&store, args);
+    let mut failed = 0;
+    for name in &names {
+        match store.read_current(name) {
+            Ok(slot) => println!(
+                "{name} {}: ok (blake3:{})",
+                slot.package.manifest.version,
+                hash8(&slot.content_hash)
+            ),
+            Err(e) => {
+                eprintln!("{name}: {e}");
+                failed += 1;
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        n => Err(format!(
+            "{n} of {} package(s) failed verification",
+            names.len()
+        )),
+    }
+}
+
+/// Prune retired slots by the publish-time retention rule.
+fn gc(args: &[String]) -> Result<(), String> {
+    let store = user_store()?;
+    let names = names_or_all(&store, args);
+    let mut removed = 0;
+    for name in &names {
+        match store.gc_package(name) {
+            Ok(n) => removed += n,
+            Err(StoreError::NotInstalled) if args.is_empty() => {}
+            Err(e) => return Err(format!("{name}: {e}")),
+        }
+    }
+    println!(
+        "removed {removed} slot(s) across {} package(s)",
+        names.len()
+    );
+    Ok(())
+}
+
+fn names_or_all(store: &Store, args: &[String]) -> Vec<String> {
+    if args.is_empty() {
+        store.list_names()
+    } else {
+        args.to_vec()
+    }
+}
+
+/// A coarse age for listings: the pointer mtime is when `current` last
+/// flipped, and minutes are all anyone reads off it.
+fn age(then: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(then)
+        .map_or(0, |d| d.as_secs());
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
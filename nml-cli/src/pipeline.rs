@@ -5,11 +5,30 @@
 
 use std::path::{Path, PathBuf};
 
+use nml_core::SchemaIndex;
+use nml_core::diagnostic::{Code, Diagnostic, Severity};
+use nml_core::schema::ExtractedSchema;
+
+use crate::output::Reporter;
+
 /// Read a schema directory's sources (`*.model.nml` / `*.schema.nml`),
 /// sorted for determinism. Loading happens once, in the caller's single
 /// schema universe (RFC 0012). Parse errors surface later as attributed
 /// diagnostics; reading here only fails on I/O.
 pub fn read_schema_dir(dir: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    schema_dir_paths(dir)?
+        .into_iter()
+        .map(|p| {
+            std::fs::read_to_string(&p)
+                .map(|text| (p.clone(), text))
+                .map_err(|e| format!("failed to read {}: {e}", p.display()))
+        })
+        .collect()
+}
+
+/// The sources [`read_schema_dir`] reads, without reading them — what
+/// `nml watch` polls.
+pub fn schema_dir_paths(dir: &Path) -> Result<Vec<PathBuf>, String> {
     let entries = std::fs::read_dir(dir)
         .map_err(|e| format!("failed to read schema dir {}: {e}", dir.display()))?;
 
@@ -23,15 +42,7 @@ pub fn read_schema_dir(dir: &Path) -> Result<Vec<(PathBuf, String)>, String> {
         })
         .collect();
     paths.sort();
-
-    paths
-        .into_iter()
-        .map(|p| {
-            std::fs::read_to_string(&p)
-                .map(|text| (p.clone(), text))
-                .map_err(|e| format!("failed to read {}: {e}", p.display()))
-        })
-        .collect()
+    Ok(paths)
 }
 
 /// One schema universe per check (RFC 0012): the `--schema` directory's
@@ -45,17 +56,21 @@ pub fn schema_universe(
     source: &str,
     schema_dir: Option<&PathBuf>,
 ) -> Result<Vec<(String, PathBuf, String)>, String> {
-    let mut named_sources: Vec<(String, PathBuf, String)> = Vec::new();
-    if let Some(sd) = schema_dir {
-        for (p, text) in read_schema_dir(sd)? {
-            let load_name = p
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("schema")
-                .to_string();
-            named_sources.push((load_name, p, text));
-        }
-    }
+    let schema_sources = match schema_dir {
+        Some(sd) => read_schema_dir(sd)?,
+        None => Vec::new(),
+    };
+    Ok(assemble_universe(path, source, &schema_sources))
+}
+
+/// [`schema_universe`] over directory sources already in hand — how
+/// `nml check` reads its `--schema` directory once for a whole run.
+pub fn assemble_universe(
+    path: &Path,
+    source: &str,
+    schema_sources: &[(PathBuf, String)],
+) -> Vec<(String, PathBuf, String)> {
+    let mut named_sources = named_schema_sources(schema_sources);
     let file_canon = path.canonicalize().ok();
     let mut file_is_a_source = false;
     for (_, p, text) in &mut named_sources {
@@ -74,5 +89,154 @@ pub fn schema_universe(
             source.to_string(),
         ));
     }
-    Ok(named_sources)
+    named_sources
+}
+
+/// A loaded schema universe: its definitions, and each finding with the
+/// `(path, text)` of the source that declared it — `None` when no single
+/// source owns it.
+pub struct LoadedUniverse<'u> {
+    pub schema: ExtractedSchema,
+    pub findings: Vec<(Diagnostic, Option<(&'u Path, &'u str)>)>,
+}
+
+impl LoadedUniverse<'_> {
+    pub fn errors(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|(diag, _)| diag.severity == Severity::Error)
+            .count()
+    }
+}
+
+/// Load a schema universe, attributing each finding to its source.
+pub fn load_universe(named: &[(String, PathBuf, String)]) -> LoadedUniverse<'_> {
+    let refs: Vec<(&str, &str)> = named
+        .iter()
+        .map(|(n, _, t)| (n.as_str(), t.as_str()))
+        .collect();
+    let (schema, diags) = nml_validate::loader::load_schema(&refs);
+    let findings = diags
+        .into_iter()
+        .map(|diag| {
+            let attributed = diag
+                .source
+                .as_deref()
+                .and_then(|name| named.iter().find(|(n, _, _)| n == name))
+                .map(|(_, at, text)| (at.as_path(), text.as_str()));
+            (diag, attributed)
+        })
+        .collect();
+    LoadedUniverse { schema, findings }
+}
+
+/// Load a schema universe into the index the defaulting and resolving
+/// passes read. Findings are reported against the source they belong to,
+/// or `unlocated` when none claims them; any error refuses the schema.
+pub fn load_index(
+    reporter: &mut Reporter,
+    named: &[(String, PathBuf, String)],
+    unlocated: &Path,
+) -> Result<SchemaIndex, String> {
+    let loaded = load_universe(named);
+    let mut first_code: Option<Code> = None;
+    for (diag, attributed) in &loaded.findings {
+        first_code = first_code.or(match attributed {
+            Some((at, text)) => reporter.report(at, text, diag),
+            None => reporter.report_unlocated(unlocated, diag),
+        });
+    }
+    reporter.explain_hint(first_code);
+    let errors = loaded.errors();
+    if errors > 0 {
+        return Err(format!("{errors} error(s) in the schema"));
+    }
+    let schema = loaded.schema;
+    Ok(SchemaIndex::build(
+        schema.models,
+        schema.enums,
+        schema.oneofs,
+    ))
+}
+
+/// `--schema` directory sources as universe entries, each under its
+/// [`schema_load_name`].
+pub fn named_schema_sources(sources: &[(PathBuf, String)]) -> Vec<(String, PathBuf, String)> {
+    sources
+        .iter()
+        .map(|(p, text)| (schema_load_name(p), p.clone(), text.clone()))
+        .collect()
+}
+
+/// The load name of a `--schema` directory source: its basename, which is
+/// what attributed findings print against.
+pub fn schema_load_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("schema")
+        .to_string()
+}
+
+/// The checked file's symbol table with its imports resolved from disk:
+/// its own declarations plus what each `import` brings in. Shared so the
+/// fixer resolves names exactly as the checker does. Import findings (a
+/// missing file, a cycle, an unknown name, an imported file's parse
+/// errors) come back on the [`ImportSet`](nml_core::import::ImportSet),
+/// each with the file it belongs to.
+pub fn symbols_with_imports(
+    path: &Path,
+    file: &nml_core::File,
+) -> (nml_core::symbols::SymbolTable, nml_core::import::ImportSet) {
+    let imports = nml_core::import::ImportSet::load(path, file, |p| std::fs::read_to_string(p));
+    (imports.symbol_table(file), imports)
+}
+
+/// Expand path arguments to `.nml` files: files pass through (whatever
+/// their extension — the user named them deliberately), directories are
+/// walked recursively for `*.nml`, skipping dot-directories and symlinks
+/// (a link cycle would otherwise recurse forever, and a link pointing
+/// outside the named tree would silently widen what the user asked to
+/// rewrite — follow-nothing is the safe default for a tool that writes).
+/// Sorted and deduplicated for deterministic output.
+pub fn collect_nml_files(paths: &[&String]) -> Result<Vec<PathBuf>, String> {
+    fn walk(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+        let entries =
+            std::fs::read_dir(dir).map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
+        for entry in entries.flatten() {
+            let p = entry.path();
+            let hidden = p
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            // `file_type()` reads the entry itself (lstat semantics), so a
+            // symlinked directory never recurses and a symlinked file is
+            // never rewritten through the link.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if hidden || file_type.is_symlink() {
+                continue;
+            }
+            if file_type.is_dir() {
+                walk(&p, out)?;
+            } else if p.extension().and_then(|e| e.to_str()) == Some("nml") {
+                out.push(p);
+            }
+        }
+        Ok(())
+    }
+    let mut files = Vec::new();
+    for arg in paths {
+        let p = PathBuf::from(arg);
+        if p.is_dir() {
+            walk(&p, &mut files)?;
+        } else if p.is_file() {
+            files.push(p);
+        } else {
+            return Err(format!("no such file or directory: {arg}"));
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
 }
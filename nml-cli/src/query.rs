@@ -0,0 +1,420 @@
+//! `nml query` — read values out of a config with a path expression
+//! ([`QueryPath`]: `service Api.endpoints[Docs].url`), for shell scripts
+//! and CI steps that would otherwise need the Rust query API.
+//!
+//! Plain output prints one match per line: scalars bare (strings
+//! unquoted), blocks and lists as compact JSON. `--format json` prints the
+//! value as one document — or, for a path with a wildcard, an array of
+//! `{"path", "value"}` pairs naming each match.
+//!
+//! `--resolve` reads the config the way a consuming program would:
+//! positional items materialized, shared properties merged, schema
+//! defaults applied (from `--schema` and the file's own definitions), then
+//! `const` references and `$ENV` values resolved. It prints
+//! environment-resolved text — the caller asked for the value.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use nml_core::ast::{
+    ArrayBody, Body, BodyEntryKind, DeclarationKind, File, ListItem, ListItemKind, ModifierValue,
+};
+use nml_core::query::{QueryPath, Selected};
+use nml_core::resolve::{apply_array_shared_properties, apply_shared_properties};
+use nml_core::types::Value;
+use nml_core::{Document, SchemaIndex, ValueResolver};
+use serde_json::json;
+
+use crate::output::{self, Format, Reporter};
+use crate::pipeline;
+
+const USAGE: &str =
+    "usage: nml query [--resolve] [--schema <dir>] [--format human|json] <path> <file>";
+
+pub fn cmd_query(args: &[String]) -> Result<(), String> {
+    let (format, rest) = output::split_format_flag(args)?;
+    if matches!(format, Format::Sarif | Format::Github) {
+        return Err("nml query supports --format human|json".to_string());
+    }
+    let mut resolve = false;
+    let mut schema_dir = None;
+    let mut positional = Vec::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--resolve" => resolve = true,
+            "--schema" => {
+                let dir = iter.next().ok_or("--schema requires a directory")?;
+                schema_dir = Some(PathBuf::from(dir));
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [expr, path] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+    if schema_dir.is_some() && !resolve {
+        return Err("--schema only applies with --resolve (schema defaults)".to_string());
+    }
+    let query = QueryPath::parse(expr).map_err(|e| format!("invalid path: {e}"))?;
+    let path = PathBuf::from(path);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let mut reporter = Reporter::new(Format::Human);
+    let parsed = crate::parse_or_report_all(&mut reporter, &path, &source);
+    let file = match parsed {
+        Ok(file) => file,
+        Err(e) => {
+            reporter.finish();
+            return Err(e);
+        }
+    };
+
+    let resolver = resolve.then(|| file_resolver(&file));
+    let file = match &resolver {
+        Some(resolver) => {
+            let index = schema_index(&mut reporter, schema_dir.as_deref(), &path, &source);
+            reporter.finish();
+            resolve_file(&file, &index?, resolver)
+        }
+        None => {
+            reporter.finish();
+            file
+        }
+    };
+
+    let doc = Document::new(&file);
+    let matches = doc.select(&query);
+    if matches.is_empty() {
+        return Err(format!("no match for '{query}' in {}", path.display()));
+    }
+    // A value that failed to resolve is left as written; it only matters
+    // if the query selects it, and then resolving it again names the cause.
+    if let Some(resolver) = &resolver {
+        for m in &matches {
+            if let Some(Err(e)) = unresolved(&m.node).map(|v| resolver.resolve(v)) {
+                return Err(format!(
+                    "{}: cannot resolve {}: {e}",
+                    path.display(),
+                    m.path
+                ));
+            }
+        }
+    }
+
+    match format {
+        Format::Json => {
+            let doc = if query.has_wildcard() {
+                serde_json::Value::Array(
+                    matches
+                        .iter()
+                        .map(|m| json!({ "path": m.path, "value": node_json(&m.node) }))
+                        .collect(),
+                )
+            } else {
+                node_json(&matches[0].node)
+            };
+            let text = serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())?;
+            println!("{text}");
+        }
+        _ => {
+            for m in &matches {
+                match m.node {
+                    Selected::Value(v) if !matches!(v, Value::Array(_)) => {
+                        println!("{}", scalar_text(v))
+                    }
+                    node => println!("{}", node_json(&node)),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The schema defaults come from: the `--schema` directory plus the file's
+/// own definitions, loaded as `nml check` loads them.
+pub(crate) fn schema_index(
+    reporter: &mut Reporter,
+    schema_dir: Option<&Path>,
+    path: &Path,
+    source: &str,
+) -> Result<SchemaIndex, String> {
+    let named = match schema_dir {
+        Some(dir) => {
+            let sources = pipeline::read_schema_dir(dir)?;
+            pipeline::assemble_universe(path, source, &sources)
+        }
+        None => pipeline::assemble_universe(path, source, &[]),
+    };
+    pipeline::load_index(reporter, &named, schema_dir.unwrap_or(path))
+}
+
+/// `$ENV` from the process, `const` references from the file being read.
+pub(crate) fn file_resolver(file: &File) -> ValueResolver {
+    let consts: HashMap<String, Value> = file
+        .declarations
+        .iter()
+        .filter_map(|d| match &d.kind {
+            DeclarationKind::Const(c) => Some((c.name.name.clone(), c.value.value.clone())),
+            _ => None,
+        })
+        .collect();
+    ValueResolver::env().with_symbols(move |name| consts.get(name).cloned())
+}
+
+/// The file as a consuming program reads it (see the module docs). Values
+/// resolve one at a time, so an unset variable in one field does not hide
+/// the rest of the file; a value that fails stays as written.
+pub(crate) fn resolve_file(file: &File, index: &SchemaIndex, resolver: &ValueResolver) -> File {
+    let mut out = file.clone();
+    for decl in &mut out.declarations {
+        match &mut decl.kind {
+            DeclarationKind::Block(b) => {
+                let root = b.keyword.name.as_str();
+                let body = nml_core::identity::apply_positional(index, root, &b.body);
+                b.body = nml_core::apply_defaults(index, root, &apply_shared_properties(&body));
+                resolve_body(&mut b.body, resolver);
+            }
+            DeclarationKind::Array(a) => {
+                let root = a.item_keyword.name.as_str();
+                a.body.items = apply_array_shared_properties(&a.body)
+                    .into_iter()
+                    .map(|item| default_item(index, root, item))
+                    .collect();
+                a.body.shared_properties.clear();
+                resolve_array(&mut a.body, resolver);
+            }
+            DeclarationKind::Const(c) => resolve_value(&mut c.value.value, resolver),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn resolve_value(value: &mut Value, resolver: &ValueResolver) {
+    if let Ok(resolved) = resolver.resolve(value) {
+        *value = resolved;
+    }
+}
+
+fn resolve_body(body: &mut Body, resolver: &ValueResolver) {
+    for entry in &mut body.entries {
+        match &mut entry.kind {
+            BodyEntryKind::Property(p) => resolve_value(&mut p.value.value, resolver),
+            BodyEntryKind::NestedBlock(n) => resolve_body(&mut n.body, resolver),
+            BodyEntryKind::ListItem(item) => resolve_item(item, resolver),
+            BodyEntryKind::Modifier(m) => resolve_modifier(&mut m.value, resolver),
+            _ => {}
+        }
+    }
+}
+
+fn resolve_array(array: &mut ArrayBody, resolver: &ValueResolver) {
+    for p in &mut array.properties {
+        resolve_value(&mut p.value.value, resolver);
+    }
+    for m in &mut array.modifiers {
+        resolve_modifier(&mut m.value, resolver);
+    }
+    for item in &mut array.items {
+        resolve_item(item, resolver);
+    }
+}
+
+fn resolve_modifier(value: &mut ModifierValue, resolver: &ValueResolver) {
+    match value {
+        ModifierValue::Inline(v) => resolve_value(&mut v.value, resolver),
+        ModifierValue::Block(items) => {
+            for item in items {
+                resolve_item(item, resolver);
+            }
+        }
+        ModifierValue::TypeAnnotation { .. } => {}
+    }
+}
+
+fn resolve_item(item: &mut ListItem, resolver: &ValueResolver) {
+    match &mut item.kind {
+        ListItemKind::Named { body, .. } => resolve_body(body, resolver),
+        ListItemKind::Shorthand { value, body } => {
+            resolve_value(&mut value.value, resolver);
+            if let Some(body) = body {
+                resolve_body(body, resolver);
+            }
+        }
+        ListItemKind::Reference(_) | ListItemKind::Role(_) => {}
+    }
+}
+
+/// Schema defaults for one item of an array declaration: its body is an
+/// instance of the array's item model.
+fn default_item(index: &SchemaIndex, root: &str, mut item: ListItem) -> ListItem {
+    match &mut item.kind {
+        ListItemKind::Named { body, .. }
+        | ListItemKind::Shorthand {
+            body: Some(body), ..
+        } => *body = nml_core::apply_defaults(index, root, body),
+        _ => {}
+    }
+    item
+}
+
+/// The first value under a selected node that resolution left as written
+/// — a `$ENV` reference or a fallback chain that did not resolve.
+pub(crate) fn unresolved<'a>(node: &Selected<'a>) -> Option<&'a Value> {
+    match *node {
+        Selected::Value(v) => unresolved_value(v),
+        Selected::Body(body) => unresolved_in_body(body),
+        Selected::Array(array) => array
+            .properties
+            .iter()
+            .find_map(|p| unresolved_value(&p.value.value))
+            .or_else(|| array.items.iter().find_map(unresolved_in_item)),
+        Selected::Item(item) => unresolved_in_item(item),
+        Selected::Items(items) => items.iter().find_map(unresolved_in_item),
+    }
+}
+
+fn unresolved_value(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Secret(_) | Value::Fallback(..) => Some(value),
+        Value::Array(items) => items.iter().find_map(|i| unresolved_value(&i.value)),
+        _ => None,
+    }
+}
+
+fn unresolved_in_body(body: &Body) -> Option<&Value> {
+    body.entries.iter().find_map(|entry| match &entry.kind {
+        BodyEntryKind::Property(p) => unresolved_value(&p.value.value),
+        BodyEntryKind::NestedBlock(n) => unresolved_in_body(&n.body),
+        BodyEntryKind::ListItem(item) => unresolved_in_item(item),
+        BodyEntryKind::Modifier(m) => match &m.value {
+            ModifierValue::Inline(v) => unresolved_value(&v.value),
+            ModifierValue::Block(items) => items.iter().find_map(unresolved_in_item),
+            ModifierValue::TypeAnnotation { .. } => None,
+        },
+        _ => None,
+    })
+}
+
+fn unresolved_in_item(item: &ListItem) -> Option<&Value> {
+    match &item.kind {
+        ListItemKind::Named { body, .. } => unresolved_in_body(body),
+        ListItemKind::Shorthand { value, body } => {
+            unresolved_value(&value.value).or_else(|| body.as_ref().and_then(unresolved_in_body))
+        }
+        ListItemKind::Reference(_) | ListItemKind::Role(_) => None,
+    }
+}
+
+/// A scalar as plain output prints it: the text itself, no quoting.
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) | Value::Role(s) | Value::Reference(s) | Value::Secret(s) => s.clone(),
+        Value::TemplateString(segments) => nml_core::template::segments_to_string(segments),
+        Value::Number(n) => n.to_string(),
+        Value::Money(m) => m.format_display(),
+        Value::Duration(d) => d.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Resolved(text) => text.as_str().to_string(),
+        Value::Fallback(primary, fallback) => format!(
+            "{} | {}",
+            scalar_text(&primary.value),
+            scalar_text(&fallback.value)
+        ),
+        Value::Array(_) => value_json(value).to_string(),
+    }
+}
+
+pub(crate) fn node_json(node: &Selected) -> serde_json::Value {
+    match node {
+        Selected::Body(body) => body_json(body),
+        Selected::Array(array) => items_json(&array.items),
+        Selected::Item(item) => item_json(item),
+        Selected::Items(items) => items_json(items),
+        Selected::Value(value) => value_json(value),
+    }
+}
+
+/// Numbers follow `nml parse`'s rule: a JSON number for integer-form
+/// values that fit, the exact digits as a string otherwise.
+fn value_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Number(n) => serde_json::to_value(n).unwrap_or_else(|_| json!(n.to_string())),
+        Value::Bool(b) => json!(b),
+        Value::Array(items) => items.iter().map(|i| value_json(&i.value)).collect(),
+        other => json!(scalar_text(other)),
+    }
+}
+
+/// A body of list items is a list; any other body is an object of its
+/// properties, nested blocks and modifiers (`|allow`), with items a mixed
+/// body also carries under `items`.
+fn body_json(body: &Body) -> serde_json::Value {
+    let items: Vec<&ListItem> = body
+        .entries
+        .iter()
+        .filter_map(|e| match &e.kind {
+            BodyEntryKind::ListItem(item) => Some(item),
+            _ => None,
+        })
+        .collect();
+    if !items.is_empty() && items.len() == body.entries.len() {
+        return items.into_iter().map(item_json).collect();
+    }
+    let mut map = serde_json::Map::new();
+    for entry in &body.entries {
+        match &entry.kind {
+            BodyEntryKind::Property(p) => {
+                map.insert(p.name.name.clone(), value_json(&p.value.value));
+            }
+            BodyEntryKind::NestedBlock(n) => {
+                map.insert(n.name.name.clone(), body_json(&n.body));
+            }
+            BodyEntryKind::Modifier(m) => {
+                let value = match &m.value {
+                    ModifierValue::Inline(v) => value_json(&v.value),
+                    ModifierValue::Block(items) => items_json(items),
+                    ModifierValue::TypeAnnotation { .. } => continue,
+                };
+                map.insert(format!("|{}", m.name.name), value);
+            }
+            _ => {}
+        }
+    }
+    if !items.is_empty() {
+        map.insert(
+            "items".to_string(),
+            items.into_iter().map(item_json).collect(),
+        );
+    }
+    serde_json::Value::Object(map)
+}
+
+fn items_json(items: &[ListItem]) -> serde_json::Value {
+    items.iter().map(item_json).collect()
+}
+
+/// A named item is its body with the label as `name` — the shape
+/// deserialization gives it — unless the body sets `name` itself.
+fn item_json(item: &ListItem) -> serde_json::Value {
+    match &item.kind {
+        ListItemKind::Named { name, body } => {
+            let mut value = body_json(body);
+            if let serde_json::Value::Object(map) = &mut value {
+                map.entry("name")
+                    .or_insert_with(|| json!(name.name.clone()));
+            }
+            value
+        }
+        ListItemKind::Shorthand { value, body: None } => value_json(&value.value),
+        ListItemKind::Shorthand {
+            body: Some(body), ..
+        } => body_json(body),
+        ListItemKind::Reference(ident) => json!(ident.name),
+        ListItemKind::Role(role) => json!(role),
+    }
+}
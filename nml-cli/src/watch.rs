@@ -0,0 +1,173 @@
+//! `nml watch` — `nml check`, re-run as files change.
+//!
+//! Change detection polls modification times and sizes: no watcher
+//! service, nothing platform-specific, and a generator rewriting a whole
+//! tree settles into one pass rather than a storm of events. Each pass
+//! re-checks only what a change can affect — the changed and new files,
+//! and every file that imports a changed one — and reprints the whole
+//! report (clearing the screen on a terminal). A change under `--schema`
+//! reloads the directory and re-checks everything. Schema packages bind
+//! as in `nml check`, read once when watching starts.
+
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::check::{FileCheck, Run};
+use crate::output::{Format, Reporter};
+use crate::pipeline;
+
+const USAGE: &str = "usage: nml watch [--schema <dir>] [--strict] <path>...";
+
+/// How often the watched paths are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// What a poll compares: a file's modification time and size, or `None`
+/// once it is gone.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+pub fn cmd_watch(args: &[String]) -> Result<(), String> {
+    let mut schema_dir: Option<PathBuf> = None;
+    let mut strict = false;
+    let mut path_args: Vec<&String> = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--schema" => {
+                let dir = iter.next().ok_or("--schema requires a path argument")?;
+                schema_dir = Some(PathBuf::from(dir));
+            }
+            "--strict" => strict = true,
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown flag {flag}; {USAGE}"));
+            }
+            _ => path_args.push(arg),
+        }
+    }
+    if path_args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    // One schema generation per iteration: a `--schema` change ends it.
+    loop {
+        let schema_sources = match &schema_dir {
+            Some(dir) => pipeline::read_schema_dir(dir)?,
+            None => Vec::new(),
+        };
+        let schema_stamps: HashMap<PathBuf, Stamp> = schema_sources
+            .iter()
+            .map(|(p, _)| (p.clone(), stamp(p)))
+            .collect();
+        let run = Run::new(schema_dir.as_deref(), &schema_sources, strict);
+        watch_files(&run, &path_args, schema_dir.as_deref(), &schema_stamps)?;
+    }
+}
+
+/// Check, then re-check on every change, until the schema directory
+/// changes.
+fn watch_files(
+    run: &Run,
+    path_args: &[&String],
+    schema_dir: Option<&Path>,
+    schema_stamps: &HashMap<PathBuf, Stamp>,
+) -> Result<(), String> {
+    let mut files = pipeline::collect_nml_files(path_args)?;
+    let before = file_stamps(&files, &HashMap::new());
+    let mut checks: HashMap<PathBuf, FileCheck> =
+        files.iter().cloned().zip(run.check_all(&files)).collect();
+    let mut stamps = with_import_stamps(before, &checks);
+    print_pass(run, &files, &checks);
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if let Some(dir) = schema_dir {
+            let now = pipeline::schema_dir_paths(dir).unwrap_or_default();
+            let changed = now.len() != schema_stamps.len()
+                || now
+                    .iter()
+                    .any(|p| schema_stamps.get(p).is_none_or(|was| *was != stamp(p)));
+            if changed {
+                return Ok(());
+            }
+        }
+
+        // A path that vanished mid-edit keeps the last good file list.
+        let now_files = pipeline::collect_nml_files(path_args).unwrap_or_else(|_| files.clone());
+        let changed: HashSet<&PathBuf> = stamps
+            .iter()
+            .filter(|(p, was)| **was != stamp(p))
+            .map(|(p, _)| p)
+            .collect();
+        let affected: Vec<PathBuf> = now_files
+            .iter()
+            .filter(|p| {
+                changed.contains(p)
+                    || checks
+                        .get(*p)
+                        .is_none_or(|c| c.imports.iter().any(|i| changed.contains(i)))
+            })
+            .cloned()
+            .collect();
+        if affected.is_empty() && now_files == files {
+            continue;
+        }
+
+        let before = file_stamps(&now_files, &checks);
+        for (path, check) in affected.iter().zip(run.check_all(&affected)) {
+            checks.insert(path.clone(), check);
+        }
+        checks.retain(|p, _| now_files.contains(p));
+        files = now_files;
+        stamps = with_import_stamps(before, &checks);
+        print_pass(run, &files, &checks);
+    }
+}
+
+/// Every file a verdict depends on: the checked files and their imports.
+/// Taken before a check runs, so an edit landing mid-check still reads as
+/// a change on the next poll instead of being stamped as already seen.
+fn file_stamps(files: &[PathBuf], checks: &HashMap<PathBuf, FileCheck>) -> HashMap<PathBuf, Stamp> {
+    files
+        .iter()
+        .chain(checks.values().flat_map(|c| &c.imports))
+        .map(|p| (p.clone(), stamp(p)))
+        .collect()
+}
+
+/// `before` plus the imports a check found that it had no stamp for —
+/// those can only be stamped once the check has named them.
+fn with_import_stamps(
+    mut before: HashMap<PathBuf, Stamp>,
+    checks: &HashMap<PathBuf, FileCheck>,
+) -> HashMap<PathBuf, Stamp> {
+    for import in checks.values().flat_map(|c| &c.imports) {
+        before
+            .entry(import.clone())
+            .or_insert_with(|| stamp(import));
+    }
+    before
+}
+
+fn print_pass(run: &Run, files: &[PathBuf], checks: &HashMap<PathBuf, FileCheck>) {
+    if std::io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+    }
+    let ordered: Vec<FileCheck> = files.iter().map(|p| checks[p].clone()).collect();
+    let mut reporter = Reporter::new(Format::Human);
+    let verdict = run.report(&mut reporter, files, &ordered, false);
+    reporter.finish();
+    let verdict = match verdict {
+        Ok(()) => "all ok".to_string(),
+        Err(why) => why,
+    };
+    println!(
+        "watching {} file(s): {verdict} — waiting for changes (Ctrl-C to stop)",
+        files.len()
+    );
+}
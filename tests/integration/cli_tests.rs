@@ -740,3 +740,1172 @@ fn test_strict_with_nothing_to_enforce_is_a_usage_error() {
     );
     assert!(combined.contains("nothing to enforce"), "{combined}");
 }
+
+fn format_fixture(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("nml_format_{name}_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    let file = dir.join("app.nml");
+    std::fs::write(
+        &file,
+        "model svc:\n    port number\n    mode string\n\nsvc A:\n    port = \"80\"\n    mdoe = \"x\"\n",
+    )
+    .expect("write");
+    file
+}
+
+/// `--format json`: stdout is exactly one document carrying the full
+/// diagnostic model — code, severity, resolved span, suggestions with
+/// their replacement text — and the exit code still reflects the errors.
+#[test]
+fn test_check_format_json_serializes_the_diagnostic_model() {
+    let file = format_fixture("json");
+    let output = nml_bin()
+        .args(["check", "--format", "json", file.to_str().unwrap()])
+        .output()
+        .expect("run nml");
+    assert!(!output.status.success());
+    let doc: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is one JSON document");
+    assert_eq!(doc["version"], 1);
+    let diags = doc["diagnostics"].as_array().unwrap();
+    let quoted = diags
+        .iter()
+        .find(|d| d["code"] == "NML0001")
+        .expect("quoted-number migration");
+    assert_eq!(quoted["severity"], "error");
+    assert_eq!(quoted["span"]["line"], 6);
+    assert_eq!(quoted["span"]["column"], 12);
+    assert_eq!(quoted["suggestions"][0]["replacement"], "80");
+    let unknown = diags.iter().find(|d| d["code"] == "NML2001").unwrap();
+    assert_eq!(unknown["severity"], "warning");
+    assert_eq!(unknown["suggestions"][0]["kind"], "didYouMean");
+    std::fs::remove_dir_all(file.parent().unwrap()).ok();
+}
+
+/// `--format sarif`: a SARIF 2.1.0 log whose suggestions are `fixes`, and
+/// `--format github`: one escaped workflow command per finding.
+#[test]
+fn test_sarif_and_github_formats() {
+    let file = format_fixture("sarif");
+    let output = nml_bin()
+        .args(["validate", "--format=sarif", file.to_str().unwrap()])
+        .output()
+        .expect("run nml");
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).expect("SARIF JSON");
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "nml");
+
+    let output = nml_bin()
+        .args([
+            "fix",
+            "--dry-run",
+            "--format",
+            "sarif",
+            file.to_str().unwrap(),
+        ])
+        .output()
+        .expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).expect("SARIF JSON");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let quoted = results.iter().find(|r| r["ruleId"] == "NML0001").unwrap();
+    let replacement = &quoted["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "80");
+    assert_eq!(replacement["deletedRegion"]["startLine"], 6);
+    assert!(
+        std::fs::read_to_string(&file).unwrap().contains("\"80\""),
+        "dry-run must not write"
+    );
+
+    let output = nml_bin()
+        .args(["check", "--format", "github", file.to_str().unwrap()])
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(",line=6,col=12,endLine=6,endColumn=16,title=NML0001::"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.lines().any(|l| l.starts_with("::warning ")),
+        "{stdout}"
+    );
+    std::fs::remove_dir_all(file.parent().unwrap()).ok();
+}
+
+#[test]
+fn test_unknown_format_is_a_usage_error() {
+    let output = nml_bin()
+        .args([
+            "check",
+            "--format",
+            "xml",
+            "tests/fixtures/valid/minimal-service.nml",
+        ])
+        .output()
+        .expect("run nml");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown --format"));
+}
+
+/// `nml check` over a directory: the `--schema` universe loads once, every
+/// `.nml` file (dot-directories skipped) gets a summary line, and one bad
+/// file fails the run.
+#[test]
+fn test_check_walks_directories_with_per_file_summary() {
+    let dir = std::env::temp_dir().join(format!("nml_check_many_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("schemas")).expect("mkdir");
+    std::fs::create_dir_all(dir.join("src/sub")).expect("mkdir");
+    std::fs::create_dir_all(dir.join("src/.cache")).expect("mkdir");
+    std::fs::write(
+        dir.join("schemas/svc.model.nml"),
+        "model svc:\n    port number\n",
+    )
+    .expect("write");
+    std::fs::write(dir.join("src/a.nml"), "svc A:\n    port = 80\n").expect("write");
+    std::fs::write(dir.join("src/sub/b.nml"), "svc B:\n    port = \"80\"\n").expect("write");
+    std::fs::write(dir.join("src/.cache/c.nml"), "svc C:\n    port = \"x\"\n").expect("write");
+
+    let output = nml_bin()
+        .arg("check")
+        .arg("--schema")
+        .arg(dir.join("schemas"))
+        .arg(dir.join("src"))
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.contains("a.nml: ok (1 declaration(s))"), "{stdout}");
+    assert!(stdout.contains("b.nml: 1 error(s)"), "{stdout}");
+    assert!(!stdout.contains("c.nml"), "{stdout}");
+    assert!(
+        stdout.contains("checked 2 file(s): 1 ok, 1 failed"),
+        "{stdout}"
+    );
+    assert!(stderr.contains("1 of 2 file(s) failed"), "{stderr}");
+
+    // The fixed tree passes as a whole.
+    std::fs::write(dir.join("src/sub/b.nml"), "svc B:\n    port = 81\n").expect("write");
+    let output = nml_bin()
+        .arg("check")
+        .arg("--schema")
+        .arg(dir.join("schemas"))
+        .arg(dir.join("src"))
+        .output()
+        .expect("run nml");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A file pinned through `nml-project.nml` and claimed by a workspace
+/// package's binding globs is validated against that package alone —
+/// strict, per the binding — while an unclaimed file stays unbound.
+#[test]
+fn test_check_honors_project_package_bindings() {
+    let dir = std::env::temp_dir().join(format!("nml_check_bound_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("apps/web")).expect("mkdir");
+    std::fs::write(
+        dir.join("nml-project.nml"),
+        "project demo:\n    schemaPackages:\n        - demo\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("demo.package.nml"),
+        "package demo:\n    version = \"0.1.0\"\n    formatVersion = 1\n\n\
+         []schema schemas:\n    - core:\n        file = \"core.model.nml\"\n\n\
+         []validator validators:\n    - core:\n        files:\n            - \"apps/*/app.nml\"\n        \
+         schemas:\n            - core\n        strict = true\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("core.model.nml"),
+        "model core:\n    name string+\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("apps/web/app.nml"),
+        "core Web:\n    name = \"web\"\n    extra = 1\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("apps/web/notes.nml"),
+        "core Free:\n    extra = 1\n",
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .arg("check")
+        .arg(dir.join("apps"))
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(
+        stderr.contains("extra"),
+        "strict binding flags the unknown property: {stderr}"
+    );
+    assert!(stdout.contains("app.nml: 1 error(s)"), "{stdout}");
+    assert!(stdout.contains("notes.nml: ok"), "unbound file: {stdout}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `--explain-binding` names the package, its provenance and the validator
+/// that claimed each file — the editor's resolution, run from the CLI.
+#[test]
+fn test_check_explain_binding_names_each_claim() {
+    let store = std::env::temp_dir().join(format!("nml_explain_store_{}", std::process::id()));
+    let output = nml_bin()
+        .env("NML_SCHEMA_STORE_DIR", &store)
+        .args(["check", "--explain-binding", "docs/tutorial/examples/09"])
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains(
+            "app.nml: bound to package 'skylight' blake3:"
+        ) && stdout.contains(
+            "(workspace manifest docs/tutorial/examples/09/skylight.package.nml), validator 'service', auto-associated"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("skylight.package.nml: bound to package 'nml'")
+            && stdout.contains("(builtin), validator 'package'"),
+        "the manifest itself is checked against the builtin meta package: {stdout}"
+    );
+    assert!(stdout.contains("skylight.model.nml: unbound"), "{stdout}");
+}
+
+/// A pin resolves through the user store when no workspace manifest
+/// defines it, and a pin to a package nobody has installed says so.
+#[test]
+fn test_check_resolves_pins_through_the_store() {
+    use nml_validate::package::SchemaPackage;
+    use nml_validate::store::Store;
+
+    let base = std::env::temp_dir().join(format!("nml_check_store_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&base);
+    let store = base.join("store");
+    let project = base.join("project");
+    std::fs::create_dir_all(&project).expect("mkdir");
+    let manifest = "package demo:\n    version = \"0.1.0\"\n    formatVersion = 1\n    \
+                    rootMarkers:\n        - \"demo.nml\"\n\n\
+                    []schema schemas:\n    - core:\n        file = \"core.model.nml\"\n\n\
+                    []validator validators:\n    - core:\n        files:\n            - \"demo.nml\"\n        \
+                    schemas:\n            - core\n        strict = true\n";
+    let package = SchemaPackage::from_parts(manifest, |_| {
+        Ok("model core:\n    name string+\n".to_string())
+    })
+    .expect("package loads");
+    Store::at(&store).publish(&package).expect("publish");
+
+    std::fs::write(
+        project.join("nml-project.nml"),
+        "project demo:\n    schemaPackages:\n        - missing\n        - demo\n",
+    )
+    .expect("write");
+    std::fs::write(project.join("demo.nml"), "core Main:\n    extra = 1\n").expect("write");
+
+    let output = nml_bin()
+        .current_dir(&project)
+        .env("NML_SCHEMA_STORE_DIR", &store)
+        .args(["check", "--explain-binding", "demo.nml"])
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stdout}{stderr}");
+    assert!(
+        stdout.contains("(store current), validator 'core', pinned"),
+        "{stdout}"
+    );
+    assert!(
+        stderr.contains("pinned schema package 'missing' is not installed"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("extra"), "strict store binding: {stderr}");
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+/// `nml package`: build prints the identity, publish lands it in the store
+/// (idempotently), list and verify read it back, and a tampered slot or
+/// pointer is named rather than hidden.
+#[test]
+fn test_package_build_publish_list_verify_gc() {
+    let base = std::env::temp_dir().join(format!("nml_package_verbs_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&base);
+    let store = base.join("store");
+    let run = |args: &[&str]| {
+        nml_bin()
+            .env("NML_SCHEMA_STORE_DIR", &store)
+            .args(args)
+            .output()
+            .expect("run nml")
+    };
+    let pkg = "docs/tutorial/examples/09";
+
+    let output = run(&["package", "build", pkg]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.starts_with("skylight 0.1.0 blake3:"), "{stdout}");
+
+    let output = run(&["package", "publish", pkg]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("skylight: published 0.1.0+"));
+    let output = run(&["package", "publish", pkg]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("skylight: already current"));
+
+    let output = run(&["package", "verify", "skylight"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("skylight 0.1.0: ok"));
+
+    // Tamper with the slot: verify re-hashes and fails.
+    let slot = std::fs::read_dir(store.join("schema-packages/skylight"))
+        .expect("package dir")
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.is_dir())
+        .expect("slot");
+    std::fs::write(slot.join("skylight.model.nml"), "model tampered:\n").expect("write");
+    let output = run(&["package", "verify"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("skylight: store entry is corrupt"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // A corrupt pointer is listed, and gc refuses to guess at it.
+    std::fs::write(store.join("schema-packages/skylight/current"), "garbage\n").expect("write");
+    let output = run(&["package", "list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("skylight  ?  corrupt pointer  (1 slot(s)"),
+        "{stdout}"
+    );
+    let output = run(&["package", "gc", "skylight"]);
+    assert!(!output.status.success());
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+/// A manifest that fails meta-validation is refused with its findings
+/// located in the manifest, and nothing is published.
+#[test]
+fn test_package_build_reports_manifest_errors() {
+    let dir = std::env::temp_dir().join(format!("nml_package_bad_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    std::fs::write(
+        dir.join("demo.package.nml"),
+        "package demo:\n    version = \"0.1.0\"\n    formatVersion = 1\n    colour = \"red\"\n",
+    )
+    .expect("write");
+    let output = nml_bin()
+        .env("NML_SCHEMA_STORE_DIR", dir.join("store"))
+        .arg("package")
+        .arg("publish")
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stderr}");
+    assert!(stderr.contains("demo.package.nml:4:5"), "{stderr}");
+    assert!(!dir.join("store").exists(), "nothing reached the store");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml diff` reports the tutorial's reload plan: classified by the
+/// nearest directive, located by origin, secrets redacted — and exits 2
+/// because a `#restart` change is among them.
+#[test]
+fn test_diff_classifies_changes_and_signals_restart() {
+    let dir = "docs/tutorial/examples/08";
+    let output = nml_bin()
+        .args(["diff", "--schema", dir])
+        .arg(format!("{dir}/app.v1.nml"))
+        .arg(format!("{dir}/app.nml"))
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{stdout}");
+    assert!(
+        stdout.contains(&format!(
+            "restart  service Api.database.poolSize: 10 -> 20  ({dir}/app.nml:54, #restart)"
+        )),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "live     service Api.logLevel: \"debug\" -> \"info\"  (schema default, #live)"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("service Api.apiKey: changed (secret — value not shown)"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("SKYLIGHT_API_KEY"), "{stdout}");
+    assert!(stdout.contains("4 change(s): 3 live, 1 restart, 0 unclassified — restart required"));
+}
+
+/// Layered sides: the new side's override file wins property-by-property,
+/// the JSON document names its origin, and live-only changes exit 0.
+#[test]
+fn test_diff_layers_files_and_prints_json() {
+    let dir = std::env::temp_dir().join(format!("nml_diff_layers_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("schemas")).expect("mkdir");
+    std::fs::write(
+        dir.join("schemas/app.model.nml"),
+        "model server:\n    port number #restart\n    rateLimit number #live\n    token secret?\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("base.nml"),
+        "server Main:\n    port = 8080\n    rateLimit = 100\n",
+    )
+    .expect("write");
+    std::fs::write(dir.join("prod.nml"), "server Main:\n    rateLimit = 500\n").expect("write");
+
+    let output = nml_bin()
+        .args(["diff", "--format", "json", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("base.nml"))
+        .arg("--")
+        .arg(dir.join("base.nml"))
+        .arg(dir.join("prod.nml"))
+        .output()
+        .expect("run nml");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json document");
+    assert_eq!(doc["version"], 1);
+    assert_eq!(doc["restartRequired"], false);
+    let changes = doc["changes"].as_array().expect("changes");
+    assert_eq!(changes.len(), 1, "{doc}");
+    assert_eq!(changes[0]["path"], "server Main.rateLimit");
+    assert_eq!(changes[0]["kind"], "modified");
+    assert_eq!(changes[0]["class"], "live");
+    assert_eq!(changes[0]["old"], "100");
+    assert_eq!(changes[0]["new"], "500");
+    assert_eq!(changes[0]["origin"]["line"], 2);
+    assert!(
+        changes[0]["origin"]["file"]
+            .as_str()
+            .is_some_and(|f| f.ends_with("prod.nml"))
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Two instances of one model diff separately: a `#restart` change to one
+/// is reported under its instance name even though the other keeps the
+/// old value, and the run exits 2.
+#[test]
+fn test_diff_keys_blocks_by_instance() {
+    let dir = std::env::temp_dir().join(format!("nml_diff_instances_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    std::fs::write(
+        dir.join("app.model.nml"),
+        "model app:\n    port number #restart\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("old.nml"),
+        "app A:\n    port = 1\n\napp B:\n    port = 1\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("new.nml"),
+        "app A:\n    port = 5\n\napp B:\n    port = 1\n",
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .args(["diff", "--schema"])
+        .arg(&dir)
+        .arg(dir.join("old.nml"))
+        .arg(dir.join("new.nml"))
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2), "{stdout}");
+    assert!(stdout.contains("restart  app A.port: 1 -> 5"), "{stdout}");
+    assert!(!stdout.contains("app B"), "{stdout}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// String values print as NML literals: escaped the way a file would
+/// spell them, newlines included, so a row stays one line.
+#[test]
+fn test_diff_renders_strings_in_nml_syntax() {
+    let dir = std::env::temp_dir().join(format!("nml_diff_strings_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    std::fs::write(
+        dir.join("app.model.nml"),
+        "model server:\n    banner string #live\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("old.nml"),
+        "server Main:\n    banner = \"say \\\"hi\\\"\"\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("new.nml"),
+        "server Main:\n    banner = \"one\\ntwo \\u{7B}{x}}\"\n",
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .args(["diff", "--schema"])
+        .arg(&dir)
+        .arg(dir.join("old.nml"))
+        .arg(dir.join("new.nml"))
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(
+        stdout.contains(r#"server Main.banner: "say \"hi\"" -> "one\ntwo \u{7B}{x}}""#),
+        "{stdout}"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml query`: a path selects values — scalars print bare, wildcards
+/// print one line per match, JSON names each wildcard match, and a path
+/// that selects nothing fails.
+#[test]
+fn test_query_selects_values_by_path() {
+    let file = "docs/tutorial/examples/08/app.nml";
+    let query = |args: &[&str]| {
+        nml_bin()
+            .arg("query")
+            .args(args)
+            .arg(file)
+            .output()
+            .expect("run nml")
+    };
+
+    let output = query(&["service Api.endpoints[Docs].url"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "https://docs.skylight.dev\n"
+    );
+
+    let output = query(&["service Api.endpoints[*].regions[1]"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "eu-west\n");
+
+    let output = query(&["--format", "json", "service Api.database.*"]);
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(
+        doc,
+        serde_json::json!([
+            {"path": "service Api.database.url", "value": "postgres://localhost/skylight"},
+            {"path": "service Api.database.poolSize", "value": 20},
+        ])
+    );
+
+    // References to top-level arrays are stepped through.
+    let output = query(&["service Api.notifiers[Oncall].to"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "oncall@skylight.dev\n"
+    );
+
+    let output = query(&["service Api.endpoints[Nope]"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no match for"));
+    let output = query(&["service Api..port"]);
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("invalid path"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// `--resolve` reads the file as a program would: schema defaults
+/// appear, `$ENV` and consts resolve — and only a selected value that
+/// cannot resolve fails the query.
+#[test]
+fn test_query_resolve_applies_defaults_and_env() {
+    let dir = "docs/tutorial/examples/08";
+    let query = |path: &str| {
+        nml_bin()
+            .args(["query", "--resolve", "--schema", dir, path])
+            .arg(format!("{dir}/app.nml"))
+            .env("PORT", "9000")
+            .env_remove("SKYLIGHT_API_KEY_V2")
+            .env_remove("SKYLIGHT_API_KEY_DEV")
+            .output()
+            .expect("run nml")
+    };
+    let stdout = |path: &str| String::from_utf8_lossy(&query(path).stdout).into_owned();
+
+    assert_eq!(stdout("service Api.port"), "9000\n");
+    assert_eq!(stdout("service Api.retries"), "3\n");
+    assert_eq!(stdout("service Api.endpoints[Api].timeout"), "5s\n");
+    assert_eq!(stdout("service Api.notifiers[Console].kind"), "log\n");
+
+    let output = query("service Api.apiKey");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("cannot resolve service Api.apiKey: environment variable"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// `nml convert` into NML: document order kept, exact numbers, and with
+/// `--schema` typed duration/money literals and positional items. The
+/// output checks clean against the same schema.
+#[test]
+fn test_convert_toml_to_nml_with_schema() {
+    let dir = std::env::temp_dir().join(format!("nml_convert_toml_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("schemas")).expect("mkdir");
+    std::fs::write(
+        dir.join("schemas/app.model.nml"),
+        "model route:\n    path string+\n    method string = \"GET\"\n\n\
+         model server:\n    name string\n    host string\n\n\
+         model service:\n    port number\n    ratio number\n    timeout duration\n    \
+         price money\n    routes []route\n    servers []server\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("app.toml"),
+        "port = 8080\nratio = 0.1\ntimeout = \"30s\"\nprice = \"19.99 USD\"\n\n\
+         [[routes]]\npath = \"/health\"\nmethod = \"HEAD\"\n\n\
+         [[routes]]\npath = \"/api\"\n\n\
+         [[servers]]\nname = \"web\"\nhost = \"10.0.0.1\"\n",
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .args(["convert", "--block", "service Api", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.toml"))
+        .output()
+        .expect("run nml");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let nml = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert_eq!(
+        nml,
+        "service Api:\n    port = 8080\n    ratio = 0.1\n    timeout = 30s\n    \
+         price = 19.99 USD\n    routes:\n        - \"/health\":\n            method = \"HEAD\"\n        \
+         - \"/api\"\n    servers:\n        - web:\n            host = \"10.0.0.1\"\n"
+    );
+    std::fs::write(dir.join("app.nml"), &nml).expect("write");
+    let check = nml_bin()
+        .args(["check", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.nml"))
+        .output()
+        .expect("run nml");
+    assert!(
+        check.status.success(),
+        "{}",
+        String::from_utf8_lossy(&check.stderr)
+    );
+
+    // Without the schema nothing says `path` is positional, and a table
+    // in a list needs a `name` to label its item.
+    let output = nml_bin()
+        .args(["convert", "--block", "service Api"])
+        .arg(dir.join("app.toml"))
+        .output()
+        .expect("run nml");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("service.Api.routes[0]: a table in a list needs a `name`"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A list element with both a `name` and the positional field keeps its
+/// label: the item is named and the positional field a property, so the
+/// output checks clean and exports back with both.
+#[test]
+fn test_convert_named_items_win_over_shorthand() {
+    let dir = std::env::temp_dir().join(format!("nml_convert_named_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("schemas")).expect("mkdir");
+    std::fs::write(
+        dir.join("schemas/app.model.nml"),
+        "model route:\n    path string+\n    method string = \"GET\"\n\n\
+         model service:\n    routes []route\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("app.toml"),
+        "[[routes]]\nname = \"Health\"\npath = \"/health\"\n\n[[routes]]\npath = \"/api\"\n",
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .args(["convert", "--block", "service Api", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.toml"))
+        .output()
+        .expect("run nml");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let nml = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert_eq!(
+        nml,
+        "service Api:\n    routes:\n        - Health:\n            path = \"/health\"\n        \
+         - \"/api\"\n"
+    );
+    std::fs::write(dir.join("app.nml"), &nml).expect("write");
+    let check = nml_bin()
+        .args(["check", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.nml"))
+        .output()
+        .expect("run nml");
+    let stderr = String::from_utf8_lossy(&check.stderr);
+    assert!(check.status.success(), "{stderr}");
+    assert!(!stderr.contains("NML2001"), "{stderr}");
+
+    let output = nml_bin()
+        .args(["convert", "--to", "json", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.nml"))
+        .output()
+        .expect("run nml");
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json document");
+    let health = &doc["service"]["Api"]["routes"][0];
+    assert_eq!(health["name"], "Health", "{doc}");
+    assert_eq!(health["path"], "/health", "{doc}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml convert` both ways: JSON's `keyword.Name` tables become
+/// declarations, and the NML exports back as resolved, defaulted JSON.
+#[test]
+fn test_convert_json_round_trip_resolves_defaults() {
+    let dir = std::env::temp_dir().join(format!("nml_convert_json_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("schemas")).expect("mkdir");
+    std::fs::write(
+        dir.join("schemas/app.model.nml"),
+        "model server:\n    host string\n    port number = 8080\n    token secret?\n",
+    )
+    .expect("write");
+    std::fs::write(
+        dir.join("app.json"),
+        r#"{"server": {"Main": {"host": "a.internal", "token": "x"}, "Edge": {"host": "b", "port": 18446744073709551615}}}"#,
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .arg("convert")
+        .arg(dir.join("app.json"))
+        .output()
+        .expect("run nml");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let nml = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(nml.starts_with("server Main:\n"), "{nml}");
+    assert!(nml.contains("port = 18446744073709551615"), "{nml}");
+
+    // Hand-edit the converted file the way a migration would: the token
+    // moves to the environment.
+    std::fs::write(
+        dir.join("app.nml"),
+        nml.replace("token = \"x\"", "token = $ENV.NML_CONVERT_TOKEN"),
+    )
+    .expect("write");
+    let output = nml_bin()
+        .args(["convert", "--to", "json", "--schema"])
+        .arg(dir.join("schemas"))
+        .arg(dir.join("app.nml"))
+        .env("NML_CONVERT_TOKEN", "s3cret")
+        .output()
+        .expect("run nml");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json document");
+    assert_eq!(doc["server"]["Main"]["port"], 8080);
+    assert_eq!(doc["server"]["Main"]["token"], "s3cret");
+    assert_eq!(
+        doc["server"]["Edge"]["port"],
+        serde_json::json!(18446744073709551615u64)
+    );
+
+    let output = nml_bin()
+        .args(["convert", "--to", "json"])
+        .arg(dir.join("app.nml"))
+        .env_remove("NML_CONVERT_TOKEN")
+        .output()
+        .expect("run nml");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("cannot resolve server Main"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Converted numbers are the digits as written: JSON numbers never pass
+/// through `f64`, a TOML float reads as its shortest decimal however large,
+/// and a JSON number NML cannot hold exactly is refused, not rounded.
+#[test]
+fn test_convert_numbers_are_exact() {
+    let dir = std::env::temp_dir().join(format!("nml_convert_numbers_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    std::fs::write(
+        dir.join("app.json"),
+        r#"{"server": {"Main": {"ratio": 0.1000000000000000000001, "big": 12345678901234567890123456789, "small": 1.5E-3}}}"#,
+    )
+    .expect("write");
+    std::fs::write(dir.join("app.toml"), "limit = 1e300\n").expect("write");
+    std::fs::write(
+        dir.join("wide.json"),
+        r#"{"server": {"Main": {"ratio": 1.00000000000000000000000000000000000001}}}"#,
+    )
+    .expect("write");
+
+    let output = nml_bin()
+        .arg("convert")
+        .arg(dir.join("app.json"))
+        .output()
+        .expect("run nml");
+    let nml = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{nml}");
+    assert!(nml.contains("ratio = 0.1000000000000000000001"), "{nml}");
+    assert!(nml.contains("big = 12345678901234567890123456789"), "{nml}");
+    assert!(nml.contains("small = 0.0015"), "{nml}");
+
+    let output = nml_bin()
+        .args(["convert", "--block", "config Main"])
+        .arg(dir.join("app.toml"))
+        .output()
+        .expect("run nml");
+    let nml = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{nml}");
+    assert!(
+        nml.contains(&format!("limit = 1{}", "0".repeat(300))),
+        "{nml}"
+    );
+
+    let output = nml_bin()
+        .arg("convert")
+        .arg(dir.join("wide.json"))
+        .output()
+        .expect("run nml");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("1.00000000000000000000000000000000000001 has no NML number"),
+        "{stderr}"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml fmt` over a directory tree: `--check` lists the unformatted files
+/// and fails, `--diff` prints the change, and a file that fails to parse
+/// is skipped and counted — never rewritten — with exit status 3.
+#[test]
+fn test_fmt_check_diff_and_directories() {
+    let dir = std::env::temp_dir().join(format!("nml_fmt_tree_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("sub")).expect("mkdir");
+    std::fs::write(dir.join("messy.nml"), "service A:\n    port=1\n").expect("write");
+    std::fs::write(dir.join("sub/clean.nml"), "service B:\n    port = 1\n").expect("write");
+    std::fs::write(dir.join("broken.nml"), "service C:\n  = =\n").expect("write");
+
+    let output = nml_bin()
+        .args(["fmt", "--check"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert_eq!(output.status.code(), Some(1), "--check failure wins");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("messy.nml"), "{stdout}");
+    assert!(!stdout.contains("clean.nml"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.nml:2:3"), "{stderr}");
+    assert!(
+        stderr.contains("would reformat 1 of 3 file(s); 1 skipped (read or parse errors)"),
+        "{stderr}"
+    );
+
+    let output = nml_bin()
+        .args(["fmt", "--diff"])
+        .arg(dir.join("messy.nml"))
+        .output()
+        .expect("run nml");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("-    port=1\n+    port = 1\n"), "{stdout}");
+
+    let output = nml_bin().arg("fmt").arg(&dir).output().expect("run nml");
+    assert_eq!(
+        output.status.code(),
+        Some(3),
+        "a skipped file has its own status"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("messy.nml")).unwrap(),
+        "service A:\n    port = 1\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("broken.nml")).unwrap(),
+        "service C:\n  = =\n"
+    );
+
+    // Only the skipped file is left to report: `--check` passes the
+    // formatted files and still says one was skipped.
+    let output = nml_bin()
+        .args(["fmt", "--check"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert_eq!(output.status.code(), Some(3));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml fmt -` formats stdin to stdout; with `--check` it only answers.
+#[test]
+fn test_fmt_stdin_to_stdout() {
+    use std::io::Write;
+    let run = |args: &[&str]| {
+        let mut child = nml_bin()
+            .arg("fmt")
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .expect("run nml");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"service A:\n    port=1\n")
+            .unwrap();
+        child.wait_with_output().expect("wait")
+    };
+
+    let output = run(&["-"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "service A:\n    port = 1\n"
+    );
+
+    let output = run(&["--check", "-"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+/// `nml watch` re-checks a file when something it imports changes, and
+/// reprints the report.
+#[test]
+fn test_watch_rechecks_importers_on_change() {
+    use std::io::{BufRead, BufReader};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("nml_watch_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    std::fs::write(dir.join("shared.nml"), "const port = 1\n").expect("write");
+    std::fs::write(
+        dir.join("app.nml"),
+        "import \"shared.nml\"\n\nservice App:\n    port = port\n",
+    )
+    .expect("write");
+
+    let mut child = nml_bin()
+        .arg("watch")
+        .arg(dir.join("app.nml"))
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("run nml");
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let next_pass = || {
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.recv_timeout(Duration::from_secs(10)) {
+            let done = line.starts_with("watching ");
+            lines.push(line);
+            if done {
+                break;
+            }
+        }
+        lines.join("\n")
+    };
+
+    let first = next_pass();
+    assert!(first.contains("app.nml: ok"), "{first}");
+    assert!(first.contains("watching 1 file(s): all ok"), "{first}");
+
+    std::fs::write(dir.join("shared.nml"), "const other = 1\n").expect("write");
+    let second = next_pass();
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(second.contains("app.nml: 1 error(s)"), "{second}");
+    assert!(
+        second.contains("watching 1 file(s): 1 error(s)"),
+        "{second}"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml init` scaffolds a project that checks and builds as written, and a
+/// re-run over an existing project adds only what is missing.
+#[test]
+fn test_init_scaffolds_and_fills_in_missing() {
+    let dir = std::env::temp_dir().join(format!("nml_init_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("mkdir");
+    let project = "// hand-written\nproject Demo:\n    autoAssociate = true\n";
+    std::fs::write(dir.join("nml-project.nml"), project).expect("write");
+
+    let output = nml_bin()
+        .args(["init", "--package", "demo"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("updated (added schemaPackages pin demo)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("created"), "{stdout}");
+    assert_eq!(
+        std::fs::read_to_string(dir.join("nml-project.nml")).unwrap(),
+        "// hand-written\nproject Demo:\n    schemaPackages:\n        - demo\n    autoAssociate = true\n"
+    );
+
+    // Bound through the pin to the generated manifest, and clean.
+    let output = nml_bin()
+        .current_dir(&dir)
+        .args(["check", "--explain-binding", "."])
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.contains("bound to package 'demo'"), "{stdout}");
+    let output = nml_bin()
+        .args(["package", "build"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+
+    // A manifest missing pieces gets just those pieces; the rest is intact.
+    std::fs::write(
+        dir.join("demo.package.nml"),
+        "package demo:\n    version = \"0.2.0\"\n",
+    )
+    .expect("write");
+    let output = nml_bin()
+        .args(["init", "--package", "demo"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+    let manifest = std::fs::read_to_string(dir.join("demo.package.nml")).unwrap();
+    assert!(
+        manifest.starts_with("package demo:\n    version = \"0.2.0\"\n    formatVersion = 1\n"),
+        "{manifest}"
+    );
+    assert!(manifest.contains("[]validator validators:"), "{manifest}");
+
+    let output = nml_bin()
+        .args(["init", "--package", "demo"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("up to date").count(), 4, "{stdout}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// `nml init` then `nml init --package`: the package adopts the model file
+/// the first run wrote instead of writing a second `model service`.
+#[test]
+fn test_init_twice_reuses_the_model_file() {
+    let dir = std::env::temp_dir().join(format!("nml_init_twice_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let output = nml_bin().arg("init").arg(&dir).output().expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+    let output = nml_bin()
+        .args(["init", "--package", "my-tool"])
+        .arg(&dir)
+        .output()
+        .expect("run nml");
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!(
+            "up to date {}",
+            dir.join("app.model.nml").display()
+        )),
+        "{stdout}"
+    );
+    assert!(!dir.join("my-tool.model.nml").exists(), "{stdout}");
+    let manifest = std::fs::read_to_string(dir.join("my-tool.package.nml")).unwrap();
+    assert!(manifest.contains("file = \"app.model.nml\""), "{manifest}");
+
+    let output = nml_bin()
+        .current_dir(&dir)
+        .args(["check", "--explain-binding", "."])
+        .output()
+        .expect("run nml");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.contains("bound to package 'my-tool'"), "{stdout}");
+    let _ = std::fs::remove_dir_all(&dir);
+}